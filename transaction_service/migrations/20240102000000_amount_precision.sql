@@ -0,0 +1,6 @@
+-- Precisión de montos según la divisa (ISO 4217)
+
+-- DECIMAL(20, 2) truncaba montos de divisas con tres decimales (KWD, BHD). El Wallet Service
+-- valida los decimales contra la divisa de cada billetera; aquí sólo se conserva el monto exacto.
+ALTER TABLE transactions
+    ALTER COLUMN amount TYPE NUMERIC;
@@ -0,0 +1,33 @@
+-- Transferencias entre divisas
+
+-- Tasa vigente por par de divisas, administrada vía HTTP. Una unidad de base_currency
+-- equivale a `rate` unidades de quote_currency.
+CREATE TABLE IF NOT EXISTS exchange_rates (
+    base_currency VARCHAR(3) NOT NULL,
+    quote_currency VARCHAR(3) NOT NULL,
+    rate NUMERIC NOT NULL CHECK (rate > 0),
+    as_of TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (base_currency, quote_currency),
+    CHECK (base_currency <> quote_currency)
+);
+
+-- Cotizaciones emitidas: fijan una tasa durante un TTL corto.
+CREATE TABLE IF NOT EXISTS fx_quotes (
+    id UUID PRIMARY KEY,
+    source_currency VARCHAR(3) NOT NULL,
+    destination_currency VARCHAR(3) NOT NULL,
+    rate NUMERIC NOT NULL CHECK (rate > 0),
+    rate_timestamp TIMESTAMP WITH TIME ZONE NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Datos de conversión de la transacción. Nulos en transacciones de una sola divisa,
+-- donde el monto destino es el mismo `amount`.
+ALTER TABLE transactions
+    ADD COLUMN source_currency VARCHAR(3),
+    ADD COLUMN destination_currency VARCHAR(3),
+    ADD COLUMN destination_amount NUMERIC,
+    ADD COLUMN exchange_rate NUMERIC,
+    ADD COLUMN rate_timestamp TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN quote_id UUID REFERENCES fx_quotes(id);
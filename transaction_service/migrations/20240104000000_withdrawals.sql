@@ -0,0 +1,23 @@
+-- Retiros explícitos hacia destinos externos
+
+-- Un retiro debita una billetera y envía los fondos fuera del sistema: no tiene billetera
+-- destino, sino una referencia al destino externo (cuenta bancaria, procesador, etc.).
+ALTER TABLE transactions
+    ALTER COLUMN destination_wallet_id DROP NOT NULL,
+    ADD COLUMN external_destination VARCHAR(255);
+
+-- Las billeteras presentes deben corresponder al tipo de transacción.
+ALTER TABLE transactions
+    ADD CONSTRAINT transactions_parties_chk CHECK (
+        CASE transaction_type
+            WHEN 'DEPOSIT' THEN source_wallet_id IS NULL
+                AND destination_wallet_id IS NOT NULL
+                AND external_destination IS NULL
+            WHEN 'TRANSFER' THEN source_wallet_id IS NOT NULL
+                AND destination_wallet_id IS NOT NULL
+                AND external_destination IS NULL
+            WHEN 'WITHDRAWAL' THEN source_wallet_id IS NOT NULL
+                AND destination_wallet_id IS NULL
+                AND external_destination IS NOT NULL
+        END
+    );
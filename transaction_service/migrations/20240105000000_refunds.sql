@@ -0,0 +1,12 @@
+-- Reembolsos totales y parciales
+
+-- Un reembolso es una transacción hija que apunta a la original. La original acumula lo
+-- reembolsado (incluidos los reembolsos en curso) y pasa a REVERSED al reembolsarse por completo.
+ALTER TABLE transactions
+    ADD COLUMN parent_transaction_id UUID REFERENCES transactions(id),
+    ADD COLUMN refunded_amount NUMERIC NOT NULL DEFAULT 0,
+    ADD CONSTRAINT transactions_refunded_amount_chk
+        CHECK (refunded_amount >= 0 AND refunded_amount <= amount);
+
+CREATE INDEX idx_transactions_parent ON transactions(parent_transaction_id)
+    WHERE parent_transaction_id IS NOT NULL;
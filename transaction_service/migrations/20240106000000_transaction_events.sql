@@ -0,0 +1,18 @@
+-- Historial de cambios de estado de las transacciones
+
+CREATE TYPE transaction_event_actor AS ENUM ('API', 'RETRY_JOB', 'ADMIN');
+
+-- Un registro por transición: estado anterior (NULL al crear), estado nuevo, quién lo
+-- provocó y qué respondió el Wallet Service.
+CREATE TABLE IF NOT EXISTS transaction_events (
+    id UUID PRIMARY KEY,
+    transaction_id UUID NOT NULL REFERENCES transactions(id),
+    from_status transaction_status,
+    to_status transaction_status NOT NULL,
+    actor transaction_event_actor NOT NULL,
+    gateway_message TEXT,
+    error_detail TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_transaction_events_transaction ON transaction_events(transaction_id, created_at);
@@ -0,0 +1,14 @@
+-- Memo, referencia externa y metadata del cliente en las transacciones
+
+ALTER TABLE transactions
+    ADD COLUMN description VARCHAR(500),
+    ADD COLUMN external_reference VARCHAR(255),
+    ADD COLUMN metadata JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+-- La metadata es siempre un objeto clave/valor.
+ALTER TABLE transactions
+    ADD CONSTRAINT transactions_metadata_object_chk CHECK (jsonb_typeof(metadata) = 'object');
+
+-- Búsqueda por referencia externa (factura, pedido); la mayoría de transacciones no la tienen.
+CREATE INDEX idx_transactions_external_reference ON transactions(external_reference)
+    WHERE external_reference IS NOT NULL;
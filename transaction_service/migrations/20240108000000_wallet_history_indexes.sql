@@ -0,0 +1,18 @@
+-- Índices para el historial paginado de billeteras
+
+-- El historial se consulta por rol (origen o destino) y se recorre por keyset sobre
+-- (created_at, id) descendente: un índice por rol sirve cada rama sin ordenar en memoria.
+CREATE INDEX idx_transactions_source_history
+    ON transactions(source_wallet_id, created_at DESC, id DESC)
+    WHERE source_wallet_id IS NOT NULL;
+
+CREATE INDEX idx_transactions_destination_history
+    ON transactions(destination_wallet_id, created_at DESC, id DESC)
+    WHERE destination_wallet_id IS NOT NULL;
+
+-- El índice compuesto (origen, destino) no sirve para buscar por destino y queda cubierto
+-- por los anteriores.
+DROP INDEX IF EXISTS idx_transactions_wallets;
+
+-- El cursor compara (created_at, id): una fila sin fecha quedaría fuera de toda página.
+ALTER TABLE transactions ALTER COLUMN created_at SET NOT NULL;
@@ -0,0 +1,16 @@
+-- Extractos de billetera pregenerados por el job mensual
+
+-- El contenido completo (movimientos y saldos corridos) se guarda como JSONB; las columnas
+-- sueltas permiten buscar y auditar sin abrir el documento.
+CREATE TABLE IF NOT EXISTS wallet_statements (
+    wallet_id UUID NOT NULL,
+    period_start TIMESTAMP WITH TIME ZONE NOT NULL,
+    period_end TIMESTAMP WITH TIME ZONE NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    opening_balance NUMERIC NOT NULL,
+    closing_balance NUMERIC NOT NULL,
+    content JSONB NOT NULL,
+    generated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (wallet_id, period_start, period_end),
+    CONSTRAINT chk_wallet_statements_period CHECK (period_start < period_end)
+);
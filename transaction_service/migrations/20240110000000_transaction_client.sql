@@ -0,0 +1,5 @@
+-- Cliente de la API que inició cada transacción
+
+-- Lo informa el header `X-Client-Id` (puesto por el gateway de autenticación). Restringe la
+-- consulta por correlation_id al cliente que la inició; las filas anteriores quedan en NULL.
+ALTER TABLE transactions ADD COLUMN client_id VARCHAR(128);
@@ -0,0 +1,10 @@
+-- Huella de la solicitud que creó cada transacción
+
+-- Un reintento con el mismo correlation_id debe traer la misma solicitud; la huella (SHA-256
+-- de los campos que mueven fondos) permite detectar la reutilización de la clave con otros
+-- datos. Las filas anteriores quedan en NULL y se comparan recalculando la huella.
+ALTER TABLE transactions ADD COLUMN request_fingerprint CHAR(64);
+
+-- Las claves vencidas pueden reutilizarse: la búsqueda toma la transacción más reciente.
+DROP INDEX IF EXISTS idx_transactions_correlation;
+CREATE INDEX idx_transactions_correlation ON transactions(correlation_id, created_at DESC);
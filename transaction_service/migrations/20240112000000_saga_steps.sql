@@ -0,0 +1,20 @@
+-- Registro persistente de la saga de cada transacción en el Wallet Service
+
+CREATE TYPE saga_step_status AS ENUM (
+    'PLANNED', 'SENT', 'ACKNOWLEDGED', 'CAPTURED', 'REJECTED', 'COMPENSATED'
+);
+
+-- Una fila por pata (movimiento de una billetera). Al reiniciar, las sagas con patas en
+-- estado no final se completan o se compensan según hasta dónde llegaron.
+CREATE TABLE IF NOT EXISTS saga_steps (
+    transaction_id UUID NOT NULL REFERENCES transactions(id),
+    leg SMALLINT NOT NULL,
+    wallet_id UUID NOT NULL,
+    amount NUMERIC NOT NULL,
+    status saga_step_status NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (transaction_id, leg)
+);
+
+CREATE INDEX idx_saga_steps_unfinished ON saga_steps(transaction_id)
+    WHERE status IN ('PLANNED', 'SENT', 'ACKNOWLEDGED');
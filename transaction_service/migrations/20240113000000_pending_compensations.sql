@@ -0,0 +1,26 @@
+-- Compensaciones de saga que fallaron y deben reintentarse
+
+CREATE TYPE compensation_status AS ENUM ('PENDING', 'SUSPENDED', 'RESOLVED');
+
+-- Una fila por pata cuya reserva no se pudo liberar. El job la reintenta con espera
+-- exponencial; al agotar los intentos queda SUSPENDED hasta que un operador la resuelva.
+CREATE TABLE IF NOT EXISTS pending_compensations (
+    id UUID PRIMARY KEY,
+    transaction_id UUID NOT NULL,
+    leg SMALLINT NOT NULL,
+    wallet_id UUID NOT NULL,
+    amount NUMERIC NOT NULL,
+    status compensation_status NOT NULL DEFAULT 'PENDING',
+    attempts INTEGER NOT NULL DEFAULT 1,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_error TEXT NOT NULL,
+    resolution_note TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (transaction_id, leg) REFERENCES saga_steps(transaction_id, leg),
+    UNIQUE (transaction_id, leg)
+);
+
+CREATE INDEX idx_pending_compensations_due ON pending_compensations(next_attempt_at)
+    WHERE status = 'PENDING';
+CREATE INDEX idx_pending_compensations_status ON pending_compensations(status, created_at DESC);
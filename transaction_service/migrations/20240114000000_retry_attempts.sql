@@ -0,0 +1,15 @@
+-- Reintentos con espera exponencial y dead-letter de transacciones atascadas
+
+-- El job de reintentos pasa a DEAD_LETTER las transacciones que agotaron sus intentos; sólo
+-- un operador las devuelve a PENDING.
+ALTER TYPE transaction_status ADD VALUE IF NOT EXISTS 'DEAD_LETTER';
+
+-- `next_attempt_at` nulo: la transacción nunca falló y el job la toma apenas supera la
+-- antigüedad mínima.
+ALTER TABLE transactions
+    ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN next_attempt_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN last_error TEXT;
+
+-- Sirve al job (PENDING más antiguas) y al listado de DEAD_LETTER.
+CREATE INDEX idx_transactions_status_created ON transactions(status, created_at);
@@ -0,0 +1,8 @@
+-- Reclamo de transacciones por instancia para el job de reintentos
+
+-- Cada pasada del job reclama sus transacciones PENDING con un lease: mientras
+-- `claimed_until` no venza, ninguna otra réplica las toma. El siguiente `update` de la
+-- transacción lo libera.
+ALTER TABLE transactions
+    ADD COLUMN claimed_by TEXT,
+    ADD COLUMN claimed_until TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,17 @@
+-- Reserva de cada correlation_id durante su ventana de idempotencia
+
+-- La comprobación previa a guardar no basta con solicitudes concurrentes: ambas ven la clave
+-- libre. Cada INSERT en `transactions` reserva aquí su correlation_id en la misma transacción;
+-- una clave vencida se reasigna y una vigente hace fallar el guardado.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    correlation_id UUID PRIMARY KEY,
+    transaction_id UUID NOT NULL REFERENCES transactions(id),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- Las transacciones existentes conservan su clave con la ventana por defecto (24 horas).
+INSERT INTO idempotency_keys (correlation_id, transaction_id, expires_at)
+SELECT DISTINCT ON (correlation_id) correlation_id, id, created_at + INTERVAL '24 hours'
+FROM transactions
+WHERE correlation_id IS NOT NULL
+ORDER BY correlation_id, created_at DESC;
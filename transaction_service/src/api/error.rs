@@ -19,13 +19,50 @@ impl From<TransactionError> for ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        // Un reintento con el mismo correlation_id y otra solicitud: devolvemos ambas para que
+        // el cliente vea qué cambió.
+        if let TransactionError::IdempotencyConflict {
+            ref original,
+            ref received,
+            ..
+        } = self.0
+        {
+            let body = Json(json!({
+                "status": "error",
+                "message": self.0.to_string(),
+                "original_request": original,
+                "received_request": received,
+            }));
+            return (StatusCode::CONFLICT, body).into_response();
+        }
+
         let (status, error_message) = match self.0 {
             TransactionError::NotFound(_) => (StatusCode::NOT_FOUND, self.0.to_string()),
+            TransactionError::CorrelationNotFound(_) => (StatusCode::NOT_FOUND, self.0.to_string()),
+            TransactionError::CompensationNotFound(_) => {
+                (StatusCode::NOT_FOUND, self.0.to_string())
+            }
+            TransactionError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, self.0.to_string()),
+            TransactionError::ClientNotRecorded(_) => (StatusCode::FORBIDDEN, self.0.to_string()),
             TransactionError::ValidationError(_) => (StatusCode::BAD_REQUEST, self.0.to_string()),
             TransactionError::InvalidAmount => (StatusCode::BAD_REQUEST, self.0.to_string()),
             TransactionError::SameWallet => (StatusCode::BAD_REQUEST, self.0.to_string()),
             TransactionError::InvalidState(_) => (StatusCode::BAD_REQUEST, self.0.to_string()),
+            TransactionError::LeaseLost(_) => (StatusCode::CONFLICT, self.0.to_string()),
+            TransactionError::InsufficientFunds(_) => (StatusCode::BAD_REQUEST, self.0.to_string()),
+            TransactionError::LimitExceeded(..) => (StatusCode::BAD_REQUEST, self.0.to_string()),
+            TransactionError::WalletNotActive(_) => (StatusCode::CONFLICT, self.0.to_string()),
+            TransactionError::CurrencyMismatch(..) => (StatusCode::BAD_REQUEST, self.0.to_string()),
+            TransactionError::ExchangeRateNotFound(..) => {
+                (StatusCode::NOT_FOUND, self.0.to_string())
+            }
+            TransactionError::QuoteNotFound(_) => (StatusCode::NOT_FOUND, self.0.to_string()),
+            TransactionError::QuoteExpired(_) => (StatusCode::CONFLICT, self.0.to_string()),
+            TransactionError::RefundExceeded(..) => (StatusCode::BAD_REQUEST, self.0.to_string()),
             TransactionError::IdempotencyError(_) => (StatusCode::CONFLICT, self.0.to_string()),
+            TransactionError::IdempotencyConflict { .. } => {
+                (StatusCode::CONFLICT, self.0.to_string())
+            }
             TransactionError::RepositoryError(ref e) => {
                 tracing::error!("Database Repository Error: {}", e);
                 (
@@ -37,6 +74,8 @@ impl IntoResponse for ApiError {
                 tracing::error!("Wallet Gateway Error: {}", e);
                 (StatusCode::BAD_REQUEST, self.0.to_string())
             }
+            // La transacción sigue PENDING: el cliente puede consultarla más tarde.
+            TransactionError::SagaIncomplete(_) => (StatusCode::ACCEPTED, self.0.to_string()),
         };
 
         let body = Json(json!({
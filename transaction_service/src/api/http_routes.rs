@@ -1,8 +1,11 @@
 use axum::{
-    extract::{Path, State},
-    routing::{get, post},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -12,24 +15,72 @@ use uuid::Uuid;
 use crate::api::error::ApiError;
 use crate::api::response::ApiResponse;
 
+use crate::use_cases::create_fx_quote::CreateFxQuoteUseCase;
 use crate::use_cases::get_transaction_details::GetTransactionDetailsUseCase;
+use crate::use_cases::get_transaction_events::GetTransactionEventsUseCase;
 use crate::use_cases::get_wallet_history::GetWalletHistoryUseCase;
+use crate::use_cases::get_wallet_statement::GetWalletStatementUseCase;
 use crate::use_cases::process_transaction::ProcessTransactionUseCase;
+use crate::use_cases::refund_transaction::RefundTransactionUseCase;
+use crate::use_cases::review_compensations::ReviewCompensationsUseCase;
+use crate::use_cases::review_dead_letters::ReviewDeadLettersUseCase;
+use crate::use_cases::search_transactions::SearchTransactionsUseCase;
+use crate::use_cases::update_exchange_rate::UpdateExchangeRateUseCase;
 
-use crate::domain::types::{TransactionId, WalletId};
+use crate::domain::compensation::CompensationStatus;
+use crate::domain::entities::{TransactionDetails, TransactionStatus, TransactionType};
+use crate::domain::error::TransactionError;
+use crate::domain::history::{HistoryDirection, HistoryFilter};
+use crate::domain::types::{QuoteId, TransactionId, WalletId};
 
 // Estado compartido de la aplicación
 pub struct AppState {
     pub process_transaction_use_case: ProcessTransactionUseCase,
+    pub refund_transaction_use_case: RefundTransactionUseCase,
     pub get_transaction_details_use_case: GetTransactionDetailsUseCase,
+    pub get_transaction_events_use_case: GetTransactionEventsUseCase,
+    pub search_transactions_use_case: SearchTransactionsUseCase,
     pub get_wallet_history_use_case: GetWalletHistoryUseCase,
+    pub get_wallet_statement_use_case: GetWalletStatementUseCase,
+    pub create_fx_quote_use_case: CreateFxQuoteUseCase,
+    pub update_exchange_rate_use_case: UpdateExchangeRateUseCase,
+    pub review_compensations_use_case: ReviewCompensationsUseCase,
+    pub review_dead_letters_use_case: ReviewDeadLettersUseCase,
 }
 
 pub fn routes(state: Arc<AppState>) -> Router {
     Router::new()
-        .route("/transactions", post(initiate_transaction))
+        .route(
+            "/transactions",
+            post(initiate_transaction).get(search_transactions),
+        )
+        .route("/transactions/withdrawals", post(initiate_withdrawal))
         .route("/transactions/{id}", get(get_transaction_details))
+        .route("/transactions/{id}/refund", post(refund_transaction))
+        .route("/transactions/{id}/events", get(get_transaction_events))
         .route("/transactions/wallet/{wallet_id}", get(get_wallet_history))
+        .route(
+            "/transactions/wallet/{wallet_id}/statement",
+            get(get_wallet_statement),
+        )
+        .route("/fx/quotes", post(create_fx_quote))
+        .route(
+            "/admin/exchange-rates/{base}/{quote}",
+            put(update_exchange_rate),
+        )
+        .route("/admin/compensations", get(list_compensations))
+        .route("/admin/compensations/{id}", get(get_compensation))
+        .route(
+            "/admin/compensations/{id}/resolve",
+            post(resolve_compensation),
+        )
+        .route("/admin/compensations/{id}/retry", post(retry_compensation))
+        .route("/admin/dead-letters", get(list_dead_letters))
+        .route("/admin/dead-letters/{id}", get(get_dead_letter))
+        .route(
+            "/admin/dead-letters/{id}/redrive",
+            post(redrive_dead_letter),
+        )
         .with_state(state) // Inyectamos el estado (Casos de Uso)
 }
 
@@ -40,6 +91,133 @@ pub struct CreateTransactionRequest {
     pub dest_wallet_id: Uuid,
     pub amount: Decimal,
     pub correlation_id: Uuid,
+    /// Cotización obtenida en `POST /fx/quotes`; obligatoria si las billeteras usan
+    /// divisas distintas.
+    #[serde(default)]
+    pub quote_id: Option<Uuid>,
+    /// Memo del pagador.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Referencia en el sistema del cliente (número de factura, id de pedido, etc.).
+    #[serde(default)]
+    pub external_reference: Option<String>,
+    /// Pares clave/valor arbitrarios, devueltos tal cual en detalle e historial.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+// DTO de entrada para crear un retiro hacia un destino externo
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWithdrawalRequest {
+    pub source_wallet_id: Uuid,
+    /// Referencia del destino fuera del sistema (cuenta bancaria, procesador de pagos, etc.).
+    pub external_destination: String,
+    pub amount: Decimal,
+    pub correlation_id: Uuid,
+    /// Memo del pagador.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Referencia en el sistema del cliente (número de factura, id de pedido, etc.).
+    #[serde(default)]
+    pub external_reference: Option<String>,
+    /// Pares clave/valor arbitrarios, devueltos tal cual en detalle e historial.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+// Header con la identidad del cliente de la API, fijado por el gateway de autenticación.
+// Es un valor de confianza, no una credencial: el servicio no lo verifica, así que el gateway
+// debe descartar el que envíe el cliente y poner el de la identidad que autenticó. Expuesto
+// directamente, cualquiera podría hacerse pasar por otro cliente.
+pub const CLIENT_ID_HEADER: &str = "x-client-id";
+// Largo máximo del identificador de cliente (columna `client_id`)
+pub const MAX_CLIENT_ID_LEN: usize = 128;
+
+// Parámetros de búsqueda de transacciones: exactamente uno de los dos criterios
+#[derive(Deserialize)]
+pub struct SearchTransactionsQuery {
+    pub external_reference: Option<String>,
+    pub correlation_id: Option<Uuid>,
+}
+
+// Parámetros de paginación de las transacciones en DEAD_LETTER
+#[derive(Deserialize)]
+pub struct DeadLettersQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+// Parámetros de paginación y filtrado del historial de una billetera
+#[derive(Deserialize)]
+pub struct WalletHistoryQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub status: Option<TransactionStatus>,
+    #[serde(rename = "type")]
+    pub transaction_type: Option<TransactionType>,
+    pub direction: Option<HistoryDirection>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+}
+
+// Formato de descarga de un extracto
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+// Parámetros de un extracto de billetera
+#[derive(Deserialize)]
+pub struct WalletStatementQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    #[serde(default)]
+    pub format: StatementFormat,
+}
+
+// DTO de entrada para reembolsar una transacción
+#[derive(Deserialize, ToSchema)]
+pub struct RefundTransactionRequest {
+    /// Monto a devolver; si se omite se reembolsa todo lo pendiente.
+    #[serde(default)]
+    pub amount: Option<Decimal>,
+    pub correlation_id: Uuid,
+}
+
+// DTO de entrada para solicitar una cotización de tipo de cambio
+#[derive(Deserialize, ToSchema)]
+pub struct CreateQuoteRequest {
+    /// Divisa de la billetera origen (ISO 4217).
+    pub source_currency: String,
+    /// Divisa de la billetera destino (ISO 4217).
+    pub destination_currency: String,
+}
+
+// DTO de entrada para publicar un tipo de cambio
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateExchangeRateRequest {
+    /// Unidades de la divisa cotizada por cada unidad de la divisa base.
+    pub rate: Decimal,
+}
+
+// Filtro del listado de compensaciones
+#[derive(Deserialize)]
+pub struct CompensationsQuery {
+    pub status: Option<CompensationStatus>,
+}
+
+// DTO de entrada para resolver a mano una compensación
+#[derive(Deserialize, ToSchema)]
+pub struct ResolveCompensationRequest {
+    /// Qué se hizo con los fondos retenidos (obligatoria).
+    pub note: String,
 }
 
 // Handler: Iniciar un movimiento entre billeteras
@@ -50,10 +228,14 @@ pub struct CreateTransactionRequest {
     request_body = CreateTransactionRequest,
     responses(
         (status = 200, description = "Transacción iniciada", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("X-Client-Id" = Option<String>, Header, description = "Cliente de la API que inicia la transacción")
     )
 )]
 pub async fn initiate_transaction(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(payload): Json<CreateTransactionRequest>,
 ) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
     let transaction = state
@@ -63,12 +245,136 @@ pub async fn initiate_transaction(
             WalletId(payload.dest_wallet_id),
             payload.amount,
             payload.correlation_id,
+            payload.quote_id.map(QuoteId),
+            TransactionDetails::new(
+                payload.description,
+                payload.external_reference,
+                payload.metadata,
+            )?,
+            client_id(&headers)?,
         )
         .await?;
 
     Ok(Json(ApiResponse::success(serde_json::json!(transaction))))
 }
 
+// Handler: Buscar transacciones por referencia externa o recuperar una por correlation_id
+// GET /transactions?external_reference=
+// GET /transactions?correlation_id=  (requiere X-Client-Id: sólo la ve el cliente que la inició)
+#[utoipa::path(
+    get,
+    path = "/transactions",
+    responses(
+        (status = 200, description = "Transacciones con la referencia indicada, o la transacción (con su estado) del correlation_id", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 401, description = "Consulta por correlation_id sin X-Client-Id"),
+        (status = 403, description = "La transacción se creó sin X-Client-Id y no se puede recuperar por correlation_id"),
+        (status = 404, description = "No hay transacción de este cliente con ese correlation_id")
+    ),
+    params(
+        ("external_reference" = Option<String>, Query, description = "Referencia externa (factura, pedido)"),
+        ("correlation_id" = Option<Uuid>, Query, description = "Clave de idempotencia enviada al crear la transacción"),
+        ("X-Client-Id" = Option<String>, Header, description = "Cliente de la API que inició la transacción; lo fija el gateway de autenticación")
+    )
+)]
+pub async fn search_transactions(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<SearchTransactionsQuery>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let result = match (query.external_reference, query.correlation_id) {
+        (Some(external_reference), None) => serde_json::json!(
+            state
+                .search_transactions_use_case
+                .execute(external_reference)
+                .await?
+        ),
+        (None, Some(correlation_id)) => serde_json::json!(
+            state
+                .search_transactions_use_case
+                .find_by_correlation_id(correlation_id, client_id(&headers)?)
+                .await?
+        ),
+        _ => {
+            return Err(TransactionError::ValidationError(
+                "Indique exactamente uno de external_reference o correlation_id".into(),
+            )
+            .into())
+        }
+    };
+
+    Ok(Json(ApiResponse::success(result)))
+}
+
+// Handler: Retirar fondos de una billetera hacia un destino externo
+// POST /transactions/withdrawals
+#[utoipa::path(
+    post,
+    path = "/transactions/withdrawals",
+    request_body = CreateWithdrawalRequest,
+    responses(
+        (status = 200, description = "Retiro iniciado", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("X-Client-Id" = Option<String>, Header, description = "Cliente de la API que inicia el retiro")
+    )
+)]
+pub async fn initiate_withdrawal(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateWithdrawalRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let transaction = state
+        .process_transaction_use_case
+        .execute_withdrawal(
+            WalletId(payload.source_wallet_id),
+            payload.external_destination,
+            payload.amount,
+            payload.correlation_id,
+            TransactionDetails::new(
+                payload.description,
+                payload.external_reference,
+                payload.metadata,
+            )?,
+            client_id(&headers)?,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(transaction))))
+}
+
+// Handler: Reembolsar total o parcialmente una transacción completada
+// POST /transactions/{id}/refund
+#[utoipa::path(
+    post,
+    path = "/transactions/{id}/refund",
+    request_body = RefundTransactionRequest,
+    responses(
+        (status = 200, description = "Reembolso procesado", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la transacción a reembolsar"),
+        ("X-Client-Id" = Option<String>, Header, description = "Cliente de la API que solicita el reembolso")
+    )
+)]
+pub async fn refund_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<RefundTransactionRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let refund = state
+        .refund_transaction_use_case
+        .execute(
+            TransactionId(id),
+            payload.amount,
+            payload.correlation_id,
+            client_id(&headers)?,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(refund))))
+}
+
 // Handler: Ver detalle de una transaccion
 // GET /transactions/{id}
 #[utoipa::path(
@@ -93,26 +399,363 @@ pub async fn get_transaction_details(
     Ok(Json(ApiResponse::success(serde_json::json!(transaction))))
 }
 
-// Handler: Historial de movimientos de una billetera especifica
+// Handler: Historial de cambios de estado de una transacción
+// GET /transactions/{id}/events
+#[utoipa::path(
+    get,
+    path = "/transactions/{id}/events",
+    responses(
+        (status = 200, description = "Cambios de estado en orden cronológico", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la transacción")
+    )
+)]
+pub async fn get_transaction_events(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let events = state
+        .get_transaction_events_use_case
+        .execute(TransactionId(id))
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(events))))
+}
+
+// Handler: Historial de movimientos de una billetera especifica, paginado por cursor
 // GET /transactions/wallet/{wallet_id}
 #[utoipa::path(
     get,
     path = "/transactions/wallet/{wallet_id}",
     responses(
-        (status = 200, description = "Historial de movimientos", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+        (status = 200, description = "Página del historial de movimientos y cursor de la siguiente", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "Filtro o cursor inválido")
     ),
     params(
-        ("wallet_id" = Uuid, Path, description = "ID de la billetera")
+        ("wallet_id" = Uuid, Path, description = "ID de la billetera"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` de la página anterior"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (por defecto 50, máximo 200)"),
+        ("from" = Option<String>, Query, description = "Desde (RFC 3339, inclusive)"),
+        ("to" = Option<String>, Query, description = "Hasta (RFC 3339, exclusive)"),
+        ("status" = Option<String>, Query, description = "PENDING, COMPLETED, FAILED, REVERSED o DEAD_LETTER"),
+        ("type" = Option<String>, Query, description = "TRANSFER, DEPOSIT o WITHDRAWAL"),
+        ("direction" = Option<String>, Query, description = "in (créditos) u out (débitos)"),
+        ("min_amount" = Option<String>, Query, description = "Monto mínimo (inclusive), en la divisa de la billetera"),
+        ("max_amount" = Option<String>, Query, description = "Monto máximo (inclusive), en la divisa de la billetera")
     )
 )]
 pub async fn get_wallet_history(
     State(state): State<Arc<AppState>>,
     Path(wallet_id): Path<Uuid>,
+    Query(query): Query<WalletHistoryQuery>,
 ) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
-    let transactions = state
+    let filter = HistoryFilter {
+        from: query.from,
+        to: query.to,
+        status: query.status,
+        transaction_type: query.transaction_type,
+        direction: query.direction,
+        min_amount: query.min_amount,
+        max_amount: query.max_amount,
+    };
+    let page = state
         .get_wallet_history_use_case
-        .execute(WalletId(wallet_id))
+        .execute(WalletId(wallet_id), filter, query.cursor, query.limit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(page))))
+}
+
+// Handler: Extracto de una billetera en un período, en JSON o CSV
+// GET /transactions/wallet/{wallet_id}/statement
+#[utoipa::path(
+    get,
+    path = "/transactions/wallet/{wallet_id}/statement",
+    responses(
+        (status = 200, description = "Extracto con saldo de apertura, movimientos con saldo corrido, totales y saldo de cierre (JSON o text/csv)", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "Período inválido o billetera inexistente")
+    ),
+    params(
+        ("wallet_id" = Uuid, Path, description = "ID de la billetera"),
+        ("from" = String, Query, description = "Inicio del período (RFC 3339, inclusive)"),
+        ("to" = String, Query, description = "Fin del período (RFC 3339, exclusive)"),
+        ("format" = Option<String>, Query, description = "json (por defecto) o csv")
+    )
+)]
+pub async fn get_wallet_statement(
+    State(state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    Query(query): Query<WalletStatementQuery>,
+) -> Result<Response, ApiError> {
+    let statement = state
+        .get_wallet_statement_use_case
+        .execute(WalletId(wallet_id), query.from, query.to)
         .await?;
 
-    Ok(Json(ApiResponse::success(serde_json::json!(transactions))))
+    Ok(match query.format {
+        StatementFormat::Json => {
+            Json(ApiResponse::success(serde_json::json!(statement))).into_response()
+        }
+        StatementFormat::Csv => {
+            let filename = format!(
+                "attachment; filename=\"statement-{}-{}.csv\"",
+                wallet_id,
+                statement.period.start.format("%Y%m%d")
+            );
+            (
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (header::CONTENT_DISPOSITION, filename),
+                ],
+                statement.to_csv(),
+            )
+                .into_response()
+        }
+    })
+}
+
+// Handler: Cotizar un tipo de cambio, fijando la tasa por un tiempo limitado
+// POST /fx/quotes
+#[utoipa::path(
+    post,
+    path = "/fx/quotes",
+    request_body = CreateQuoteRequest,
+    responses(
+        (status = 200, description = "Cotización emitida", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 404, description = "No hay tipo de cambio para el par")
+    )
+)]
+pub async fn create_fx_quote(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateQuoteRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let quote = state
+        .create_fx_quote_use_case
+        .execute(&payload.source_currency, &payload.destination_currency)
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(quote))))
+}
+
+// Handler: Publicar la tasa vigente de un par de divisas (administración)
+// PUT /admin/exchange-rates/{base}/{quote}
+#[utoipa::path(
+    put,
+    path = "/admin/exchange-rates/{base}/{quote}",
+    request_body = UpdateExchangeRateRequest,
+    responses(
+        (status = 200, description = "Tipo de cambio actualizado", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("base" = String, Path, description = "Divisa base (ISO 4217)"),
+        ("quote" = String, Path, description = "Divisa cotizada (ISO 4217)")
+    )
+)]
+pub async fn update_exchange_rate(
+    State(state): State<Arc<AppState>>,
+    Path((base, quote)): Path<(String, String)>,
+    Json(payload): Json<UpdateExchangeRateRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let rate = state
+        .update_exchange_rate_use_case
+        .execute(&base, &quote, payload.rate)
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(rate))))
+}
+
+// Handler: Listar las compensaciones de saga pendientes o suspendidas (administración)
+// GET /admin/compensations?status=SUSPENDED
+#[utoipa::path(
+    get,
+    path = "/admin/compensations",
+    responses(
+        (status = 200, description = "Compensaciones registradas", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("status" = Option<String>, Query, description = "PENDING, SUSPENDED o RESOLVED")
+    )
+)]
+pub async fn list_compensations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CompensationsQuery>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let compensations = state
+        .review_compensations_use_case
+        .list(query.status)
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(compensations))))
+}
+
+// Handler: Inspeccionar una compensación (administración)
+// GET /admin/compensations/{id}
+#[utoipa::path(
+    get,
+    path = "/admin/compensations/{id}",
+    responses(
+        (status = 200, description = "Compensación encontrada", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 404, description = "Compensación no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la compensación")
+    )
+)]
+pub async fn get_compensation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let compensation = state.review_compensations_use_case.get(id).await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(compensation))))
+}
+
+// Handler: Dar por resuelta a mano una compensación (administración)
+// POST /admin/compensations/{id}/resolve
+#[utoipa::path(
+    post,
+    path = "/admin/compensations/{id}/resolve",
+    request_body = ResolveCompensationRequest,
+    responses(
+        (status = 200, description = "Compensación resuelta", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "Falta la nota o la compensación ya estaba resuelta"),
+        (status = 404, description = "Compensación no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la compensación")
+    )
+)]
+pub async fn resolve_compensation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ResolveCompensationRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let compensation = state
+        .review_compensations_use_case
+        .resolve(id, &payload.note)
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(compensation))))
+}
+
+// Handler: Volver a poner en cola una compensación (administración)
+// POST /admin/compensations/{id}/retry
+#[utoipa::path(
+    post,
+    path = "/admin/compensations/{id}/retry",
+    responses(
+        (status = 200, description = "Compensación en cola", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "La compensación ya estaba resuelta"),
+        (status = 404, description = "Compensación no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la compensación")
+    )
+)]
+pub async fn retry_compensation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let compensation = state.review_compensations_use_case.retry(id).await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(compensation))))
+}
+
+// Handler: Listar las transacciones que agotaron los reintentos (administración)
+// GET /admin/dead-letters?cursor=&limit=
+#[utoipa::path(
+    get,
+    path = "/admin/dead-letters",
+    responses(
+        (status = 200, description = "Página de transacciones en DEAD_LETTER con sus intentos y cursor de la siguiente", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "Cursor inválido")
+    ),
+    params(
+        ("cursor" = Option<String>, Query, description = "`next_cursor` de la página anterior"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (por defecto 50, máximo 200)")
+    )
+)]
+pub async fn list_dead_letters(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<DeadLettersQuery>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let page = state
+        .review_dead_letters_use_case
+        .list(query.cursor, query.limit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(page))))
+}
+
+// Handler: Inspeccionar una transacción en DEAD_LETTER (administración)
+// GET /admin/dead-letters/{id}
+#[utoipa::path(
+    get,
+    path = "/admin/dead-letters/{id}",
+    responses(
+        (status = 200, description = "Transacción con sus intentos e historial de estados", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "La transacción no está en DEAD_LETTER"),
+        (status = 404, description = "Transacción no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la transacción")
+    )
+)]
+pub async fn get_dead_letter(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let dead_letter = state
+        .review_dead_letters_use_case
+        .get(TransactionId(id))
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(dead_letter))))
+}
+
+// Handler: Devolver una transacción de DEAD_LETTER al job de reintentos (administración)
+// POST /admin/dead-letters/{id}/redrive
+#[utoipa::path(
+    post,
+    path = "/admin/dead-letters/{id}/redrive",
+    responses(
+        (status = 200, description = "Transacción de nuevo PENDING", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "La transacción no está en DEAD_LETTER"),
+        (status = 404, description = "Transacción no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la transacción")
+    )
+)]
+pub async fn redrive_dead_letter(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let transaction = state
+        .review_dead_letters_use_case
+        .redrive(TransactionId(id))
+        .await?;
+
+    Ok(Json(ApiResponse::success(serde_json::json!(transaction))))
+}
+
+/// Extrae el cliente de la API de `X-Client-Id`, si vino. Un valor vacío cuenta como ausente.
+fn client_id(headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    let Some(value) = headers.get(CLIENT_ID_HEADER) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| TransactionError::ValidationError("X-Client-Id inválido".into()))?
+        .trim();
+
+    if value.len() > MAX_CLIENT_ID_LEN {
+        return Err(TransactionError::ValidationError(format!(
+            "X-Client-Id no puede superar {} caracteres",
+            MAX_CLIENT_ID_LEN
+        ))
+        .into());
+    }
+    Ok((!value.is_empty()).then(|| value.to_string()))
 }
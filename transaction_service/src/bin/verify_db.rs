@@ -1,7 +1,10 @@
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use rust_decimal::Decimal;
 use sqlx::postgres::PgPoolOptions;
-use transaction_service::domain::entities::{Transaction, TransactionStatus, TransactionType};
+use transaction_service::domain::entities::{
+    Transaction, TransactionDetails, TransactionStatus, TransactionType,
+};
+use transaction_service::domain::error::TransactionError;
 use transaction_service::domain::repository::TransactionRepository;
 use transaction_service::domain::types::{TransactionId, WalletId};
 use transaction_service::infrastructure::persistence::transaction_repository::PostgresTransactionRepository;
@@ -37,24 +40,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let new_transaction = Transaction::reconstitute(
         TransactionId::new(),   // Random TransactionId
         Some(WalletId::new()),  // Random Source Wallet
-        WalletId::new(),        // Random Dest Wallet
+        Some(WalletId::new()),  // Random Dest Wallet
+        None,                   // Sin destino externo (no es un retiro)
         Decimal::new(10050, 2), // 100.50
         TransactionStatus::PENDING,
         TransactionType::TRANSFER,
         Utc::now(),
         Uuid::new_v4(),
+        None, // Misma divisa: sin conversión
+        None, // No es un reembolso
+        Decimal::ZERO,
+        TransactionDetails::default(), // Sin memo, referencia ni metadata
     )
     .expect("Failed to create mock transaction");
 
     println!("Attempting to save transaction: {:?}", new_transaction);
 
-    match repository.save(new_transaction.clone()).await {
+    match repository
+        .save(new_transaction.clone(), Duration::hours(24))
+        .await
+    {
         Ok(saved) => {
             println!("✅ Transaction saved successfully!");
             println!("Saved ID: {}", saved.id());
             println!("Status: {:?}", saved.status());
             println!("Amount: {}", saved.amount());
             println!("Type: {:?}", saved.transaction_type());
+            verify_lease_fence(&repository, saved).await;
         }
         Err(e) => {
             eprintln!("❌ Failed to save transaction: {:?}", e);
@@ -65,3 +77,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Comprueba que una instancia cuyo lease fue retomado por otra no puede escribir la transacción.
+async fn verify_lease_fence(repository: &PostgresTransactionRepository, saved: Transaction) {
+    let claimed = repository
+        .claim_pending_older_than(Utc::now(), "verify-db-owner", Duration::minutes(1))
+        .await
+        .expect("Failed to claim pending transactions");
+    if !claimed.iter().any(|tx| tx.id() == saved.id()) {
+        println!("⚠️ Transaction was not among the claimed ones; skipping lease check.");
+        return;
+    }
+
+    let mut failed = saved;
+    let previous = failed
+        .update_status(TransactionStatus::FAILED)
+        .expect("PENDING -> FAILED must be valid");
+
+    match repository
+        .update_claimed(failed.clone(), previous, "verify-db-stale-owner")
+        .await
+    {
+        Err(TransactionError::LeaseLost(_)) => {
+            println!("✅ Write from a stale lease owner rejected.")
+        }
+        other => eprintln!("❌ Stale lease owner was not fenced: {:?}", other),
+    }
+
+    match repository
+        .update_claimed(failed, previous, "verify-db-owner")
+        .await
+    {
+        Ok(updated) => println!("✅ Lease owner wrote status {:?}.", updated.status()),
+        Err(e) => eprintln!("❌ Lease owner could not write: {:?}", e),
+    }
+}
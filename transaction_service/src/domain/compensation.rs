@@ -0,0 +1,199 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    error::TransactionError,
+    saga::{SagaStep, SagaStepStatus},
+    types::{TransactionId, WalletId},
+};
+
+/// Estado de una compensación que no se pudo aplicar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "compensation_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompensationStatus {
+    /// El job la reintentará a partir de `next_attempt_at`.
+    PENDING,
+    /// Agotó los reintentos: espera revisión manual (cuenta de suspenso).
+    SUSPENDED,
+    /// La reserva se liberó, o un operador la dio por resuelta.
+    RESOLVED,
+}
+
+/// Cuántas veces y con qué espera se reintenta una compensación antes de escalarla.
+///
+/// La espera se duplica en cada intento fallido, desde `base_delay` hasta `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompensationPolicy {
+    /// Intentos (incluido el original) antes de pasar a revisión manual.
+    pub max_attempts: i32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl CompensationPolicy {
+    pub const DEFAULT_MAX_ATTEMPTS: i32 = 8;
+
+    /// Espera antes del siguiente intento, tras `attempts` intentos fallidos.
+    ///
+    /// # Examples
+    /// ```
+    /// use transaction_service::domain::compensation::CompensationPolicy;
+    /// use chrono::Duration;
+    ///
+    /// let policy = CompensationPolicy::default();
+    /// assert_eq!(policy.delay(1), Duration::seconds(30));
+    /// assert_eq!(policy.delay(3), Duration::seconds(120));
+    /// assert_eq!(policy.delay(20), Duration::hours(1));
+    /// ```
+    pub fn delay(&self, attempts: i32) -> Duration {
+        let exponent = attempts.clamp(1, 31) as u32 - 1;
+        self.base_delay
+            .checked_mul(2_i32.saturating_pow(exponent))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for CompensationPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+            base_delay: Duration::seconds(30),
+            max_delay: Duration::hours(1),
+        }
+    }
+}
+
+/// Liberación de una pata que falló al compensar la saga y debe reintentarse.
+///
+/// Mientras no se resuelva, los fondos de `wallet_id` siguen retenidos en el Wallet Service.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingCompensation {
+    pub id: Uuid,
+    pub transaction_id: TransactionId,
+    pub leg: i16,
+    pub wallet_id: WalletId,
+    /// Monto con signo de la pata, como en la saga.
+    pub amount: Decimal,
+    pub status: CompensationStatus,
+    /// Intentos fallidos hasta ahora (el original cuenta como el primero).
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: String,
+    /// Explicación del operador o del job al resolverla.
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PendingCompensation {
+    /// Registra la liberación fallida de `step`, lista para reintentarse de inmediato.
+    pub fn new(step: &SagaStep, error: &TransactionError) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            transaction_id: step.transaction_id,
+            leg: step.leg,
+            wallet_id: step.wallet_id,
+            amount: step.amount,
+            status: CompensationStatus::PENDING,
+            attempts: 1,
+            next_attempt_at: now,
+            last_error: error.to_string(),
+            resolution_note: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Pata de la saga a liberar.
+    pub fn step(&self) -> SagaStep {
+        SagaStep {
+            transaction_id: self.transaction_id,
+            leg: self.leg,
+            wallet_id: self.wallet_id,
+            amount: self.amount,
+            status: SagaStepStatus::SENT,
+            updated_at: self.updated_at,
+        }
+    }
+
+    /// Registra otro intento fallido: programa el siguiente según `policy` o, si se agotaron,
+    /// la pasa a revisión manual.
+    pub fn record_failure(&mut self, error: &TransactionError, policy: &CompensationPolicy) {
+        let now = Utc::now();
+        self.attempts += 1;
+        self.last_error = error.to_string();
+        self.updated_at = now;
+        if self.attempts >= policy.max_attempts {
+            self.status = CompensationStatus::SUSPENDED;
+        } else {
+            self.next_attempt_at = now + policy.delay(self.attempts);
+        }
+    }
+
+    /// Da la compensación por resuelta con la explicación `note`.
+    pub fn resolve(&mut self, note: String) -> Result<(), TransactionError> {
+        if self.status == CompensationStatus::RESOLVED {
+            return Err(TransactionError::InvalidState(format!(
+                "La compensación {} ya está resuelta",
+                self.id
+            )));
+        }
+        self.status = CompensationStatus::RESOLVED;
+        self.resolution_note = Some(note);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Vuelve a poner en cola una compensación, con los reintentos desde cero.
+    pub fn requeue(&mut self) -> Result<(), TransactionError> {
+        if self.status == CompensationStatus::RESOLVED {
+            return Err(TransactionError::InvalidState(format!(
+                "La compensación {} ya está resuelta",
+                self.id
+            )));
+        }
+        let now = Utc::now();
+        self.status = CompensationStatus::PENDING;
+        self.attempts = 0;
+        self.next_attempt_at = now;
+        self.updated_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Transaction;
+    use crate::domain::saga::Saga;
+
+    #[test]
+    fn test_compensation_is_suspended_after_max_attempts() {
+        let tx =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+        let step = &Saga::plan(&tx).steps[0];
+        let error = TransactionError::GatewayError("unavailable".into());
+        let policy = CompensationPolicy {
+            max_attempts: 3,
+            ..Default::default()
+        };
+
+        let mut compensation = PendingCompensation::new(step, &error);
+        compensation.record_failure(&error, &policy);
+        assert_eq!(compensation.status, CompensationStatus::PENDING);
+        assert!(compensation.next_attempt_at > Utc::now() + Duration::seconds(50));
+
+        compensation.record_failure(&error, &policy);
+        assert_eq!(compensation.status, CompensationStatus::SUSPENDED);
+        assert_eq!(compensation.attempts, 3);
+
+        compensation.resolve("Liberada a mano".into()).unwrap();
+        assert!(matches!(
+            compensation.requeue(),
+            Err(TransactionError::InvalidState(_))
+        ));
+    }
+}
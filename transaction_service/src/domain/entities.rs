@@ -3,6 +3,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::exchange::ExchangeDetails;
+use crate::domain::retry::{RetryPolicy, RetryState};
 use crate::domain::types::{TransactionId, WalletId};
 
 use super::error::TransactionError;
@@ -16,11 +18,47 @@ use super::error::TransactionError;
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "transaction_status", rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(non_camel_case_types)]
 pub enum TransactionStatus {
     PENDING,
     COMPLETED,
     FAILED,
     REVERSED,
+    /// El job de reintentos agotó sus intentos: espera que un operador la reenvíe.
+    DEAD_LETTER,
+}
+
+impl TransactionStatus {
+    /// Indica si la máquina de estados permite pasar de `self` a `next`.
+    ///
+    /// - `PENDING` → `COMPLETED` | `FAILED`: el Wallet Service aplicó o rechazó el movimiento.
+    /// - `COMPLETED` → `REVERSED`: la transacción quedó reembolsada por completo.
+    /// - `REVERSED` → `COMPLETED`: falló un reembolso en curso y vuelve a quedar saldo
+    ///   reembolsable.
+    /// - `PENDING` → `DEAD_LETTER`: el job de reintentos agotó sus intentos.
+    /// - `DEAD_LETTER` → `PENDING`: un operador la reenvía al job.
+    ///
+    /// `FAILED` es terminal y sólo `DEAD_LETTER` vuelve a `PENDING`.
+    ///
+    /// # Examples
+    /// ```
+    /// use transaction_service::domain::entities::TransactionStatus;
+    ///
+    /// assert!(TransactionStatus::PENDING.can_transition_to(TransactionStatus::COMPLETED));
+    /// assert!(!TransactionStatus::COMPLETED.can_transition_to(TransactionStatus::PENDING));
+    /// ```
+    pub fn can_transition_to(self, next: TransactionStatus) -> bool {
+        use TransactionStatus::*;
+        matches!(
+            (self, next),
+            (PENDING, COMPLETED)
+                | (PENDING, FAILED)
+                | (COMPLETED, REVERSED)
+                | (REVERSED, COMPLETED)
+                | (PENDING, DEAD_LETTER)
+                | (DEAD_LETTER, PENDING)
+        )
+    }
 }
 
 /// Tipo de transacción financiera.
@@ -38,6 +76,99 @@ pub enum TransactionType {
     WITHDRAWAL,
 }
 
+/// Datos descriptivos que el cliente adjunta a una transacción.
+///
+/// No intervienen en el movimiento de fondos: sirven para conciliar la transacción con los
+/// sistemas del cliente (factura, pedido) y se devuelven tal cual en detalle e historial.
+///
+/// # Examples
+/// ```
+/// use transaction_service::domain::entities::TransactionDetails;
+/// use serde_json::json;
+///
+/// let details = TransactionDetails::new(
+///     Some("Pago de almuerzo".into()),
+///     Some(" INV-2024-001 ".into()),
+///     json!({ "order_id": 42 }).as_object().cloned().unwrap(),
+/// )
+/// .unwrap();
+/// assert_eq!(details.external_reference.as_deref(), Some("INV-2024-001"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionDetails {
+    /// Memo del pagador.
+    pub description: Option<String>,
+    /// Referencia en el sistema del cliente (número de factura, id de pedido, etc.).
+    pub external_reference: Option<String>,
+    /// Pares clave/valor arbitrarios.
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+impl TransactionDetails {
+    pub const MAX_DESCRIPTION_LEN: usize = 500;
+    pub const MAX_REFERENCE_LEN: usize = 255;
+    pub const MAX_METADATA_KEYS: usize = 50;
+    pub const MAX_METADATA_KEY_LEN: usize = 64;
+
+    /// Valida y normaliza los datos: recorta espacios (un texto vacío equivale a omitirlo) y
+    /// limita longitudes y cantidad de claves.
+    pub fn new(
+        description: Option<String>,
+        external_reference: Option<String>,
+        metadata: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Self, TransactionError> {
+        let description = Self::normalize(description, "descripción", Self::MAX_DESCRIPTION_LEN)?;
+        let external_reference = Self::normalize(
+            external_reference,
+            "referencia externa",
+            Self::MAX_REFERENCE_LEN,
+        )?;
+
+        if metadata.len() > Self::MAX_METADATA_KEYS {
+            return Err(TransactionError::ValidationError(format!(
+                "La metadata admite como máximo {} claves",
+                Self::MAX_METADATA_KEYS
+            )));
+        }
+        if let Some(key) = metadata
+            .keys()
+            .find(|k| k.trim().is_empty() || k.chars().count() > Self::MAX_METADATA_KEY_LEN)
+        {
+            return Err(TransactionError::ValidationError(format!(
+                "Clave de metadata inválida: '{}' (1 a {} caracteres)",
+                key,
+                Self::MAX_METADATA_KEY_LEN
+            )));
+        }
+
+        Ok(Self {
+            description,
+            external_reference,
+            metadata,
+        })
+    }
+
+    fn normalize(
+        value: Option<String>,
+        field: &str,
+        max_len: usize,
+    ) -> Result<Option<String>, TransactionError> {
+        let Some(value) = value.map(|v| v.trim().to_string()) else {
+            return Ok(None);
+        };
+        if value.is_empty() {
+            return Ok(None);
+        }
+        if value.chars().count() > max_len {
+            return Err(TransactionError::ValidationError(format!(
+                "La {} no puede superar los {} caracteres",
+                field, max_len
+            )));
+        }
+        Ok(Some(value))
+    }
+}
+
 /// Entidad que representa una transacción financiera entre billeteras.
 ///
 /// # Examples
@@ -52,13 +183,28 @@ pub enum TransactionType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     id: TransactionId,
-    source_wallet_id: Option<WalletId>, // Nullable
-    destination_wallet_id: WalletId,
+    source_wallet_id: Option<WalletId>, // Nullable: los depósitos vienen de fuera
+    destination_wallet_id: Option<WalletId>, // Nullable: los retiros salen hacia fuera
+    external_destination: Option<String>, // Sólo en retiros: cuenta/referencia externa
     amount: Decimal,
     status: TransactionStatus,
     transaction_type: TransactionType,
     created_at: DateTime<Utc>,
-    correlation_id: Uuid, // Ya no es opcional
+    correlation_id: Uuid,                         // Ya no es opcional
+    exchange: Option<ExchangeDetails>,            // Sólo en transferencias entre divisas
+    parent_transaction_id: Option<TransactionId>, // Sólo en reembolsos: transacción reembolsada
+    refunded_amount: Decimal, // Acumulado reembolsado (o en curso), en unidades de `amount`
+    #[serde(flatten)]
+    details: TransactionDetails, // Memo, referencia externa y metadata del cliente
+    // Cliente de la API que la inició (header `X-Client-Id`). No se serializa: cualquiera que
+    // conozca el id de la transacción la consulta, y el cliente es lo que autoriza a buscarla
+    // por correlation_id.
+    #[serde(skip)]
+    client_id: Option<String>,
+    #[serde(skip)]
+    request_fingerprint: Option<String>, // Huella de la solicitud original (idempotencia)
+    #[serde(skip)]
+    retry: RetryState, // Intentos del job de reintentos (sólo visibles para administración)
 }
 
 impl Transaction {
@@ -104,46 +250,148 @@ impl Transaction {
         Ok(Self {
             id: TransactionId::new(),
             source_wallet_id: source_wallet,
-            destination_wallet_id: dest_wallet,
+            destination_wallet_id: Some(dest_wallet),
+            external_destination: None,
             amount,
             status: TransactionStatus::PENDING,
             transaction_type: tx_type,
             created_at: Utc::now(),
             correlation_id,
+            exchange: None,
+            parent_transaction_id: None,
+            refunded_amount: Decimal::ZERO,
+            details: TransactionDetails::default(),
+            client_id: None,
+            request_fingerprint: None,
+            retry: RetryState::default(),
+        })
+    }
+
+    /// Inicializa un retiro: fondos que salen de `source_wallet` hacia un destino externo
+    /// (cuenta bancaria, procesador de pagos, etc.) identificado por `external_destination`.
+    ///
+    /// # Examples
+    /// ```
+    /// use transaction_service::domain::entities::{Transaction, TransactionType};
+    /// use transaction_service::domain::types::WalletId;
+    /// use uuid::Uuid;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let tx = Transaction::new_withdrawal(
+    ///     WalletId::new(),
+    ///     "IBAN:ES9121000418450200051332".to_string(),
+    ///     Decimal::from(50),
+    ///     Uuid::new_v4(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(tx.transaction_type(), TransactionType::WITHDRAWAL);
+    /// assert_eq!(tx.destination_wallet_id(), None);
+    /// ```
+    pub fn new_withdrawal(
+        source_wallet: WalletId,
+        external_destination: String,
+        amount: Decimal,
+        correlation_id: Uuid,
+    ) -> Result<Self, TransactionError> {
+        if amount <= Decimal::ZERO {
+            return Err(TransactionError::InvalidAmount);
+        }
+        let external_destination = Self::validate_external_destination(external_destination)?;
+
+        Ok(Self {
+            id: TransactionId::new(),
+            source_wallet_id: Some(source_wallet),
+            destination_wallet_id: None,
+            external_destination: Some(external_destination),
+            amount,
+            status: TransactionStatus::PENDING,
+            transaction_type: TransactionType::WITHDRAWAL,
+            created_at: Utc::now(),
+            correlation_id,
+            exchange: None,
+            parent_transaction_id: None,
+            refunded_amount: Decimal::ZERO,
+            details: TransactionDetails::default(),
+            client_id: None,
+            request_fingerprint: None,
+            retry: RetryState::default(),
         })
     }
 
     /// Reconstruye una instancia de `Transaction` desde los datos persistidos.
+    ///
+    /// Verifica que las billeteras presentes correspondan al tipo de transacción.
+    #[allow(clippy::too_many_arguments)]
     pub fn reconstitute(
         id: TransactionId,
         source_wallet_id: Option<WalletId>,
-        destination_wallet_id: WalletId,
+        destination_wallet_id: Option<WalletId>,
+        external_destination: Option<String>,
         amount: Decimal,
         status: TransactionStatus,
         transaction_type: TransactionType,
         created_at: DateTime<Utc>,
         correlation_id: Uuid,
+        exchange: Option<ExchangeDetails>,
+        parent_transaction_id: Option<TransactionId>,
+        refunded_amount: Decimal,
+        details: TransactionDetails,
     ) -> Result<Self, TransactionError> {
         if amount <= Decimal::ZERO {
             return Err(TransactionError::InvalidAmount);
         }
-        if let Some(src) = source_wallet_id {
-            if src == destination_wallet_id {
-                return Err(TransactionError::SameWallet);
+        let shape_matches = match transaction_type {
+            TransactionType::DEPOSIT => {
+                source_wallet_id.is_none()
+                    && destination_wallet_id.is_some()
+                    && external_destination.is_none()
+            }
+            TransactionType::TRANSFER => {
+                source_wallet_id.is_some()
+                    && destination_wallet_id.is_some()
+                    && external_destination.is_none()
             }
+            TransactionType::WITHDRAWAL => {
+                source_wallet_id.is_some()
+                    && destination_wallet_id.is_none()
+                    && external_destination.is_some()
+            }
+        };
+        if !shape_matches {
+            return Err(TransactionError::InvalidState(format!(
+                "Billeteras inconsistentes con una transacción {:?}",
+                transaction_type
+            )));
+        }
+        if source_wallet_id.is_some() && source_wallet_id == destination_wallet_id {
+            return Err(TransactionError::SameWallet);
         }
         Ok(Self {
             id,
             source_wallet_id,
             destination_wallet_id,
+            external_destination,
             amount,
             status,
             transaction_type,
             created_at,
             correlation_id,
+            exchange,
+            parent_transaction_id,
+            refunded_amount,
+            details,
+            client_id: None,
+            request_fingerprint: None,
+            retry: RetryState::default(),
         })
     }
 
+    /// Adjunta el memo, la referencia externa y la metadata del cliente.
+    pub fn with_details(mut self, details: TransactionDetails) -> Self {
+        self.details = details;
+        self
+    }
+
     pub fn id(&self) -> TransactionId {
         self.id
     }
@@ -152,10 +400,16 @@ impl Transaction {
         self.source_wallet_id
     }
 
-    pub fn destination_wallet_id(&self) -> WalletId {
+    /// Billetera acreditada; `None` en retiros.
+    pub fn destination_wallet_id(&self) -> Option<WalletId> {
         self.destination_wallet_id
     }
 
+    /// Destino externo de un retiro; `None` en depósitos y transferencias.
+    pub fn external_destination(&self) -> Option<&str> {
+        self.external_destination.as_deref()
+    }
+
     pub fn amount(&self) -> Decimal {
         self.amount
     }
@@ -176,8 +430,249 @@ impl Transaction {
         self.correlation_id
     }
 
-    pub fn update_status(&mut self, new_status: TransactionStatus) {
+    pub fn exchange(&self) -> Option<&ExchangeDetails> {
+        self.exchange.as_ref()
+    }
+
+    /// Monto que se acredita en la billetera destino: el convertido si hubo cambio de divisa,
+    /// o el mismo monto de origen en caso contrario.
+    pub fn destination_amount(&self) -> Decimal {
+        self.exchange
+            .as_ref()
+            .map_or(self.amount, |fx| fx.destination_amount)
+    }
+
+    /// Registra la conversión de divisa aplicada a una transferencia.
+    ///
+    /// Sólo se admite sobre transferencias pendientes y con un monto destino positivo.
+    pub fn apply_exchange(&mut self, exchange: ExchangeDetails) -> Result<(), TransactionError> {
+        if self.transaction_type != TransactionType::TRANSFER {
+            return Err(TransactionError::ValidationError(
+                "Sólo las transferencias admiten conversión de divisa".into(),
+            ));
+        }
+        if self.status != TransactionStatus::PENDING {
+            return Err(TransactionError::InvalidState(format!(
+                "No se puede aplicar una conversión a una transacción {:?}",
+                self.status
+            )));
+        }
+        if exchange.destination_amount <= Decimal::ZERO {
+            return Err(TransactionError::ValidationError(
+                "El monto convertido es demasiado pequeño para la divisa destino".into(),
+            ));
+        }
+        self.exchange = Some(exchange);
+        Ok(())
+    }
+
+    /// Transacción original de la que este es un reembolso.
+    pub fn parent_transaction_id(&self) -> Option<TransactionId> {
+        self.parent_transaction_id
+    }
+
+    pub fn details(&self) -> &TransactionDetails {
+        &self.details
+    }
+
+    /// Registra el cliente de la API que inició la transacción.
+    pub fn with_client_id(mut self, client_id: Option<String>) -> Self {
+        self.client_id = client_id;
+        self
+    }
+
+    pub fn client_id(&self) -> Option<&str> {
+        self.client_id.as_deref()
+    }
+
+    /// Registra la huella de la solicitud que creó la transacción (ver `RequestSummary`).
+    pub fn with_request_fingerprint(mut self, fingerprint: Option<String>) -> Self {
+        self.request_fingerprint = fingerprint;
+        self
+    }
+
+    /// Huella guardada de la solicitud original; `None` en transacciones anteriores a su registro.
+    pub fn request_fingerprint(&self) -> Option<&str> {
+        self.request_fingerprint.as_deref()
+    }
+
+    /// Restaura los intentos del job de reintentos.
+    pub fn with_retry_state(mut self, retry: RetryState) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn retry_state(&self) -> &RetryState {
+        &self.retry
+    }
+
+    /// Registra un intento fallido del job de reintentos sobre una transacción PENDING.
+    ///
+    /// Programa el siguiente intento según `policy` o, si se agotaron, la pasa a
+    /// `DEAD_LETTER`. Como `update_status`, retorna el estado anterior para el
+    /// compare-and-set del repositorio.
+    pub fn record_retry_failure(
+        &mut self,
+        error: &TransactionError,
+        policy: &RetryPolicy,
+    ) -> Result<TransactionStatus, TransactionError> {
+        if self.status != TransactionStatus::PENDING {
+            return Err(TransactionError::InvalidState(format!(
+                "Sólo se reintentan transacciones PENDING; la transacción {} está {:?}",
+                self.id, self.status
+            )));
+        }
+        if self.retry.record_failure(error, policy) {
+            return self.update_status(TransactionStatus::DEAD_LETTER);
+        }
+        Ok(self.status)
+    }
+
+    /// Devuelve una transacción de `DEAD_LETTER` al job de reintentos, con los intentos desde
+    /// cero.
+    pub fn requeue(&mut self) -> Result<TransactionStatus, TransactionError> {
+        if self.status != TransactionStatus::DEAD_LETTER {
+            return Err(TransactionError::InvalidState(format!(
+                "Sólo se reenvían transacciones DEAD_LETTER; la transacción {} está {:?}",
+                self.id, self.status
+            )));
+        }
+        let previous = self.update_status(TransactionStatus::PENDING)?;
+        self.retry.reset();
+        Ok(previous)
+    }
+
+    /// Indica si `client_id` puede consultar la transacción: sólo el cliente que la inició.
+    /// Las transacciones sin cliente registrado (`client_id()` es `None`) no son visibles para
+    /// nadie.
+    pub fn is_visible_to(&self, client_id: &str) -> bool {
+        self.client_id.as_deref() == Some(client_id)
+    }
+
+    /// Monto reembolsado acumulado, incluidos los reembolsos aún en curso.
+    pub fn refunded_amount(&self) -> Decimal {
+        self.refunded_amount
+    }
+
+    /// Monto que todavía puede reembolsarse.
+    pub fn refundable_amount(&self) -> Decimal {
+        self.amount - self.refunded_amount
+    }
+
+    /// Construye el reembolso (total o parcial) de esta transacción como una transacción hija.
+    ///
+    /// `amount` se expresa en las mismas unidades que `amount()` de la original y se valida
+    /// contra lo que queda por reembolsar. El hijo recorre el camino inverso:
+    /// - Transferencia A -> B: transferencia B -> A. Si hubo cambio de divisa, se debita de B
+    ///   la parte proporcional del monto convertido (truncada a `destination_minor_units`) y se
+    ///   acredita `amount` en A, con la tasa original.
+    /// - Depósito a B: retiro desde B hacia el origen externo del depósito.
+    pub fn refund(
+        &self,
+        amount: Decimal,
+        correlation_id: Uuid,
+        destination_minor_units: u32,
+    ) -> Result<Transaction, TransactionError> {
+        if self.parent_transaction_id.is_some() {
+            return Err(TransactionError::ValidationError(
+                "Un reembolso no puede reembolsarse".into(),
+            ));
+        }
+        if self.status != TransactionStatus::COMPLETED {
+            return Err(TransactionError::InvalidState(format!(
+                "Sólo se reembolsan transacciones COMPLETED; la transacción {} está {:?}",
+                self.id, self.status
+            )));
+        }
+        if amount <= Decimal::ZERO {
+            return Err(TransactionError::InvalidAmount);
+        }
+        if amount > self.refundable_amount() {
+            return Err(TransactionError::RefundExceeded(
+                self.id,
+                self.refundable_amount(),
+            ));
+        }
+
+        let mut child = match (
+            self.transaction_type,
+            self.source_wallet_id,
+            self.destination_wallet_id,
+        ) {
+            (TransactionType::TRANSFER, Some(source), Some(destination)) => {
+                // Porción del monto convertido que corresponde a lo reembolsado, calculada sobre
+                // los acumulados para que la suma de reembolsos parciales dé el total exacto.
+                let debit = match &self.exchange {
+                    Some(fx) => {
+                        let portion = |refunded: Decimal| {
+                            (fx.destination_amount * refunded / self.amount).round_dp_with_strategy(
+                                destination_minor_units,
+                                rust_decimal::RoundingStrategy::ToZero,
+                            )
+                        };
+                        portion(self.refunded_amount + amount) - portion(self.refunded_amount)
+                    }
+                    None => amount,
+                };
+                Transaction::new(Some(destination), source, debit, correlation_id)?
+            }
+            (TransactionType::DEPOSIT, None, Some(destination)) => Transaction::new_withdrawal(
+                destination,
+                format!("REFUND:{}", self.id),
+                amount,
+                correlation_id,
+            )?,
+            _ => {
+                return Err(TransactionError::ValidationError(format!(
+                    "Las transacciones {:?} no admiten reembolso",
+                    self.transaction_type
+                )))
+            }
+        };
+
+        if let Some(fx) = &self.exchange {
+            child.apply_exchange(ExchangeDetails {
+                source_currency: fx.destination_currency.clone(),
+                destination_currency: fx.source_currency.clone(),
+                destination_amount: amount,
+                rate: Decimal::ONE / fx.rate,
+                rate_timestamp: fx.rate_timestamp,
+                quote_id: fx.quote_id,
+            })?;
+        }
+        child.parent_transaction_id = Some(self.id);
+
+        Ok(child)
+    }
+
+    fn validate_external_destination(destination: String) -> Result<String, TransactionError> {
+        let destination = destination.trim().to_string();
+        if destination.is_empty() || destination.len() > 255 {
+            return Err(TransactionError::ValidationError(
+                "El destino externo del retiro debe tener entre 1 y 255 caracteres".into(),
+            ));
+        }
+        Ok(destination)
+    }
+
+    /// Cambia el estado de la transacción respetando la máquina de estados.
+    ///
+    /// Retorna el estado anterior, que el repositorio usa como precondición
+    /// (compare-and-set) al persistir el cambio, o `TransactionError::InvalidState` si la
+    /// transición no está permitida.
+    pub fn update_status(
+        &mut self,
+        new_status: TransactionStatus,
+    ) -> Result<TransactionStatus, TransactionError> {
+        if !self.status.can_transition_to(new_status) {
+            return Err(TransactionError::InvalidState(format!(
+                "La transacción {} no puede pasar de {:?} a {:?}",
+                self.id, self.status, new_status
+            )));
+        }
+        let previous = self.status;
         self.status = new_status;
+        Ok(previous)
     }
 }
 
@@ -200,7 +695,7 @@ mod tests {
         assert_eq!(tx.status(), TransactionStatus::PENDING);
         assert_eq!(tx.amount(), amount);
         assert_eq!(tx.source_wallet_id(), Some(source));
-        assert_eq!(tx.destination_wallet_id(), dest);
+        assert_eq!(tx.destination_wallet_id(), Some(dest));
     }
 
     #[test]
@@ -240,4 +735,291 @@ mod tests {
 
         assert_eq!(result.unwrap_err(), TransactionError::SameWallet);
     }
+
+    #[test]
+    fn test_create_withdrawal_success() {
+        let source = WalletId::new();
+
+        let tx = Transaction::new_withdrawal(
+            source,
+            "  bank:0042-123456  ".into(),
+            Decimal::from(75),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        assert_eq!(tx.transaction_type(), TransactionType::WITHDRAWAL);
+        assert_eq!(tx.source_wallet_id(), Some(source));
+        assert_eq!(tx.destination_wallet_id(), None);
+        assert_eq!(tx.external_destination(), Some("bank:0042-123456"));
+    }
+
+    #[test]
+    fn test_create_withdrawal_without_destination_fails() {
+        let result = Transaction::new_withdrawal(
+            WalletId::new(),
+            " ".into(),
+            Decimal::from(75),
+            Uuid::new_v4(),
+        );
+
+        assert!(matches!(result, Err(TransactionError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_reconstitute_rejects_withdrawal_with_destination_wallet() {
+        let result = Transaction::reconstitute(
+            TransactionId::new(),
+            Some(WalletId::new()),
+            Some(WalletId::new()),
+            Some("bank:1".into()),
+            Decimal::from(10),
+            TransactionStatus::PENDING,
+            TransactionType::WITHDRAWAL,
+            Utc::now(),
+            Uuid::new_v4(),
+            None,
+            None,
+            Decimal::ZERO,
+            TransactionDetails::default(),
+        );
+
+        assert!(matches!(result, Err(TransactionError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_details_blank_text_is_omitted() {
+        let details = TransactionDetails::new(
+            Some("   ".into()),
+            Some(" ORD-7 ".into()),
+            serde_json::Map::new(),
+        )
+        .unwrap();
+
+        assert_eq!(details.description, None);
+        assert_eq!(details.external_reference.as_deref(), Some("ORD-7"));
+    }
+
+    #[test]
+    fn test_details_reject_oversized_fields() {
+        let long_reference = "x".repeat(TransactionDetails::MAX_REFERENCE_LEN + 1);
+        let mut metadata = serde_json::Map::new();
+        metadata.insert(" ".into(), serde_json::Value::Bool(true));
+
+        let reference_result =
+            TransactionDetails::new(None, Some(long_reference), serde_json::Map::new());
+        let metadata_result = TransactionDetails::new(None, None, metadata);
+
+        assert!(matches!(
+            reference_result,
+            Err(TransactionError::ValidationError(_))
+        ));
+        assert!(matches!(
+            metadata_result,
+            Err(TransactionError::ValidationError(_))
+        ));
+    }
+
+    #[rstest]
+    #[case(TransactionStatus::PENDING, TransactionStatus::COMPLETED)]
+    #[case(TransactionStatus::PENDING, TransactionStatus::FAILED)]
+    #[case(TransactionStatus::COMPLETED, TransactionStatus::REVERSED)]
+    #[case(TransactionStatus::REVERSED, TransactionStatus::COMPLETED)]
+    #[case(TransactionStatus::PENDING, TransactionStatus::DEAD_LETTER)]
+    #[case(TransactionStatus::DEAD_LETTER, TransactionStatus::PENDING)]
+    fn test_allowed_status_transitions(
+        #[case] from: TransactionStatus,
+        #[case] to: TransactionStatus,
+    ) {
+        assert!(from.can_transition_to(to));
+    }
+
+    #[rstest]
+    #[case(TransactionStatus::COMPLETED, TransactionStatus::PENDING)]
+    #[case(TransactionStatus::FAILED, TransactionStatus::COMPLETED)]
+    #[case(TransactionStatus::FAILED, TransactionStatus::PENDING)]
+    #[case(TransactionStatus::COMPLETED, TransactionStatus::FAILED)]
+    #[case(TransactionStatus::PENDING, TransactionStatus::REVERSED)]
+    #[case(TransactionStatus::PENDING, TransactionStatus::PENDING)]
+    #[case(TransactionStatus::DEAD_LETTER, TransactionStatus::COMPLETED)]
+    fn test_forbidden_status_transitions(
+        #[case] from: TransactionStatus,
+        #[case] to: TransactionStatus,
+    ) {
+        assert!(!from.can_transition_to(to));
+    }
+
+    #[test]
+    fn test_update_status_returns_previous_and_rejects_invalid_transition() {
+        let mut tx =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+
+        let previous = tx.update_status(TransactionStatus::FAILED).unwrap();
+        let result = tx.update_status(TransactionStatus::COMPLETED);
+
+        assert_eq!(previous, TransactionStatus::PENDING);
+        assert!(matches!(result, Err(TransactionError::InvalidState(_))));
+        assert_eq!(tx.status(), TransactionStatus::FAILED);
+    }
+
+    #[test]
+    fn test_exhausted_retries_dead_letter_until_requeued() {
+        let mut tx =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..Default::default()
+        };
+        let error = TransactionError::GatewayError("unavailable".into());
+
+        assert!(matches!(
+            tx.requeue(),
+            Err(TransactionError::InvalidState(_))
+        ));
+        assert_eq!(
+            tx.record_retry_failure(&error, &policy),
+            Ok(TransactionStatus::PENDING)
+        );
+        assert_eq!(tx.status(), TransactionStatus::PENDING);
+        assert_eq!(
+            tx.record_retry_failure(&error, &policy),
+            Ok(TransactionStatus::PENDING)
+        );
+        assert_eq!(tx.status(), TransactionStatus::DEAD_LETTER);
+
+        assert_eq!(tx.requeue(), Ok(TransactionStatus::DEAD_LETTER));
+        assert_eq!(tx.status(), TransactionStatus::PENDING);
+        assert_eq!(tx.retry_state().attempts, 0);
+    }
+
+    fn completed(mut tx: Transaction) -> Transaction {
+        tx.update_status(TransactionStatus::COMPLETED).unwrap();
+        tx
+    }
+
+    #[test]
+    fn test_refund_transfer_reverses_wallets() {
+        let source = WalletId::new();
+        let dest = WalletId::new();
+        let original = completed(
+            Transaction::new(Some(source), dest, Decimal::from(100), Uuid::new_v4()).unwrap(),
+        );
+
+        let child = original
+            .refund(Decimal::from(40), Uuid::new_v4(), 2)
+            .unwrap();
+
+        assert_eq!(child.transaction_type(), TransactionType::TRANSFER);
+        assert_eq!(child.source_wallet_id(), Some(dest));
+        assert_eq!(child.destination_wallet_id(), Some(source));
+        assert_eq!(child.amount(), Decimal::from(40));
+        assert_eq!(child.parent_transaction_id(), Some(original.id()));
+    }
+
+    #[test]
+    fn test_refund_deposit_withdraws_to_origin() {
+        let dest = WalletId::new();
+        let original =
+            completed(Transaction::new(None, dest, Decimal::from(100), Uuid::new_v4()).unwrap());
+
+        let child = original
+            .refund(Decimal::from(100), Uuid::new_v4(), 2)
+            .unwrap();
+
+        assert_eq!(child.transaction_type(), TransactionType::WITHDRAWAL);
+        assert_eq!(child.source_wallet_id(), Some(dest));
+    }
+
+    #[test]
+    fn test_refund_cannot_exceed_refundable_amount() {
+        let original = completed(
+            Transaction::new(None, WalletId::new(), Decimal::from(100), Uuid::new_v4()).unwrap(),
+        );
+
+        let result = original.refund(Decimal::from(101), Uuid::new_v4(), 2);
+
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::RefundExceeded(original.id(), Decimal::from(100))
+        );
+    }
+
+    #[test]
+    fn test_refund_pending_transaction_fails() {
+        let original =
+            Transaction::new(None, WalletId::new(), Decimal::from(100), Uuid::new_v4()).unwrap();
+
+        let result = original.refund(Decimal::from(10), Uuid::new_v4(), 2);
+
+        assert!(matches!(result, Err(TransactionError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_partial_refunds_of_cross_currency_transfer_add_up() {
+        let mut original = Transaction::new(
+            Some(WalletId::new()),
+            WalletId::new(),
+            Decimal::from(3),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        original
+            .apply_exchange(ExchangeDetails {
+                source_currency: "USD".into(),
+                destination_currency: "COP".into(),
+                destination_amount: Decimal::new(1000001, 2),
+                rate: Decimal::new(333333667, 5),
+                rate_timestamp: Utc::now(),
+                quote_id: crate::domain::types::QuoteId::new(),
+            })
+            .unwrap();
+        let mut original = completed(original);
+
+        let first = original.refund(Decimal::ONE, Uuid::new_v4(), 2).unwrap();
+        original.refunded_amount = Decimal::ONE;
+        let second = original
+            .refund(Decimal::from(2), Uuid::new_v4(), 2)
+            .unwrap();
+
+        assert_eq!(first.amount(), Decimal::new(333333, 2));
+        assert_eq!(first.destination_amount(), Decimal::ONE);
+        assert_eq!(first.amount() + second.amount(), Decimal::new(1000001, 2));
+    }
+
+    #[test]
+    fn test_apply_exchange_sets_destination_amount() {
+        let mut tx = Transaction::new(
+            Some(WalletId::new()),
+            WalletId::new(),
+            Decimal::from(10),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        assert_eq!(tx.destination_amount(), Decimal::from(10));
+
+        tx.apply_exchange(ExchangeDetails {
+            source_currency: "USD".into(),
+            destination_currency: "COP".into(),
+            destination_amount: Decimal::from(40000),
+            rate: Decimal::from(4000),
+            rate_timestamp: Utc::now(),
+            quote_id: crate::domain::types::QuoteId::new(),
+        })
+        .unwrap();
+
+        assert_eq!(tx.amount(), Decimal::from(10));
+        assert_eq!(tx.destination_amount(), Decimal::from(40000));
+    }
+
+    #[test]
+    fn test_client_id_is_not_serialized() {
+        let tx = Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4())
+            .unwrap()
+            .with_client_id(Some("shop-a".into()));
+
+        let json = serde_json::to_value(&tx).unwrap();
+
+        assert!(json.get("client_id").is_none());
+        assert_eq!(tx.client_id(), Some("shop-a"));
+    }
 }
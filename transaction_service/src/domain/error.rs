@@ -1,7 +1,8 @@
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::domain::types::TransactionId;
+use crate::domain::idempotency::RequestSummary;
+use crate::domain::types::{QuoteId, TransactionId, WalletId};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum TransactionError {
@@ -23,9 +24,74 @@ pub enum TransactionError {
     #[error("Source and destination wallets must be different")]
     SameWallet,
 
+    #[error("No transaction found with correlation_id {0}")]
+    CorrelationNotFound(Uuid),
+
+    #[error("Transaction {0} is claimed by another instance")]
+    LeaseLost(TransactionId),
+
+    #[error("Pending compensation not found with ID: {0}")]
+    CompensationNotFound(Uuid),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Transaction with correlation_id {0} was created without a client id and cannot be recovered by correlation_id")]
+    ClientNotRecorded(Uuid),
+
     #[error("Idempotency conflict: Transaction with correlation_id {0} already exists")]
     IdempotencyError(Uuid),
 
+    #[error("Idempotency conflict: correlation_id {correlation_id} was already used with a different request")]
+    IdempotencyConflict {
+        correlation_id: Uuid,
+        original: Box<RequestSummary>,
+        received: Box<RequestSummary>,
+    },
+
+    #[error("Insufficient funds in wallet {0}")]
+    InsufficientFunds(WalletId),
+
+    #[error("Spending limit exceeded for wallet {0}: {1}")]
+    LimitExceeded(WalletId, String),
+
+    #[error("Wallet {0} is frozen or closed")]
+    WalletNotActive(WalletId),
+
+    #[error("Currency mismatch: source wallet uses {0} and destination wallet uses {1}; an FX quote is required")]
+    CurrencyMismatch(String, String),
+
+    #[error("No exchange rate available from {0} to {1}")]
+    ExchangeRateNotFound(String, String),
+
+    #[error("FX quote not found with ID: {0}")]
+    QuoteNotFound(QuoteId),
+
+    #[error("FX quote {0} has expired")]
+    QuoteExpired(QuoteId),
+
+    #[error("Refund exceeds what remains refundable for transaction {0} ({1})")]
+    RefundExceeded(TransactionId, rust_decimal::Decimal),
+
     #[error("Wallet Gateway error: {0}")]
     GatewayError(String),
+
+    #[error(
+        "Transaction {0} was interrupted in the Wallet Service and will be completed in background"
+    )]
+    SagaIncomplete(TransactionId),
+}
+
+impl TransactionError {
+    /// Si es un rechazo de negocio del Wallet Service (fondos, límites, estado de la billetera,
+    /// monto inválido), a diferencia de un fallo de comunicación o de infraestructura.
+    pub fn is_rejection(&self) -> bool {
+        matches!(
+            self,
+            TransactionError::InsufficientFunds(_)
+                | TransactionError::LimitExceeded(..)
+                | TransactionError::WalletNotActive(_)
+                | TransactionError::ValidationError(_)
+        )
+    }
 }
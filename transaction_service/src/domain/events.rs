@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Transaction, TransactionStatus},
+    error::TransactionError,
+    types::TransactionId,
+};
+
+/// Quién provocó un cambio de estado de una transacción.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(
+    type_name = "transaction_event_actor",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum EventActor {
+    /// Una petición HTTP de un cliente.
+    Api,
+    /// El job que recupera transacciones atascadas en PENDING.
+    RetryJob,
+    /// Un operador desde los endpoints de administración.
+    Admin,
+}
+
+/// Registro de un cambio de estado de una transacción (historial de auditoría).
+///
+/// `from_status` es `None` en el evento de creación. `gateway_message` resume lo que respondió
+/// el Wallet Service y `error_detail` el motivo del fallo, si lo hubo.
+///
+/// # Examples
+/// ```
+/// use transaction_service::domain::entities::{Transaction, TransactionStatus};
+/// use transaction_service::domain::events::{EventActor, TransactionEvent};
+/// use transaction_service::domain::types::WalletId;
+/// use rust_decimal::Decimal;
+/// use uuid::Uuid;
+///
+/// let tx = Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+/// let event = TransactionEvent::created(&tx, EventActor::Api);
+/// assert_eq!(event.from_status, None);
+/// assert_eq!(event.to_status, TransactionStatus::PENDING);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionEvent {
+    pub id: Uuid,
+    pub transaction_id: TransactionId,
+    pub from_status: Option<TransactionStatus>,
+    pub to_status: TransactionStatus,
+    pub actor: EventActor,
+    pub gateway_message: Option<String>,
+    pub error_detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TransactionEvent {
+    /// Evento de un cambio de estado `from_status` → `to_status`.
+    pub fn new(
+        transaction_id: TransactionId,
+        from_status: Option<TransactionStatus>,
+        to_status: TransactionStatus,
+        actor: EventActor,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            transaction_id,
+            from_status,
+            to_status,
+            actor,
+            gateway_message: None,
+            error_detail: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Evento de registro inicial de la transacción, en su estado actual.
+    pub fn created(transaction: &Transaction, actor: EventActor) -> Self {
+        Self::new(transaction.id(), None, transaction.status(), actor)
+    }
+
+    /// Evento de la transición que acaba de aplicarse sobre `transaction`.
+    pub fn transition(
+        transaction: &Transaction,
+        previous: TransactionStatus,
+        actor: EventActor,
+    ) -> Self {
+        Self::new(
+            transaction.id(),
+            Some(previous),
+            transaction.status(),
+            actor,
+        )
+    }
+
+    pub fn with_gateway_message(mut self, message: impl Into<String>) -> Self {
+        self.gateway_message = Some(message.into());
+        self
+    }
+
+    pub fn with_error(mut self, error: &TransactionError) -> Self {
+        self.error_detail = Some(error.to_string());
+        self
+    }
+
+    /// Completa el evento con el resultado de la saga en el Wallet Service.
+    pub fn with_gateway_result(self, result: &Result<bool, TransactionError>) -> Self {
+        match result {
+            Ok(true) => self.with_gateway_message("Movimiento aplicado por el Wallet Service"),
+            Ok(false) => self.with_gateway_message("Movimiento rechazado por el Wallet Service"),
+            Err(e @ TransactionError::GatewayError(_)) => self
+                .with_gateway_message("Sin respuesta válida del Wallet Service")
+                .with_error(e),
+            Err(e) => self
+                .with_gateway_message("Movimiento rechazado por el Wallet Service")
+                .with_error(e),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use tonic::async_trait;
+
+use crate::domain::{error::TransactionError, types::QuoteId};
+
+/// Tipo de cambio vigente: cuántas unidades de `quote_currency` vale una de `base_currency`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: Decimal,
+    pub as_of: DateTime<Utc>,
+}
+
+impl ExchangeRate {
+    /// Crea un tipo de cambio validando los códigos de divisa y que la tasa sea positiva.
+    pub fn new(
+        base_currency: &str,
+        quote_currency: &str,
+        rate: Decimal,
+        as_of: DateTime<Utc>,
+    ) -> Result<Self, TransactionError> {
+        let base_currency = normalize_currency(base_currency)?;
+        let quote_currency = normalize_currency(quote_currency)?;
+        if base_currency == quote_currency {
+            return Err(TransactionError::ValidationError(
+                "Un tipo de cambio requiere dos divisas distintas".into(),
+            ));
+        }
+        if rate <= Decimal::ZERO {
+            return Err(TransactionError::ValidationError(
+                "El tipo de cambio debe ser mayor a cero".into(),
+            ));
+        }
+
+        Ok(Self {
+            base_currency,
+            quote_currency,
+            rate,
+            as_of,
+        })
+    }
+}
+
+/// Cotización que fija un tipo de cambio durante un tiempo limitado.
+///
+/// El cliente la solicita antes de una transferencia entre divisas y la referencia al
+/// iniciarla, de modo que el monto acreditado no dependa de cambios de tasa intermedios.
+///
+/// # Examples
+/// ```
+/// use transaction_service::domain::exchange::{ExchangeRate, FxQuote};
+/// use chrono::{Duration, Utc};
+/// use rust_decimal::Decimal;
+///
+/// let rate = ExchangeRate::new("USD", "COP", Decimal::from(4000), Utc::now()).unwrap();
+/// let quote = FxQuote::new(rate, Duration::seconds(30));
+/// assert_eq!(quote.convert(Decimal::from(10), 2), Decimal::from(40000));
+/// assert!(!quote.is_expired(Utc::now()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxQuote {
+    pub id: QuoteId,
+    pub source_currency: String,
+    pub destination_currency: String,
+    pub rate: Decimal,
+    pub rate_timestamp: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FxQuote {
+    /// Fija `rate` desde ahora y durante `ttl`.
+    pub fn new(rate: ExchangeRate, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: QuoteId::new(),
+            source_currency: rate.base_currency,
+            destination_currency: rate.quote_currency,
+            rate: rate.rate,
+            rate_timestamp: rate.as_of,
+            expires_at: now + ttl,
+            created_at: now,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Convierte un monto de la divisa origen a la destino, truncando a `minor_units`
+    /// decimales: nunca se acredita más de lo que resulta de aplicar la tasa.
+    pub fn convert(&self, amount: Decimal, minor_units: u32) -> Decimal {
+        (amount * self.rate).round_dp_with_strategy(minor_units, RoundingStrategy::ToZero)
+    }
+}
+
+/// Datos de conversión registrados en una transferencia entre divisas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeDetails {
+    pub source_currency: String,
+    pub destination_currency: String,
+    pub destination_amount: Decimal,
+    pub rate: Decimal,
+    pub rate_timestamp: DateTime<Utc>,
+    pub quote_id: QuoteId,
+}
+
+/// Puerto de consulta de tipos de cambio.
+///
+/// Permite cambiar la fuente de tasas (tabla administrada, proveedor externo) sin tocar la
+/// lógica de cotizaciones.
+#[async_trait]
+pub trait ExchangeRateProvider: Send + Sync {
+    /// Tasa vigente para convertir de `base_currency` a `quote_currency`.
+    ///
+    /// Falla con `TransactionError::ExchangeRateNotFound` si no hay tasa para el par.
+    async fn current_rate(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<ExchangeRate, TransactionError>;
+}
+
+/// Normaliza un código de divisa ISO 4217 (tres letras, mayúsculas).
+pub fn normalize_currency(code: &str) -> Result<String, TransactionError> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(TransactionError::ValidationError(format!(
+            "'{}' no es un código de divisa ISO 4217",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_convert_truncates_to_destination_minor_units() {
+        let rate = ExchangeRate::new(
+            "USD",
+            "JPY",
+            Decimal::from_str("151.237").unwrap(),
+            Utc::now(),
+        )
+        .unwrap();
+        let quote = FxQuote::new(rate, Duration::seconds(30));
+
+        assert_eq!(
+            quote.convert(Decimal::from_str("10.50").unwrap(), 0),
+            Decimal::from(1587)
+        );
+    }
+
+    #[test]
+    fn test_quote_expires_after_ttl() {
+        let rate = ExchangeRate::new("usd", "eur", Decimal::new(92, 2), Utc::now()).unwrap();
+        let quote = FxQuote::new(rate, Duration::seconds(30));
+
+        assert_eq!(quote.source_currency, "USD");
+        assert!(!quote.is_expired(quote.created_at));
+        assert!(quote.is_expired(quote.created_at + Duration::seconds(30)));
+    }
+
+    #[test]
+    fn test_exchange_rate_validation() {
+        assert!(ExchangeRate::new("USD", "USD", Decimal::ONE, Utc::now()).is_err());
+        assert!(ExchangeRate::new("USD", "EUR", Decimal::ZERO, Utc::now()).is_err());
+        assert!(ExchangeRate::new("US", "EUR", Decimal::ONE, Utc::now()).is_err());
+    }
+}
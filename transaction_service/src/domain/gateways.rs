@@ -1,9 +1,110 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use tonic::async_trait;
 
-use crate::domain::{entities::Transaction, error::TransactionError};
+use crate::domain::{
+    entities::Transaction,
+    error::TransactionError,
+    saga::SagaStep,
+    types::{TransactionId, WalletId},
+};
+
+/// Estado agregado de las patas de una transacción en el Wallet Service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementStatus {
+    /// Ninguna pata llegó a registrarse.
+    NotFound,
+    /// Al menos una pata está reservada o falta por registrar; la transacción no terminó.
+    Reserved,
+    /// Todas las patas están aplicadas al balance contable.
+    Applied,
+    /// Alguna pata fue liberada (compensada): la transacción se abortó.
+    Released,
+}
+
+/// Divisa de una billetera según el Wallet Service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletCurrency {
+    /// Código ISO 4217 (p. ej. "USD").
+    pub code: String,
+    /// Decimales que admite la divisa; los montos convertidos se truncan a esta escala.
+    pub minor_units: u32,
+}
+
+/// Saldo contable de una billetera en un instante pasado según el Wallet Service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoricalBalance {
+    pub currency: WalletCurrency,
+    pub ledger_balance: Decimal,
+}
+
+/// Asiento del libro mayor del Wallet Service sobre una billetera.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerPosting {
+    /// Transacción que originó el movimiento.
+    pub transaction_id: TransactionId,
+    /// Con signo: positivo si acreditó la billetera, negativo si la debitó.
+    pub amount: Decimal,
+    /// Fecha del asiento en el libro mayor (el reloj de `historical_balance`).
+    pub posted_at: DateTime<Utc>,
+}
 
 #[async_trait]
 pub trait WalletGateway: Send + Sync {
-    // Retorna true si fue exitoso, o un error si falló (saldo insuficiente, usuario no existe, etc.)
-    async fn process_movement(&self, transaction: &Transaction) -> Result<bool, TransactionError>;
+    // Reserva (hold) una pata de la transacción. Retorna true si quedó reservada, false si el
+    // Wallet Service la rechazó sin motivo tipado, o el rechazo (saldo insuficiente, límite,
+    // billetera inactiva, monto inválido) como error. Es idempotente por (transacción, billetera).
+    async fn reserve_leg(
+        &self,
+        transaction: &Transaction,
+        step: &SagaStep,
+    ) -> Result<bool, TransactionError>;
+
+    // Captura la reserva de una pata: el movimiento se aplica al balance contable.
+    async fn capture_leg(&self, step: &SagaStep) -> Result<(), TransactionError>;
+
+    // Ejecuta las dos patas de una transferencia (débito y crédito) en una única operación
+    // atómica del Wallet Service, sin reservas intermedias. Mismos resultados que `reserve_leg`;
+    // es idempotente por transacción, así que reenviarla tras un error de comunicación es seguro.
+    async fn execute_transfer(
+        &self,
+        debit: &SagaStep,
+        credit: &SagaStep,
+    ) -> Result<bool, TransactionError>;
+
+    // Libera la reserva de una pata (compensación). Liberar una pata que nunca llegó a
+    // reservarse no es un error.
+    async fn release_leg(&self, step: &SagaStep) -> Result<(), TransactionError>;
+
+    // Consulta en qué estado quedaron las patas de la transacción, sin modificarlas.
+    async fn movement_status(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<MovementStatus, TransactionError>;
+
+    // Consulta la divisa de una billetera, para decidir si una transferencia requiere conversión.
+    async fn wallet_currency(
+        &self,
+        wallet_id: WalletId,
+    ) -> Result<WalletCurrency, TransactionError>;
+
+    // Consulta el saldo contable de una billetera en `as_of` (saldo de apertura de un extracto).
+    async fn historical_balance(
+        &self,
+        wallet_id: WalletId,
+        as_of: DateTime<Utc>,
+    ) -> Result<HistoricalBalance, TransactionError>;
+
+    // Lista los asientos de una billetera con `from <= posted_at < to`, en orden cronológico.
+    // Junto con `historical_balance` cuadra un extracto: el saldo antes de `from` más estos
+    // asientos es el saldo antes de `to`.
+    async fn ledger_postings(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LedgerPosting>, TransactionError>;
+
+    // Lista las billeteras activas (ni congeladas ni cerradas), para los procesos masivos.
+    async fn active_wallets(&self) -> Result<Vec<WalletId>, TransactionError>;
 }
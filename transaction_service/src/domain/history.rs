@@ -0,0 +1,134 @@
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{Transaction, TransactionStatus, TransactionType},
+    error::TransactionError,
+    types::TransactionId,
+};
+
+/// Sentido de un movimiento respecto de la billetera consultada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryDirection {
+    /// La billetera es la destino (créditos).
+    In,
+    /// La billetera es la origen (débitos).
+    Out,
+}
+
+impl HistoryDirection {
+    /// Monto de `transaction` en la divisa de la billetera en este sentido: lo debitado en
+    /// origen, o lo acreditado en destino (el monto convertido si hubo cambio de divisa).
+    pub fn amount_of(self, transaction: &Transaction) -> Decimal {
+        match self {
+            HistoryDirection::In => transaction.destination_amount(),
+            HistoryDirection::Out => transaction.amount(),
+        }
+    }
+}
+
+/// Filtros del historial de una billetera. Todos son opcionales y se combinan con AND.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    /// Desde (inclusive).
+    pub from: Option<DateTime<Utc>>,
+    /// Hasta (exclusive).
+    pub to: Option<DateTime<Utc>>,
+    pub status: Option<TransactionStatus>,
+    pub transaction_type: Option<TransactionType>,
+    pub direction: Option<HistoryDirection>,
+    /// Monto mínimo (inclusive), en la divisa de la billetera (ver
+    /// `HistoryDirection::amount_of`).
+    pub min_amount: Option<Decimal>,
+    /// Monto máximo (inclusive), en la divisa de la billetera.
+    pub max_amount: Option<Decimal>,
+}
+
+impl HistoryFilter {
+    /// Rechaza rangos vacíos o montos negativos.
+    pub fn validate(&self) -> Result<(), TransactionError> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from >= to {
+                return Err(TransactionError::ValidationError(
+                    "El rango de fechas está vacío: 'from' debe ser anterior a 'to'".into(),
+                ));
+            }
+        }
+        if self.min_amount.is_some_and(|m| m < Decimal::ZERO)
+            || self.max_amount.is_some_and(|m| m < Decimal::ZERO)
+        {
+            return Err(TransactionError::ValidationError(
+                "Los montos del filtro no pueden ser negativos".into(),
+            ));
+        }
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return Err(TransactionError::ValidationError(
+                    "El monto mínimo no puede superar al máximo".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Posición de paginación por keyset: la última transacción devuelta, en el orden
+/// `(created_at, id)` descendente del historial.
+///
+/// Se expone al cliente como un token opaco.
+///
+/// # Examples
+/// ```
+/// use transaction_service::domain::history::HistoryCursor;
+/// use transaction_service::domain::types::TransactionId;
+/// use chrono::Utc;
+///
+/// let cursor = HistoryCursor { created_at: Utc::now(), id: TransactionId::new() };
+/// let token = cursor.encode();
+/// assert_eq!(HistoryCursor::decode(&token).unwrap().id, cursor.id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: TransactionId,
+}
+
+impl HistoryCursor {
+    /// Cursor que apunta a `transaction`.
+    pub fn after(transaction: &Transaction) -> Self {
+        Self {
+            created_at: transaction.created_at(),
+            id: transaction.id(),
+        }
+    }
+
+    /// Token opaco: microsegundos desde epoch y el id de la transacción.
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(token: &str) -> Result<Self, TransactionError> {
+        let invalid = || TransactionError::ValidationError("Cursor de paginación inválido".into());
+
+        let (micros, id) = token.split_once('_').ok_or_else(invalid)?;
+        let micros: i64 = micros.parse().map_err(|_| invalid())?;
+        let created_at = Utc.timestamp_micros(micros).single().ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+
+        Ok(Self {
+            created_at,
+            id: TransactionId(id),
+        })
+    }
+}
+
+/// Página del historial de una billetera.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryPage {
+    pub transactions: Vec<Transaction>,
+    /// Token para pedir la página siguiente; `None` en la última página.
+    pub next_cursor: Option<String>,
+}
@@ -0,0 +1,123 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    entities::{Transaction, TransactionType},
+    types::{QuoteId, TransactionId, WalletId},
+};
+
+/// Campos de una solicitud que definen el movimiento de fondos.
+///
+/// Dos solicitudes con el mismo `correlation_id` son la misma operación sólo si coinciden en
+/// todos ellos; memo, referencia externa y metadata no cuentan. Se devuelve en el 409 para
+/// que el cliente vea qué cambió entre el envío original y el reintento.
+///
+/// En un reembolso, `refund_of` es la transacción reembolsada y `amount` lo reembolsado en
+/// las unidades de la original.
+///
+/// # Examples
+/// ```
+/// use transaction_service::domain::entities::Transaction;
+/// use transaction_service::domain::idempotency::RequestSummary;
+/// use transaction_service::domain::types::WalletId;
+/// use rust_decimal::Decimal;
+/// use uuid::Uuid;
+///
+/// let wallet = WalletId::new();
+/// let a = Transaction::new(None, wallet, Decimal::new(1000, 2), Uuid::new_v4()).unwrap();
+/// let b = Transaction::new(None, wallet, Decimal::from(10), Uuid::new_v4()).unwrap();
+/// assert_eq!(RequestSummary::of(&a).fingerprint(), RequestSummary::of(&b).fingerprint());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequestSummary {
+    pub transaction_type: TransactionType,
+    pub source_wallet_id: Option<WalletId>,
+    pub destination_wallet_id: Option<WalletId>,
+    pub external_destination: Option<String>,
+    pub amount: Decimal,
+    pub quote_id: Option<QuoteId>,
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_of: Option<TransactionId>,
+}
+
+impl RequestSummary {
+    /// Resumen de la solicitud que originó `transaction`.
+    pub fn of(transaction: &Transaction) -> Self {
+        Self {
+            transaction_type: transaction.transaction_type(),
+            source_wallet_id: transaction.source_wallet_id(),
+            destination_wallet_id: transaction.destination_wallet_id(),
+            external_destination: transaction.external_destination().map(str::to_string),
+            // El hijo de un reembolso acredita en la original exactamente lo reembolsado.
+            amount: match transaction.parent_transaction_id() {
+                Some(_) => transaction.destination_amount().normalize(),
+                None => transaction.amount().normalize(),
+            },
+            quote_id: transaction.exchange().map(|fx| fx.quote_id),
+            client_id: transaction.client_id().map(str::to_string),
+            refund_of: transaction.parent_transaction_id(),
+        }
+    }
+
+    /// Huella SHA-256 (hex) de los campos, independiente de la escala del monto.
+    ///
+    /// Cada campo se codifica con su largo delante para que ningún valor de texto pueda
+    /// imitar el separador de otro. `refund_of` sólo se agrega si está presente, de modo que
+    /// las huellas guardadas antes de que existiera siguen siendo válidas.
+    pub fn fingerprint(&self) -> String {
+        let mut fields = vec![
+            format!("{:?}", self.transaction_type),
+            optional(self.source_wallet_id.map(|w| w.to_string())),
+            optional(self.destination_wallet_id.map(|w| w.to_string())),
+            optional(self.external_destination.clone()),
+            self.amount.normalize().to_string(),
+            optional(self.quote_id.map(|q| q.to_string())),
+            optional(self.client_id.clone()),
+        ];
+        if let Some(original) = self.refund_of {
+            fields.push(original.to_string());
+        }
+
+        let mut hasher = Sha256::new();
+        for field in fields {
+            hasher.update(format!("{}:{};", field.len(), field));
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+fn optional(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_fingerprint_changes_with_amount_destination_and_client() {
+        let source = WalletId::new();
+        let destination = WalletId::new();
+        let base =
+            Transaction::new(Some(source), destination, Decimal::from(10), Uuid::new_v4()).unwrap();
+        let fingerprint = RequestSummary::of(&base).fingerprint();
+
+        let other_amount =
+            Transaction::new(Some(source), destination, Decimal::from(11), Uuid::new_v4()).unwrap();
+        let other_destination = Transaction::new(
+            Some(source),
+            WalletId::new(),
+            Decimal::from(10),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let other_client = base.clone().with_client_id(Some("shop-b".into()));
+
+        for changed in [other_amount, other_destination, other_client] {
+            assert_ne!(RequestSummary::of(&changed).fingerprint(), fingerprint);
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::error::TransactionError;
+
+/// Puerto de elección de líder entre las réplicas del servicio.
+///
+/// Los jobs que deben ejecutarse en una sola instancia a la vez (extractos mensuales,
+/// compensaciones) lo consultan antes de cada pasada; el resto de réplicas se la saltan.
+#[async_trait]
+pub trait LeaderElection: Send + Sync {
+    /// Intenta tomar el liderazgo, o confirma que esta instancia lo conserva.
+    ///
+    /// Una vez tomado se mantiene hasta que la instancia muere o pierde la conexión; a partir
+    /// de ahí otra réplica puede tomarlo.
+    async fn is_leader(&self) -> Result<bool, TransactionError>;
+}
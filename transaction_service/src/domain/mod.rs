@@ -1,5 +1,14 @@
+pub mod compensation;
 pub mod entities;
 pub mod error;
+pub mod events;
+pub mod exchange;
 pub mod gateways;
+pub mod history;
+pub mod idempotency;
+pub mod leader;
 pub mod repository;
+pub mod retry;
+pub mod saga;
+pub mod statement;
 pub mod types;
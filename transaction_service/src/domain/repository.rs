@@ -1,7 +1,14 @@
-use crate::domain::entities::Transaction;
+use crate::domain::compensation::{CompensationStatus, PendingCompensation};
+use crate::domain::entities::{Transaction, TransactionStatus};
 use crate::domain::error::TransactionError;
-use crate::domain::types::{TransactionId, WalletId};
+use crate::domain::events::TransactionEvent;
+use crate::domain::exchange::{ExchangeRate, FxQuote};
+use crate::domain::history::{HistoryCursor, HistoryFilter};
+use crate::domain::saga::{Saga, SagaStep};
+use crate::domain::statement::{StatementPeriod, WalletStatement};
+use crate::domain::types::{QuoteId, TransactionId, WalletId};
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
 /// Puerto de Persistencia de Transacciones (Domain Layer).
@@ -14,25 +21,64 @@ pub trait TransactionRepository: Send + Sync {
     ///
     /// Se debe usar al inicio del flujo (Saga) para registrar la intención de pago
     /// con estado `PENDING`. Esto garantiza auditoría incluso si el proceso falla después.
-    async fn save(&self, transaction: Transaction) -> Result<Transaction, TransactionError>;
+    ///
+    /// En la misma operación reserva su `correlation_id` durante `key_ttl`: si otra transacción
+    /// lo tiene reservado y aún vigente (p. ej. una solicitud concurrente con la misma clave),
+    /// no se guarda nada y se retorna `TransactionError::IdempotencyError`.
+    async fn save(
+        &self,
+        transaction: Transaction,
+        key_ttl: chrono::Duration,
+    ) -> Result<Transaction, TransactionError>;
 
     /// Actualiza el estado de una transacción existente.
     ///
     /// Crítico para la consistencia eventual. Se llama después de recibir respuesta del Wallet Service.
     /// - `PENDING` -> `COMPLETED`: Si el Wallet debitó los fondos correctamente.
     /// - `PENDING` -> `FAILED`: Si el Wallet rechazó por saldo insuficiente.
-    async fn update(&self, transaction: Transaction) -> Result<Transaction, TransactionError>;
+    ///
+    /// Es un compare-and-set sobre `expected_status` (el estado anterior, tal como lo retorna
+    /// `Transaction::update_status`): si otro proceso ya cambió el estado, no se escribe nada y
+    /// se retorna `TransactionError::InvalidState`.
+    async fn update(
+        &self,
+        transaction: Transaction,
+        expected_status: TransactionStatus,
+    ) -> Result<Transaction, TransactionError>;
+
+    /// Como `update`, pero para escrituras de un job sobre una transacción que reclamó.
+    ///
+    /// Sólo escribe si la transacción sigue sin dueño o reclamada por `owner`: si su lease
+    /// venció y otra instancia la reclamó, no se escribe nada y se retorna
+    /// `TransactionError::LeaseLost`.
+    async fn update_claimed(
+        &self,
+        transaction: Transaction,
+        expected_status: TransactionStatus,
+        owner: &str,
+    ) -> Result<Transaction, TransactionError>;
 
     /// Busca una transacción por su ID único.
     async fn find_by_id(&self, id: TransactionId) -> Result<Option<Transaction>, TransactionError>;
 
-    /// Recupera el historial de transacciones de una billetera.
+    /// Busca varias transacciones por ID; las inexistentes simplemente no aparecen.
+    async fn find_by_ids(
+        &self,
+        ids: &[TransactionId],
+    ) -> Result<Vec<Transaction>, TransactionError>;
+
+    /// Recupera una página del historial de transacciones de una billetera.
     ///
     /// Debe devolver tanto transacciones donde la wallet es `source` (débitos)
-    /// como donde es `destination` (créditos).
+    /// como donde es `destination` (créditos), que cumplan `filter`, ordenadas por
+    /// `(created_at, id)` descendente y estrictamente posteriores a `after` en ese orden.
+    /// Devuelve como máximo `limit` transacciones.
     async fn find_by_wallet_id(
         &self,
         wallet_id: WalletId,
+        filter: &HistoryFilter,
+        after: Option<HistoryCursor>,
+        limit: i64,
     ) -> Result<Vec<Transaction>, TransactionError>;
 
     /// Busca una transacción por su ID de Correlación (Idempotency Key).
@@ -40,16 +86,171 @@ pub trait TransactionRepository: Send + Sync {
     /// **Esencial para evitar duplicidad de pagos.**
     /// Antes de procesar cualquier solicitud, se debe verificar si este ID ya existe.
     /// Si existe, se devuelve la transacción previa sin volver a ejecutar la lógica de cobro.
+    ///
+    /// Una clave vencida puede reutilizarse, así que puede haber varias transacciones con el
+    /// mismo `correlation_id`: se devuelve la más reciente.
     async fn find_by_correlation_id(
         &self,
         correlation_id: Uuid,
     ) -> Result<Option<Transaction>, TransactionError>;
 
-    /// Busca transacciones que han quedado en estado PENDING por más de cierto tiempo.
+    /// Busca las transacciones con la referencia externa indicada (más recientes primero).
+    ///
+    /// La referencia no es única: una misma factura puede pagarse en varias transacciones.
+    async fn find_by_external_reference(
+        &self,
+        external_reference: &str,
+    ) -> Result<Vec<Transaction>, TransactionError>;
+
+    /// Reclama las transacciones que han quedado en estado PENDING por más de cierto tiempo.
     ///
     /// Utilizado por jobs en segundo plano para reintentar o revertir transacciones atascadas.
-    async fn find_pending_older_than(
+    /// Omite las que tienen su siguiente intento (`RetryState::next_attempt_at`) en el futuro
+    /// y las que otra instancia tiene reclamadas. Las devueltas quedan reclamadas por `owner`
+    /// durante `lease` (o hasta su siguiente `update`), así que con varias réplicas cada
+    /// transacción la procesa una sola.
+    async fn claim_pending_older_than(
         &self,
         timestamp: chrono::DateTime<chrono::Utc>,
+        owner: &str,
+        lease: chrono::Duration,
     ) -> Result<Vec<Transaction>, TransactionError>;
+
+    /// Página de transacciones en `DEAD_LETTER`, las más recientes primero, comenzando
+    /// después de `after` (keyset pagination).
+    async fn find_dead_lettered(
+        &self,
+        after: Option<HistoryCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, TransactionError>;
+
+    /// Suma `amount` al acumulado reembolsado de una transacción COMPLETED.
+    ///
+    /// Es un compare-and-set sobre `refunded_before`: si otro reembolso modificó el acumulado
+    /// desde la lectura (o la transacción ya no está COMPLETED) retorna `None` sin cambios.
+    async fn reserve_refund(
+        &self,
+        id: TransactionId,
+        amount: Decimal,
+        refunded_before: Decimal,
+    ) -> Result<Option<Transaction>, TransactionError>;
+
+    /// Descuenta `amount` del acumulado reembolsado (el reembolso hijo falló).
+    async fn release_refund(
+        &self,
+        id: TransactionId,
+        amount: Decimal,
+    ) -> Result<(), TransactionError>;
+}
+
+/// Puerto de persistencia de los tipos de cambio administrados.
+///
+/// La lectura se hace a través de `ExchangeRateProvider`; este puerto sólo cubre la escritura
+/// que realizan los administradores.
+#[async_trait]
+pub trait ExchangeRateRepository: Send + Sync {
+    /// Inserta o reemplaza la tasa vigente para el par `base_currency` -> `quote_currency`.
+    async fn save_rate(&self, rate: ExchangeRate) -> Result<ExchangeRate, TransactionError>;
+}
+
+/// Puerto de persistencia de cotizaciones de tipo de cambio.
+#[async_trait]
+pub trait FxQuoteRepository: Send + Sync {
+    /// Registra una cotización emitida a un cliente.
+    async fn save(&self, quote: FxQuote) -> Result<FxQuote, TransactionError>;
+
+    /// Busca una cotización por su ID, esté vigente o no.
+    async fn find_by_id(&self, id: QuoteId) -> Result<Option<FxQuote>, TransactionError>;
+}
+
+/// Puerto de persistencia del historial de estados de las transacciones.
+#[async_trait]
+pub trait TransactionEventRepository: Send + Sync {
+    /// Registra un cambio de estado.
+    async fn record(&self, event: TransactionEvent) -> Result<TransactionEvent, TransactionError>;
+
+    /// Historial de una transacción en orden cronológico.
+    async fn find_by_transaction_id(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Vec<TransactionEvent>, TransactionError>;
+}
+
+/// Puerto de persistencia de los extractos pregenerados.
+#[async_trait]
+pub trait WalletStatementRepository: Send + Sync {
+    /// Guarda un extracto; si ya existe uno para la misma billetera y período se reemplaza
+    /// sólo si el nuevo se generó después.
+    async fn save(&self, statement: &WalletStatement) -> Result<(), TransactionError>;
+
+    /// Busca el extracto pregenerado de una billetera para exactamente ese período.
+    async fn find(
+        &self,
+        wallet_id: WalletId,
+        period: StatementPeriod,
+    ) -> Result<Option<WalletStatement>, TransactionError>;
+}
+
+/// Puerto de persistencia del registro de sagas (patas de cada transacción en el Wallet
+/// Service).
+#[async_trait]
+pub trait SagaRepository: Send + Sync {
+    /// Registra las patas planificadas de una saga.
+    ///
+    /// Si la transacción ya tenía saga (un reintento) no se modifica y se retorna la guardada,
+    /// para retomarla desde donde quedó.
+    async fn start(&self, saga: Saga) -> Result<Saga, TransactionError>;
+
+    /// Persiste el estado actual de una pata.
+    async fn update_step(&self, step: &SagaStep) -> Result<(), TransactionError>;
+
+    /// Reclama las sagas con alguna pata en estado no final (`PLANNED`, `SENT` o
+    /// `ACKNOWLEDGED`) que nadie avanza desde antes de `older_than`.
+    ///
+    /// Usa el mismo lease que `TransactionRepository::claim_pending_older_than` sobre la
+    /// transacción de cada saga: omite las que otra instancia tiene reclamadas y deja las
+    /// devueltas reclamadas por `owner` durante `lease`. Así una réplica que arranca no
+    /// recupera sagas que otra aún ejecuta. Sólo se reclaman sagas de transacciones PENDING.
+    async fn claim_unfinished(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+        owner: &str,
+        lease: chrono::Duration,
+    ) -> Result<Vec<Saga>, TransactionError>;
+
+    /// Libera el reclamo de `claim_unfinished` sobre la transacción, si sigue siendo de
+    /// `owner`, para que el job de reintentos pueda conciliarla sin esperar a que venza.
+    async fn release(
+        &self,
+        transaction_id: TransactionId,
+        owner: &str,
+    ) -> Result<(), TransactionError>;
+}
+
+/// Puerto de persistencia de las compensaciones fallidas que quedan por reintentar.
+#[async_trait]
+pub trait CompensationRepository: Send + Sync {
+    /// Registra una compensación fallida. Si la pata ya tenía una registrada se conserva la
+    /// existente (con su conteo de intentos).
+    async fn schedule(&self, compensation: &PendingCompensation) -> Result<(), TransactionError>;
+
+    /// Persiste el estado, los intentos y la resolución de una compensación.
+    async fn update(&self, compensation: &PendingCompensation) -> Result<(), TransactionError>;
+
+    /// Compensaciones `PENDING` cuyo próximo intento vence antes de `now`, las más atrasadas
+    /// primero. Devuelve como máximo `limit`.
+    async fn find_due(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<PendingCompensation>, TransactionError>;
+
+    /// Compensaciones en `status` (todas si es `None`), las más recientes primero.
+    async fn find_by_status(
+        &self,
+        status: Option<CompensationStatus>,
+    ) -> Result<Vec<PendingCompensation>, TransactionError>;
+
+    /// Busca una compensación por su ID.
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PendingCompensation>, TransactionError>;
 }
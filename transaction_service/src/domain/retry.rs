@@ -0,0 +1,123 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::TransactionError;
+
+/// Reintentos que lleva el job de recuperación sobre una transacción PENDING.
+///
+/// Una transacción que nunca falló tiene `attempts = 0` y `next_attempt_at = None`: el job la
+/// toma en cuanto supera la antigüedad mínima.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetryState {
+    /// Intentos fallidos del job hasta ahora.
+    pub attempts: i32,
+    /// Desde cuándo el job puede volver a intentarla.
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Último error del Wallet Service o de la comunicación.
+    pub last_error: Option<String>,
+}
+
+impl RetryState {
+    /// Registra un intento fallido. Retorna `true` si se agotaron los intentos de `policy`;
+    /// si no, programa el siguiente.
+    pub fn record_failure(&mut self, error: &TransactionError, policy: &RetryPolicy) -> bool {
+        self.attempts += 1;
+        self.last_error = Some(error.to_string());
+        if self.attempts >= policy.max_attempts {
+            return true;
+        }
+        self.next_attempt_at = Some(Utc::now() + policy.jittered_delay(self.attempts));
+        false
+    }
+
+    /// Reinicia los intentos para que el job la retome de inmediato. Se conserva el último
+    /// error como referencia.
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+        self.next_attempt_at = None;
+    }
+}
+
+/// Cuántas veces y con qué espera reintenta el job una transacción antes de enviarla a
+/// `DEAD_LETTER`.
+///
+/// La espera se duplica en cada intento fallido, desde `base_delay` hasta `max_delay`, y se
+/// le aplica jitter para que las transacciones que fallaron juntas (p. ej. durante una caída
+/// del Wallet Service) no se reintenten todas a la vez.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Intentos fallidos antes de enviarla a `DEAD_LETTER`.
+    pub max_attempts: i32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_MAX_ATTEMPTS: i32 = 10;
+
+    /// Espera máxima antes del siguiente intento, tras `attempts` intentos fallidos.
+    ///
+    /// # Examples
+    /// ```
+    /// use transaction_service::domain::retry::RetryPolicy;
+    /// use chrono::Duration;
+    ///
+    /// let policy = RetryPolicy::default();
+    /// assert_eq!(policy.delay(1), Duration::minutes(1));
+    /// assert_eq!(policy.delay(4), Duration::minutes(8));
+    /// assert_eq!(policy.delay(20), Duration::hours(1));
+    /// ```
+    pub fn delay(&self, attempts: i32) -> Duration {
+        let exponent = attempts.clamp(1, 31) as u32 - 1;
+        self.base_delay
+            .checked_mul(2_i32.saturating_pow(exponent))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// `delay(attempts)` con jitter: un valor al azar entre la mitad y el total.
+    pub fn jittered_delay(&self, attempts: i32) -> Duration {
+        let delay = self.delay(attempts).num_milliseconds();
+        let half = delay / 2;
+        Duration::milliseconds(half + rand::thread_rng().gen_range(0..=delay - half))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+            base_delay: Duration::minutes(1),
+            max_delay: Duration::hours(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failures_back_off_with_jitter_until_exhausted() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            ..Default::default()
+        };
+        let error = TransactionError::GatewayError("unavailable".into());
+        let mut state = RetryState::default();
+
+        assert!(!state.record_failure(&error, &policy));
+        assert!(!state.record_failure(&error, &policy));
+        // Segundo fallo: entre 1 y 2 minutos.
+        let wait = state.next_attempt_at.unwrap() - Utc::now();
+        assert!(wait > Duration::seconds(55) && wait <= Duration::minutes(2));
+
+        assert!(state.record_failure(&error, &policy));
+        assert_eq!(state.attempts, 3);
+        assert_eq!(state.last_error, Some(error.to_string()));
+
+        state.reset();
+        assert_eq!(state.attempts, 0);
+        assert_eq!(state.next_attempt_at, None);
+    }
+}
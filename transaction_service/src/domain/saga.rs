@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::{Transaction, TransactionType},
+    types::{TransactionId, WalletId},
+};
+
+/// Estado de una pata de la saga en el Wallet Service.
+///
+/// Cada transición se persiste *antes* de actuar en base a ella, para que un reinicio sepa
+/// hasta dónde llegó la saga.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "saga_step_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SagaStepStatus {
+    /// Registrada, todavía no se pidió la reserva.
+    PLANNED,
+    /// Se pidió la reserva y no se conoce la respuesta: puede haber fondos retenidos.
+    SENT,
+    /// El Wallet Service confirmó la reserva (fondos retenidos).
+    ACKNOWLEDGED,
+    /// La reserva se capturó: el movimiento está en el balance contable.
+    CAPTURED,
+    /// El Wallet Service rechazó la reserva; no hay nada retenido.
+    REJECTED,
+    /// La reserva se liberó (o nunca llegó a pedirse) porque la saga se abortó.
+    COMPENSATED,
+}
+
+impl SagaStepStatus {
+    /// Si la pata ya no requiere ninguna acción.
+    pub fn is_final(self) -> bool {
+        matches!(
+            self,
+            SagaStepStatus::CAPTURED | SagaStepStatus::REJECTED | SagaStepStatus::COMPENSATED
+        )
+    }
+}
+
+/// Una pata de la saga: el movimiento de una billetera en el Wallet Service.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaStep {
+    pub transaction_id: TransactionId,
+    /// Posición de la pata dentro de la saga (orden de ejecución).
+    pub leg: i16,
+    pub wallet_id: WalletId,
+    /// Monto con signo: negativo para débitos, positivo para créditos.
+    pub amount: Decimal,
+    pub status: SagaStepStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SagaStep {
+    /// Pasa la pata a `status`.
+    pub fn mark(&mut self, status: SagaStepStatus) {
+        self.status = status;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// Registro persistente de las patas de una transacción en el Wallet Service.
+///
+/// # Examples
+/// ```
+/// use transaction_service::domain::entities::Transaction;
+/// use transaction_service::domain::saga::{Saga, SagaStepStatus};
+/// use transaction_service::domain::types::WalletId;
+/// use rust_decimal::Decimal;
+/// use uuid::Uuid;
+///
+/// let tx = Transaction::new(Some(WalletId::new()), WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+/// let saga = Saga::plan(&tx);
+/// assert_eq!(saga.steps.len(), 2);
+/// assert_eq!(saga.steps[0].amount, Decimal::from(-10));
+/// assert!(saga.steps.iter().all(|s| s.status == SagaStepStatus::PLANNED));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Saga {
+    pub transaction_id: TransactionId,
+    /// Patas en orden de ejecución; las compensaciones se aplican en orden inverso.
+    pub steps: Vec<SagaStep>,
+}
+
+impl Saga {
+    /// Patas que la transacción mueve en el Wallet Service, todas en `PLANNED`.
+    ///
+    /// Una transferencia debita primero el origen y luego acredita el destino (en su divisa
+    /// si hubo conversión).
+    pub fn plan(transaction: &Transaction) -> Self {
+        let legs = match transaction.transaction_type() {
+            TransactionType::DEPOSIT => {
+                vec![(transaction.destination_wallet_id(), transaction.amount())]
+            }
+            TransactionType::WITHDRAWAL => {
+                vec![(transaction.source_wallet_id(), -transaction.amount())]
+            }
+            TransactionType::TRANSFER => vec![
+                (transaction.source_wallet_id(), -transaction.amount()),
+                (
+                    transaction.destination_wallet_id(),
+                    transaction.destination_amount(),
+                ),
+            ],
+        };
+
+        let now = Utc::now();
+        let steps = legs
+            .into_iter()
+            .enumerate()
+            .filter_map(|(leg, (wallet_id, amount))| {
+                Some(SagaStep {
+                    transaction_id: transaction.id(),
+                    leg: leg as i16,
+                    wallet_id: wallet_id?,
+                    amount,
+                    status: SagaStepStatus::PLANNED,
+                    updated_at: now,
+                })
+            })
+            .collect();
+
+        Self {
+            transaction_id: transaction.id(),
+            steps,
+        }
+    }
+
+    /// Si todas las patas terminaron (capturadas, rechazadas o compensadas).
+    pub fn is_finished(&self) -> bool {
+        self.steps.iter().all(|s| s.status.is_final())
+    }
+
+    /// Si la saga se abortó: alguna pata fue rechazada o compensada y el resto debe liberarse.
+    pub fn is_aborted(&self) -> bool {
+        self.steps.iter().any(|s| {
+            matches!(
+                s.status,
+                SagaStepStatus::REJECTED | SagaStepStatus::COMPENSATED
+            )
+        })
+    }
+
+    /// Si la saga puede ejecutarse como una transferencia atómica del Wallet Service: dos
+    /// patas que todavía no se enviaron, o que se enviaron juntas y no tuvieron respuesta.
+    ///
+    /// Una transferencia que ya reservó alguna pata por separado (en dos fases) no califica.
+    pub fn runs_as_transfer(&self) -> bool {
+        self.steps.len() == 2
+            && (self
+                .steps
+                .iter()
+                .all(|s| s.status == SagaStepStatus::PLANNED)
+                || self.is_transfer_in_flight())
+    }
+
+    /// Si es una transferencia atómica enviada sin respuesta: ambas patas `SENT`.
+    ///
+    /// En dos fases nunca hay dos patas `SENT` a la vez, porque el crédito sólo se envía una
+    /// vez reservado el débito.
+    pub fn is_transfer_in_flight(&self) -> bool {
+        self.steps.len() == 2 && self.steps.iter().all(|s| s.status == SagaStepStatus::SENT)
+    }
+
+    /// Si todas las patas quedaron reservadas o capturadas, de modo que la saga puede
+    /// completarse capturando las restantes.
+    ///
+    /// Al recuperar una saga interrumpida es el único caso en que se avanza; en cualquier
+    /// otro se compensa.
+    pub fn can_commit(&self) -> bool {
+        self.steps.iter().all(|s| {
+            matches!(
+                s.status,
+                SagaStepStatus::ACKNOWLEDGED | SagaStepStatus::CAPTURED
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_interrupted_saga_commits_only_when_every_leg_is_reserved() {
+        let tx = Transaction::new(
+            Some(WalletId::new()),
+            WalletId::new(),
+            Decimal::from(10),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let mut saga = Saga::plan(&tx);
+
+        // Débito reservado, crédito en vuelo: se compensa.
+        saga.steps[0].mark(SagaStepStatus::ACKNOWLEDGED);
+        saga.steps[1].mark(SagaStepStatus::SENT);
+        assert!(!saga.can_commit());
+        assert!(!saga.is_finished());
+
+        // Débito capturado, crédito reservado: se completa.
+        saga.steps[0].mark(SagaStepStatus::CAPTURED);
+        saga.steps[1].mark(SagaStepStatus::ACKNOWLEDGED);
+        assert!(saga.can_commit());
+        assert!(!saga.is_aborted());
+
+        saga.steps[1].mark(SagaStepStatus::CAPTURED);
+        assert!(saga.is_finished());
+    }
+}
@@ -0,0 +1,365 @@
+use chrono::{DateTime, Datelike, Duration, Months, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::{Transaction, TransactionStatus, TransactionType},
+    error::TransactionError,
+    gateways::LedgerPosting,
+    types::{TransactionId, WalletId},
+};
+
+/// Duración máxima del período de un extracto.
+pub const MAX_PERIOD_DAYS: i64 = 366;
+
+/// Período `[start, end)` de un extracto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementPeriod {
+    /// Desde (inclusive).
+    pub start: DateTime<Utc>,
+    /// Hasta (exclusive).
+    pub end: DateTime<Utc>,
+}
+
+impl StatementPeriod {
+    /// Valida que el período no esté vacío ni supere `MAX_PERIOD_DAYS`.
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Self, TransactionError> {
+        if start >= end {
+            return Err(TransactionError::ValidationError(
+                "El período está vacío: 'from' debe ser anterior a 'to'".into(),
+            ));
+        }
+        if end - start > Duration::days(MAX_PERIOD_DAYS) {
+            return Err(TransactionError::ValidationError(format!(
+                "El período de un extracto no puede superar {} días",
+                MAX_PERIOD_DAYS
+            )));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Mes calendario (UTC) anterior al de `now`.
+    ///
+    /// # Examples
+    /// ```
+    /// use transaction_service::domain::statement::StatementPeriod;
+    /// use chrono::{TimeZone, Utc};
+    ///
+    /// let period = StatementPeriod::previous_month(Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap());
+    /// assert_eq!(period.start, Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap());
+    /// assert_eq!(period.end, Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+    /// ```
+    pub fn previous_month(now: DateTime<Utc>) -> Self {
+        let end = Utc
+            .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+            .unwrap();
+        let start = end - Months::new(1);
+        Self { start, end }
+    }
+}
+
+/// Movimiento de un extracto, con el saldo resultante tras aplicarlo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatementLine {
+    pub transaction_id: TransactionId,
+    /// Fecha del asiento en el libro mayor del Wallet Service.
+    #[serde(alias = "created_at")]
+    pub posted_at: DateTime<Utc>,
+    pub transaction_type: TransactionType,
+    /// Estado de la transacción al generar el extracto.
+    pub status: TransactionStatus,
+    pub description: Option<String>,
+    pub external_reference: Option<String>,
+    /// Monto acreditado a la billetera (cero si fue un débito).
+    pub credit: Decimal,
+    /// Monto debitado de la billetera (cero si fue un crédito).
+    pub debit: Decimal,
+    pub running_balance: Decimal,
+}
+
+/// Extracto de una billetera para un período: saldo de apertura, movimientos con saldo
+/// corrido, totales y saldo de cierre.
+///
+/// Los movimientos son los asientos del libro mayor del Wallet Service en el período, con el
+/// mismo reloj que el saldo de apertura: una transacción cuenta en el período en que se
+/// asentó, no en el que se creó. Las reservas liberadas no generan asiento y la devolución
+/// de una transacción revertida aparece como su propio movimiento.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletStatement {
+    pub wallet_id: WalletId,
+    pub currency: String,
+    pub period: StatementPeriod,
+    pub opening_balance: Decimal,
+    pub total_credits: Decimal,
+    pub total_debits: Decimal,
+    pub closing_balance: Decimal,
+    pub lines: Vec<StatementLine>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl WalletStatement {
+    /// Arma el extracto a partir del saldo de apertura y los asientos del período, cada uno
+    /// con la transacción que lo originó.
+    ///
+    /// Los asientos pueden llegar en cualquier orden; se aplican por `(posted_at, id)`
+    /// ascendente.
+    pub fn build(
+        wallet_id: WalletId,
+        currency: String,
+        period: StatementPeriod,
+        opening_balance: Decimal,
+        mut postings: Vec<(LedgerPosting, Transaction)>,
+    ) -> Self {
+        postings.sort_by_key(|(posting, _)| (posting.posted_at, posting.transaction_id.0));
+
+        let mut balance = opening_balance;
+        let mut total_credits = Decimal::ZERO;
+        let mut total_debits = Decimal::ZERO;
+        let mut lines = Vec::with_capacity(postings.len());
+
+        for (posting, transaction) in postings {
+            let (credit, debit) = if posting.amount >= Decimal::ZERO {
+                (posting.amount, Decimal::ZERO)
+            } else {
+                (Decimal::ZERO, -posting.amount)
+            };
+
+            balance += posting.amount;
+            total_credits += credit;
+            total_debits += debit;
+            lines.push(StatementLine {
+                transaction_id: transaction.id(),
+                posted_at: posting.posted_at,
+                transaction_type: transaction.transaction_type(),
+                status: transaction.status(),
+                description: transaction.details().description.clone(),
+                external_reference: transaction.details().external_reference.clone(),
+                credit,
+                debit,
+                running_balance: balance,
+            });
+        }
+
+        Self {
+            wallet_id,
+            currency,
+            period,
+            opening_balance,
+            total_credits,
+            total_debits,
+            closing_balance: balance,
+            lines,
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Representación CSV: una fila de apertura, una por movimiento y una de cierre con los
+    /// totales de créditos y débitos.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "date,transaction_id,type,status,description,external_reference,credit,debit,balance\n",
+        );
+
+        csv.push_str(&format!(
+            "{},,OPENING_BALANCE,,,,,,{}\n",
+            self.period.start.to_rfc3339(),
+            self.opening_balance
+        ));
+        for line in &self.lines {
+            csv.push_str(&format!(
+                "{},{},{:?},{:?},{},{},{},{},{}\n",
+                line.posted_at.to_rfc3339(),
+                line.transaction_id,
+                line.transaction_type,
+                line.status,
+                csv_text(line.description.as_deref()),
+                csv_text(line.external_reference.as_deref()),
+                line.credit,
+                line.debit,
+                line.running_balance
+            ));
+        }
+        csv.push_str(&format!(
+            "{},,CLOSING_BALANCE,,,,{},{},{}\n",
+            self.period.end.to_rfc3339(),
+            self.total_credits,
+            self.total_debits,
+            self.closing_balance
+        ));
+
+        csv
+    }
+}
+
+/// Escapa un campo de texto libre para CSV (RFC 4180).
+///
+/// Los valores que una hoja de cálculo interpretaría como fórmula se prefijan con `'`.
+fn csv_text(value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return String::new();
+    };
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::TransactionDetails;
+    use uuid::Uuid;
+
+    fn transaction(
+        source: Option<WalletId>,
+        destination: Option<WalletId>,
+        amount: i64,
+        status: TransactionStatus,
+        minutes: i64,
+        description: Option<&str>,
+    ) -> Transaction {
+        let transaction_type = match (source, destination) {
+            (None, _) => TransactionType::DEPOSIT,
+            (_, None) => TransactionType::WITHDRAWAL,
+            _ => TransactionType::TRANSFER,
+        };
+        Transaction::reconstitute(
+            TransactionId::new(),
+            source,
+            destination,
+            destination.is_none().then(|| "IBAN".to_string()),
+            Decimal::from(amount),
+            status,
+            transaction_type,
+            Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap() + Duration::minutes(minutes),
+            Uuid::new_v4(),
+            None,
+            None,
+            Decimal::ZERO,
+            TransactionDetails::new(description.map(String::from), None, Default::default())
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Asiento de `amount` (con signo) sobre la billetera, `minutes` después del inicio de
+    /// febrero de 2024.
+    fn posting(
+        transaction: &Transaction,
+        amount: i64,
+        minutes: i64,
+    ) -> (LedgerPosting, Transaction) {
+        (
+            LedgerPosting {
+                transaction_id: transaction.id(),
+                amount: Decimal::from(amount),
+                posted_at: Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()
+                    + Duration::minutes(minutes),
+            },
+            transaction.clone(),
+        )
+    }
+
+    #[test]
+    fn test_build_applies_postings_in_ledger_order_with_running_balance() {
+        let wallet = WalletId::new();
+        let other = WalletId::new();
+        let period =
+            StatementPeriod::previous_month(Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap());
+        let transfer = transaction(
+            Some(wallet),
+            Some(other),
+            30,
+            TransactionStatus::COMPLETED,
+            2,
+            None,
+        );
+        // Creada en enero pero asentada en febrero: cuenta en febrero.
+        let late_deposit = transaction(
+            None,
+            Some(wallet),
+            50,
+            TransactionStatus::COMPLETED,
+            -10,
+            None,
+        );
+
+        let statement = WalletStatement::build(
+            wallet,
+            "USD".into(),
+            period,
+            Decimal::from(100),
+            vec![posting(&transfer, -30, 2), posting(&late_deposit, 50, 1)],
+        );
+
+        let ids: Vec<TransactionId> = statement.lines.iter().map(|l| l.transaction_id).collect();
+        assert_eq!(ids, vec![late_deposit.id(), transfer.id()]);
+        let balances: Vec<Decimal> = statement.lines.iter().map(|l| l.running_balance).collect();
+        assert_eq!(balances, vec![Decimal::from(150), Decimal::from(120)]);
+        assert_eq!(statement.total_credits, Decimal::from(50));
+        assert_eq!(statement.total_debits, Decimal::from(30));
+        assert_eq!(statement.closing_balance, Decimal::from(120));
+    }
+
+    #[test]
+    fn test_csv_escapes_free_text() {
+        let wallet = WalletId::new();
+        let period = StatementPeriod::previous_month(Utc::now());
+        let deposit = transaction(
+            None,
+            Some(wallet),
+            10,
+            TransactionStatus::COMPLETED,
+            0,
+            Some("=HYPERLINK(\"x\"), pago"),
+        );
+
+        let statement = WalletStatement::build(
+            wallet,
+            "USD".into(),
+            period,
+            Decimal::ZERO,
+            vec![posting(&deposit, 10, 0)],
+        );
+
+        let csv = statement.to_csv();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.contains(",\"'=HYPERLINK(\"\"x\"\"), pago\","));
+    }
+
+    #[test]
+    fn test_statement_saved_before_rename_still_deserializes() {
+        let line = serde_json::json!({
+            "transaction_id": TransactionId::new(),
+            "created_at": "2024-02-01T00:00:00Z",
+            "transaction_type": "DEPOSIT",
+            "status": "COMPLETED",
+            "description": null,
+            "external_reference": null,
+            "credit": "10",
+            "debit": "0",
+            "running_balance": "10"
+        });
+
+        let line: StatementLine = serde_json::from_value(line).unwrap();
+        assert_eq!(
+            line.posted_at,
+            Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_period_rejects_empty_range() {
+        let now = Utc::now();
+        assert!(matches!(
+            StatementPeriod::new(now, now),
+            Err(TransactionError::ValidationError(_))
+        ));
+    }
+}
@@ -1,10 +1,18 @@
-use crate::domain::{entities::Transaction, error::TransactionError, gateways::WalletGateway};
+use crate::domain::{
+    entities::Transaction,
+    error::TransactionError,
+    gateways::{HistoricalBalance, LedgerPosting, MovementStatus, WalletCurrency, WalletGateway},
+    saga::SagaStep,
+    types::WalletId,
+};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use tracing::info;
 
 /// Implementación Mock del Gateway de Wallet para desarrollo y testing.
 ///
-/// Siempre retorna `true` (éxito) y loguea la operación.
+/// Siempre reserva, captura, transfiere y libera con éxito y loguea la operación.
 /// Útil para probar el flujo de Transaction Service sin levantar Wallet Service.
 pub struct FakeWalletGateway;
 
@@ -14,24 +22,122 @@ impl FakeWalletGateway {
     }
 }
 
+impl Default for FakeWalletGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl WalletGateway for FakeWalletGateway {
-    async fn process_movement(&self, transaction: &Transaction) -> Result<bool, TransactionError> {
+    async fn reserve_leg(
+        &self,
+        transaction: &Transaction,
+        step: &SagaStep,
+    ) -> Result<bool, TransactionError> {
         info!(
-            " [FakeWalletGateway] Processing movement for Transaction ID: {}",
+            " [FakeWalletGateway] Reserving leg {} of Transaction ID: {}",
+            step.leg,
             transaction.id()
         );
         info!(
-            " [FakeWalletGateway] Amount: {}, Source: {:?}, Dest: {}",
-            transaction.amount(),
-            transaction.source_wallet_id(),
-            transaction.destination_wallet_id()
+            " [FakeWalletGateway] Wallet: {}, Amount: {}",
+            step.wallet_id, step.amount
         );
 
         // Simulamos un pequeño delay de red
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
-        info!(" [FakeWalletGateway] Movement APPROVED");
+        info!(" [FakeWalletGateway] Leg RESERVED");
+        Ok(true)
+    }
+
+    async fn capture_leg(&self, step: &SagaStep) -> Result<(), TransactionError> {
+        info!(
+            " [FakeWalletGateway] Capturing leg {} of Transaction ID: {}",
+            step.leg, step.transaction_id
+        );
+        Ok(())
+    }
+
+    async fn execute_transfer(
+        &self,
+        debit: &SagaStep,
+        credit: &SagaStep,
+    ) -> Result<bool, TransactionError> {
+        info!(
+            " [FakeWalletGateway] Transferring {} from {} to {} (Transaction ID: {})",
+            credit.amount, debit.wallet_id, credit.wallet_id, debit.transaction_id
+        );
         Ok(true)
     }
+
+    async fn release_leg(&self, step: &SagaStep) -> Result<(), TransactionError> {
+        info!(
+            " [FakeWalletGateway] Releasing leg {} of Transaction ID: {}",
+            step.leg, step.transaction_id
+        );
+        Ok(())
+    }
+
+    async fn movement_status(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<MovementStatus, TransactionError> {
+        info!(
+            " [FakeWalletGateway] Movement status for Transaction ID: {} -> NotFound",
+            transaction.id()
+        );
+        Ok(MovementStatus::NotFound)
+    }
+
+    async fn wallet_currency(
+        &self,
+        wallet_id: WalletId,
+    ) -> Result<WalletCurrency, TransactionError> {
+        info!(
+            " [FakeWalletGateway] Currency for Wallet ID: {} -> USD",
+            wallet_id
+        );
+        Ok(WalletCurrency {
+            code: "USD".to_string(),
+            minor_units: 2,
+        })
+    }
+
+    async fn historical_balance(
+        &self,
+        wallet_id: WalletId,
+        as_of: DateTime<Utc>,
+    ) -> Result<HistoricalBalance, TransactionError> {
+        info!(
+            " [FakeWalletGateway] Balance for Wallet ID: {} at {} -> 0 USD",
+            wallet_id, as_of
+        );
+        Ok(HistoricalBalance {
+            currency: WalletCurrency {
+                code: "USD".to_string(),
+                minor_units: 2,
+            },
+            ledger_balance: Decimal::ZERO,
+        })
+    }
+
+    async fn ledger_postings(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LedgerPosting>, TransactionError> {
+        info!(
+            " [FakeWalletGateway] Ledger postings for Wallet ID: {} in [{}, {}) -> none",
+            wallet_id, from, to
+        );
+        Ok(Vec::new())
+    }
+
+    async fn active_wallets(&self) -> Result<Vec<WalletId>, TransactionError> {
+        info!(" [FakeWalletGateway] Active wallets -> none");
+        Ok(Vec::new())
+    }
 }
@@ -1,12 +1,23 @@
 use crate::api::proto::wallet::wallet_service_client::WalletServiceClient;
-use crate::api::proto::wallet::ValidateAndReserveRequest;
+use crate::api::proto::wallet::{
+    ConfirmBalanceUpdateRequest, ExecuteTransferRequest, GetBalanceRequest,
+    GetHistoricalBalanceRequest, GetMovementStatusRequest, MovementResult,
+    MovementStatus as ProtoMovementStatus, StreamBalancesRequest, StreamLedgerEntriesRequest,
+    ValidateAndReserveRequest,
+};
 use crate::domain::{
-    entities::{Transaction, TransactionType},
+    entities::Transaction,
     error::TransactionError,
-    gateways::WalletGateway,
+    gateways::{HistoricalBalance, LedgerPosting, MovementStatus, WalletCurrency, WalletGateway},
+    saga::{Saga, SagaStep},
+    types::{TransactionId, WalletId},
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use core::str::FromStr;
+use rust_decimal::Decimal;
 use tracing::{error, info};
+use uuid::Uuid;
 
 pub struct GrpcWalletGateway {
     wallet_url: String,
@@ -20,131 +31,405 @@ impl GrpcWalletGateway {
 
 #[async_trait]
 impl WalletGateway for GrpcWalletGateway {
-    async fn process_movement(&self, transaction: &Transaction) -> Result<bool, TransactionError> {
-        info!("Conectando al Wallet Service en {}", self.wallet_url);
+    async fn reserve_leg(
+        &self,
+        transaction: &Transaction,
+        step: &SagaStep,
+    ) -> Result<bool, TransactionError> {
+        let mut client = self.connect().await?;
 
-        let mut client = WalletServiceClient::connect(self.wallet_url.clone())
+        // El Wallet Service retiene los fondos de débito sin alterar todavía el balance contable.
+        let request = tonic::Request::new(ValidateAndReserveRequest {
+            wallet_id: step.wallet_id.to_string(),
+            amount: step.amount.to_string(),
+            transaction_id: step.transaction_id.to_string(),
+            transaction_type: format!("{:?}", transaction.transaction_type()),
+        });
+
+        let inner = client
+            .validate_and_reserve(request)
             .await
             .map_err(|e| {
-                TransactionError::GatewayError(format!(
-                    "Fallo de conexión al Wallet Service: {}",
-                    e
-                ))
-            })?;
-
-        // Determinar los movimientos a realizar dependiendo del tipo de transacción
-        let mut movements: Vec<(String, String)> = Vec::new();
+                error!(
+                    "Error gRPC al validar y reservar (wallet: {}, amount: {}): {}",
+                    step.wallet_id, step.amount, e
+                );
+                TransactionError::GatewayError(e.to_string())
+            })?
+            .into_inner();
 
-        match transaction.transaction_type() {
-            TransactionType::DEPOSIT => {
-                movements.push((
-                    transaction.destination_wallet_id().to_string(),
-                    transaction.amount().to_string(),
-                ));
-            }
-            TransactionType::WITHDRAWAL => {
-                movements.push((
-                    transaction.source_wallet_id().unwrap().to_string(),
-                    format!("-{}", transaction.amount()),
-                ));
-            }
-            TransactionType::TRANSFER => {
-                // Para TRANSFER, realizamos dos llamadas gRPC:
-                // 1. Débito a la billetera origen (reserva/retiro)
-                movements.push((
-                    transaction.source_wallet_id().unwrap().to_string(),
-                    format!("-{}", transaction.amount()),
-                ));
-                // 2. Crédito a la billetera destino (depósito)
-                movements.push((
-                    transaction.destination_wallet_id().to_string(),
-                    transaction.amount().to_string(),
-                ));
+        if inner.success {
+            if inner.replayed {
+                // El Wallet Service ya conocía esta pata (reintento): no se retuvo dos veces.
+                info!(
+                    "Movimiento ya procesado previamente (wallet: {}, estado: {:?})",
+                    step.wallet_id,
+                    inner.status()
+                );
+            } else {
+                info!(
+                    "Movimiento validado y reservado por Wallet Service (wallet: {}, amount: {})",
+                    step.wallet_id, step.amount
+                );
             }
+            return Ok(true);
+        }
+
+        info!(
+            "Wallet Service rechazó el movimiento (wallet: {}, amount: {}): {}",
+            step.wallet_id, step.amount, inner.message
+        );
+        Self::rejection(inner.result(), step.wallet_id, inner.message)
+    }
+
+    async fn execute_transfer(
+        &self,
+        debit: &SagaStep,
+        credit: &SagaStep,
+    ) -> Result<bool, TransactionError> {
+        let mut client = self.connect().await?;
+
+        // Ambas patas se aplican en una sola transacción de BD del Wallet Service.
+        let request = tonic::Request::new(ExecuteTransferRequest {
+            transaction_id: debit.transaction_id.to_string(),
+            source_wallet_id: debit.wallet_id.to_string(),
+            destination_wallet_id: credit.wallet_id.to_string(),
+            source_amount: (-debit.amount).to_string(),
+            destination_amount: credit.amount.to_string(),
+        });
+
+        let inner = client
+            .execute_transfer(request)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Error gRPC al ejecutar la transferencia {} ({} -> {}): {}",
+                    debit.transaction_id, debit.wallet_id, credit.wallet_id, e
+                );
+                TransactionError::GatewayError(e.to_string())
+            })?
+            .into_inner();
+
+        if inner.success {
+            info!(
+                "Transferencia {} ejecutada por Wallet Service{}",
+                debit.transaction_id,
+                if inner.replayed {
+                    " (ya procesada)"
+                } else {
+                    ""
+                }
+            );
+            return Ok(true);
+        }
+
+        info!(
+            "Wallet Service rechazó la transferencia {}: {}",
+            debit.transaction_id, inner.message
+        );
+        // Si el Wallet Service no indica qué billetera causó el rechazo, se atribuye al origen.
+        let wallet_id = Uuid::parse_str(&inner.rejected_wallet_id)
+            .map(WalletId)
+            .unwrap_or(debit.wallet_id);
+        Self::rejection(inner.result(), wallet_id, inner.message)
+    }
+
+    async fn capture_leg(&self, step: &SagaStep) -> Result<(), TransactionError> {
+        let mut client = self.connect().await?;
+
+        if let Some(reason) = Self::confirm(&mut client, step, true).await {
+            error!(
+                "Error crítico: Falló la captura de la reserva (wallet: {}, amount: {}): {}",
+                step.wallet_id, step.amount, reason
+            );
+            return Err(TransactionError::GatewayError(reason));
+        }
+        Ok(())
+    }
+
+    async fn release_leg(&self, step: &SagaStep) -> Result<(), TransactionError> {
+        let mut client = self.connect().await?;
+
+        info!(
+            "Ejecutando compensación (liberación de reserva) para wallet: {} por monto: {}",
+            step.wallet_id, step.amount
+        );
+
+        // Liberar devuelve los fondos retenidos al balance disponible sin generar movimientos
+        // contables inversos.
+        let Some(reason) = Self::confirm(&mut client, step, false).await else {
+            return Ok(());
         };
 
-        let mut executed_movements: Vec<(String, String)> = Vec::new();
+        // Una pata cuya reserva nunca llegó al Wallet Service (o que ya se liberó) no tiene
+        // nada que compensar.
+        let status = client
+            .get_movement_status(tonic::Request::new(GetMovementStatusRequest {
+                wallet_id: step.wallet_id.to_string(),
+                transaction_id: step.transaction_id.to_string(),
+            }))
+            .await
+            .map(|response| response.into_inner().status());
+        if let Ok(ProtoMovementStatus::NotFound | ProtoMovementStatus::Released) = status {
+            return Ok(());
+        }
+
+        error!(
+            "Error crítico: Falló la compensación para la wallet {}: {}",
+            step.wallet_id, reason
+        );
+        Err(TransactionError::GatewayError(reason))
+    }
+
+    async fn movement_status(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<MovementStatus, TransactionError> {
+        let mut client = self.connect().await?;
 
-        for (wallet_id, amount_str) in movements {
-            let request = tonic::Request::new(ValidateAndReserveRequest {
-                wallet_id: wallet_id.clone(),
-                amount: amount_str.clone(),
+        let mut statuses = Vec::new();
+        for step in Saga::plan(transaction).steps {
+            let request = tonic::Request::new(GetMovementStatusRequest {
+                wallet_id: step.wallet_id.to_string(),
                 transaction_id: transaction.id().to_string(),
             });
 
-            match client.validate_and_reserve(request).await {
-                Ok(response) => {
-                    let inner = response.into_inner();
-                    if inner.success {
-                        info!("Movimiento validado y procesado por Wallet Service (wallet: {}, amount: {})", wallet_id, amount_str);
-                        executed_movements.push((wallet_id, amount_str));
-                    } else {
-                        info!(
-                            "Wallet Service rechazó el movimiento (wallet: {}, amount: {}): {}",
-                            wallet_id, amount_str, inner.message
-                        );
-                        Self::compensate_movements(
-                            &mut client,
-                            &executed_movements,
-                            transaction.id().to_string(),
-                        )
-                        .await;
-                        return Ok(false);
-                    }
+            let response = client
+                .get_movement_status(request)
+                .await
+                .map_err(|e| TransactionError::GatewayError(e.to_string()))?;
+            statuses.push(response.into_inner().status());
+        }
+
+        // Agregamos el estado de las patas en un único estado de la transacción.
+        let status = if statuses.contains(&ProtoMovementStatus::Released) {
+            MovementStatus::Released
+        } else if statuses.iter().all(|s| *s == ProtoMovementStatus::Applied) {
+            MovementStatus::Applied
+        } else if statuses.iter().all(|s| *s == ProtoMovementStatus::NotFound) {
+            MovementStatus::NotFound
+        } else {
+            MovementStatus::Reserved
+        };
+
+        Ok(status)
+    }
+
+    async fn wallet_currency(
+        &self,
+        wallet_id: WalletId,
+    ) -> Result<WalletCurrency, TransactionError> {
+        let mut client = self.connect().await?;
+
+        let request = tonic::Request::new(GetBalanceRequest {
+            wallet_id: wallet_id.to_string(),
+        });
+
+        let balance = client
+            .get_balance(request)
+            .await
+            .map_err(|e| match e.code() {
+                tonic::Code::NotFound => {
+                    TransactionError::ValidationError(format!("Wallet {} not found", wallet_id))
+                }
+                _ => TransactionError::GatewayError(e.to_string()),
+            })?
+            .into_inner();
+
+        Ok(WalletCurrency {
+            code: balance.currency,
+            minor_units: balance.minor_units,
+        })
+    }
+
+    async fn historical_balance(
+        &self,
+        wallet_id: WalletId,
+        as_of: DateTime<Utc>,
+    ) -> Result<HistoricalBalance, TransactionError> {
+        let mut client = self.connect().await?;
+
+        let request = tonic::Request::new(GetHistoricalBalanceRequest {
+            wallet_id: wallet_id.to_string(),
+            as_of: as_of.to_rfc3339(),
+        });
+
+        let balance = client
+            .get_historical_balance(request)
+            .await
+            .map_err(|e| match e.code() {
+                tonic::Code::NotFound => {
+                    TransactionError::ValidationError(format!("Wallet {} not found", wallet_id))
+                }
+                tonic::Code::InvalidArgument => {
+                    TransactionError::ValidationError(e.message().to_string())
+                }
+                _ => TransactionError::GatewayError(e.to_string()),
+            })?
+            .into_inner();
+
+        let ledger_balance = Decimal::from_str(&balance.ledger_balance).map_err(|_| {
+            TransactionError::GatewayError(format!(
+                "Saldo histórico inválido: {}",
+                balance.ledger_balance
+            ))
+        })?;
+
+        Ok(HistoricalBalance {
+            currency: WalletCurrency {
+                code: balance.currency,
+                minor_units: balance.minor_units,
+            },
+            ledger_balance,
+        })
+    }
+
+    async fn ledger_postings(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LedgerPosting>, TransactionError> {
+        let mut client = self.connect().await?;
+
+        let mut stream = client
+            .stream_ledger_entries(tonic::Request::new(StreamLedgerEntriesRequest {
+                wallet_id: wallet_id.to_string(),
+                from: from.to_rfc3339(),
+                to: to.to_rfc3339(),
+                page_size: 0,
+            }))
+            .await
+            .map_err(|e| match e.code() {
+                tonic::Code::NotFound => {
+                    TransactionError::ValidationError(format!("Wallet {} not found", wallet_id))
                 }
-                Err(e) => {
-                    error!(
-                        "Error gRPC al validar y reservar (wallet: {}, amount: {}): {}",
-                        wallet_id, amount_str, e
-                    );
-                    Self::compensate_movements(
-                        &mut client,
-                        &executed_movements,
-                        transaction.id().to_string(),
-                    )
-                    .await;
-                    return Err(TransactionError::GatewayError(e.to_string()));
+                tonic::Code::InvalidArgument => {
+                    TransactionError::ValidationError(e.message().to_string())
                 }
+                _ => TransactionError::GatewayError(e.to_string()),
+            })?
+            .into_inner();
+
+        let mut postings = Vec::new();
+        while let Some(entry) = stream
+            .message()
+            .await
+            .map_err(|e| TransactionError::GatewayError(e.to_string()))?
+        {
+            let invalid = || {
+                TransactionError::GatewayError(format!(
+                    "Asiento {} inválido en el stream del libro mayor",
+                    entry.entry_id
+                ))
+            };
+            postings.push(LedgerPosting {
+                transaction_id: Uuid::parse_str(&entry.transaction_id)
+                    .map(TransactionId)
+                    .map_err(|_| invalid())?,
+                amount: Decimal::from_str(&entry.amount).map_err(|_| invalid())?,
+                posted_at: DateTime::parse_from_rfc3339(&entry.posted_at)
+                    .map_err(|_| invalid())?
+                    .with_timezone(&Utc),
+            });
+        }
+
+        Ok(postings)
+    }
+
+    async fn active_wallets(&self) -> Result<Vec<WalletId>, TransactionError> {
+        let mut client = self.connect().await?;
+
+        let mut stream = client
+            .stream_balances(tonic::Request::new(StreamBalancesRequest {
+                page_size: 0,
+                after_wallet_id: String::new(),
+            }))
+            .await
+            .map_err(|e| TransactionError::GatewayError(e.to_string()))?
+            .into_inner();
+
+        let mut wallets = Vec::new();
+        while let Some(balance) = stream
+            .message()
+            .await
+            .map_err(|e| TransactionError::GatewayError(e.to_string()))?
+        {
+            if balance.status != "ACTIVE" {
+                continue;
             }
+            let id = Uuid::parse_str(&balance.wallet_id).map_err(|_| {
+                TransactionError::GatewayError(format!(
+                    "wallet_id inválido en el stream de saldos: {}",
+                    balance.wallet_id
+                ))
+            })?;
+            wallets.push(WalletId(id));
         }
 
-        info!("Todos los movimientos de la transacción procesados exitosamente");
-        Ok(true)
+        Ok(wallets)
     }
 }
 
 impl GrpcWalletGateway {
-    /// Compensación simple (Saga) para deshacer movimientos ejecutados en caso de fallo
-    async fn compensate_movements(
-        client: &mut WalletServiceClient<tonic::transport::Channel>,
-        executed_movements: &[(String, String)],
-        transaction_id: String,
-    ) {
-        for (wallet_id, amount_str) in executed_movements.iter().rev() {
-            // Invertir el monto
-            let reversed_amount = if amount_str.starts_with('-') {
-                amount_str[1..].to_string()
-            } else {
-                format!("-{}", amount_str)
-            };
-
-            info!(
-                "Ejecutando compensación para wallet: {} por monto: {}",
-                wallet_id, reversed_amount
-            );
+    async fn connect(
+        &self,
+    ) -> Result<WalletServiceClient<tonic::transport::Channel>, TransactionError> {
+        info!("Conectando al Wallet Service en {}", self.wallet_url);
 
-            let req = tonic::Request::new(ValidateAndReserveRequest {
-                wallet_id: wallet_id.clone(),
-                amount: reversed_amount,
-                transaction_id: format!("{}-rollback", transaction_id),
-            });
+        WalletServiceClient::connect(self.wallet_url.clone())
+            .await
+            .map_err(|e| {
+                TransactionError::GatewayError(format!(
+                    "Fallo de conexión al Wallet Service: {}",
+                    e
+                ))
+            })
+    }
 
-            if let Err(e) = client.validate_and_reserve(req).await {
-                error!(
-                    "Error crítico: Falló la compensación para la wallet {}: {}",
-                    wallet_id, e
-                );
+    /// Traduce el rechazo de un movimiento de `wallet_id` al resultado de `reserve_leg`.
+    ///
+    /// Los fondos insuficientes, los límites de gasto, las billeteras congeladas/cerradas y los
+    /// montos inválidos se reportan con su propio error para que el cliente HTTP pueda
+    /// distinguirlos de un rechazo genérico.
+    fn rejection(
+        result: MovementResult,
+        wallet_id: WalletId,
+        message: String,
+    ) -> Result<bool, TransactionError> {
+        match result {
+            MovementResult::InsufficientFunds => {
+                Err(TransactionError::InsufficientFunds(wallet_id))
             }
+            MovementResult::LimitExceeded => {
+                Err(TransactionError::LimitExceeded(wallet_id, message))
+            }
+            MovementResult::WalletNotActive => Err(TransactionError::WalletNotActive(wallet_id)),
+            // Monto inválido para la billetera (p. ej. más decimales de los que admite su
+            // divisa): el mensaje del Wallet Service explica el motivo.
+            MovementResult::InvalidRequest => Err(TransactionError::ValidationError(message)),
+            _ => Ok(false),
+        }
+    }
+
+    /// Captura (`is_success = true`) o libera la reserva de una pata. Retorna el motivo si el
+    /// Wallet Service no pudo hacerlo.
+    async fn confirm(
+        client: &mut WalletServiceClient<tonic::transport::Channel>,
+        step: &SagaStep,
+        is_success: bool,
+    ) -> Option<String> {
+        let request = tonic::Request::new(ConfirmBalanceUpdateRequest {
+            wallet_id: step.wallet_id.to_string(),
+            amount: step.amount.to_string(),
+            transaction_id: step.transaction_id.to_string(),
+            is_success,
+        });
+
+        match client.confirm_balance_update(request).await {
+            Ok(response) if response.get_ref().success => None,
+            Ok(response) => Some(response.into_inner().message),
+            Err(e) => Some(e.to_string()),
         }
     }
 }
@@ -0,0 +1,131 @@
+use crate::domain::compensation::{CompensationStatus, PendingCompensation};
+use crate::domain::error::TransactionError;
+use crate::domain::repository::CompensationRepository;
+use crate::infrastructure::persistence::models::PendingCompensationModel;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Repositorio de compensaciones pendientes implementado para PostgreSQL.
+pub struct PostgresCompensationRepository {
+    pool: PgPool,
+}
+
+impl PostgresCompensationRepository {
+    /// Crea una nueva instancia del repositorio.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CompensationRepository for PostgresCompensationRepository {
+    /// `ON CONFLICT DO NOTHING`: si la recuperación al arrancar vuelve a fallar sobre una pata
+    /// ya registrada, no se reinicia su conteo de intentos.
+    async fn schedule(&self, compensation: &PendingCompensation) -> Result<(), TransactionError> {
+        sqlx::query(
+            r#"
+            INSERT INTO pending_compensations (
+                id, transaction_id, leg, wallet_id, amount, status, attempts, next_attempt_at,
+                last_error, resolution_note, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (transaction_id, leg) DO NOTHING
+            "#,
+        )
+        .bind(compensation.id)
+        .bind(compensation.transaction_id)
+        .bind(compensation.leg)
+        .bind(compensation.wallet_id)
+        .bind(compensation.amount)
+        .bind(compensation.status)
+        .bind(compensation.attempts)
+        .bind(compensation.next_attempt_at)
+        .bind(&compensation.last_error)
+        .bind(&compensation.resolution_note)
+        .bind(compensation.created_at)
+        .bind(compensation.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn update(&self, compensation: &PendingCompensation) -> Result<(), TransactionError> {
+        sqlx::query(
+            r#"
+            UPDATE pending_compensations
+            SET status = $2, attempts = $3, next_attempt_at = $4, last_error = $5,
+                resolution_note = $6, updated_at = $7
+            WHERE id = $1
+            "#,
+        )
+        .bind(compensation.id)
+        .bind(compensation.status)
+        .bind(compensation.attempts)
+        .bind(compensation.next_attempt_at)
+        .bind(&compensation.last_error)
+        .bind(&compensation.resolution_note)
+        .bind(compensation.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<PendingCompensation>, TransactionError> {
+        let models = sqlx::query_as::<_, PendingCompensationModel>(
+            r#"
+            SELECT * FROM pending_compensations
+            WHERE status = 'PENDING' AND next_attempt_at <= $1
+            ORDER BY next_attempt_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_by_status(
+        &self,
+        status: Option<CompensationStatus>,
+    ) -> Result<Vec<PendingCompensation>, TransactionError> {
+        let models = sqlx::query_as::<_, PendingCompensationModel>(
+            r#"
+            SELECT * FROM pending_compensations
+            WHERE $1::compensation_status IS NULL OR status = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PendingCompensation>, TransactionError> {
+        let model = sqlx::query_as::<_, PendingCompensationModel>(
+            "SELECT * FROM pending_compensations WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(model.map(Into::into))
+    }
+}
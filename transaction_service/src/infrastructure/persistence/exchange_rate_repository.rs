@@ -0,0 +1,90 @@
+use crate::domain::error::TransactionError;
+use crate::domain::exchange::{ExchangeRate, ExchangeRateProvider};
+use crate::domain::repository::ExchangeRateRepository;
+use crate::infrastructure::persistence::models::ExchangeRateModel;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+/// Tipos de cambio respaldados por la tabla `exchange_rates`.
+///
+/// Implementa tanto la escritura administrativa (`ExchangeRateRepository`) como la lectura
+/// que usan las cotizaciones (`ExchangeRateProvider`).
+pub struct PostgresExchangeRateRepository {
+    pool: PgPool,
+}
+
+impl PostgresExchangeRateRepository {
+    /// Crea una nueva instancia del repositorio.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn find_rate(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<Option<ExchangeRate>, TransactionError> {
+        let model_opt = sqlx::query_as::<_, ExchangeRateModel>(
+            r#"SELECT * FROM exchange_rates WHERE base_currency = $1 AND quote_currency = $2"#,
+        )
+        .bind(base_currency)
+        .bind(quote_currency)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(Into::into))
+    }
+}
+
+#[async_trait]
+impl ExchangeRateRepository for PostgresExchangeRateRepository {
+    async fn save_rate(&self, rate: ExchangeRate) -> Result<ExchangeRate, TransactionError> {
+        let saved = sqlx::query_as::<_, ExchangeRateModel>(
+            r#"
+            INSERT INTO exchange_rates (base_currency, quote_currency, rate, as_of)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (base_currency, quote_currency)
+            DO UPDATE SET rate = EXCLUDED.rate, as_of = EXCLUDED.as_of
+            RETURNING *
+            "#,
+        )
+        .bind(&rate.base_currency)
+        .bind(&rate.quote_currency)
+        .bind(rate.rate)
+        .bind(rate.as_of)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(saved.into())
+    }
+}
+
+#[async_trait]
+impl ExchangeRateProvider for PostgresExchangeRateRepository {
+    /// Busca el par directo y, si no existe, deriva la tasa del par inverso.
+    async fn current_rate(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+    ) -> Result<ExchangeRate, TransactionError> {
+        if let Some(rate) = self.find_rate(base_currency, quote_currency).await? {
+            return Ok(rate);
+        }
+
+        match self.find_rate(quote_currency, base_currency).await? {
+            Some(inverse) => Ok(ExchangeRate {
+                base_currency: base_currency.to_string(),
+                quote_currency: quote_currency.to_string(),
+                rate: Decimal::ONE / inverse.rate,
+                as_of: inverse.as_of,
+            }),
+            None => Err(TransactionError::ExchangeRateNotFound(
+                base_currency.to_string(),
+                quote_currency.to_string(),
+            )),
+        }
+    }
+}
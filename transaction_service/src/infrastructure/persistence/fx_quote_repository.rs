@@ -0,0 +1,57 @@
+use crate::domain::error::TransactionError;
+use crate::domain::exchange::FxQuote;
+use crate::domain::repository::FxQuoteRepository;
+use crate::domain::types::QuoteId;
+use crate::infrastructure::persistence::models::FxQuoteModel;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+/// Repositorio de cotizaciones de tipo de cambio implementado para PostgreSQL.
+pub struct PostgresFxQuoteRepository {
+    pool: PgPool,
+}
+
+impl PostgresFxQuoteRepository {
+    /// Crea una nueva instancia del repositorio.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FxQuoteRepository for PostgresFxQuoteRepository {
+    async fn save(&self, quote: FxQuote) -> Result<FxQuote, TransactionError> {
+        let saved = sqlx::query_as::<_, FxQuoteModel>(
+            r#"
+            INSERT INTO fx_quotes (
+                id, source_currency, destination_currency, rate, rate_timestamp, expires_at, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(quote.id)
+        .bind(&quote.source_currency)
+        .bind(&quote.destination_currency)
+        .bind(quote.rate)
+        .bind(quote.rate_timestamp)
+        .bind(quote.expires_at)
+        .bind(quote.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(saved.into())
+    }
+
+    async fn find_by_id(&self, id: QuoteId) -> Result<Option<FxQuote>, TransactionError> {
+        let model_opt =
+            sqlx::query_as::<_, FxQuoteModel>(r#"SELECT * FROM fx_quotes WHERE id = $1"#)
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(Into::into))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::domain::error::TransactionError;
+use crate::domain::leader::LeaderElection;
+use async_trait::async_trait;
+use sqlx::{Connection, PgConnection, PgPool};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// Elección de líder sobre un advisory lock de sesión de PostgreSQL.
+///
+/// El líder es la instancia que tiene `pg_try_advisory_lock(key)`. El lock pertenece a la
+/// sesión, así que se guarda una conexión dedicada (fuera del pool) mientras dure el
+/// liderazgo: si la instancia muere o la conexión se cae, PostgreSQL lo libera y otra réplica
+/// lo toma en su siguiente intento.
+///
+/// Cada job singleton usa su propia `key`, de modo que distintas réplicas pueden liderar
+/// distintos jobs.
+pub struct PostgresLeaderElection {
+    pool: PgPool,
+    key: i64,
+    /// Sesión que tiene el lock, si esta instancia es la líder.
+    session: Mutex<Option<PgConnection>>,
+}
+
+impl PostgresLeaderElection {
+    /// Crea una nueva instancia sobre el advisory lock `key`.
+    pub fn new(pool: PgPool, key: i64) -> Self {
+        Self {
+            pool,
+            key,
+            session: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElection for PostgresLeaderElection {
+    async fn is_leader(&self) -> Result<bool, TransactionError> {
+        let mut session = self.session.lock().await;
+
+        if let Some(connection) = session.as_mut() {
+            // Mientras la sesión siga viva, el lock sigue siendo nuestro.
+            match connection.ping().await {
+                Ok(()) => return Ok(true),
+                Err(e) => {
+                    warn!(
+                        "Lost leader session for lock {}, re-electing: {:?}",
+                        self.key, e
+                    );
+                    *session = None;
+                }
+            }
+        }
+
+        let mut connection = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(self.key)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        if acquired {
+            info!("Acquired leadership for lock {}", self.key);
+            // Se saca la conexión del pool: al cerrarse (o al soltar el líder) se libera el
+            // lock, en lugar de quedar tomado en una conexión reutilizada.
+            *session = Some(connection.detach());
+        }
+        Ok(acquired)
+    }
+}
@@ -1,2 +1,9 @@
+pub mod compensation_repository;
+pub mod exchange_rate_repository;
+pub mod fx_quote_repository;
+pub mod leader_election;
 pub mod models;
+pub mod saga_repository;
+pub mod transaction_event_repository;
 pub mod transaction_repository;
+pub mod wallet_statement_repository;
@@ -1,7 +1,15 @@
-use crate::domain::entities::{Transaction, TransactionStatus, TransactionType};
-use crate::domain::types::{TransactionId, WalletId};
+use crate::domain::compensation::{CompensationStatus, PendingCompensation};
+use crate::domain::entities::{
+    Transaction, TransactionDetails, TransactionStatus, TransactionType,
+};
+use crate::domain::events::{EventActor, TransactionEvent};
+use crate::domain::exchange::{ExchangeDetails, ExchangeRate, FxQuote};
+use crate::domain::retry::RetryState;
+use crate::domain::saga::{SagaStep, SagaStepStatus};
+use crate::domain::types::{QuoteId, TransactionId, WalletId};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use sqlx::types::Json;
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -12,12 +20,31 @@ use uuid::Uuid;
 pub struct TransactionModel {
     pub id: TransactionId,
     pub source_wallet_id: Option<WalletId>,
-    pub destination_wallet_id: WalletId,
+    pub destination_wallet_id: Option<WalletId>,
+    pub external_destination: Option<String>,
     pub amount: Decimal,
     pub status: TransactionStatus,
     pub transaction_type: TransactionType,
     pub created_at: DateTime<Utc>,
     pub correlation_id: Uuid,
+    // Columnas de conversión: todas nulas o todas presentes.
+    pub source_currency: Option<String>,
+    pub destination_currency: Option<String>,
+    pub destination_amount: Option<Decimal>,
+    pub exchange_rate: Option<Decimal>,
+    pub rate_timestamp: Option<DateTime<Utc>>,
+    pub quote_id: Option<QuoteId>,
+    pub parent_transaction_id: Option<TransactionId>,
+    pub refunded_amount: Decimal,
+    pub description: Option<String>,
+    pub external_reference: Option<String>,
+    pub metadata: Json<serde_json::Map<String, serde_json::Value>>, // JSONB
+    pub client_id: Option<String>,
+    pub request_fingerprint: Option<String>,
+    // Estado del job de reintentos.
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
 }
 
 // Conversión Dominio -> Modelo (Eficiente: Copy Semantics)
@@ -27,15 +54,33 @@ pub struct TransactionModel {
 // y esto nos permite reutilizar la entidad original si fuera necesario.
 impl From<&Transaction> for TransactionModel {
     fn from(t: &Transaction) -> Self {
+        let fx = t.exchange();
         Self {
             id: t.id(),
             source_wallet_id: t.source_wallet_id(),
             destination_wallet_id: t.destination_wallet_id(),
+            external_destination: t.external_destination().map(str::to_string),
             amount: t.amount(),
             status: t.status(),
             transaction_type: t.transaction_type(),
             created_at: t.created_at(),
             correlation_id: t.correlation_id(),
+            source_currency: fx.map(|fx| fx.source_currency.clone()),
+            destination_currency: fx.map(|fx| fx.destination_currency.clone()),
+            destination_amount: fx.map(|fx| fx.destination_amount),
+            exchange_rate: fx.map(|fx| fx.rate),
+            rate_timestamp: fx.map(|fx| fx.rate_timestamp),
+            quote_id: fx.map(|fx| fx.quote_id),
+            parent_transaction_id: t.parent_transaction_id(),
+            refunded_amount: t.refunded_amount(),
+            description: t.details().description.clone(),
+            external_reference: t.details().external_reference.clone(),
+            metadata: Json(t.details().metadata.clone()),
+            client_id: t.client_id().map(str::to_string),
+            request_fingerprint: t.request_fingerprint().map(str::to_string),
+            attempts: t.retry_state().attempts,
+            next_attempt_at: t.retry_state().next_attempt_at,
+            last_error: t.retry_state().last_error.clone(),
         }
     }
 }
@@ -45,16 +90,192 @@ impl From<&Transaction> for TransactionModel {
 // la validación de tipos la garantiza SQLx al leer de la DB.
 impl From<TransactionModel> for Transaction {
     fn from(m: TransactionModel) -> Self {
+        let exchange = match (
+            m.source_currency,
+            m.destination_currency,
+            m.destination_amount,
+            m.exchange_rate,
+            m.rate_timestamp,
+            m.quote_id,
+        ) {
+            (
+                Some(source_currency),
+                Some(destination_currency),
+                Some(destination_amount),
+                Some(rate),
+                Some(rate_timestamp),
+                Some(quote_id),
+            ) => Some(ExchangeDetails {
+                source_currency,
+                destination_currency,
+                destination_amount,
+                rate,
+                rate_timestamp,
+                quote_id,
+            }),
+            _ => None,
+        };
+
         Transaction::reconstitute(
             m.id,
             m.source_wallet_id,
             m.destination_wallet_id,
+            m.external_destination,
             m.amount,
             m.status,
             m.transaction_type,
             m.created_at,
             m.correlation_id,
+            exchange,
+            m.parent_transaction_id,
+            m.refunded_amount,
+            TransactionDetails {
+                description: m.description,
+                external_reference: m.external_reference,
+                metadata: m.metadata.0,
+            },
         )
         .expect("Invalid Transaction state from DB")
+        .with_client_id(m.client_id)
+        .with_request_fingerprint(m.request_fingerprint)
+        .with_retry_state(RetryState {
+            attempts: m.attempts,
+            next_attempt_at: m.next_attempt_at,
+            last_error: m.last_error,
+        })
+    }
+}
+
+// Modelo de la tabla 'exchange_rates'.
+#[derive(Debug, FromRow)]
+pub struct ExchangeRateModel {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: Decimal,
+    pub as_of: DateTime<Utc>,
+}
+
+impl From<ExchangeRateModel> for ExchangeRate {
+    fn from(m: ExchangeRateModel) -> Self {
+        ExchangeRate {
+            base_currency: m.base_currency,
+            quote_currency: m.quote_currency,
+            rate: m.rate,
+            as_of: m.as_of,
+        }
+    }
+}
+
+// Modelo de la tabla 'fx_quotes'.
+#[derive(Debug, FromRow)]
+pub struct FxQuoteModel {
+    pub id: QuoteId,
+    pub source_currency: String,
+    pub destination_currency: String,
+    pub rate: Decimal,
+    pub rate_timestamp: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<FxQuoteModel> for FxQuote {
+    fn from(m: FxQuoteModel) -> Self {
+        FxQuote {
+            id: m.id,
+            source_currency: m.source_currency,
+            destination_currency: m.destination_currency,
+            rate: m.rate,
+            rate_timestamp: m.rate_timestamp,
+            expires_at: m.expires_at,
+            created_at: m.created_at,
+        }
+    }
+}
+
+// Modelo de la tabla 'transaction_events'.
+#[derive(Debug, FromRow)]
+pub struct TransactionEventModel {
+    pub id: Uuid,
+    pub transaction_id: TransactionId,
+    pub from_status: Option<TransactionStatus>,
+    pub to_status: TransactionStatus,
+    pub actor: EventActor,
+    pub gateway_message: Option<String>,
+    pub error_detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TransactionEventModel> for TransactionEvent {
+    fn from(m: TransactionEventModel) -> Self {
+        TransactionEvent {
+            id: m.id,
+            transaction_id: m.transaction_id,
+            from_status: m.from_status,
+            to_status: m.to_status,
+            actor: m.actor,
+            gateway_message: m.gateway_message,
+            error_detail: m.error_detail,
+            created_at: m.created_at,
+        }
+    }
+}
+
+// Modelo de la tabla 'saga_steps'.
+#[derive(Debug, FromRow)]
+pub struct SagaStepModel {
+    pub transaction_id: TransactionId,
+    pub leg: i16,
+    pub wallet_id: WalletId,
+    pub amount: Decimal,
+    pub status: SagaStepStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<SagaStepModel> for SagaStep {
+    fn from(m: SagaStepModel) -> Self {
+        SagaStep {
+            transaction_id: m.transaction_id,
+            leg: m.leg,
+            wallet_id: m.wallet_id,
+            amount: m.amount,
+            status: m.status,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+// Modelo de la tabla 'pending_compensations'.
+#[derive(Debug, FromRow)]
+pub struct PendingCompensationModel {
+    pub id: Uuid,
+    pub transaction_id: TransactionId,
+    pub leg: i16,
+    pub wallet_id: WalletId,
+    pub amount: Decimal,
+    pub status: CompensationStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: String,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PendingCompensationModel> for PendingCompensation {
+    fn from(m: PendingCompensationModel) -> Self {
+        PendingCompensation {
+            id: m.id,
+            transaction_id: m.transaction_id,
+            leg: m.leg,
+            wallet_id: m.wallet_id,
+            amount: m.amount,
+            status: m.status,
+            attempts: m.attempts,
+            next_attempt_at: m.next_attempt_at,
+            last_error: m.last_error,
+            resolution_note: m.resolution_note,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
     }
 }
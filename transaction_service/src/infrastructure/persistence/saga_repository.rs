@@ -0,0 +1,169 @@
+use crate::domain::error::TransactionError;
+use crate::domain::repository::SagaRepository;
+use crate::domain::saga::{Saga, SagaStep};
+use crate::domain::types::TransactionId;
+use crate::infrastructure::persistence::models::SagaStepModel;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+/// Repositorio del registro de sagas implementado para PostgreSQL.
+pub struct PostgresSagaRepository {
+    pool: PgPool,
+}
+
+impl PostgresSagaRepository {
+    /// Crea una nueva instancia del repositorio.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn steps(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Vec<SagaStep>, TransactionError> {
+        let models = sqlx::query_as::<_, SagaStepModel>(
+            r#"
+            SELECT * FROM saga_steps
+            WHERE transaction_id = $1
+            ORDER BY leg ASC
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
+
+#[async_trait]
+impl SagaRepository for PostgresSagaRepository {
+    /// Inserta todas las patas en una sola sentencia; `ON CONFLICT DO NOTHING` conserva la
+    /// saga de un intento anterior, que se relee a continuación.
+    async fn start(&self, saga: Saga) -> Result<Saga, TransactionError> {
+        let mut builder = sqlx::QueryBuilder::new(
+            "INSERT INTO saga_steps (transaction_id, leg, wallet_id, amount, status, updated_at) ",
+        );
+        builder.push_values(&saga.steps, |mut row, step| {
+            row.push_bind(step.transaction_id)
+                .push_bind(step.leg)
+                .push_bind(step.wallet_id)
+                .push_bind(step.amount)
+                .push_bind(step.status)
+                .push_bind(step.updated_at);
+        });
+        builder.push(" ON CONFLICT (transaction_id, leg) DO NOTHING");
+
+        builder
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(Saga {
+            transaction_id: saga.transaction_id,
+            steps: self.steps(saga.transaction_id).await?,
+        })
+    }
+
+    async fn update_step(&self, step: &SagaStep) -> Result<(), TransactionError> {
+        sqlx::query(
+            r#"
+            UPDATE saga_steps
+            SET status = $3, updated_at = $4
+            WHERE transaction_id = $1 AND leg = $2
+            "#,
+        )
+        .bind(step.transaction_id)
+        .bind(step.leg)
+        .bind(step.status)
+        .bind(step.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// El lease se toma sobre la fila de la transacción con `FOR UPDATE SKIP LOCKED`, igual
+    /// que el job de reintentos: dos réplicas que recuperan a la vez no toman la misma saga, y
+    /// ninguna toma una que el job tiene reclamada. Una saga con alguna pata avanzada después
+    /// de `older_than` sigue en curso y se omite, igual que la de una transacción que ya no está
+    /// PENDING (p. ej. en `DEAD_LETTER`, a la espera de un operador).
+    async fn claim_unfinished(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+        owner: &str,
+        lease: chrono::Duration,
+    ) -> Result<Vec<Saga>, TransactionError> {
+        let models = sqlx::query_as::<_, SagaStepModel>(
+            r#"
+            WITH claimed AS (
+                UPDATE transactions
+                SET claimed_by = $2, claimed_until = NOW() + make_interval(secs => $3)
+                WHERE id IN (
+                    SELECT t.id FROM transactions t
+                    WHERE EXISTS (
+                        SELECT 1 FROM saga_steps s
+                        WHERE s.transaction_id = t.id
+                          AND s.status IN ('PLANNED', 'SENT', 'ACKNOWLEDGED')
+                    )
+                      AND NOT EXISTS (
+                        SELECT 1 FROM saga_steps s
+                        WHERE s.transaction_id = t.id AND s.updated_at >= $1
+                    )
+                      AND t.status = 'PENDING'
+                      AND (t.claimed_until IS NULL OR t.claimed_until < NOW())
+                    FOR UPDATE OF t SKIP LOCKED
+                )
+                RETURNING id
+            )
+            SELECT saga_steps.* FROM saga_steps
+            JOIN claimed ON claimed.id = saga_steps.transaction_id
+            ORDER BY saga_steps.transaction_id, saga_steps.leg ASC
+            "#,
+        )
+        .bind(older_than)
+        .bind(owner)
+        .bind(lease.num_milliseconds() as f64 / 1000.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        // Las filas llegan agrupadas por transacción.
+        let mut sagas: Vec<Saga> = Vec::new();
+        for step in models.into_iter().map(SagaStep::from) {
+            match sagas.last_mut() {
+                Some(saga) if saga.transaction_id == step.transaction_id => saga.steps.push(step),
+                _ => sagas.push(Saga {
+                    transaction_id: step.transaction_id,
+                    steps: vec![step],
+                }),
+            }
+        }
+
+        Ok(sagas)
+    }
+
+    async fn release(
+        &self,
+        transaction_id: TransactionId,
+        owner: &str,
+    ) -> Result<(), TransactionError> {
+        sqlx::query(
+            r#"
+            UPDATE transactions
+            SET claimed_by = NULL, claimed_until = NULL
+            WHERE id = $1 AND claimed_by = $2
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(owner)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
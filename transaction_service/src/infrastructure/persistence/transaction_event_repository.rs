@@ -0,0 +1,66 @@
+use crate::domain::error::TransactionError;
+use crate::domain::events::TransactionEvent;
+use crate::domain::repository::TransactionEventRepository;
+use crate::domain::types::TransactionId;
+use crate::infrastructure::persistence::models::TransactionEventModel;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+/// Repositorio del historial de estados de transacciones implementado para PostgreSQL.
+pub struct PostgresTransactionEventRepository {
+    pool: PgPool,
+}
+
+impl PostgresTransactionEventRepository {
+    /// Crea una nueva instancia del repositorio.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TransactionEventRepository for PostgresTransactionEventRepository {
+    async fn record(&self, event: TransactionEvent) -> Result<TransactionEvent, TransactionError> {
+        let saved = sqlx::query_as::<_, TransactionEventModel>(
+            r#"
+            INSERT INTO transaction_events (
+                id, transaction_id, from_status, to_status, actor, gateway_message, error_detail, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(event.id)
+        .bind(event.transaction_id)
+        .bind(event.from_status)
+        .bind(event.to_status)
+        .bind(event.actor)
+        .bind(&event.gateway_message)
+        .bind(&event.error_detail)
+        .bind(event.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(saved.into())
+    }
+
+    async fn find_by_transaction_id(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Vec<TransactionEvent>, TransactionError> {
+        let models = sqlx::query_as::<_, TransactionEventModel>(
+            r#"
+            SELECT * FROM transaction_events
+            WHERE transaction_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+}
@@ -1,10 +1,12 @@
-use crate::domain::entities::Transaction;
+use crate::domain::entities::{Transaction, TransactionStatus};
 use crate::domain::error::TransactionError;
+use crate::domain::history::{HistoryCursor, HistoryDirection, HistoryFilter};
 use crate::domain::repository::TransactionRepository;
 use crate::domain::types::{TransactionId, WalletId};
 use crate::infrastructure::persistence::models::TransactionModel;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 /// Repositorio de transacciones implementado para PostgreSQL.
@@ -22,6 +24,59 @@ impl PostgresTransactionRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Compare-and-set del estado compartido por `update` y `update_claimed`. Con `owner`, la
+    /// escritura además exige que nadie más tenga reclamada la transacción.
+    async fn write_status(
+        &self,
+        transaction: Transaction,
+        expected_status: TransactionStatus,
+        owner: Option<&str>,
+    ) -> Result<Transaction, TransactionError> {
+        let model = TransactionModel::from(&transaction);
+
+        // Compare-and-set: sólo se escribe si nadie cambió el estado desde que se leyó.
+        let updated_model = sqlx::query_as::<_, TransactionModel>(
+            r#"
+            UPDATE transactions
+            SET status = $1, transaction_type = $2, attempts = $5, next_attempt_at = $6,
+                last_error = $7, claimed_by = NULL, claimed_until = NULL
+            WHERE id = $3 AND status = $4
+              AND ($8::TEXT IS NULL OR claimed_by IS NULL OR claimed_by = $8)
+            RETURNING *
+            "#,
+        )
+        .bind(model.status)
+        .bind(model.transaction_type)
+        .bind(model.id)
+        .bind(expected_status)
+        .bind(model.attempts)
+        .bind(model.next_attempt_at)
+        .bind(model.last_error)
+        .bind(owner)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        if let Some(m) = updated_model {
+            return Ok(m.into());
+        }
+
+        // Distinguimos "no existe", "otro proceso cambió el estado primero" y "otra instancia
+        // la reclamó".
+        match self.find_by_id(transaction.id()).await? {
+            Some(current) if current.status() == expected_status && owner.is_some() => {
+                Err(TransactionError::LeaseLost(transaction.id()))
+            }
+            Some(current) => Err(TransactionError::InvalidState(format!(
+                "La transacción {} está en {:?}, se esperaba {:?}",
+                transaction.id(),
+                current.status(),
+                expected_status
+            ))),
+            None => Err(TransactionError::NotFound(transaction.id())),
+        }
+    }
 }
 
 #[async_trait]
@@ -31,18 +86,41 @@ impl TransactionRepository for PostgresTransactionRepository {
     /// Se utiliza al inicio del flujo (Saga) para registrar la intención de pago
     /// antes de ejecutar cualquier lógica de negocio en otros servicios.
     ///
+    /// El INSERT y la reserva del `correlation_id` en `idempotency_keys` van en la misma
+    /// transacción de base de datos: si la clave sigue reservada por otra transacción, se
+    /// deshace el INSERT.
+    ///
     /// # Errores
-    /// Retorna `TransactionError::RepositoryError` si falla la conexión o la query SQL (ej. constraints).
-    async fn save(&self, transaction: Transaction) -> Result<Transaction, TransactionError> {
+    /// Retorna `TransactionError::IdempotencyError` si el `correlation_id` está reservado y
+    /// vigente, y `TransactionError::RepositoryError` si falla la conexión o la query SQL
+    /// (ej. constraints).
+    async fn save(
+        &self,
+        transaction: Transaction,
+        key_ttl: chrono::Duration,
+    ) -> Result<Transaction, TransactionError> {
         // Convertimos la entidad de dominio a nuestro modelo de persistencia (Infrastructure Layer)
         let model = TransactionModel::from(&transaction);
+        let correlation_id = model.correlation_id;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
 
         let saved_model = sqlx::query_as::<_, TransactionModel>(
             r#"
             INSERT INTO transactions (
-                id, source_wallet_id, destination_wallet_id, amount, status, transaction_type, created_at, correlation_id
+                id, source_wallet_id, destination_wallet_id, external_destination, amount, status,
+                transaction_type, created_at, correlation_id, source_currency, destination_currency,
+                destination_amount, exchange_rate, rate_timestamp, quote_id, parent_transaction_id,
+                refunded_amount, description, external_reference, metadata, client_id,
+                request_fingerprint
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                $19, $20, $21, $22
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING *
             "#,
         )
@@ -51,15 +129,55 @@ impl TransactionRepository for PostgresTransactionRepository {
         .bind(model.id)
         .bind(model.source_wallet_id)
         .bind(model.destination_wallet_id)
+        .bind(model.external_destination)
         .bind(model.amount)
         .bind(model.status)
         .bind(model.transaction_type)
         .bind(model.created_at)
         .bind(model.correlation_id)
-        .fetch_one(&self.pool)
+        .bind(model.source_currency)
+        .bind(model.destination_currency)
+        .bind(model.destination_amount)
+        .bind(model.exchange_rate)
+        .bind(model.rate_timestamp)
+        .bind(model.quote_id)
+        .bind(model.parent_transaction_id)
+        .bind(model.refunded_amount)
+        .bind(model.description)
+        .bind(model.external_reference)
+        .bind(model.metadata)
+        .bind(model.client_id)
+        .bind(model.request_fingerprint)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        // Una clave vencida se reasigna a esta transacción; una vigente no devuelve fila.
+        let reserved = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (correlation_id, transaction_id, expires_at)
+            VALUES ($1, $2, NOW() + make_interval(secs => $3))
+            ON CONFLICT (correlation_id) DO UPDATE
+            SET transaction_id = EXCLUDED.transaction_id, expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at <= NOW()
+            RETURNING transaction_id
+            "#,
+        )
+        .bind(correlation_id)
+        .bind(saved_model.id)
+        .bind(key_ttl.num_milliseconds() as f64 / 1000.0)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
 
+        if reserved.is_none() {
+            // Al soltar `tx` sin commit se deshace el INSERT.
+            return Err(TransactionError::IdempotencyError(correlation_id));
+        }
+        tx.commit()
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
         // Mapeamos de vuelta al dominio
         Ok(saved_model.into())
     }
@@ -68,28 +186,24 @@ impl TransactionRepository for PostgresTransactionRepository {
     ///
     /// Se utiliza para finalizar el proceso de pago (COMPLETED/FAILED/REVERSED).
     /// Solo actualizamos campos mutables, los detalles financieros (monto, wallets) son inmutables.
-    async fn update(&self, transaction: Transaction) -> Result<Transaction, TransactionError> {
-        let model = TransactionModel::from(&transaction);
-
-        let updated_model = sqlx::query_as::<_, TransactionModel>(
-            r#"
-            UPDATE transactions 
-            SET status = $1, transaction_type = $2 
-            WHERE id = $3 
-            RETURNING *
-            "#,
-        )
-        .bind(model.status)
-        .bind(model.transaction_type)
-        .bind(model.id)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+    async fn update(
+        &self,
+        transaction: Transaction,
+        expected_status: TransactionStatus,
+    ) -> Result<Transaction, TransactionError> {
+        self.write_status(transaction, expected_status, None).await
+    }
 
-        match updated_model {
-            Some(m) => Ok(m.into()),
-            None => Err(TransactionError::NotFound(transaction.id())),
-        }
+    /// Actualiza una transacción reclamada por el job de reintentos, salvo que otra instancia
+    /// se la haya quitado al vencer el lease.
+    async fn update_claimed(
+        &self,
+        transaction: Transaction,
+        expected_status: TransactionStatus,
+        owner: &str,
+    ) -> Result<Transaction, TransactionError> {
+        self.write_status(transaction, expected_status, Some(owner))
+            .await
     }
 
     async fn find_by_id(&self, id: TransactionId) -> Result<Option<Transaction>, TransactionError> {
@@ -107,32 +221,60 @@ impl TransactionRepository for PostgresTransactionRepository {
         }
     }
 
-    /// Recupera historial de transacciones para una Wallet específica.
-    ///
-    /// Retorna una lista ordenada por fecha de creación descendente (lo más reciente primero).
-    /// Incluye transacciones donde la wallet actúa como origen O destino.
-    async fn find_by_wallet_id(
+    async fn find_by_ids(
         &self,
-        wallet_id: WalletId,
+        ids: &[TransactionId],
     ) -> Result<Vec<Transaction>, TransactionError> {
+        let ids: Vec<Uuid> = ids.iter().map(|id| id.0).collect();
         let models = sqlx::query_as::<_, TransactionModel>(
-            r#"
-            SELECT * FROM transactions 
-            WHERE source_wallet_id = $1 OR destination_wallet_id = $1
-            ORDER BY created_at DESC
-            "#,
+            r#"SELECT * FROM transactions WHERE id = ANY($1)"#,
         )
-        .bind(wallet_id)
+        .bind(ids)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
 
-        let mut transactions = Vec::new();
-        for model in models {
-            transactions.push(model.into());
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    /// Recupera una página del historial de transacciones de una Wallet específica.
+    ///
+    /// Retorna una lista ordenada por fecha de creación descendente (lo más reciente primero).
+    /// Incluye transacciones donde la wallet actúa como origen O destino.
+    ///
+    /// En lugar de `source_wallet_id = $1 OR destination_wallet_id = $1` (que obliga a leer y
+    /// ordenar todo el historial) se une una rama por rol, cada una servida por su índice
+    /// `(wallet, created_at DESC, id DESC)` y cortada en `limit`. Una wallet nunca es origen y
+    /// destino de la misma transacción, así que `UNION ALL` no produce duplicados.
+    async fn find_by_wallet_id(
+        &self,
+        wallet_id: WalletId,
+        filter: &HistoryFilter,
+        after: Option<HistoryCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, TransactionError> {
+        let branches: &[HistoryDirection] = match filter.direction {
+            Some(direction) => &[direction][..],
+            None => &[HistoryDirection::Out, HistoryDirection::In],
+        };
+
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM (");
+        for (i, direction) in branches.iter().enumerate() {
+            if i > 0 {
+                query.push(" UNION ALL ");
+            }
+            push_history_branch(&mut query, *direction, wallet_id, filter, after, limit);
         }
+        query.push(") AS history ORDER BY created_at DESC, id DESC LIMIT ");
+        query.push_bind(limit);
 
-        Ok(transactions)
+        let models = query
+            .build_query_as::<TransactionModel>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
     }
 
     /// Busca por Correlation ID (ID de Idempotencia).
@@ -143,7 +285,12 @@ impl TransactionRepository for PostgresTransactionRepository {
         correlation_id: Uuid,
     ) -> Result<Option<Transaction>, TransactionError> {
         let model_opt = sqlx::query_as::<_, TransactionModel>(
-            r#"SELECT * FROM transactions WHERE correlation_id = $1"#,
+            r#"
+            SELECT * FROM transactions
+            WHERE correlation_id = $1
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
         )
         .bind(correlation_id)
         .fetch_optional(&self.pool)
@@ -157,29 +304,262 @@ impl TransactionRepository for PostgresTransactionRepository {
         }
     }
 
-    /// Busca transacciones pendientes antiguas.
-    async fn find_pending_older_than(
+    /// Busca por referencia externa (factura, pedido) usando el índice parcial
+    /// `idx_transactions_external_reference`.
+    async fn find_by_external_reference(
+        &self,
+        external_reference: &str,
+    ) -> Result<Vec<Transaction>, TransactionError> {
+        let models = sqlx::query_as::<_, TransactionModel>(
+            r#"
+            SELECT * FROM transactions
+            WHERE external_reference = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(external_reference)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    /// Reclama transacciones pendientes antiguas cuyo siguiente intento ya venció.
+    ///
+    /// `FOR UPDATE SKIP LOCKED` evita que dos instancias que reclaman a la vez se bloqueen o
+    /// tomen las mismas filas, y el lease (`claimed_until`) las protege mientras se procesan
+    /// fuera de esta transacción. Si la instancia muere, el lease vence y otra las retoma.
+    async fn claim_pending_older_than(
         &self,
         timestamp: chrono::DateTime<chrono::Utc>,
+        owner: &str,
+        lease: chrono::Duration,
     ) -> Result<Vec<Transaction>, TransactionError> {
         let models = sqlx::query_as::<_, TransactionModel>(
             r#"
-            SELECT * FROM transactions 
-            WHERE status = 'PENDING' AND created_at < $1
-            ORDER BY created_at ASC
-            LIMIT 50
+            UPDATE transactions
+            SET claimed_by = $2, claimed_until = NOW() + make_interval(secs => $3)
+            WHERE id IN (
+                SELECT id FROM transactions
+                WHERE status = 'PENDING' AND created_at < $1
+                  AND (next_attempt_at IS NULL OR next_attempt_at <= NOW())
+                  AND (claimed_until IS NULL OR claimed_until < NOW())
+                ORDER BY created_at ASC
+                LIMIT 50
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
             "#,
         )
         .bind(timestamp)
+        .bind(owner)
+        .bind(lease.num_milliseconds() as f64 / 1000.0)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
 
-        let mut transactions = Vec::new();
-        for model in models {
-            transactions.push(model.into());
+        // RETURNING no respeta el orden de la subconsulta: las más antiguas primero.
+        let mut transactions: Vec<Transaction> = models.into_iter().map(Into::into).collect();
+        transactions.sort_by_key(|tx| tx.created_at());
+        Ok(transactions)
+    }
+
+    /// Página de transacciones en `DEAD_LETTER` con el mismo cursor `(created_at, id)` del
+    /// historial, servida por el índice `(status, created_at)`.
+    async fn find_dead_lettered(
+        &self,
+        after: Option<HistoryCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, TransactionError> {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT * FROM transactions WHERE status = 'DEAD_LETTER'",
+        );
+        if let Some(cursor) = after {
+            query.push(" AND (created_at, id) < (");
+            query.push_bind(cursor.created_at);
+            query.push(", ");
+            query.push_bind(cursor.id);
+            query.push(")");
         }
+        query.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+        query.push_bind(limit);
 
-        Ok(transactions)
+        let models = query
+            .build_query_as::<TransactionModel>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(Into::into).collect())
+    }
+
+    /// Reserva el monto de un reembolso con un UPDATE condicional (compare-and-set).
+    ///
+    /// El CHECK `refunded_amount <= amount` de la tabla impide además superar el original.
+    async fn reserve_refund(
+        &self,
+        id: TransactionId,
+        amount: Decimal,
+        refunded_before: Decimal,
+    ) -> Result<Option<Transaction>, TransactionError> {
+        let model_opt = sqlx::query_as::<_, TransactionModel>(
+            r#"
+            UPDATE transactions
+            SET refunded_amount = refunded_amount + $2
+            WHERE id = $1 AND status = 'COMPLETED' AND refunded_amount = $3
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(amount)
+        .bind(refunded_before)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(Into::into))
+    }
+
+    async fn release_refund(
+        &self,
+        id: TransactionId,
+        amount: Decimal,
+    ) -> Result<(), TransactionError> {
+        sqlx::query(
+            r#"
+            UPDATE transactions
+            SET refunded_amount = GREATEST(refunded_amount - $2, 0)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(amount)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Columna de la wallet y expresión del monto de cada sentido del historial. El monto es el
+/// de `HistoryDirection::amount_of`: en destino, el convertido si hubo cambio de divisa.
+fn history_columns(direction: HistoryDirection) -> (&'static str, &'static str) {
+    match direction {
+        HistoryDirection::Out => ("source_wallet_id", "amount"),
+        HistoryDirection::In => (
+            "destination_wallet_id",
+            "COALESCE(destination_amount, amount)",
+        ),
+    }
+}
+
+/// Agrega al query una rama del historial: las transacciones de la wallet en `direction`.
+fn push_history_branch(
+    query: &mut QueryBuilder<'_, Postgres>,
+    direction: HistoryDirection,
+    wallet_id: WalletId,
+    filter: &HistoryFilter,
+    after: Option<HistoryCursor>,
+    limit: i64,
+) {
+    let (wallet_column, amount) = history_columns(direction);
+    query.push("(SELECT * FROM transactions WHERE ");
+    query.push(wallet_column);
+    query.push(" = ");
+    query.push_bind(wallet_id);
+
+    if let Some(cursor) = after {
+        query.push(" AND (created_at, id) < (");
+        query.push_bind(cursor.created_at);
+        query.push(", ");
+        query.push_bind(cursor.id);
+        query.push(")");
+    }
+    if let Some(from) = filter.from {
+        query.push(" AND created_at >= ");
+        query.push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        query.push(" AND created_at < ");
+        query.push_bind(to);
+    }
+    if let Some(status) = filter.status {
+        query.push(" AND status = ");
+        query.push_bind(status);
+    }
+    if let Some(transaction_type) = filter.transaction_type {
+        query.push(" AND transaction_type = ");
+        query.push_bind(transaction_type);
+    }
+    if let Some(min_amount) = filter.min_amount {
+        query.push(format_args!(" AND {} >= ", amount));
+        query.push_bind(min_amount);
+    }
+    if let Some(max_amount) = filter.max_amount {
+        query.push(format_args!(" AND {} <= ", amount));
+        query.push_bind(max_amount);
+    }
+
+    query.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+    query.push_bind(limit);
+    query.push(")");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::exchange::ExchangeDetails;
+    use crate::domain::types::QuoteId;
+    use chrono::Utc;
+
+    fn branch_sql(direction: HistoryDirection, filter: &HistoryFilter) -> String {
+        let mut query = QueryBuilder::<Postgres>::new("");
+        push_history_branch(&mut query, direction, WalletId::new(), filter, None, 10);
+        query.sql().to_string()
+    }
+
+    #[test]
+    fn test_amount_filter_on_received_fx_transfer_uses_converted_amount() {
+        // 100 USD enviados llegan como 1800 MXN a la billetera destino.
+        let mut transfer = Transaction::new(
+            Some(WalletId::new()),
+            WalletId::new(),
+            Decimal::from(100),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        transfer
+            .apply_exchange(ExchangeDetails {
+                source_currency: "USD".into(),
+                destination_currency: "MXN".into(),
+                destination_amount: Decimal::from(1800),
+                rate: Decimal::from(18),
+                rate_timestamp: Utc::now(),
+                quote_id: QuoteId::new(),
+            })
+            .unwrap();
+        let filter = HistoryFilter {
+            min_amount: Some(Decimal::from(1000)),
+            max_amount: Some(Decimal::from(2000)),
+            ..Default::default()
+        };
+        let in_range =
+            |amount: Decimal| (Decimal::from(1000)..=Decimal::from(2000)).contains(&amount);
+
+        // Para quien la recibe entra en el rango; para quien la envía, no.
+        assert!(in_range(HistoryDirection::In.amount_of(&transfer)));
+        assert!(!in_range(HistoryDirection::Out.amount_of(&transfer)));
+
+        let received = branch_sql(HistoryDirection::In, &filter);
+        assert!(received.contains("destination_wallet_id = "));
+        assert!(received.contains("COALESCE(destination_amount, amount) >= "));
+        assert!(received.contains("COALESCE(destination_amount, amount) <= "));
+
+        let sent = branch_sql(HistoryDirection::Out, &filter);
+        assert!(sent.contains("source_wallet_id = "));
+        assert!(sent.contains(" AND amount >= "));
+        assert!(!sent.contains("destination_amount"));
     }
 }
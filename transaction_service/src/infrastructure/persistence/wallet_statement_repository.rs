@@ -0,0 +1,77 @@
+use crate::domain::error::TransactionError;
+use crate::domain::repository::WalletStatementRepository;
+use crate::domain::statement::{StatementPeriod, WalletStatement};
+use crate::domain::types::WalletId;
+use async_trait::async_trait;
+use sqlx::types::Json;
+use sqlx::PgPool;
+
+/// Repositorio de extractos pregenerados implementado para PostgreSQL.
+pub struct PostgresWalletStatementRepository {
+    pool: PgPool,
+}
+
+impl PostgresWalletStatementRepository {
+    /// Crea una nueva instancia del repositorio.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WalletStatementRepository for PostgresWalletStatementRepository {
+    /// Inserta el extracto o reemplaza el guardado si éste se generó antes; una copia más
+    /// reciente nunca se pisa con una más vieja.
+    async fn save(&self, statement: &WalletStatement) -> Result<(), TransactionError> {
+        sqlx::query(
+            r#"
+            INSERT INTO wallet_statements (
+                wallet_id, period_start, period_end, currency, opening_balance, closing_balance,
+                content, generated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (wallet_id, period_start, period_end) DO UPDATE SET
+                currency = EXCLUDED.currency,
+                opening_balance = EXCLUDED.opening_balance,
+                closing_balance = EXCLUDED.closing_balance,
+                content = EXCLUDED.content,
+                generated_at = EXCLUDED.generated_at
+            WHERE wallet_statements.generated_at < EXCLUDED.generated_at
+            "#,
+        )
+        .bind(statement.wallet_id)
+        .bind(statement.period.start)
+        .bind(statement.period.end)
+        .bind(&statement.currency)
+        .bind(statement.opening_balance)
+        .bind(statement.closing_balance)
+        .bind(Json(statement))
+        .bind(statement.generated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        wallet_id: WalletId,
+        period: StatementPeriod,
+    ) -> Result<Option<WalletStatement>, TransactionError> {
+        let content = sqlx::query_scalar::<_, Json<WalletStatement>>(
+            r#"
+            SELECT content FROM wallet_statements
+            WHERE wallet_id = $1 AND period_start = $2 AND period_end = $3
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(period.start)
+        .bind(period.end)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| TransactionError::RepositoryError(e.to_string()))?;
+
+        Ok(content.map(|Json(statement)| statement))
+    }
+}
@@ -0,0 +1,243 @@
+use crate::domain::compensation::{CompensationPolicy, CompensationStatus};
+use crate::domain::gateways::WalletGateway;
+use crate::domain::leader::LeaderElection;
+use crate::domain::repository::{CompensationRepository, SagaRepository};
+use crate::jobs::holds_leadership;
+use crate::use_cases::saga_executor::SagaExecutor;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Compensaciones que se reintentan como máximo en cada pasada.
+const BATCH_SIZE: i64 = 100;
+
+/// Job en segundo plano que reintenta las compensaciones de saga que fallaron.
+///
+/// Cada fallo programa el siguiente intento con espera exponencial; al agotar los intentos de
+/// la política la compensación pasa a `SUSPENDED` y queda para revisión manual.
+///
+/// Con varias réplicas sólo la líder ejecuta las pasadas (ver `with_leader_election`).
+pub struct CompensationRetryJob {
+    compensation_repo: Arc<dyn CompensationRepository>,
+    saga_executor: SagaExecutor,
+    policy: CompensationPolicy,
+    leader: Option<Arc<dyn LeaderElection>>,
+}
+
+impl CompensationRetryJob {
+    /// Advisory lock de PostgreSQL con el que se elige la réplica que ejecuta el job.
+    pub const LEADER_LOCK_KEY: i64 = 7_310_001;
+
+    pub fn new(
+        compensation_repo: Arc<dyn CompensationRepository>,
+        saga_repo: Arc<dyn SagaRepository>,
+        wallet_gateway: Arc<dyn WalletGateway>,
+    ) -> Self {
+        Self {
+            saga_executor: SagaExecutor::new(saga_repo, compensation_repo.clone(), wallet_gateway),
+            compensation_repo,
+            policy: CompensationPolicy::default(),
+            leader: None,
+        }
+    }
+
+    /// Ejecuta las pasadas sólo mientras esta instancia sea la líder.
+    pub fn with_leader_election(mut self, leader: Arc<dyn LeaderElection>) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+
+    /// Reemplaza la política de reintentos.
+    pub fn with_policy(mut self, policy: CompensationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Reintenta las compensaciones vencidas.
+    pub async fn run(&self) {
+        if !holds_leadership(self.leader.as_deref(), "CompensationRetryJob").await {
+            return;
+        }
+
+        let due = match self
+            .compensation_repo
+            .find_due(Utc::now(), BATCH_SIZE)
+            .await
+        {
+            Ok(due) => due,
+            Err(e) => {
+                error!("Failed to fetch pending compensations: {:?}", e);
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+
+        info!("Retrying {} pending compensations...", due.len());
+
+        for mut compensation in due {
+            match self.saga_executor.retry_compensation(&compensation).await {
+                Ok(()) => {
+                    info!(
+                        "Compensation {} (transaction {}, leg {}) applied on attempt {}",
+                        compensation.id,
+                        compensation.transaction_id,
+                        compensation.leg,
+                        compensation.attempts + 1
+                    );
+                    if let Err(e) = compensation.resolve(format!(
+                        "Reserva liberada por el job en el intento {}",
+                        compensation.attempts + 1
+                    )) {
+                        error!("Invalid compensation state {}: {:?}", compensation.id, e);
+                        continue;
+                    }
+                }
+                Err(e) => {
+                    compensation.record_failure(&e, &self.policy);
+                    if compensation.status == CompensationStatus::SUSPENDED {
+                        error!(
+                            "Compensation {} (transaction {}, wallet {}) failed {} times; escalated to manual review: {:?}",
+                            compensation.id,
+                            compensation.transaction_id,
+                            compensation.wallet_id,
+                            compensation.attempts,
+                            e
+                        );
+                    } else {
+                        warn!(
+                            "Compensation {} failed (attempt {}), next attempt at {}: {:?}",
+                            compensation.id, compensation.attempts, compensation.next_attempt_at, e
+                        );
+                    }
+                }
+            }
+
+            if let Err(e) = self.compensation_repo.update(&compensation).await {
+                error!(
+                    "FATAL: Failed to update compensation {}: {:?}",
+                    compensation.id, e
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::compensation::PendingCompensation;
+    use crate::domain::entities::Transaction;
+    use crate::domain::error::TransactionError;
+    use crate::domain::saga::{Saga, SagaStep, SagaStepStatus};
+    use crate::domain::types::WalletId;
+    use crate::test_support::{
+        MockCompensationRepositoryImpl, MockLeaderElectionImpl, MockSagaRepositoryImpl,
+        MockWalletGatewayImpl,
+    };
+    use mockall::predicate::function;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    /// Compensación pendiente de un depósito, con `attempts` intentos fallidos.
+    fn pending(attempts: i32) -> PendingCompensation {
+        let tx =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+        let mut compensation = PendingCompensation::new(
+            &Saga::plan(&tx).steps[0],
+            &TransactionError::GatewayError("unavailable".into()),
+        );
+        compensation.attempts = attempts;
+        compensation
+    }
+
+    #[tokio::test]
+    async fn test_released_compensation_is_resolved() {
+        let compensation = pending(1);
+        let mut mock_compensations = MockCompensationRepositoryImpl::new();
+        mock_compensations
+            .expect_find_due()
+            .returning(move |_, _| Ok(vec![compensation.clone()]));
+        mock_compensations
+            .expect_update()
+            .with(function(|c: &PendingCompensation| {
+                c.status == CompensationStatus::RESOLVED
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_sagas = MockSagaRepositoryImpl::new();
+        mock_sagas
+            .expect_update_step()
+            .with(function(|step: &SagaStep| {
+                step.status == SagaStepStatus::COMPENSATED
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway.expect_release_leg().returning(|_| Ok(()));
+
+        CompensationRetryJob::new(
+            Arc::new(mock_compensations),
+            Arc::new(mock_sagas),
+            Arc::new(mock_gateway),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_compensation_is_escalated_after_last_attempt() {
+        let compensation = pending(CompensationPolicy::DEFAULT_MAX_ATTEMPTS - 1);
+        let mut mock_compensations = MockCompensationRepositoryImpl::new();
+        mock_compensations
+            .expect_find_due()
+            .returning(move |_, _| Ok(vec![compensation.clone()]));
+        mock_compensations
+            .expect_update()
+            .with(function(|c: &PendingCompensation| {
+                c.status == CompensationStatus::SUSPENDED
+                    && c.attempts == CompensationPolicy::DEFAULT_MAX_ATTEMPTS
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_sagas = MockSagaRepositoryImpl::new();
+        mock_sagas.expect_update_step().never();
+
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_release_leg()
+            .returning(|_| Err(TransactionError::GatewayError("unavailable".into())));
+
+        CompensationRetryJob::new(
+            Arc::new(mock_compensations),
+            Arc::new(mock_sagas),
+            Arc::new(mock_gateway),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_follower_instance_skips_the_run() {
+        let mut mock_compensations = MockCompensationRepositoryImpl::new();
+        mock_compensations.expect_find_due().never();
+        let mut mock_leader = MockLeaderElectionImpl::new();
+        mock_leader
+            .expect_is_leader()
+            .times(1)
+            .returning(|| Ok(false));
+
+        CompensationRetryJob::new(
+            Arc::new(mock_compensations),
+            Arc::new(MockSagaRepositoryImpl::new()),
+            Arc::new(MockWalletGatewayImpl::new()),
+        )
+        .with_leader_election(Arc::new(mock_leader))
+        .run()
+        .await;
+    }
+}
@@ -1 +1,23 @@
+pub mod compensations;
+pub mod monthly_statements;
 pub mod retry;
+
+use crate::domain::leader::LeaderElection;
+use tracing::error;
+
+/// Indica si esta instancia debe ejecutar la pasada de un job singleton `job`.
+///
+/// Sin elección de líder configurada (una sola instancia) siempre la ejecuta; si no se puede
+/// consultar el liderazgo, se salta la pasada.
+pub(crate) async fn holds_leadership(leader: Option<&dyn LeaderElection>, job: &str) -> bool {
+    let Some(leader) = leader else {
+        return true;
+    };
+    match leader.is_leader().await {
+        Ok(is_leader) => is_leader,
+        Err(e) => {
+            error!("Failed to check leadership for {}: {:?}", job, e);
+            false
+        }
+    }
+}
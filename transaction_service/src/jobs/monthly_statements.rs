@@ -0,0 +1,82 @@
+use crate::domain::gateways::WalletGateway;
+use crate::domain::leader::LeaderElection;
+use crate::domain::statement::StatementPeriod;
+use crate::jobs::holds_leadership;
+use crate::use_cases::get_wallet_statement::GetWalletStatementUseCase;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Job en segundo plano que pregenera los extractos del mes anterior de todas las
+/// billeteras activas.
+///
+/// Es idempotente: no genera nada hasta que vence el período de gracia de liquidación del
+/// mes y luego salta las billeteras que ya tienen extracto, así que puede ejecutarse con
+/// frecuencia y sólo trabaja al comenzar cada mes (o para completar las billeteras que
+/// fallaron en la pasada anterior).
+///
+/// Con varias réplicas sólo la líder ejecuta las pasadas (ver `with_leader_election`).
+pub struct MonthlyStatementJob {
+    statement_use_case: GetWalletStatementUseCase,
+    wallet_gateway: Arc<dyn WalletGateway>,
+    leader: Option<Arc<dyn LeaderElection>>,
+}
+
+impl MonthlyStatementJob {
+    /// Advisory lock de PostgreSQL con el que se elige la réplica que ejecuta el job.
+    pub const LEADER_LOCK_KEY: i64 = 7_310_002;
+
+    pub fn new(
+        statement_use_case: GetWalletStatementUseCase,
+        wallet_gateway: Arc<dyn WalletGateway>,
+    ) -> Self {
+        Self {
+            statement_use_case,
+            wallet_gateway,
+            leader: None,
+        }
+    }
+
+    /// Ejecuta las pasadas sólo mientras esta instancia sea la líder.
+    pub fn with_leader_election(mut self, leader: Arc<dyn LeaderElection>) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+
+    /// Ejecuta una pasada sobre el mes calendario anterior.
+    pub async fn run(&self) {
+        if !holds_leadership(self.leader.as_deref(), "MonthlyStatementJob").await {
+            return;
+        }
+
+        let period = StatementPeriod::previous_month(Utc::now());
+
+        let wallets = match self.wallet_gateway.active_wallets().await {
+            Ok(wallets) => wallets,
+            Err(e) => {
+                error!("Failed to list active wallets for statements: {:?}", e);
+                return;
+            }
+        };
+
+        let mut generated = 0;
+        for wallet_id in wallets {
+            match self.statement_use_case.pregenerate(wallet_id, period).await {
+                Ok(true) => generated += 1,
+                Ok(false) => {}
+                // Un fallo no detiene la pasada: la billetera se reintenta en la siguiente.
+                Err(e) => error!(
+                    "Failed to generate statement for wallet {}: {:?}",
+                    wallet_id, e
+                ),
+            }
+        }
+
+        if generated > 0 {
+            info!(
+                "Generated {} wallet statements for {} - {}",
+                generated, period.start, period.end
+            );
+        }
+    }
+}
@@ -1,30 +1,75 @@
-use crate::domain::entities::TransactionStatus;
-use crate::domain::gateways::WalletGateway;
-use crate::domain::repository::TransactionRepository;
+use crate::domain::entities::{Transaction, TransactionStatus};
+use crate::domain::error::TransactionError;
+use crate::domain::events::{EventActor, TransactionEvent};
+use crate::domain::gateways::{MovementStatus, WalletGateway};
+use crate::domain::repository::{
+    CompensationRepository, SagaRepository, TransactionEventRepository, TransactionRepository,
+};
+use crate::domain::retry::RetryPolicy;
+use crate::use_cases::process_transaction::record_event;
+use crate::use_cases::refund_transaction::settle_refund;
+use crate::use_cases::saga_executor::SagaExecutor;
 use chrono::{Duration, Utc};
 use std::sync::Arc;
 use tracing::{error, info, warn};
+use uuid::Uuid;
 
 /// Job en segundo plano para reintentar transacciones que quedaron en estado PENDING.
 ///
 /// Esto puede ocurrir si el Transaction Service se reinició antes de recibir respuesta
 /// del Wallet Service, o si hubo un timeout en la comunicación.
+///
+/// Cada intento fallido programa el siguiente con espera exponencial y jitter; al agotar los
+/// intentos de la política la transacción pasa a `DEAD_LETTER` y queda para revisión manual.
+///
+/// Puede ejecutarse en varias réplicas a la vez: cada pasada reclama sus transacciones con un
+/// lease, así que ninguna se procesa dos veces.
 pub struct RetryFailedTransactionJob {
     transaction_repo: Arc<dyn TransactionRepository>,
     wallet_gateway: Arc<dyn WalletGateway>,
+    event_repo: Arc<dyn TransactionEventRepository>,
+    saga_executor: SagaExecutor,
+    policy: RetryPolicy,
+    /// Identifica a esta instancia como dueña de los leases.
+    lease_owner: String,
+    lease: Duration,
 }
 
 impl RetryFailedTransactionJob {
+    /// Duración por defecto del lease sobre las transacciones reclamadas.
+    pub const DEFAULT_LEASE_MINUTES: i64 = 10;
+
     pub fn new(
         transaction_repo: Arc<dyn TransactionRepository>,
         wallet_gateway: Arc<dyn WalletGateway>,
+        event_repo: Arc<dyn TransactionEventRepository>,
+        saga_repo: Arc<dyn SagaRepository>,
+        compensation_repo: Arc<dyn CompensationRepository>,
     ) -> Self {
         Self {
             transaction_repo,
+            saga_executor: SagaExecutor::new(saga_repo, compensation_repo, wallet_gateway.clone()),
             wallet_gateway,
+            event_repo,
+            policy: RetryPolicy::default(),
+            lease_owner: Uuid::new_v4().to_string(),
+            lease: Duration::minutes(Self::DEFAULT_LEASE_MINUTES),
         }
     }
 
+    /// Reemplaza la duración del lease. Debe superar lo que tarda una pasada completa; si no,
+    /// otra instancia puede retomar una transacción que esta aún procesa.
+    pub fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    /// Reemplaza la política de reintentos.
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     /// Ejecuta el proceso de recuperación.
     pub async fn run(&self) {
         info!("Starting RetryFailedTransactionJob...");
@@ -35,7 +80,7 @@ impl RetryFailedTransactionJob {
 
         match self
             .transaction_repo
-            .find_pending_older_than(cutoff_time)
+            .claim_pending_older_than(cutoff_time, &self.lease_owner, self.lease)
             .await
         {
             Ok(transactions) => {
@@ -50,50 +95,111 @@ impl RetryFailedTransactionJob {
 
                 for mut tx in transactions {
                     info!(
-                        "Retrying transaction {} (Created at: {})",
+                        "Retrying transaction {} (Created at: {}, attempt {})",
                         tx.correlation_id(),
-                        tx.created_at()
+                        tx.created_at(),
+                        tx.retry_state().attempts + 1
                     );
 
-                    // Reintentamos la comunicación con el Wallet Service
-                    match self.wallet_gateway.process_movement(&tx).await {
-                        Ok(true) => {
+                    // Antes de reenviar preguntamos al Wallet Service si las patas ya se
+                    // aplicaron: la transacción pudo completarse allá aunque aquí quedara PENDING.
+                    // El evento del historial lleva el estado al que pasará la transacción.
+                    let event = match self.wallet_gateway.movement_status(&tx).await {
+                        Ok(MovementStatus::Applied) => {
                             info!(
-                                "Transaction {} approved by Wallet Service on retry.",
+                                "Transaction {} already applied in Wallet Service. Skipping re-drive.",
                                 tx.id()
                             );
-                            tx.update_status(TransactionStatus::COMPLETED);
+                            Self::event(&tx, TransactionStatus::COMPLETED)
+                                .with_gateway_message("Movimiento ya aplicado en el Wallet Service")
                         }
-                        Ok(false) => {
+                        Ok(MovementStatus::Released) => {
                             warn!(
-                                "Transaction {} rejected by Wallet Service on retry.",
+                                "Transaction {} was already compensated in Wallet Service.",
                                 tx.id()
                             );
-                            tx.update_status(TransactionStatus::FAILED);
+                            Self::event(&tx, TransactionStatus::FAILED).with_gateway_message(
+                                "Movimiento ya compensado en el Wallet Service",
+                            )
+                        }
+                        Ok(MovementStatus::NotFound | MovementStatus::Reserved) => {
+                            // Reenviar es seguro: la saga se retoma desde su registro y el
+                            // Wallet Service es idempotente por (transaction_id, wallet_id).
+                            match self.redrive(&tx).await {
+                                Ok(event) => event,
+                                Err(e) => {
+                                    self.record_failure(tx, e).await;
+                                    continue;
+                                }
+                            }
                         }
                         Err(e) => {
-                            // Si falla la comunicación, logueamos y seguimos (se reintentará en la próxima ejecución)
                             error!(
-                                "Communication error with Wallet Service for tx {}: {:?}. Keeping as PENDING.",
-                                tx.id(), e
+                                "Could not query movement status for tx {}: {:?}",
+                                tx.id(),
+                                e
                             );
+                            self.record_failure(tx, e).await;
                             continue;
                         }
-                    }
+                    };
+
+                    let previous = match tx.update_status(event.to_status) {
+                        Ok(previous) => previous,
+                        Err(e) => {
+                            error!("Invalid status transition for tx {}: {:?}", tx.id(), e);
+                            continue;
+                        }
+                    };
 
-                    // Actualizamos el estado final en base de datos
-                    if let Err(e) = self.transaction_repo.update(tx.clone()).await {
-                        error!(
-                            "FATAL: Failed to update status for tx {} after retry: {:?}",
-                            tx.id(),
-                            e
-                        );
-                    } else {
-                        info!(
-                            "Transaction {} status updated to {:?}",
-                            tx.id(),
-                            tx.status()
-                        );
+                    // Actualizamos el estado final en base de datos. El update es un
+                    // compare-and-set sobre PENDING y sobre nuestro lease: si una petición
+                    // concurrente ya cerró la transacción o otra instancia la reclamó, su
+                    // resultado prevalece.
+                    match self
+                        .transaction_repo
+                        .update_claimed(tx.clone(), previous, &self.lease_owner)
+                        .await
+                    {
+                        Ok(_) => {
+                            info!(
+                                "Transaction {} status updated to {:?}",
+                                tx.id(),
+                                tx.status()
+                            );
+                            record_event(self.event_repo.as_ref(), event).await;
+                            // Si era un reembolso, su resultado se refleja en la original.
+                            if let Err(e) = settle_refund(
+                                self.transaction_repo.as_ref(),
+                                self.event_repo.as_ref(),
+                                &tx,
+                                EventActor::RetryJob,
+                            )
+                            .await
+                            {
+                                error!("Failed to settle refund {}: {:?}", tx.id(), e);
+                            }
+                        }
+                        Err(TransactionError::LeaseLost(_)) => {
+                            warn!(
+                                "Lease on transaction {} expired and another instance claimed it; discarding this result",
+                                tx.id()
+                            );
+                        }
+                        Err(TransactionError::InvalidState(reason)) => {
+                            warn!(
+                                "Transaction {} was settled concurrently, keeping its status: {}",
+                                tx.id(),
+                                reason
+                            );
+                        }
+                        Err(e) => {
+                            error!(
+                                "FATAL: Failed to update status for tx {} after retry: {:?}",
+                                tx.id(),
+                                e
+                            );
+                        }
                     }
                 }
             }
@@ -102,4 +208,386 @@ impl RetryFailedTransactionJob {
             }
         }
     }
+
+    /// Evento del job para la transición de `tx` (aún PENDING) a `to_status`.
+    fn event(tx: &Transaction, to_status: TransactionStatus) -> TransactionEvent {
+        TransactionEvent::new(tx.id(), Some(tx.status()), to_status, EventActor::RetryJob)
+    }
+
+    /// Retoma la saga de la transacción y retorna el evento con el resultado, o el error por el
+    /// que debe seguir PENDING.
+    async fn redrive(&self, tx: &Transaction) -> Result<TransactionEvent, TransactionError> {
+        let result = self.saga_executor.execute(tx).await;
+        let status = match &result {
+            Ok(true) => {
+                info!(
+                    "Transaction {} approved by Wallet Service on retry.",
+                    tx.id()
+                );
+                TransactionStatus::COMPLETED
+            }
+            Ok(false)
+            | Err(TransactionError::InsufficientFunds(_))
+            | Err(TransactionError::LimitExceeded(..))
+            | Err(TransactionError::WalletNotActive(_))
+            | Err(TransactionError::ValidationError(_)) => {
+                warn!(
+                    "Transaction {} rejected by Wallet Service on retry.",
+                    tx.id()
+                );
+                TransactionStatus::FAILED
+            }
+            Err(e) => {
+                // Si falla la comunicación, se reintentará cuando venza la espera.
+                error!(
+                    "Communication error with Wallet Service for tx {}: {:?}",
+                    tx.id(),
+                    e
+                );
+                return Err(result.unwrap_err());
+            }
+        };
+
+        Ok(Self::event(tx, status).with_gateway_result(&result))
+    }
+
+    /// Registra el intento fallido de `tx`: queda PENDING con el siguiente intento programado
+    /// o, si agotó los intentos, pasa a `DEAD_LETTER`.
+    async fn record_failure(&self, mut tx: Transaction, error: TransactionError) {
+        let previous = match tx.record_retry_failure(&error, &self.policy) {
+            Ok(previous) => previous,
+            Err(e) => {
+                error!("Invalid retry state for tx {}: {:?}", tx.id(), e);
+                return;
+            }
+        };
+
+        match self
+            .transaction_repo
+            .update_claimed(tx.clone(), previous, &self.lease_owner)
+            .await
+        {
+            Ok(_) if tx.status() == TransactionStatus::DEAD_LETTER => {
+                error!(
+                    "Transaction {} failed {} times; moved to dead letter: {:?}",
+                    tx.id(),
+                    tx.retry_state().attempts,
+                    error
+                );
+                let event = TransactionEvent::transition(&tx, previous, EventActor::RetryJob)
+                    .with_gateway_message("Reintentos agotados")
+                    .with_error(&error);
+                record_event(self.event_repo.as_ref(), event).await;
+            }
+            Ok(_) => {
+                warn!(
+                    "Transaction {} kept as PENDING (attempt {}), next attempt at {:?}",
+                    tx.id(),
+                    tx.retry_state().attempts,
+                    tx.retry_state().next_attempt_at
+                );
+            }
+            Err(TransactionError::LeaseLost(_)) => {
+                warn!(
+                    "Lease on transaction {} expired and another instance claimed it; discarding this attempt",
+                    tx.id()
+                );
+            }
+            Err(TransactionError::InvalidState(reason)) => {
+                warn!(
+                    "Transaction {} was settled concurrently, keeping its status: {}",
+                    tx.id(),
+                    reason
+                );
+            }
+            Err(e) => {
+                error!(
+                    "FATAL: Failed to record retry attempt for tx {}: {:?}",
+                    tx.id(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::types::WalletId;
+    use crate::test_support::{
+        approve_legs, compensations, sagas, MockTransactionEventRepositoryImpl,
+        MockTransactionRepositoryImpl, MockWalletGatewayImpl,
+    };
+    use mockall::predicate::{always, eq, function};
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    /// Historial que espera exactamente un evento del job hacia `to_status`.
+    fn events_expecting(to_status: TransactionStatus) -> Arc<MockTransactionEventRepositoryImpl> {
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events
+            .expect_record()
+            .withf(move |event: &TransactionEvent| {
+                event.actor == EventActor::RetryJob
+                    && event.from_status == Some(TransactionStatus::PENDING)
+                    && event.to_status == to_status
+                    && event.gateway_message.is_some()
+            })
+            .times(1)
+            .returning(Ok);
+        Arc::new(mock_events)
+    }
+
+    fn stuck_transfer() -> Transaction {
+        Transaction::new(
+            Some(WalletId::new()),
+            WalletId::new(),
+            Decimal::from(30),
+            Uuid::new_v4(),
+        )
+        .unwrap()
+    }
+
+    fn repo_with_pending(expected_status: TransactionStatus) -> MockTransactionRepositoryImpl {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_claim_pending_older_than()
+            .returning(|_, _, _| Ok(vec![stuck_transfer()]));
+        mock_repo
+            .expect_update_claimed()
+            .with(
+                function(move |tx: &Transaction| tx.status() == expected_status),
+                eq(TransactionStatus::PENDING),
+                always(),
+            )
+            .times(1)
+            .returning(|tx, _, _| Ok(tx));
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_already_applied_transaction_is_not_redriven() {
+        let mock_repo = repo_with_pending(TransactionStatus::COMPLETED);
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Ok(MovementStatus::Applied));
+        mock_gateway.expect_execute_transfer().never();
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            events_expecting(TransactionStatus::COMPLETED),
+            sagas(),
+            compensations(),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_compensated_transaction_is_marked_failed() {
+        let mock_repo = repo_with_pending(TransactionStatus::FAILED);
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Ok(MovementStatus::Released));
+        mock_gateway.expect_execute_transfer().never();
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            events_expecting(TransactionStatus::FAILED),
+            sagas(),
+            compensations(),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_unapplied_transaction_is_redriven() {
+        let mock_repo = repo_with_pending(TransactionStatus::COMPLETED);
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Ok(MovementStatus::Reserved));
+        approve_legs(&mut mock_gateway);
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            events_expecting(TransactionStatus::COMPLETED),
+            sagas(),
+            compensations(),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_concurrently_settled_transaction_is_left_untouched() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+
+        mock_repo
+            .expect_claim_pending_older_than()
+            .returning(|_, _, _| Ok(vec![stuck_transfer()]));
+        // Una petición concurrente ya marcó la transacción como FAILED.
+        mock_repo
+            .expect_update_claimed()
+            .times(1)
+            .returning(|_, _, _| Err(TransactionError::InvalidState("FAILED".into())));
+        mock_repo.expect_find_by_id().never();
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Ok(MovementStatus::Applied));
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events.expect_record().never();
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(mock_events),
+            sagas(),
+            compensations(),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_result_is_discarded_when_another_instance_took_the_lease() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let claimed_by = Arc::new(std::sync::Mutex::new(String::new()));
+        let claimant = claimed_by.clone();
+
+        mock_repo
+            .expect_claim_pending_older_than()
+            .times(1)
+            .returning(move |_, owner, _| {
+                *claimant.lock().unwrap() = owner.to_string();
+                Ok(vec![stuck_transfer()])
+            });
+        // La escritura va con el mismo dueño del reclamo, pero el lease venció y otra instancia
+        // la reclamó: el repositorio la rechaza.
+        mock_repo
+            .expect_update_claimed()
+            .withf(move |_, _, owner| *claimed_by.lock().unwrap() == owner)
+            .times(1)
+            .returning(|tx, _, _| Err(TransactionError::LeaseLost(tx.id())));
+        mock_repo.expect_update().never();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Ok(MovementStatus::Applied));
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events.expect_record().never();
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(mock_events),
+            sagas(),
+            compensations(),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_wallet_service_schedules_next_attempt() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_claim_pending_older_than()
+            .returning(|_, _, _| Ok(vec![stuck_transfer()]));
+        // Sigue PENDING, con el fallo registrado y el siguiente intento en el futuro.
+        mock_repo
+            .expect_update_claimed()
+            .with(
+                function(|tx: &Transaction| {
+                    let retry = tx.retry_state();
+                    tx.status() == TransactionStatus::PENDING
+                        && retry.attempts == 1
+                        && retry.last_error.is_some()
+                        && retry.next_attempt_at.is_some_and(|at| at > Utc::now())
+                }),
+                eq(TransactionStatus::PENDING),
+                always(),
+            )
+            .times(1)
+            .returning(|tx, _, _| Ok(tx));
+
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Err(TransactionError::GatewayError("unavailable".into())));
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events.expect_record().never();
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(mock_events),
+            sagas(),
+            compensations(),
+        )
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_transaction_is_dead_lettered() {
+        let mock_repo = repo_with_pending(TransactionStatus::DEAD_LETTER);
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_movement_status()
+            .returning(|_| Ok(MovementStatus::NotFound));
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(|_, _| Err(TransactionError::GatewayError("timeout".into())));
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            events_expecting(TransactionStatus::DEAD_LETTER),
+            sagas(),
+            compensations(),
+        )
+        .with_policy(RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        })
+        .run()
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_run_claims_transactions_with_lease() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_claim_pending_older_than()
+            .withf(|cutoff, owner, lease| {
+                *cutoff < Utc::now() - Duration::seconds(59)
+                    && !owner.is_empty()
+                    && *lease == Duration::minutes(3)
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(Vec::new()));
+
+        RetryFailedTransactionJob::new(
+            Arc::new(mock_repo),
+            Arc::new(MockWalletGatewayImpl::new()),
+            Arc::new(MockTransactionEventRepositoryImpl::new()),
+            sagas(),
+            compensations(),
+        )
+        .with_lease(Duration::minutes(3))
+        .run()
+        .await;
+    }
 }
@@ -15,3 +15,6 @@ pub mod domain;
 pub mod infrastructure;
 pub mod jobs;
 pub mod use_cases;
+
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -6,14 +6,32 @@ use tracing::info;
 use tracing_subscriber::FmtSubscriber;
 use transaction_service::{
     api::http_routes::{routes, AppState},
+    domain::{compensation::CompensationPolicy, retry::RetryPolicy},
     infrastructure::{
         gateways::grpc_wallet_gateway::GrpcWalletGateway,
-        persistence::transaction_repository::PostgresTransactionRepository,
+        persistence::{
+            compensation_repository::PostgresCompensationRepository,
+            exchange_rate_repository::PostgresExchangeRateRepository,
+            fx_quote_repository::PostgresFxQuoteRepository,
+            leader_election::PostgresLeaderElection, saga_repository::PostgresSagaRepository,
+            transaction_event_repository::PostgresTransactionEventRepository,
+            transaction_repository::PostgresTransactionRepository,
+            wallet_statement_repository::PostgresWalletStatementRepository,
+        },
     },
+    jobs::{compensations::CompensationRetryJob, monthly_statements::MonthlyStatementJob},
     use_cases::{
+        create_fx_quote::CreateFxQuoteUseCase,
         get_transaction_details::GetTransactionDetailsUseCase,
+        get_transaction_events::GetTransactionEventsUseCase,
         get_wallet_history::GetWalletHistoryUseCase,
+        get_wallet_statement::GetWalletStatementUseCase,
         process_transaction::ProcessTransactionUseCase,
+        refund_transaction::RefundTransactionUseCase,
+        review_compensations::ReviewCompensationsUseCase,
+        review_dead_letters::ReviewDeadLettersUseCase, saga_executor::SagaExecutor,
+        search_transactions::SearchTransactionsUseCase,
+        update_exchange_rate::UpdateExchangeRateUseCase,
     },
 };
 use utoipa::OpenApi;
@@ -23,11 +41,30 @@ use utoipa_swagger_ui::SwaggerUi;
 #[openapi(
     paths(
         transaction_service::api::http_routes::initiate_transaction,
+        transaction_service::api::http_routes::search_transactions,
+        transaction_service::api::http_routes::initiate_withdrawal,
+        transaction_service::api::http_routes::refund_transaction,
         transaction_service::api::http_routes::get_transaction_details,
-        transaction_service::api::http_routes::get_wallet_history
+        transaction_service::api::http_routes::get_transaction_events,
+        transaction_service::api::http_routes::get_wallet_history,
+        transaction_service::api::http_routes::get_wallet_statement,
+        transaction_service::api::http_routes::create_fx_quote,
+        transaction_service::api::http_routes::update_exchange_rate,
+        transaction_service::api::http_routes::list_compensations,
+        transaction_service::api::http_routes::get_compensation,
+        transaction_service::api::http_routes::resolve_compensation,
+        transaction_service::api::http_routes::retry_compensation,
+        transaction_service::api::http_routes::list_dead_letters,
+        transaction_service::api::http_routes::get_dead_letter,
+        transaction_service::api::http_routes::redrive_dead_letter
     ),
     components(schemas(
         transaction_service::api::http_routes::CreateTransactionRequest,
+        transaction_service::api::http_routes::CreateWithdrawalRequest,
+        transaction_service::api::http_routes::RefundTransactionRequest,
+        transaction_service::api::http_routes::CreateQuoteRequest,
+        transaction_service::api::http_routes::UpdateExchangeRateRequest,
+        transaction_service::api::http_routes::ResolveCompensationRequest,
         transaction_service::api::response::ApiResponse<serde_json::Value>
     ))
 )]
@@ -56,24 +93,107 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Connected to Database");
 
     // 4. Instanciar Dependencias (Infraestructura)
-    let transaction_repo = Arc::new(PostgresTransactionRepository::new(pool));
+    let transaction_repo = Arc::new(PostgresTransactionRepository::new(pool.clone()));
+    let exchange_rate_repo = Arc::new(PostgresExchangeRateRepository::new(pool.clone()));
+    let fx_quote_repo = Arc::new(PostgresFxQuoteRepository::new(pool.clone()));
+    let transaction_event_repo = Arc::new(PostgresTransactionEventRepository::new(pool.clone()));
+    let saga_repo = Arc::new(PostgresSagaRepository::new(pool.clone()));
+    let compensation_repo = Arc::new(PostgresCompensationRepository::new(pool.clone()));
+    let wallet_statement_repo = Arc::new(PostgresWalletStatementRepository::new(pool.clone()));
+    // Con varias réplicas, los jobs singleton sólo corren en la que tenga su advisory lock.
+    let compensation_leader = Arc::new(PostgresLeaderElection::new(
+        pool.clone(),
+        CompensationRetryJob::LEADER_LOCK_KEY,
+    ));
+    let statement_leader = Arc::new(PostgresLeaderElection::new(
+        pool,
+        MonthlyStatementJob::LEADER_LOCK_KEY,
+    ));
+    // Vigencia de las cotizaciones de tipo de cambio, en segundos.
+    let fx_quote_ttl = env::var("FX_QUOTE_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(CreateFxQuoteUseCase::DEFAULT_TTL_SECONDS);
+    // Ventana durante la cual un correlation_id identifica a su transacción, en horas.
+    let idempotency_ttl = env::var("IDEMPOTENCY_KEY_TTL_HOURS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(ProcessTransactionUseCase::DEFAULT_IDEMPOTENCY_TTL_HOURS);
+    // Intentos de liberar una reserva antes de escalarla a revisión manual.
+    let compensation_max_attempts = env::var("COMPENSATION_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(CompensationPolicy::DEFAULT_MAX_ATTEMPTS);
+    // Intentos del job de reintentos antes de enviar una transacción a DEAD_LETTER.
+    let retry_max_attempts = env::var("RETRY_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(RetryPolicy::DEFAULT_MAX_ATTEMPTS);
+    // Espera tras el cierre de un mes antes de guardar sus extractos, en horas.
+    let statement_settlement_grace = env::var("STATEMENT_SETTLEMENT_GRACE_HOURS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(GetWalletStatementUseCase::DEFAULT_SETTLEMENT_GRACE_HOURS);
 
     let wallet_url =
         env::var("WALLET_SERVICE_URL").unwrap_or_else(|_| "http://127.0.0.1:4000".to_string());
     let wallet_gateway = Arc::new(GrpcWalletGateway::new(wallet_url));
 
     // 5. Instanciar Casos de Uso
-    let process_transaction_use_case =
-        ProcessTransactionUseCase::new(transaction_repo.clone(), wallet_gateway.clone());
+    let process_transaction_use_case = ProcessTransactionUseCase::new(
+        transaction_repo.clone(),
+        wallet_gateway.clone(),
+        fx_quote_repo.clone(),
+        transaction_event_repo.clone(),
+        saga_repo.clone(),
+        compensation_repo.clone(),
+    )
+    .with_idempotency_ttl(chrono::Duration::hours(idempotency_ttl));
+    let refund_transaction_use_case = RefundTransactionUseCase::new(
+        transaction_repo.clone(),
+        wallet_gateway.clone(),
+        transaction_event_repo.clone(),
+        saga_repo.clone(),
+        compensation_repo.clone(),
+    )
+    .with_idempotency_ttl(chrono::Duration::hours(idempotency_ttl));
     let get_transaction_details_use_case =
         GetTransactionDetailsUseCase::new(transaction_repo.clone());
+    let get_transaction_events_use_case =
+        GetTransactionEventsUseCase::new(transaction_repo.clone(), transaction_event_repo.clone());
+    let search_transactions_use_case = SearchTransactionsUseCase::new(transaction_repo.clone());
     let get_wallet_history_use_case = GetWalletHistoryUseCase::new(transaction_repo.clone());
+    let get_wallet_statement_use_case = GetWalletStatementUseCase::new(
+        transaction_repo.clone(),
+        wallet_statement_repo.clone(),
+        wallet_gateway.clone(),
+    )
+    .with_settlement_grace(chrono::Duration::hours(statement_settlement_grace));
+    let create_fx_quote_use_case =
+        CreateFxQuoteUseCase::new(exchange_rate_repo.clone(), fx_quote_repo.clone())
+            .with_ttl(chrono::Duration::seconds(fx_quote_ttl));
+    let update_exchange_rate_use_case = UpdateExchangeRateUseCase::new(exchange_rate_repo.clone());
+    let review_compensations_use_case = ReviewCompensationsUseCase::new(
+        compensation_repo.clone(),
+        saga_repo.clone(),
+        wallet_gateway.clone(),
+    );
+    let review_dead_letters_use_case =
+        ReviewDeadLettersUseCase::new(transaction_repo.clone(), transaction_event_repo.clone());
 
     // 6. Configurar Estado de la App Axum
     let app_state = Arc::new(AppState {
         process_transaction_use_case,
+        refund_transaction_use_case,
         get_transaction_details_use_case,
+        get_transaction_events_use_case,
+        search_transactions_use_case,
         get_wallet_history_use_case,
+        get_wallet_statement_use_case: get_wallet_statement_use_case.clone(),
+        create_fx_quote_use_case,
+        update_exchange_rate_use_case,
+        review_compensations_use_case,
+        review_dead_letters_use_case,
     });
 
     // 7. Configurar Rutas y Servidor
@@ -85,14 +205,45 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = format!("{}:{}", host, port);
 
     // 8. Iniciar Background Jobs (Procesos en Segundo Plano)
+    // Antes que nada, completamos o compensamos las sagas que un reinicio dejó a medio camino;
+    // el job de reintentos concilia después el estado de sus transacciones. Cada réplica
+    // reclama sólo sagas sin avances recientes, así que no toca las que otra está ejecutando.
+    let recovery_owner = uuid::Uuid::new_v4().to_string();
+    if let Err(e) = SagaExecutor::new(
+        saga_repo.clone(),
+        compensation_repo.clone(),
+        wallet_gateway.clone(),
+    )
+    .recover(
+        &recovery_owner,
+        chrono::Duration::minutes(SagaExecutor::DEFAULT_RECOVERY_LEASE_MINUTES),
+    )
+    .await
+    {
+        tracing::error!("Failed to recover unfinished sagas: {:?}", e);
+    }
+
     let job_repo = transaction_repo.clone();
     let job_gateway = wallet_gateway.clone();
+    let job_events = transaction_event_repo.clone();
+    let job_sagas = saga_repo.clone();
+    let job_compensations = compensation_repo.clone();
 
+    // El job de reintentos corre en todas las réplicas: cada una reclama sus transacciones.
     tokio::spawn(async move {
         // Intervalo de ejecución: cada 60 segundos
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
-        let job =
-            transaction_service::jobs::retry::RetryFailedTransactionJob::new(job_repo, job_gateway);
+        let job = transaction_service::jobs::retry::RetryFailedTransactionJob::new(
+            job_repo,
+            job_gateway,
+            job_events,
+            job_sagas,
+            job_compensations,
+        )
+        .with_policy(RetryPolicy {
+            max_attempts: retry_max_attempts,
+            ..Default::default()
+        });
 
         info!("Background Job Scheduler started");
 
@@ -102,6 +253,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    let compensation_job =
+        CompensationRetryJob::new(compensation_repo, saga_repo, wallet_gateway.clone())
+            .with_policy(CompensationPolicy {
+                max_attempts: compensation_max_attempts,
+                ..Default::default()
+            })
+            .with_leader_election(compensation_leader);
+    tokio::spawn(async move {
+        // Cada 30 segundos: cada compensación lleva su propia espera en `next_attempt_at`.
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+
+        info!("Compensation retry job started");
+
+        loop {
+            interval.tick().await;
+            compensation_job.run().await;
+        }
+    });
+
+    let statement_job = MonthlyStatementJob::new(get_wallet_statement_use_case, wallet_gateway)
+        .with_leader_election(statement_leader);
+    tokio::spawn(async move {
+        // Una vez al día: el job sólo genera los extractos del mes anterior que falten.
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(86400));
+
+        info!("Monthly statement job started");
+
+        loop {
+            interval.tick().await;
+            statement_job.run().await;
+        }
+    });
+
     info!("Listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
@@ -0,0 +1,155 @@
+//! Mocks de los puertos del dominio y fixtures compartidos por las pruebas unitarias.
+//!
+//! Cada cambio en un trait se refleja aquí una sola vez en lugar de en cada módulo de pruebas.
+
+use crate::domain::compensation::{CompensationStatus, PendingCompensation};
+use crate::domain::entities::{Transaction, TransactionStatus};
+use crate::domain::error::TransactionError;
+use crate::domain::events::TransactionEvent;
+use crate::domain::exchange::FxQuote;
+use crate::domain::gateways::{
+    HistoricalBalance, LedgerPosting, MovementStatus, WalletCurrency, WalletGateway,
+};
+use crate::domain::history::{HistoryCursor, HistoryFilter};
+use crate::domain::leader::LeaderElection;
+use crate::domain::repository::{
+    CompensationRepository, FxQuoteRepository, SagaRepository, TransactionEventRepository,
+    TransactionRepository, WalletStatementRepository,
+};
+use crate::domain::saga::{Saga, SagaStep};
+use crate::domain::statement::{StatementPeriod, WalletStatement};
+use crate::domain::types::{QuoteId, TransactionId, WalletId};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mock! {
+    pub TransactionRepositoryImpl {}
+
+    #[async_trait]
+    impl TransactionRepository for TransactionRepositoryImpl {
+        async fn save(&self, transaction: Transaction, key_ttl: chrono::Duration) -> Result<Transaction, TransactionError>;
+        async fn update(&self, transaction: Transaction, expected_status: TransactionStatus) -> Result<Transaction, TransactionError>;
+        async fn update_claimed(&self, transaction: Transaction, expected_status: TransactionStatus, owner: &str) -> Result<Transaction, TransactionError>;
+        async fn find_by_id(&self, id: TransactionId) -> Result<Option<Transaction>, TransactionError>;
+        async fn find_by_ids(&self, ids: &[TransactionId]) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_by_wallet_id(&self, wallet_id: WalletId, filter: &HistoryFilter, after: Option<HistoryCursor>, limit: i64) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_by_correlation_id(&self, correlation_id: Uuid) -> Result<Option<Transaction>, TransactionError>;
+        async fn find_by_external_reference(&self, external_reference: &str) -> Result<Vec<Transaction>, TransactionError>;
+        async fn claim_pending_older_than(&self, timestamp: DateTime<Utc>, owner: &str, lease: chrono::Duration) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_dead_lettered(&self, after: Option<HistoryCursor>, limit: i64) -> Result<Vec<Transaction>, TransactionError>;
+        async fn reserve_refund(&self, id: TransactionId, amount: Decimal, refunded_before: Decimal) -> Result<Option<Transaction>, TransactionError>;
+        async fn release_refund(&self, id: TransactionId, amount: Decimal) -> Result<(), TransactionError>;
+    }
+}
+
+mock! {
+    pub WalletGatewayImpl {}
+
+    #[async_trait]
+    impl WalletGateway for WalletGatewayImpl {
+        async fn reserve_leg(&self, transaction: &Transaction, step: &SagaStep) -> Result<bool, TransactionError>;
+        async fn capture_leg(&self, step: &SagaStep) -> Result<(), TransactionError>;
+        async fn execute_transfer(&self, debit: &SagaStep, credit: &SagaStep) -> Result<bool, TransactionError>;
+        async fn release_leg(&self, step: &SagaStep) -> Result<(), TransactionError>;
+        async fn movement_status(&self, transaction: &Transaction) -> Result<MovementStatus, TransactionError>;
+        async fn wallet_currency(&self, wallet_id: WalletId) -> Result<WalletCurrency, TransactionError>;
+        async fn historical_balance(&self, wallet_id: WalletId, as_of: DateTime<Utc>) -> Result<HistoricalBalance, TransactionError>;
+        async fn ledger_postings(&self, wallet_id: WalletId, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<LedgerPosting>, TransactionError>;
+        async fn active_wallets(&self) -> Result<Vec<WalletId>, TransactionError>;
+    }
+}
+
+mock! {
+    pub TransactionEventRepositoryImpl {}
+
+    #[async_trait]
+    impl TransactionEventRepository for TransactionEventRepositoryImpl {
+        async fn record(&self, event: TransactionEvent) -> Result<TransactionEvent, TransactionError>;
+        async fn find_by_transaction_id(&self, transaction_id: TransactionId) -> Result<Vec<TransactionEvent>, TransactionError>;
+    }
+}
+
+mock! {
+    pub SagaRepositoryImpl {}
+
+    #[async_trait]
+    impl SagaRepository for SagaRepositoryImpl {
+        async fn start(&self, saga: Saga) -> Result<Saga, TransactionError>;
+        async fn update_step(&self, step: &SagaStep) -> Result<(), TransactionError>;
+        async fn claim_unfinished(&self, older_than: DateTime<Utc>, owner: &str, lease: chrono::Duration) -> Result<Vec<Saga>, TransactionError>;
+        async fn release(&self, transaction_id: TransactionId, owner: &str) -> Result<(), TransactionError>;
+    }
+}
+
+mock! {
+    pub CompensationRepositoryImpl {}
+
+    #[async_trait]
+    impl CompensationRepository for CompensationRepositoryImpl {
+        async fn schedule(&self, compensation: &PendingCompensation) -> Result<(), TransactionError>;
+        async fn update(&self, compensation: &PendingCompensation) -> Result<(), TransactionError>;
+        async fn find_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<PendingCompensation>, TransactionError>;
+        async fn find_by_status(&self, status: Option<CompensationStatus>) -> Result<Vec<PendingCompensation>, TransactionError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<PendingCompensation>, TransactionError>;
+    }
+}
+
+mock! {
+    pub FxQuoteRepositoryImpl {}
+
+    #[async_trait]
+    impl FxQuoteRepository for FxQuoteRepositoryImpl {
+        async fn save(&self, quote: FxQuote) -> Result<FxQuote, TransactionError>;
+        async fn find_by_id(&self, id: QuoteId) -> Result<Option<FxQuote>, TransactionError>;
+    }
+}
+
+mock! {
+    pub WalletStatementRepositoryImpl {}
+
+    #[async_trait]
+    impl WalletStatementRepository for WalletStatementRepositoryImpl {
+        async fn save(&self, statement: &WalletStatement) -> Result<(), TransactionError>;
+        async fn find(&self, wallet_id: WalletId, period: StatementPeriod) -> Result<Option<WalletStatement>, TransactionError>;
+    }
+}
+
+mock! {
+    pub LeaderElectionImpl {}
+
+    #[async_trait]
+    impl LeaderElection for LeaderElectionImpl {
+        async fn is_leader(&self) -> Result<bool, TransactionError>;
+    }
+}
+
+/// Registro de sagas que acepta cualquier paso.
+pub(crate) fn sagas() -> Arc<MockSagaRepositoryImpl> {
+    let mut mock_sagas = MockSagaRepositoryImpl::new();
+    mock_sagas.expect_start().returning(Ok);
+    mock_sagas.expect_update_step().returning(|_| Ok(()));
+    Arc::new(mock_sagas)
+}
+
+/// Compensaciones pendientes sin uso: ninguna liberación falla.
+pub(crate) fn compensations() -> Arc<MockCompensationRepositoryImpl> {
+    Arc::new(MockCompensationRepositoryImpl::new())
+}
+
+/// Configura el gateway para que apruebe todas las patas y transferencias.
+pub(crate) fn approve_legs(gateway: &mut MockWalletGatewayImpl) {
+    gateway.expect_reserve_leg().returning(|_, _| Ok(true));
+    gateway.expect_capture_leg().returning(|_| Ok(()));
+    gateway.expect_execute_transfer().returning(|_, _| Ok(true));
+}
+
+/// Historial que acepta cualquier evento.
+pub(crate) fn events() -> Arc<MockTransactionEventRepositoryImpl> {
+    let mut mock_events = MockTransactionEventRepositoryImpl::new();
+    mock_events.expect_record().returning(Ok);
+    Arc::new(mock_events)
+}
@@ -0,0 +1,147 @@
+use crate::domain::{
+    error::TransactionError,
+    exchange::{normalize_currency, ExchangeRateProvider, FxQuote},
+    repository::FxQuoteRepository,
+};
+use chrono::Duration;
+use std::sync::Arc;
+
+/// Caso de uso para emitir una cotización que fija el tipo de cambio durante un TTL corto.
+///
+/// La tasa se obtiene del `ExchangeRateProvider` configurado y la cotización se persiste para
+/// que `ProcessTransactionUseCase` pueda validarla cuando el cliente inicie la transferencia.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::create_fx_quote::CreateFxQuoteUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = CreateFxQuoteUseCase::new(rate_provider, quote_repo);
+/// ```
+#[derive(Clone)]
+pub struct CreateFxQuoteUseCase {
+    rate_provider: Arc<dyn ExchangeRateProvider>,
+    quote_repo: Arc<dyn FxQuoteRepository>,
+    ttl: Duration,
+}
+
+impl CreateFxQuoteUseCase {
+    /// Vigencia por defecto de una cotización.
+    pub const DEFAULT_TTL_SECONDS: i64 = 30;
+
+    /// Construye una nueva instancia de `CreateFxQuoteUseCase`.
+    pub fn new(
+        rate_provider: Arc<dyn ExchangeRateProvider>,
+        quote_repo: Arc<dyn FxQuoteRepository>,
+    ) -> Self {
+        Self {
+            rate_provider,
+            quote_repo,
+            ttl: Duration::seconds(Self::DEFAULT_TTL_SECONDS),
+        }
+    }
+
+    /// Reemplaza la vigencia de las cotizaciones emitidas.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Emite una cotización para convertir de `source_currency` a `destination_currency`.
+    ///
+    /// # Retornos
+    ///
+    /// La cotización persistida, `TransactionError::ValidationError` si los códigos no son
+    /// válidos o coinciden, o `TransactionError::ExchangeRateNotFound` si no hay tasa para el par.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let quote = use_case.execute("USD", "COP").await?;
+    /// ```
+    #[tracing::instrument(name = "CreateFxQuoteUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        source_currency: &str,
+        destination_currency: &str,
+    ) -> Result<FxQuote, TransactionError> {
+        let source_currency = normalize_currency(source_currency)?;
+        let destination_currency = normalize_currency(destination_currency)?;
+        if source_currency == destination_currency {
+            return Err(TransactionError::ValidationError(
+                "Una cotización requiere dos divisas distintas".into(),
+            ));
+        }
+
+        let rate = self
+            .rate_provider
+            .current_rate(&source_currency, &destination_currency)
+            .await?;
+
+        self.quote_repo.save(FxQuote::new(rate, self.ttl)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::exchange::ExchangeRate;
+    use crate::test_support::MockFxQuoteRepositoryImpl;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use mockall::mock;
+    use mockall::predicate::*;
+    use rust_decimal::Decimal;
+
+    mock! {
+        pub RateProvider {}
+
+        #[async_trait]
+        impl ExchangeRateProvider for RateProvider {
+            async fn current_rate(&self, base_currency: &str, quote_currency: &str) -> Result<ExchangeRate, TransactionError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_quote_locks_current_rate_for_ttl() {
+        let mut provider = MockRateProvider::new();
+        let mut quote_repo = MockFxQuoteRepositoryImpl::new();
+
+        provider
+            .expect_current_rate()
+            .with(eq("USD"), eq("COP"))
+            .times(1)
+            .returning(|base, quote| {
+                ExchangeRate::new(base, quote, Decimal::from(4000), Utc::now())
+            });
+        quote_repo.expect_save().times(1).returning(Ok);
+
+        let use_case = CreateFxQuoteUseCase::new(Arc::new(provider), Arc::new(quote_repo))
+            .with_ttl(Duration::seconds(10));
+        let quote = use_case.execute("usd", "cop").await.unwrap();
+
+        assert_eq!(quote.rate, Decimal::from(4000));
+        assert_eq!(quote.expires_at - quote.created_at, Duration::seconds(10));
+    }
+
+    #[tokio::test]
+    async fn test_create_quote_without_rate_is_rejected() {
+        let mut provider = MockRateProvider::new();
+        let mut quote_repo = MockFxQuoteRepositoryImpl::new();
+
+        provider.expect_current_rate().returning(|base, quote| {
+            Err(TransactionError::ExchangeRateNotFound(
+                base.to_string(),
+                quote.to_string(),
+            ))
+        });
+        quote_repo.expect_save().never();
+
+        let use_case = CreateFxQuoteUseCase::new(Arc::new(provider), Arc::new(quote_repo));
+        let result = use_case.execute("USD", "XAF").await;
+
+        assert!(matches!(
+            result,
+            Err(TransactionError::ExchangeRateNotFound(..))
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::domain::{
+    error::TransactionError,
+    events::TransactionEvent,
+    repository::{TransactionEventRepository, TransactionRepository},
+    types::TransactionId,
+};
+use std::sync::Arc;
+
+/// Caso de uso para consultar el historial de estados de una transacción.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::get_transaction_events::GetTransactionEventsUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = GetTransactionEventsUseCase::new(transaction_repo, event_repo);
+/// ```
+#[derive(Clone)]
+pub struct GetTransactionEventsUseCase {
+    transaction_repo: Arc<dyn TransactionRepository>,
+    event_repo: Arc<dyn TransactionEventRepository>,
+}
+
+impl GetTransactionEventsUseCase {
+    pub fn new(
+        transaction_repo: Arc<dyn TransactionRepository>,
+        event_repo: Arc<dyn TransactionEventRepository>,
+    ) -> Self {
+        Self {
+            transaction_repo,
+            event_repo,
+        }
+    }
+
+    /// Retorna los cambios de estado de la transacción en orden cronológico, o
+    /// `TransactionError::NotFound` si la transacción no existe.
+    #[tracing::instrument(name = "GetTransactionEventsUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Vec<TransactionEvent>, TransactionError> {
+        if self
+            .transaction_repo
+            .find_by_id(transaction_id)
+            .await?
+            .is_none()
+        {
+            return Err(TransactionError::NotFound(transaction_id));
+        }
+
+        self.event_repo.find_by_transaction_id(transaction_id).await
+    }
+}
@@ -1,14 +1,24 @@
 use crate::domain::{
-    entities::Transaction, error::TransactionError, repository::TransactionRepository,
+    error::TransactionError,
+    history::{HistoryCursor, HistoryFilter, HistoryPage},
+    repository::TransactionRepository,
     types::WalletId,
 };
 use std::sync::Arc;
 
+/// Tamaño de página por defecto cuando el cliente no especifica `limit`.
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+/// Tamaño máximo de página permitido para proteger la base de datos.
+pub const MAX_PAGE_SIZE: i64 = 200;
+
 /// Caso de uso para obtener el historial de transacciones de una billetera.
 ///
 /// Encapsula la lógica de buscar transacciones (tanto depósitos, como retiros y transferencias)
 /// utilizando el repositorio de persistencia `TransactionRepository`.
 ///
+/// Pagina por cursor sobre `(created_at, id)`, de modo que recorrer historiales de millones de
+/// transacciones no degrada cada página como lo haría un `OFFSET`.
+///
 /// # Examples
 /// ```ignore
 /// use transaction_service::use_cases::get_wallet_history::GetWalletHistoryUseCase;
@@ -33,25 +43,148 @@ impl GetWalletHistoryUseCase {
         Self { transaction_repo }
     }
 
-    /// Ejecuta el caso de uso para buscar el listado de movimientos por ID de billetera.
+    /// Ejecuta el caso de uso para buscar una página de movimientos por ID de billetera.
     ///
     /// # Argumentos
     ///
     /// * `wallet_id` - El identificador único (`Uuid`) de la billetera involucrada en las transacciones.
+    /// * `filter` - Rango de fechas, estado, tipo, sentido y rango de montos.
+    /// * `cursor` - `next_cursor` de la página anterior (`None` para empezar).
+    /// * `page_size` - Tamaño de página; se acota a `1..=MAX_PAGE_SIZE` (por defecto `DEFAULT_PAGE_SIZE`).
     ///
     /// # Retornos
     ///
-    /// Devuelve un `Result<Vec<Transaction>, TransactionError>`. Retorna una colección
-    /// (quizás vacía si no hay transacciones) de `Transaction`s al tener una operación en BD exitosa.
+    /// Devuelve un `Result<HistoryPage, TransactionError>` con las transacciones (más recientes
+    /// primero) y el cursor de la página siguiente, o `TransactionError::ValidationError` si
+    /// el filtro o el cursor no son válidos.
     ///
     /// # Examples
     /// ```ignore
-    /// use uuid::Uuid;
-    /// let wallet_id = Uuid::new_v4();
-    /// let history = use_case.execute(wallet_id).await.unwrap();
+    /// let page = use_case.execute(wallet_id, HistoryFilter::default(), None, Some(20)).await?;
+    /// let next = use_case.execute(wallet_id, HistoryFilter::default(), page.next_cursor, Some(20)).await?;
     /// ```
     #[tracing::instrument(name = "GetWalletHistoryUseCase::execute", skip(self))]
-    pub async fn execute(&self, wallet_id: WalletId) -> Result<Vec<Transaction>, TransactionError> {
-        self.transaction_repo.find_by_wallet_id(wallet_id).await
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        filter: HistoryFilter,
+        cursor: Option<String>,
+        page_size: Option<i64>,
+    ) -> Result<HistoryPage, TransactionError> {
+        filter.validate()?;
+        let after = cursor.as_deref().map(HistoryCursor::decode).transpose()?;
+        let limit = page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        // Pedimos una transacción de más para saber si existe una página siguiente.
+        let mut transactions = self
+            .transaction_repo
+            .find_by_wallet_id(wallet_id, &filter, after, limit + 1)
+            .await?;
+
+        let next_cursor = if transactions.len() as i64 > limit {
+            transactions.truncate(limit as usize);
+            transactions
+                .last()
+                .map(|last| HistoryCursor::after(last).encode())
+        } else {
+            None
+        };
+
+        Ok(HistoryPage {
+            transactions,
+            next_cursor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Transaction, TransactionDetails, TransactionStatus, TransactionType,
+    };
+    use crate::domain::types::TransactionId;
+    use crate::test_support::MockTransactionRepositoryImpl;
+    use chrono::{Duration, Utc};
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn deposit(wallet_id: WalletId, minutes_ago: i64) -> Transaction {
+        Transaction::reconstitute(
+            TransactionId::new(),
+            None,
+            Some(wallet_id),
+            None,
+            Decimal::from(10),
+            TransactionStatus::COMPLETED,
+            TransactionType::DEPOSIT,
+            Utc::now() - Duration::minutes(minutes_ago),
+            Uuid::new_v4(),
+            None,
+            None,
+            Decimal::ZERO,
+            TransactionDetails::default(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_full_page_returns_cursor_of_last_transaction() {
+        let wallet_id = WalletId::new();
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+
+        mock_repo
+            .expect_find_by_wallet_id()
+            .withf(|_, _, after, limit| after.is_none() && *limit == 3)
+            .times(1)
+            .returning(move |id, _, _, _| Ok((0..3).map(|i| deposit(id, i)).collect()));
+
+        let use_case = GetWalletHistoryUseCase::new(Arc::new(mock_repo));
+        let page = use_case
+            .execute(wallet_id, HistoryFilter::default(), None, Some(2))
+            .await
+            .unwrap();
+
+        assert_eq!(page.transactions.len(), 2);
+        let cursor = HistoryCursor::decode(&page.next_cursor.unwrap()).unwrap();
+        assert_eq!(cursor.id, page.transactions[1].id());
+    }
+
+    #[tokio::test]
+    async fn test_last_page_has_no_cursor() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+
+        mock_repo
+            .expect_find_by_wallet_id()
+            .returning(|id, _, _, _| Ok(vec![deposit(id, 0)]));
+
+        let use_case = GetWalletHistoryUseCase::new(Arc::new(mock_repo));
+        let page = use_case
+            .execute(WalletId::new(), HistoryFilter::default(), None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(page.transactions.len(), 1);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_cursor_is_rejected() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo.expect_find_by_wallet_id().never();
+
+        let use_case = GetWalletHistoryUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                HistoryFilter::default(),
+                Some("basura".into()),
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(TransactionError::ValidationError(_))));
     }
 }
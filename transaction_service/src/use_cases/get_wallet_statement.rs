@@ -0,0 +1,457 @@
+use crate::domain::{
+    entities::Transaction,
+    error::TransactionError,
+    gateways::WalletGateway,
+    repository::{TransactionRepository, WalletStatementRepository},
+    statement::{StatementPeriod, WalletStatement},
+    types::{TransactionId, WalletId},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Caso de uso para obtener el extracto de una billetera en un período.
+///
+/// El saldo de apertura y los movimientos los aporta el libro mayor del Wallet Service (vía
+/// gRPC) y el detalle de cada movimiento sale del historial de transacciones. Si el job
+/// mensual ya pregeneró el extracto de ese período exacto, se devuelve el guardado siempre
+/// que se haya generado después del período de gracia de liquidación (ver
+/// `with_settlement_grace`); si no, se regenera.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::get_wallet_statement::GetWalletStatementUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = GetWalletStatementUseCase::new(transaction_repo, statement_repo, wallet_gateway);
+/// ```
+#[derive(Clone)]
+pub struct GetWalletStatementUseCase {
+    transaction_repo: Arc<dyn TransactionRepository>,
+    statement_repo: Arc<dyn WalletStatementRepository>,
+    wallet_gateway: Arc<dyn WalletGateway>,
+    settlement_grace: Duration,
+}
+
+impl GetWalletStatementUseCase {
+    /// Horas que se esperan tras el cierre de un período antes de darlo por liquidado.
+    pub const DEFAULT_SETTLEMENT_GRACE_HOURS: i64 = 24;
+
+    /// Construye una nueva instancia inyectando el historial, el almacén de extractos y el
+    /// gateway al Wallet Service.
+    pub fn new(
+        transaction_repo: Arc<dyn TransactionRepository>,
+        statement_repo: Arc<dyn WalletStatementRepository>,
+        wallet_gateway: Arc<dyn WalletGateway>,
+    ) -> Self {
+        Self {
+            transaction_repo,
+            statement_repo,
+            wallet_gateway,
+            settlement_grace: Duration::hours(Self::DEFAULT_SETTLEMENT_GRACE_HOURS),
+        }
+    }
+
+    /// Configura cuánto se espera tras el cierre de un período para que se asienten las
+    /// liquidaciones tardías y los reversos antes de guardar su extracto.
+    pub fn with_settlement_grace(mut self, grace: Duration) -> Self {
+        self.settlement_grace = grace;
+        self
+    }
+
+    /// Devuelve el extracto de `wallet_id` para `[from, to)`.
+    ///
+    /// # Retornos
+    ///
+    /// El extracto, o `TransactionError::ValidationError` si el período está vacío, supera
+    /// `MAX_PERIOD_DAYS` o la billetera no existe en el Wallet Service.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let statement = use_case.execute(wallet_id, from, to).await?;
+    /// let csv = statement.to_csv();
+    /// ```
+    #[tracing::instrument(name = "GetWalletStatementUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<WalletStatement, TransactionError> {
+        let period = StatementPeriod::new(from, to)?;
+
+        if let Some(statement) = self.statement_repo.find(wallet_id, period).await? {
+            if self.is_settled(&statement) {
+                return Ok(statement);
+            }
+        }
+
+        self.generate(wallet_id, period).await
+    }
+
+    /// Genera y guarda el extracto del período una vez vencido el período de gracia, si no
+    /// hay ya uno guardado después de vencer (uno anterior se reemplaza).
+    ///
+    /// Retorna `true` si se generó, `false` si todavía está en gracia o ya estaba guardado.
+    #[tracing::instrument(name = "GetWalletStatementUseCase::pregenerate", skip(self))]
+    pub async fn pregenerate(
+        &self,
+        wallet_id: WalletId,
+        period: StatementPeriod,
+    ) -> Result<bool, TransactionError> {
+        if Utc::now() < self.settled_at(period) {
+            return Ok(false);
+        }
+        if let Some(statement) = self.statement_repo.find(wallet_id, period).await? {
+            if self.is_settled(&statement) {
+                return Ok(false);
+            }
+        }
+
+        let statement = self.generate(wallet_id, period).await?;
+        self.statement_repo.save(&statement).await?;
+        Ok(true)
+    }
+
+    /// Instante a partir del cual se considera que el período ya no recibe asientos.
+    fn settled_at(&self, period: StatementPeriod) -> DateTime<Utc> {
+        period.end + self.settlement_grace
+    }
+
+    /// Un extracto generado antes de vencer la gracia puede no incluir liquidaciones tardías
+    /// ni reversos, así que no se reutiliza.
+    fn is_settled(&self, statement: &WalletStatement) -> bool {
+        statement.generated_at >= self.settled_at(statement.period)
+    }
+
+    /// El saldo de apertura y los movimientos salen del libro mayor del Wallet Service con
+    /// el mismo reloj, así que apertura + movimientos = cierre aunque una transacción se
+    /// asiente mucho después de crearse. Este servicio sólo aporta el detalle de cada una.
+    async fn generate(
+        &self,
+        wallet_id: WalletId,
+        period: StatementPeriod,
+    ) -> Result<WalletStatement, TransactionError> {
+        // El Wallet Service incluye los asientos del instante consultado; pedimos el saldo
+        // justo antes del inicio para no contar dos veces un movimiento de ese mismo instante.
+        let opening = self
+            .wallet_gateway
+            .historical_balance(wallet_id, period.start - Duration::microseconds(1))
+            .await?;
+        let postings = self
+            .wallet_gateway
+            .ledger_postings(wallet_id, period.start, period.end)
+            .await?;
+
+        let mut ids: Vec<TransactionId> = postings.iter().map(|p| p.transaction_id).collect();
+        ids.sort_by_key(|id| id.0);
+        ids.dedup();
+        let transactions: HashMap<TransactionId, Transaction> = self
+            .transaction_repo
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|t| (t.id(), t))
+            .collect();
+
+        // Todo asiento lo origina una transacción de este servicio; omitir uno descuadraría
+        // el extracto.
+        let postings = postings
+            .into_iter()
+            .map(|posting| match transactions.get(&posting.transaction_id) {
+                Some(transaction) => Ok((posting, transaction.clone())),
+                None => Err(TransactionError::RepositoryError(format!(
+                    "El asiento de la transacción {} no corresponde a ninguna transacción registrada",
+                    posting.transaction_id
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(WalletStatement::build(
+            wallet_id,
+            opening.currency.code,
+            period,
+            opening.ledger_balance,
+            postings,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{TransactionDetails, TransactionStatus, TransactionType};
+    use crate::domain::gateways::{HistoricalBalance, LedgerPosting, WalletCurrency};
+    use crate::test_support::{
+        MockTransactionRepositoryImpl, MockWalletGatewayImpl, MockWalletStatementRepositoryImpl,
+    };
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn february() -> StatementPeriod {
+        StatementPeriod::new(
+            Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn deposit(wallet_id: WalletId, created_at: DateTime<Utc>) -> Transaction {
+        Transaction::reconstitute(
+            TransactionId::new(),
+            None,
+            Some(wallet_id),
+            None,
+            Decimal::from(10),
+            TransactionStatus::COMPLETED,
+            TransactionType::DEPOSIT,
+            created_at,
+            Uuid::new_v4(),
+            None,
+            None,
+            Decimal::ZERO,
+            TransactionDetails::default(),
+        )
+        .unwrap()
+    }
+
+    fn gateway_with_opening(opening: i64) -> MockWalletGatewayImpl {
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_historical_balance()
+            .withf(|_, as_of| *as_of < february().start)
+            .returning(move |_, _| {
+                Ok(HistoricalBalance {
+                    currency: WalletCurrency {
+                        code: "USD".into(),
+                        minor_units: 2,
+                    },
+                    ledger_balance: Decimal::from(opening),
+                })
+            });
+        mock_gateway
+    }
+
+    #[tokio::test]
+    async fn test_pregenerate_takes_lines_from_ledger_postings_of_the_period() {
+        let wallet_id = WalletId::new();
+        let period = february();
+        // Creado en enero pero asentado en febrero: el saldo de apertura no lo incluye, así
+        // que debe aparecer como movimiento de febrero.
+        let late = deposit(wallet_id, period.start - Duration::seconds(1));
+        let on_time = deposit(wallet_id, period.start + Duration::days(3));
+        let transactions = vec![late.clone(), on_time.clone()];
+        let postings = vec![
+            LedgerPosting {
+                transaction_id: late.id(),
+                amount: Decimal::from(10),
+                posted_at: period.start + Duration::seconds(2),
+            },
+            LedgerPosting {
+                transaction_id: on_time.id(),
+                amount: Decimal::from(10),
+                posted_at: period.start + Duration::days(3),
+            },
+        ];
+
+        let mut mock_statements = MockWalletStatementRepositoryImpl::new();
+        mock_statements.expect_find().returning(|_, _| Ok(None));
+        mock_statements
+            .expect_save()
+            .withf(move |s: &WalletStatement| {
+                s.opening_balance == Decimal::from(5)
+                    && s.lines.len() == 2
+                    && s.lines[0].transaction_id == late.id()
+                    && s.closing_balance == Decimal::from(25)
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_gateway = gateway_with_opening(5);
+        mock_gateway
+            .expect_ledger_postings()
+            .withf(move |_, from, to| *from == period.start && *to == period.end)
+            .times(1)
+            .returning(move |_, _, _| Ok(postings.clone()));
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_by_ids()
+            .withf(|ids| ids.len() == 2)
+            .times(1)
+            .returning(move |_| Ok(transactions.clone()));
+
+        let use_case = GetWalletStatementUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_statements),
+            Arc::new(mock_gateway),
+        );
+
+        assert!(use_case.pregenerate(wallet_id, period).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_posting_without_transaction_fails_instead_of_unbalancing() {
+        let wallet_id = WalletId::new();
+        let period = february();
+
+        let mut mock_gateway = gateway_with_opening(0);
+        mock_gateway
+            .expect_ledger_postings()
+            .returning(move |_, _, _| {
+                Ok(vec![LedgerPosting {
+                    transaction_id: TransactionId::new(),
+                    amount: Decimal::from(10),
+                    posted_at: period.start,
+                }])
+            });
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo.expect_find_by_ids().returning(|_| Ok(Vec::new()));
+        let mut mock_statements = MockWalletStatementRepositoryImpl::new();
+        mock_statements.expect_find().returning(|_, _| Ok(None));
+        mock_statements.expect_save().never();
+
+        let use_case = GetWalletStatementUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_statements),
+            Arc::new(mock_gateway),
+        );
+
+        assert!(matches!(
+            use_case.pregenerate(wallet_id, period).await,
+            Err(TransactionError::RepositoryError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_stored_statement() {
+        let wallet_id = WalletId::new();
+        let period = february();
+        let stored = WalletStatement::build(
+            wallet_id,
+            "USD".into(),
+            period,
+            Decimal::from(7),
+            Vec::new(),
+        );
+
+        let mut mock_statements = MockWalletStatementRepositoryImpl::new();
+        let found = stored.clone();
+        mock_statements
+            .expect_find()
+            .returning(move |_, _| Ok(Some(found.clone())));
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway.expect_historical_balance().never();
+
+        let use_case = GetWalletStatementUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            Arc::new(mock_statements),
+            Arc::new(mock_gateway),
+        );
+        let statement = use_case
+            .execute(wallet_id, period.start, period.end)
+            .await
+            .unwrap();
+
+        assert_eq!(statement, stored);
+    }
+
+    #[tokio::test]
+    async fn test_execute_regenerates_statement_saved_before_settlement_grace() {
+        let wallet_id = WalletId::new();
+        let period = february();
+        let mut stale = WalletStatement::build(
+            wallet_id,
+            "USD".into(),
+            period,
+            Decimal::from(7),
+            Vec::new(),
+        );
+        stale.generated_at = period.end + Duration::hours(1);
+
+        let mut mock_statements = MockWalletStatementRepositoryImpl::new();
+        mock_statements
+            .expect_find()
+            .returning(move |_, _| Ok(Some(stale.clone())));
+        let mut mock_gateway = gateway_with_opening(7);
+        mock_gateway
+            .expect_ledger_postings()
+            .times(1)
+            .returning(|_, _, _| Ok(Vec::new()));
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo.expect_find_by_ids().returning(|_| Ok(Vec::new()));
+
+        let use_case = GetWalletStatementUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_statements),
+            Arc::new(mock_gateway),
+        );
+        let statement = use_case
+            .execute(wallet_id, period.start, period.end)
+            .await
+            .unwrap();
+
+        // La copia guardada una hora después del cierre no se reutiliza.
+        assert!(statement.generated_at > period.end + Duration::days(1));
+    }
+
+    #[tokio::test]
+    async fn test_pregenerate_waits_for_settlement_grace() {
+        let now = Utc::now();
+        let period =
+            StatementPeriod::new(now - Duration::days(30), now - Duration::hours(1)).unwrap();
+
+        let mut mock_statements = MockWalletStatementRepositoryImpl::new();
+        mock_statements.expect_find().never();
+        mock_statements.expect_save().never();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway.expect_ledger_postings().never();
+
+        let use_case = GetWalletStatementUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            Arc::new(mock_statements),
+            Arc::new(mock_gateway),
+        )
+        .with_settlement_grace(Duration::hours(24));
+
+        assert!(!use_case.pregenerate(WalletId::new(), period).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_pregenerate_replaces_statement_saved_before_settlement_grace() {
+        let wallet_id = WalletId::new();
+        let period = february();
+        let mut stale = WalletStatement::build(
+            wallet_id,
+            "USD".into(),
+            period,
+            Decimal::from(7),
+            Vec::new(),
+        );
+        stale.generated_at = period.end;
+
+        let mut mock_statements = MockWalletStatementRepositoryImpl::new();
+        mock_statements
+            .expect_find()
+            .returning(move |_, _| Ok(Some(stale.clone())));
+        mock_statements
+            .expect_save()
+            .withf(move |s: &WalletStatement| s.generated_at > period.end + Duration::days(1))
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_gateway = gateway_with_opening(7);
+        mock_gateway
+            .expect_ledger_postings()
+            .returning(|_, _, _| Ok(Vec::new()));
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo.expect_find_by_ids().returning(|_| Ok(Vec::new()));
+
+        let use_case = GetWalletStatementUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_statements),
+            Arc::new(mock_gateway),
+        );
+
+        assert!(use_case.pregenerate(wallet_id, period).await.unwrap());
+    }
+}
@@ -1,3 +1,12 @@
+pub mod create_fx_quote;
 pub mod get_transaction_details;
+pub mod get_transaction_events;
 pub mod get_wallet_history;
+pub mod get_wallet_statement;
 pub mod process_transaction;
+pub mod refund_transaction;
+pub mod review_compensations;
+pub mod review_dead_letters;
+pub mod saga_executor;
+pub mod search_transactions;
+pub mod update_exchange_rate;
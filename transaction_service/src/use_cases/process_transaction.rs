@@ -1,10 +1,18 @@
 use crate::domain::{
-    entities::{Transaction, TransactionStatus},
+    entities::{Transaction, TransactionDetails, TransactionStatus, TransactionType},
     error::TransactionError,
+    events::{EventActor, TransactionEvent},
+    exchange::ExchangeDetails,
     gateways::WalletGateway,
-    repository::TransactionRepository,
-    types::WalletId,
+    idempotency::RequestSummary,
+    repository::{
+        CompensationRepository, FxQuoteRepository, SagaRepository, TransactionEventRepository,
+        TransactionRepository,
+    },
+    types::{QuoteId, WalletId},
 };
+use crate::use_cases::saga_executor::SagaExecutor;
+use chrono::{Duration, Utc};
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -12,7 +20,7 @@ use uuid::Uuid;
 /// Caso de uso central para el procesamiento y orquestamiento de transacciones.
 ///
 /// Coordina la persistencia en la base de datos de transacciones, chequea la
-/// idempotencia e interactúa con el `WalletGateway` para actualizar saldos.
+/// idempotencia y mueve los saldos en el Wallet Service a través del `SagaExecutor`.
 ///
 /// # Examples
 /// ```ignore
@@ -23,30 +31,66 @@ use uuid::Uuid;
 ///
 /// let repo = Arc::new(MockTransactionRepositoryImpl::new());
 /// let gateway = Arc::new(MockWalletGatewayImpl::new());
-/// let use_case = ProcessTransactionUseCase::new(repo, gateway);
+/// let quotes = Arc::new(MockFxQuoteRepositoryImpl::new());
+/// let events = Arc::new(MockTransactionEventRepositoryImpl::new());
+/// let sagas = Arc::new(MockSagaRepositoryImpl::new());
+/// let compensations = Arc::new(MockCompensationRepositoryImpl::new());
+/// let use_case =
+///     ProcessTransactionUseCase::new(repo, gateway, quotes, events, sagas, compensations);
 /// ```
 pub struct ProcessTransactionUseCase {
     transaction_repo: Arc<dyn TransactionRepository>,
     wallet_gateway: Arc<dyn WalletGateway>,
+    quote_repo: Arc<dyn FxQuoteRepository>,
+    event_repo: Arc<dyn TransactionEventRepository>,
+    saga_executor: SagaExecutor,
+    idempotency_ttl: Duration,
 }
 
 impl ProcessTransactionUseCase {
+    /// Ventana por defecto durante la cual un `correlation_id` identifica a su transacción.
+    pub const DEFAULT_IDEMPOTENCY_TTL_HOURS: i64 = 24;
+
     pub fn new(
         transaction_repo: Arc<dyn TransactionRepository>,
         wallet_gateway: Arc<dyn WalletGateway>,
+        quote_repo: Arc<dyn FxQuoteRepository>,
+        event_repo: Arc<dyn TransactionEventRepository>,
+        saga_repo: Arc<dyn SagaRepository>,
+        compensation_repo: Arc<dyn CompensationRepository>,
     ) -> Self {
         Self {
             transaction_repo,
+            saga_executor: SagaExecutor::new(saga_repo, compensation_repo, wallet_gateway.clone()),
             wallet_gateway,
+            quote_repo,
+            event_repo,
+            idempotency_ttl: Duration::hours(Self::DEFAULT_IDEMPOTENCY_TTL_HOURS),
         }
     }
 
+    /// Reemplaza la ventana de idempotencia: pasado ese tiempo desde la creación de una
+    /// transacción, su `correlation_id` puede reutilizarse para una operación nueva.
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_ttl = ttl;
+        self
+    }
+
     /// Ejecuta el proceso de inicio y completitud de una transacción, manejando su estado transicional.
     ///
-    /// Valida la idempotencia basándose en `correlation_id`, crea la entidad `Transaction`, la
+    /// Crea la entidad `Transaction`, valida la idempotencia basándose en `correlation_id`, la
     /// guarda inicialmente como "PENDING", hace el llamado por external gateway, y finaliza
     /// guardando el estado como "COMPLETED" o "FAILED".
     ///
+    /// Reutilizar un `correlation_id` vigente con otra solicitud (monto, billeteras, cotización
+    /// o cliente distintos) retorna `TransactionError::IdempotencyConflict`.
+    ///
+    /// Las transferencias entre billeteras de distinta divisa requieren `quote_id`: la
+    /// cotización fija la tasa con la que se calcula el monto acreditado en destino.
+    ///
+    /// `details` (memo, referencia externa y metadata) se guarda junto a la transacción, al
+    /// igual que `client_id` (el cliente de la API que la inicia, si se identificó).
+    ///
     /// # Examples
     /// ```ignore
     /// use transaction_service::domain::entities::Transaction;
@@ -54,50 +98,159 @@ impl ProcessTransactionUseCase {
     /// use uuid::Uuid;
     /// use rust_decimal::Decimal;
     /// let dest = WalletId::new();
-    /// let tx = use_case.execute(None, dest, Decimal::from(100), Uuid::new_v4()).await.unwrap();
+    /// let tx = use_case
+    ///     .execute(None, dest, Decimal::from(100), Uuid::new_v4(), None, TransactionDetails::default(), None)
+    ///     .await
+    ///     .unwrap();
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute(
         &self,
         source_wallet: Option<WalletId>,
         dest_wallet: WalletId,
         amount: Decimal,
         correlation_id: Uuid, // Now mandatory
+        quote_id: Option<QuoteId>,
+        details: TransactionDetails,
+        client_id: Option<String>,
     ) -> Result<Transaction, TransactionError> {
-        // 1. Idempotency Check (Verificación de Idempotencia)
+        // 1. Create Entity (Creación de Entidad y Reglas de Negocio)
+        // Delegamos la validación de la "forma" (monto positivo, wallets distintas) al constructor de la Entidad.
+        // Esto asegura que nunca trabajemos con una estructura `Transaction` inválida en la capa de aplicación.
+        let transaction = Transaction::new(source_wallet, dest_wallet, amount, correlation_id)?
+            .with_details(details)
+            .with_client_id(client_id);
+        let mut request = RequestSummary::of(&transaction);
+        request.quote_id = quote_id;
+
+        // 2. Idempotency Check (Verificación de Idempotencia)
         // Antes de iniciar cualquier proceso, verificamos si esta solicitud ya fue procesada anteriormente.
         // Esto previene cobros duplicados en caso de reintentos por fallos de red o errores del cliente.
-        // Si el `correlation_id` existe, devolvemos la transacción previa sin re-ejecutar la lógica.
-        if let Ok(Some(existing_transaction)) = self
-            .transaction_repo
-            .find_by_correlation_id(correlation_id)
-            .await
-        {
+        // Si el `correlation_id` existe con la misma solicitud, devolvemos la transacción previa sin
+        // re-ejecutar la lógica; si la solicitud difiere, es un conflicto.
+        if let Some(existing_transaction) = self.find_replay(correlation_id, &request).await? {
             return Ok(existing_transaction);
         }
+        let mut transaction = transaction.with_request_fingerprint(Some(request.fingerprint()));
 
-        // 2. Create Entity (Creación de Entidad y Reglas de Negocio)
-        // Delegamos la validación de la "forma" (monto positivo, wallets distintas) al constructor de la Entidad.
-        // Esto asegura que nunca trabajemos con una estructura `Transaction` inválida en la capa de aplicación.
-        let transaction = Transaction::new(source_wallet, dest_wallet, amount, correlation_id)?;
+        // 2.1 Currency Check (Conversión de Divisa)
+        // Una transferencia entre divisas distintas sólo procede con una cotización vigente;
+        // sin ella se rechaza antes de persistir nada.
+        self.resolve_exchange(&mut transaction, quote_id).await?;
+
+        self.process(transaction, &request).await
+    }
+
+    /// Ejecuta un retiro: debita `source_wallet` hacia el destino externo indicado.
+    ///
+    /// Sigue el mismo ciclo que `execute` (idempotencia por `correlation_id`, PENDING, llamado
+    /// al Wallet Service y estado final), sin conversión de divisa.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let tx = use_case
+    ///     .execute_withdrawal(
+    ///         source,
+    ///         "bank:0042-123456".into(),
+    ///         Decimal::from(50),
+    ///         Uuid::new_v4(),
+    ///         TransactionDetails::default(),
+    ///         Some("checkout-web".into()),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn execute_withdrawal(
+        &self,
+        source_wallet: WalletId,
+        external_destination: String,
+        amount: Decimal,
+        correlation_id: Uuid,
+        details: TransactionDetails,
+        client_id: Option<String>,
+    ) -> Result<Transaction, TransactionError> {
+        let transaction = Transaction::new_withdrawal(
+            source_wallet,
+            external_destination,
+            amount,
+            correlation_id,
+        )?
+        .with_details(details)
+        .with_client_id(client_id);
+        let request = RequestSummary::of(&transaction);
+
+        if let Some(existing_transaction) = self.find_replay(correlation_id, &request).await? {
+            return Ok(existing_transaction);
+        }
+
+        self.process(
+            transaction.with_request_fingerprint(Some(request.fingerprint())),
+            &request,
+        )
+        .await
+    }
+
+    /// Transacción ya registrada con el mismo `correlation_id` dentro de la ventana de
+    /// idempotencia, si existe.
+    ///
+    /// Si la solicitud original coincide con `request` es un reintento y se devuelve la
+    /// transacción previa; si difiere, `TransactionError::IdempotencyConflict` con ambas.
+    async fn find_replay(
+        &self,
+        correlation_id: Uuid,
+        request: &RequestSummary,
+    ) -> Result<Option<Transaction>, TransactionError> {
+        find_unexpired(
+            self.transaction_repo.as_ref(),
+            correlation_id,
+            self.idempotency_ttl,
+        )
+        .await?
+        .map(|existing| replay(existing, request))
+        .transpose()
+    }
 
+    /// Persiste la transacción como PENDING, la envía al Wallet Service y guarda el estado final.
+    async fn process(
+        &self,
+        transaction: Transaction,
+        request: &RequestSummary,
+    ) -> Result<Transaction, TransactionError> {
         // 3. Persist Initial Intent (Persistencia del Intento - Estado PENDING)
         // Guardamos la transacción con estado `PENDING` *antes* de contactar al servicio externo.
         // Esto actúa como un registro de auditoría (write-ahead log). Si el proceso muere aquí,
         // sabremos que hubo un intento fallido (o pendiente de conciliación).
-        // Usamos `map_err` para envolver el error original de la BD con contexto útil.
-        let saved_transaction = self
+        // El guardado reserva el `correlation_id`: si una solicitud concurrente con la misma
+        // clave se guardó primero, se responde como a un reintento de aquella.
+        let saved_transaction = match self
             .transaction_repo
-            .save(transaction.clone())
+            .save(transaction.clone(), self.idempotency_ttl)
             .await
-            .map_err(|e| TransactionError::RepositoryError(format!("DB Save Error: {}", e)))?;
+        {
+            Ok(saved) => saved,
+            Err(TransactionError::IdempotencyError(correlation_id)) => {
+                return self
+                    .find_replay(correlation_id, request)
+                    .await?
+                    .ok_or(TransactionError::IdempotencyError(correlation_id));
+            }
+            Err(e) => {
+                return Err(TransactionError::RepositoryError(format!(
+                    "DB Save Error: {}",
+                    e
+                )))
+            }
+        };
+        record_event(
+            self.event_repo.as_ref(),
+            TransactionEvent::created(&saved_transaction, EventActor::Api),
+        )
+        .await;
 
         // 4. Call Wallet Service (Ejecución de la Acción Distribuida)
         // Solicitamos al Wallet Service que mueva los fondos. Esta es la operación crítica ("Point of No Return").
-        // El Gateway abstrae si es una llamada gRPC, HTTP o mensaje en cola.
-        let result = self
-            .wallet_gateway
-            .process_movement(&saved_transaction)
-            .await;
+        // La saga reserva y captura cada pata dejando registro de cada paso, para poder
+        // completarla o compensarla si el proceso muere a mitad de camino.
+        let result = self.saga_executor.execute(&saved_transaction).await;
 
         // 5. Handle Result (Commit o Rollback - Consistencia Eventual)
         match result {
@@ -105,35 +258,203 @@ impl ProcessTransactionUseCase {
                 // Happy Path: El Wallet Service confirmó el movimiento.
                 // Actualizamos el estado local a `COMPLETED`.
                 let mut success_transaction = saved_transaction;
-                success_transaction.update_status(TransactionStatus::COMPLETED);
-                self.transaction_repo
-                    .update(success_transaction)
+                let previous = success_transaction.update_status(TransactionStatus::COMPLETED)?;
+                let completed_transaction = self
+                    .transaction_repo
+                    .update(success_transaction, previous)
                     .await
-                    .map_err(|e| {
-                        TransactionError::RepositoryError(format!("DB Commit Error: {}", e))
-                    })
+                    .map_err(|e| match e {
+                        // El job de reintentos cerró la transacción antes que nosotros.
+                        TransactionError::InvalidState(_) => e,
+                        e => TransactionError::RepositoryError(format!("DB Commit Error: {}", e)),
+                    })?;
+                record_event(
+                    self.event_repo.as_ref(),
+                    TransactionEvent::transition(&completed_transaction, previous, EventActor::Api)
+                        .with_gateway_result(&result),
+                )
+                .await;
+
+                Ok(completed_transaction)
+            }
+            Err(TransactionError::SagaIncomplete(id)) => {
+                // Parte de los fondos ya se movió: la transacción sigue PENDING hasta que el
+                // job de reintentos termine la saga.
+                Err(TransactionError::SagaIncomplete(id))
             }
             Ok(false) | Err(_) => {
                 // Failure Path: El Wallet Service rechazó (fondos insuficientes) o falló la comunicación.
                 // Debemos marcar la transacción como `FAILED` para cerrar el ciclo de vida.
                 let mut failed_transaction = saved_transaction;
-                failed_transaction.update_status(TransactionStatus::FAILED);
+                let previous = failed_transaction.update_status(TransactionStatus::FAILED)?;
 
-                // Best-effort rollback: Intentamos guardar el estado de fallo.
-                // Ignoramos el resultado de este update (`let _`) porque nuestro objetivo principal
+                // Best-effort rollback: Intentamos guardar el estado de fallo (y el motivo en el
+                // historial). Si este update falla lo ignoramos, porque nuestro objetivo principal
                 // es retornar el error original que causó el fallo.
-                let _ = self.transaction_repo.update(failed_transaction).await;
+                if let Ok(failed_transaction) = self
+                    .transaction_repo
+                    .update(failed_transaction, previous)
+                    .await
+                {
+                    record_event(
+                        self.event_repo.as_ref(),
+                        TransactionEvent::transition(
+                            &failed_transaction,
+                            previous,
+                            EventActor::Api,
+                        )
+                        .with_gateway_result(&result),
+                    )
+                    .await;
+                }
 
                 // Retornamos el error específico para que el cliente sepa qué pasó.
-                match result {
-                    Err(e) => Err(TransactionError::GatewayError(e.to_string())),
-                    Ok(false) => Err(TransactionError::GatewayError(
-                        "Wallet rejected the transaction".to_string(),
+                Err(rejection_error(result))
+            }
+        }
+    }
+
+    /// Compara las divisas de origen y destino y, si difieren, aplica la cotización indicada.
+    async fn resolve_exchange(
+        &self,
+        transaction: &mut Transaction,
+        quote_id: Option<QuoteId>,
+    ) -> Result<(), TransactionError> {
+        let (source_wallet, destination_wallet) = match (
+            transaction.source_wallet_id(),
+            transaction.destination_wallet_id(),
+        ) {
+            (Some(source), Some(destination))
+                if transaction.transaction_type() == TransactionType::TRANSFER =>
+            {
+                (source, destination)
+            }
+            _ => {
+                return match quote_id {
+                    Some(_) => Err(TransactionError::ValidationError(
+                        "Sólo las transferencias admiten cotización de divisa".into(),
                     )),
-                    _ => unreachable!(),
-                }
+                    None => Ok(()),
+                };
             }
+        };
+
+        let source = self.wallet_gateway.wallet_currency(source_wallet).await?;
+        let destination = self
+            .wallet_gateway
+            .wallet_currency(destination_wallet)
+            .await?;
+
+        if source.code == destination.code {
+            return match quote_id {
+                Some(_) => Err(TransactionError::ValidationError(format!(
+                    "Ambas billeteras usan {}; la transferencia no requiere cotización",
+                    source.code
+                ))),
+                None => Ok(()),
+            };
         }
+
+        let quote_id = quote_id.ok_or_else(|| {
+            TransactionError::CurrencyMismatch(source.code.clone(), destination.code.clone())
+        })?;
+        let quote = self
+            .quote_repo
+            .find_by_id(quote_id)
+            .await?
+            .ok_or(TransactionError::QuoteNotFound(quote_id))?;
+
+        if quote.is_expired(Utc::now()) {
+            return Err(TransactionError::QuoteExpired(quote_id));
+        }
+        if quote.source_currency != source.code || quote.destination_currency != destination.code {
+            return Err(TransactionError::ValidationError(format!(
+                "La cotización {} es de {} a {}, pero la transferencia es de {} a {}",
+                quote_id,
+                quote.source_currency,
+                quote.destination_currency,
+                source.code,
+                destination.code
+            )));
+        }
+
+        transaction.apply_exchange(ExchangeDetails {
+            destination_amount: quote.convert(transaction.amount(), destination.minor_units),
+            source_currency: quote.source_currency,
+            destination_currency: quote.destination_currency,
+            rate: quote.rate,
+            rate_timestamp: quote.rate_timestamp,
+            quote_id,
+        })
+    }
+}
+
+/// Transacción registrada con `correlation_id` dentro de la ventana de idempotencia `ttl`,
+/// si existe. Un error del repositorio se propaga: tratarlo como "no existe" volvería a
+/// ejecutar un reintento.
+pub(crate) async fn find_unexpired(
+    transaction_repo: &dyn TransactionRepository,
+    correlation_id: Uuid,
+    ttl: Duration,
+) -> Result<Option<Transaction>, TransactionError> {
+    Ok(transaction_repo
+        .find_by_correlation_id(correlation_id)
+        .await?
+        .filter(|existing| existing.created_at() > Utc::now() - ttl))
+}
+
+/// Decide qué responder a una solicitud cuyo `correlation_id` ya usó `existing`.
+///
+/// Si la solicitud original coincide con `request` es un reintento y se devuelve `existing`;
+/// si difiere, `TransactionError::IdempotencyConflict` con ambas.
+pub(crate) fn replay(
+    existing: Transaction,
+    request: &RequestSummary,
+) -> Result<Transaction, TransactionError> {
+    // Las transacciones anteriores a la huella guardada se comparan recalculándola.
+    let original = RequestSummary::of(&existing);
+    let original_fingerprint = existing
+        .request_fingerprint()
+        .map(str::to_string)
+        .unwrap_or_else(|| original.fingerprint());
+    if original_fingerprint == request.fingerprint() {
+        return Ok(existing);
+    }
+
+    Err(TransactionError::IdempotencyConflict {
+        correlation_id: existing.correlation_id(),
+        original: Box::new(original),
+        received: Box::new(request.clone()),
+    })
+}
+
+/// Error a devolver al cliente cuando el Wallet Service no aplicó el movimiento.
+///
+/// Los rechazos de negocio (fondos, límites, estado de la billetera, monto inválido) llegan
+/// tal cual; cualquier otro fallo se reporta como error del gateway.
+pub(crate) fn rejection_error(result: Result<bool, TransactionError>) -> TransactionError {
+    match result {
+        Err(e) if e.is_rejection() => e,
+        Err(e) => TransactionError::GatewayError(e.to_string()),
+        Ok(_) => TransactionError::GatewayError("Wallet rejected the transaction".to_string()),
+    }
+}
+
+/// Registra un cambio de estado en el historial.
+///
+/// Es best-effort: un fallo al escribir el historial no revierte ni bloquea la transacción,
+/// sólo se loguea.
+pub(crate) async fn record_event(
+    event_repo: &dyn TransactionEventRepository,
+    event: TransactionEvent,
+) {
+    let transaction_id = event.transaction_id;
+    if let Err(e) = event_repo.record(event).await {
+        tracing::error!(
+            "Failed to record status event for tx {}: {:?}",
+            transaction_id,
+            e
+        );
     }
 }
 
@@ -142,38 +463,49 @@ mod tests {
     use super::*;
     use crate::domain::entities::{Transaction, TransactionStatus, TransactionType};
     use crate::domain::error::TransactionError;
-    use crate::domain::gateways::WalletGateway;
-    use crate::domain::repository::TransactionRepository;
+    use crate::domain::exchange::{ExchangeRate, FxQuote};
+    use crate::domain::gateways::WalletCurrency;
     use crate::domain::types::{TransactionId, WalletId};
-    use async_trait::async_trait;
-    use chrono::{DateTime, Utc};
-    use mockall::mock;
+    use crate::test_support::{
+        approve_legs, compensations, events, sagas, MockFxQuoteRepositoryImpl,
+        MockTransactionEventRepositoryImpl, MockTransactionRepositoryImpl, MockWalletGatewayImpl,
+    };
+    use chrono::Utc;
     use mockall::predicate::*;
     use rust_decimal::Decimal;
     use std::sync::Arc;
     use uuid::Uuid;
 
-    mock! {
-        pub TransactionRepositoryImpl {}
-
-        #[async_trait]
-        impl TransactionRepository for TransactionRepositoryImpl {
-            async fn save(&self, transaction: Transaction) -> Result<Transaction, TransactionError>;
-            async fn update(&self, transaction: Transaction) -> Result<Transaction, TransactionError>;
-            async fn find_by_id(&self, id: TransactionId) -> Result<Option<Transaction>, TransactionError>;
-            async fn find_by_wallet_id(&self, wallet_id: WalletId) -> Result<Vec<Transaction>, TransactionError>;
-            async fn find_by_correlation_id(&self, correlation_id: Uuid) -> Result<Option<Transaction>, TransactionError>;
-            async fn find_pending_older_than(&self, timestamp: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionError>;
-        }
+    /// Configura el gateway para que origen y destino usen la misma divisa.
+    fn same_currency(gateway: &mut MockWalletGatewayImpl) {
+        gateway.expect_wallet_currency().returning(|_| {
+            Ok(WalletCurrency {
+                code: "USD".into(),
+                minor_units: 2,
+            })
+        });
     }
 
-    mock! {
-        pub WalletGatewayImpl {}
+    /// Configura el gateway con una billetera origen en USD y cualquier otra en COP.
+    fn usd_to_cop(gateway: &mut MockWalletGatewayImpl, source: WalletId) {
+        gateway.expect_wallet_currency().returning(move |id| {
+            Ok(if id == source {
+                WalletCurrency {
+                    code: "USD".into(),
+                    minor_units: 2,
+                }
+            } else {
+                WalletCurrency {
+                    code: "COP".into(),
+                    minor_units: 2,
+                }
+            })
+        });
+    }
 
-        #[async_trait]
-        impl WalletGateway for WalletGatewayImpl {
-            async fn process_movement(&self, transaction: &Transaction) -> Result<bool, TransactionError>;
-        }
+    fn usd_cop_quote(ttl_seconds: i64) -> FxQuote {
+        let rate = ExchangeRate::new("USD", "COP", Decimal::new(40005, 1), Utc::now()).unwrap();
+        FxQuote::new(rate, chrono::Duration::seconds(ttl_seconds))
     }
 
     #[tokio::test]
@@ -183,15 +515,21 @@ mod tests {
         let mock_gateway = MockWalletGatewayImpl::new();
 
         let correlation_id = Uuid::new_v4();
+        let (source, destination) = (WalletId::new(), WalletId::new());
         let existing_tx = Transaction::reconstitute(
             TransactionId::new(),
-            Some(WalletId::new()),
-            WalletId::new(),
+            Some(source),
+            Some(destination),
+            None,
             Decimal::from(100),
             TransactionStatus::COMPLETED,
             TransactionType::TRANSFER,
             Utc::now(),
             correlation_id,
+            None,
+            None,
+            Decimal::ZERO,
+            TransactionDetails::default(),
         )
         .unwrap();
         let expected_tx = existing_tx.clone();
@@ -202,15 +540,25 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(existing_tx.clone())));
 
-        let use_case = ProcessTransactionUseCase::new(Arc::new(mock_repo), Arc::new(mock_gateway));
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
 
         // Act
         let result = use_case
             .execute(
-                Some(WalletId::new()),
-                WalletId::new(),
-                Decimal::from(100),
+                Some(source),
+                destination,
+                Decimal::new(10000, 2),
                 correlation_id,
+                None,
+                TransactionDetails::default(),
+                None,
             )
             .await;
 
@@ -221,11 +569,201 @@ mod tests {
         assert_eq!(tx.status(), TransactionStatus::COMPLETED);
     }
 
+    #[tokio::test]
+    async fn test_reused_correlation_id_with_other_amount_conflicts() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let correlation_id = Uuid::new_v4();
+        let destination = WalletId::new();
+        let existing_tx =
+            Transaction::new(None, destination, Decimal::from(100), correlation_id).unwrap();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(move |_| Ok(Some(existing_tx.clone())));
+        mock_repo.expect_save().never();
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockWalletGatewayImpl::new()),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+        let result = use_case
+            .execute(
+                None,
+                destination,
+                Decimal::from(150),
+                correlation_id,
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        match result {
+            Err(TransactionError::IdempotencyConflict {
+                original, received, ..
+            }) => {
+                assert_eq!(original.amount, Decimal::from(100));
+                assert_eq!(received.amount, Decimal::from(150));
+            }
+            other => panic!("expected IdempotencyConflict, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_idempotency_lookup_does_not_process_the_request() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Err(TransactionError::RepositoryError("connection reset".into())));
+        mock_repo.expect_save().never();
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockWalletGatewayImpl::new()),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+        let result = use_case
+            .execute(
+                None,
+                WalletId::new(),
+                Decimal::from(100),
+                Uuid::new_v4(),
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(TransactionError::RepositoryError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_request_with_same_key_is_answered_as_replay() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        same_currency(&mut mock_gateway);
+        let correlation_id = Uuid::new_v4();
+        let destination = WalletId::new();
+        // La solicitud concurrente ganó la reserva de la clave y sigue en curso.
+        let winner =
+            Transaction::new(None, destination, Decimal::from(100), correlation_id).unwrap();
+        let winner_id = winner.id();
+
+        let mut seq = mockall::Sequence::new();
+        mock_repo
+            .expect_find_by_correlation_id()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(None));
+        mock_repo
+            .expect_save()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|tx, _| Err(TransactionError::IdempotencyError(tx.correlation_id())));
+        mock_repo
+            .expect_find_by_correlation_id()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move |_| Ok(Some(winner.clone())));
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+        let result = use_case
+            .execute(
+                None,
+                destination,
+                Decimal::from(100),
+                correlation_id,
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.id(), winner_id);
+        assert_eq!(result.status(), TransactionStatus::PENDING);
+    }
+
+    #[tokio::test]
+    async fn test_expired_correlation_id_starts_a_new_transaction() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        let correlation_id = Uuid::new_v4();
+        let old_tx = Transaction::reconstitute(
+            TransactionId::new(),
+            None,
+            Some(WalletId::new()),
+            None,
+            Decimal::from(100),
+            TransactionStatus::COMPLETED,
+            TransactionType::DEPOSIT,
+            Utc::now() - Duration::hours(2),
+            correlation_id,
+            None,
+            None,
+            Decimal::ZERO,
+            TransactionDetails::default(),
+        )
+        .unwrap();
+        let old_id = old_tx.id();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(move |_| Ok(Some(old_tx.clone())));
+        mock_repo
+            .expect_save()
+            .withf(|t: &Transaction, _| t.request_fingerprint().is_some())
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+        mock_repo.expect_update().returning(|t, _| Ok(t));
+        approve_legs(&mut mock_gateway);
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        )
+        .with_idempotency_ttl(Duration::hours(1));
+        let tx = use_case
+            .execute(
+                None,
+                WalletId::new(),
+                Decimal::from(20),
+                correlation_id,
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_ne!(tx.id(), old_id);
+        assert_eq!(tx.status(), TransactionStatus::COMPLETED);
+    }
+
     #[tokio::test]
     async fn test_process_transaction_success() {
         // Arrange
         let mut mock_repo = MockTransactionRepositoryImpl::new();
         let mut mock_gateway = MockWalletGatewayImpl::new();
+        same_currency(&mut mock_gateway);
 
         let source_wallet = WalletId::new();
         let dest_wallet = WalletId::new();
@@ -240,28 +778,41 @@ mod tests {
             .returning(|_| Ok(None));
 
         // 2. Save PENDING
-        mock_repo.expect_save().times(1).returning(|tx| Ok(tx)); // Return what was passed (with generated ID)
+        mock_repo.expect_save().times(1).returning(|tx, _| Ok(tx)); // Return what was passed (with generated ID)
 
         // 3. Call Wallet Gateway (returns true)
-        mock_gateway
-            .expect_process_movement()
-            .times(1)
-            .returning(|_| Ok(true));
+        approve_legs(&mut mock_gateway);
 
         // 4. Update COMPLETED
         mock_repo
             .expect_update()
-            .with(function(|tx: &Transaction| {
-                tx.status() == TransactionStatus::COMPLETED
-            }))
+            .with(
+                function(|tx: &Transaction| tx.status() == TransactionStatus::COMPLETED),
+                eq(TransactionStatus::PENDING),
+            )
             .times(1)
-            .returning(|tx| Ok(tx));
+            .returning(|tx, _| Ok(tx));
 
-        let use_case = ProcessTransactionUseCase::new(Arc::new(mock_repo), Arc::new(mock_gateway));
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
 
         // Act
         let result = use_case
-            .execute(Some(source_wallet), dest_wallet, amount, correlation_id)
+            .execute(
+                Some(source_wallet),
+                dest_wallet,
+                amount,
+                correlation_id,
+                None,
+                TransactionDetails::default(),
+                None,
+            )
             .await;
 
         // Assert (verify result state, though mock expectations cover flow)
@@ -274,6 +825,7 @@ mod tests {
         // Arrange
         let mut mock_repo = MockTransactionRepositoryImpl::new();
         let mut mock_gateway = MockWalletGatewayImpl::new();
+        same_currency(&mut mock_gateway);
 
         let source_wallet = WalletId::new();
         let dest_wallet = WalletId::new();
@@ -283,27 +835,43 @@ mod tests {
         mock_repo
             .expect_find_by_correlation_id()
             .returning(|_| Ok(None));
-        mock_repo.expect_save().returning(|tx| Ok(tx));
+        mock_repo.expect_save().returning(|tx, _| Ok(tx));
 
         // Gateway returns false (e.g. insufficient funds)
         mock_gateway
-            .expect_process_movement()
-            .returning(|_| Ok(false));
+            .expect_execute_transfer()
+            .returning(|_, _| Ok(false));
 
         // Should update to FAILED
         mock_repo
             .expect_update()
-            .with(function(|tx: &Transaction| {
-                tx.status() == TransactionStatus::FAILED
-            }))
+            .with(
+                function(|tx: &Transaction| tx.status() == TransactionStatus::FAILED),
+                eq(TransactionStatus::PENDING),
+            )
             .times(1)
-            .returning(|tx| Ok(tx));
+            .returning(|tx, _| Ok(tx));
 
-        let use_case = ProcessTransactionUseCase::new(Arc::new(mock_repo), Arc::new(mock_gateway));
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
 
         // Act
         let result = use_case
-            .execute(Some(source_wallet), dest_wallet, amount, correlation_id)
+            .execute(
+                Some(source_wallet),
+                dest_wallet,
+                amount,
+                correlation_id,
+                None,
+                TransactionDetails::default(),
+                None,
+            )
             .await;
 
         // Assert
@@ -313,4 +881,400 @@ mod tests {
             TransactionError::GatewayError("Wallet rejected the transaction".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn test_unanswered_transfer_keeps_transaction_pending() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        same_currency(&mut mock_gateway);
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo.expect_save().returning(|tx, _| Ok(tx));
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(|_, _| Err(TransactionError::GatewayError("timeout".into())));
+        // No se sabe si la transferencia se aplicó: ni se compensa ni se cierra la transacción.
+        mock_gateway.expect_release_leg().never();
+        mock_repo.expect_update().never();
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(
+                Some(WalletId::new()),
+                WalletId::new(),
+                Decimal::from(50),
+                Uuid::new_v4(),
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(TransactionError::SagaIncomplete(_))));
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_insufficient_funds() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        same_currency(&mut mock_gateway);
+
+        let source_wallet = WalletId::new();
+        let dest_wallet = WalletId::new();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo.expect_save().returning(|tx, _| Ok(tx));
+
+        // El Wallet Service responde con el resultado tipado de fondos insuficientes
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(move |_, _| Err(TransactionError::InsufficientFunds(source_wallet)));
+
+        mock_repo
+            .expect_update()
+            .with(
+                function(|tx: &Transaction| tx.status() == TransactionStatus::FAILED),
+                eq(TransactionStatus::PENDING),
+            )
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+
+        // El historial registra la creación y el fallo con su motivo.
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events
+            .expect_record()
+            .withf(|event: &TransactionEvent| {
+                event.from_status.is_none() && event.to_status == TransactionStatus::PENDING
+            })
+            .times(1)
+            .returning(Ok);
+        mock_events
+            .expect_record()
+            .withf(move |event: &TransactionEvent| {
+                event.from_status == Some(TransactionStatus::PENDING)
+                    && event.to_status == TransactionStatus::FAILED
+                    && event.actor == EventActor::Api
+                    && event.error_detail
+                        == Some(TransactionError::InsufficientFunds(source_wallet).to_string())
+            })
+            .times(1)
+            .returning(Ok);
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            Arc::new(mock_events),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(
+                Some(source_wallet),
+                dest_wallet,
+                Decimal::from(50),
+                Uuid::new_v4(),
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        // Assert: el error llega sin envolver en GatewayError
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InsufficientFunds(source_wallet)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_limit_exceeded() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        same_currency(&mut mock_gateway);
+
+        let source_wallet = WalletId::new();
+        let detail = "Daily limit is 500.00".to_string();
+        let expected = TransactionError::LimitExceeded(source_wallet, detail.clone());
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo.expect_save().returning(|tx, _| Ok(tx));
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(move |_, _| {
+                Err(TransactionError::LimitExceeded(
+                    source_wallet,
+                    detail.clone(),
+                ))
+            });
+        mock_repo
+            .expect_update()
+            .with(
+                function(|tx: &Transaction| tx.status() == TransactionStatus::FAILED),
+                eq(TransactionStatus::PENDING),
+            )
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(
+                Some(source_wallet),
+                WalletId::new(),
+                Decimal::from(600),
+                Uuid::new_v4(),
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        // Assert: el límite excedido llega tal cual a la capa HTTP
+        assert_eq!(result.unwrap_err(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_process_withdrawal_success() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+
+        let source_wallet = WalletId::new();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo
+            .expect_save()
+            .with(
+                function(move |tx: &Transaction| {
+                    tx.transaction_type() == TransactionType::WITHDRAWAL
+                        && tx.source_wallet_id() == Some(source_wallet)
+                        && tx.destination_wallet_id().is_none()
+                }),
+                always(),
+            )
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+        // Sin billetera destino no hay conversión de divisa que resolver.
+        mock_gateway.expect_wallet_currency().never();
+        approve_legs(&mut mock_gateway);
+        mock_repo
+            .expect_update()
+            .with(
+                function(|tx: &Transaction| tx.status() == TransactionStatus::COMPLETED),
+                eq(TransactionStatus::PENDING),
+            )
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let tx = use_case
+            .execute_withdrawal(
+                source_wallet,
+                "bank:0042-123456".into(),
+                Decimal::from(40),
+                Uuid::new_v4(),
+                TransactionDetails::default(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(tx.external_destination(), Some("bank:0042-123456"));
+    }
+
+    #[tokio::test]
+    async fn test_process_cross_currency_transfer_applies_quote() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        let mut mock_quotes = MockFxQuoteRepositoryImpl::new();
+
+        let source_wallet = WalletId::new();
+        let quote = usd_cop_quote(30);
+        let quote_id = quote.id;
+
+        usd_to_cop(&mut mock_gateway, source_wallet);
+        mock_quotes
+            .expect_find_by_id()
+            .with(eq(quote_id))
+            .returning(move |_| Ok(Some(quote.clone())));
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        // Se persiste con la conversión ya registrada: 10.25 USD * 4000.5 = 41005.125 -> 41005.12 COP
+        mock_repo
+            .expect_save()
+            .with(
+                function(move |tx: &Transaction| {
+                    tx.exchange().map(|fx| fx.quote_id) == Some(quote_id)
+                        && tx.destination_amount() == Decimal::new(4100512, 2)
+                }),
+                always(),
+            )
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+        approve_legs(&mut mock_gateway);
+        mock_repo.expect_update().times(1).returning(|tx, _| Ok(tx));
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(mock_quotes),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let tx = use_case
+            .execute(
+                Some(source_wallet),
+                WalletId::new(),
+                Decimal::new(1025, 2),
+                Uuid::new_v4(),
+                Some(quote_id),
+                TransactionDetails::default(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(tx.amount(), Decimal::new(1025, 2));
+        assert_eq!(tx.exchange().unwrap().rate, Decimal::new(40005, 1));
+    }
+
+    #[tokio::test]
+    async fn test_process_cross_currency_transfer_without_quote_is_rejected() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+
+        let source_wallet = WalletId::new();
+        usd_to_cop(&mut mock_gateway, source_wallet);
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo.expect_save().never();
+        mock_gateway.expect_reserve_leg().never();
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(
+                Some(source_wallet),
+                WalletId::new(),
+                Decimal::from(10),
+                Uuid::new_v4(),
+                None,
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::CurrencyMismatch("USD".into(), "COP".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_cross_currency_transfer_with_expired_quote() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        let mut mock_quotes = MockFxQuoteRepositoryImpl::new();
+
+        let source_wallet = WalletId::new();
+        let quote = usd_cop_quote(0);
+        let quote_id = quote.id;
+
+        usd_to_cop(&mut mock_gateway, source_wallet);
+        mock_quotes
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(quote.clone())));
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo.expect_save().never();
+
+        let use_case = ProcessTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            Arc::new(mock_quotes),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(
+                Some(source_wallet),
+                WalletId::new(),
+                Decimal::from(10),
+                Uuid::new_v4(),
+                Some(quote_id),
+                TransactionDetails::default(),
+                None,
+            )
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::QuoteExpired(quote_id)
+        );
+    }
 }
@@ -0,0 +1,577 @@
+use crate::domain::{
+    entities::{Transaction, TransactionStatus},
+    error::TransactionError,
+    events::{EventActor, TransactionEvent},
+    gateways::WalletGateway,
+    idempotency::RequestSummary,
+    repository::{
+        CompensationRepository, SagaRepository, TransactionEventRepository, TransactionRepository,
+    },
+    types::TransactionId,
+};
+use crate::use_cases::process_transaction::{
+    find_unexpired, record_event, rejection_error, replay, ProcessTransactionUseCase,
+};
+use crate::use_cases::saga_executor::SagaExecutor;
+use chrono::Duration;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Caso de uso para reembolsar, total o parcialmente, una transferencia o depósito COMPLETED.
+///
+/// Cada reembolso es una transacción hija (enlazada por `parent_transaction_id`) que mueve los
+/// fondos de vuelta a través del `SagaExecutor`. La original acumula lo reembolsado con un
+/// compare-and-set, por lo que reembolsos concurrentes nunca superan el monto original, y pasa
+/// a REVERSED cuando queda reembolsada por completo.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::refund_transaction::RefundTransactionUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = RefundTransactionUseCase::new(
+///     transaction_repo,
+///     wallet_gateway,
+///     event_repo,
+///     saga_repo,
+///     compensation_repo,
+/// );
+/// ```
+pub struct RefundTransactionUseCase {
+    transaction_repo: Arc<dyn TransactionRepository>,
+    wallet_gateway: Arc<dyn WalletGateway>,
+    event_repo: Arc<dyn TransactionEventRepository>,
+    saga_executor: SagaExecutor,
+    idempotency_ttl: Duration,
+}
+
+impl RefundTransactionUseCase {
+    pub fn new(
+        transaction_repo: Arc<dyn TransactionRepository>,
+        wallet_gateway: Arc<dyn WalletGateway>,
+        event_repo: Arc<dyn TransactionEventRepository>,
+        saga_repo: Arc<dyn SagaRepository>,
+        compensation_repo: Arc<dyn CompensationRepository>,
+    ) -> Self {
+        Self {
+            transaction_repo,
+            saga_executor: SagaExecutor::new(saga_repo, compensation_repo, wallet_gateway.clone()),
+            wallet_gateway,
+            event_repo,
+            idempotency_ttl: Duration::hours(
+                ProcessTransactionUseCase::DEFAULT_IDEMPOTENCY_TTL_HOURS,
+            ),
+        }
+    }
+
+    /// Reemplaza la ventana de idempotencia, la misma que la de
+    /// `ProcessTransactionUseCase::with_idempotency_ttl`.
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_ttl = ttl;
+        self
+    }
+
+    /// Reembolsa `amount` de la transacción `original_id` (todo lo pendiente si es `None`).
+    ///
+    /// # Argumentos
+    ///
+    /// * `original_id` - Transacción a reembolsar.
+    /// * `amount` - Monto a devolver, en las unidades del monto original.
+    /// * `correlation_id` - Clave de idempotencia del reembolso.
+    /// * `client_id` - Cliente de la API que solicita el reembolso, si se identificó.
+    ///
+    /// # Retornos
+    ///
+    /// La transacción hija en su estado final (o la de un reembolso previo con la misma
+    /// solicitud y `correlation_id`), `TransactionError::IdempotencyConflict` si el
+    /// `correlation_id` vigente se usó con otra solicitud, `TransactionError::NotFound` si la
+    /// original no existe, `TransactionError::InvalidState` si no está COMPLETED (o cambió durante el
+    /// reembolso), `TransactionError::RefundExceeded` si se pide más de lo reembolsable, el
+    /// rechazo del Wallet Service si no pudo mover los fondos, o
+    /// `TransactionError::SagaIncomplete` si los movió sólo en parte.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let refund = use_case
+    ///     .execute(original_id, Some(Decimal::from(20)), Uuid::new_v4(), None)
+    ///     .await?;
+    /// ```
+    #[tracing::instrument(name = "RefundTransactionUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        original_id: TransactionId,
+        amount: Option<Decimal>,
+        correlation_id: Uuid,
+        client_id: Option<String>,
+    ) -> Result<Transaction, TransactionError> {
+        // 1. Idempotencia: un reintento con el mismo correlation_id y la misma solicitud
+        // devuelve el reembolso previo; con otra solicitud es un conflicto.
+        if let Some(existing) = find_unexpired(
+            self.transaction_repo.as_ref(),
+            correlation_id,
+            self.idempotency_ttl,
+        )
+        .await?
+        {
+            let request = received_refund(&existing, original_id, amount, client_id);
+            return replay(existing, &request);
+        }
+
+        // 2. Construir el reembolso a partir de la original (valida estado y monto).
+        let original = self
+            .transaction_repo
+            .find_by_id(original_id)
+            .await?
+            .ok_or(TransactionError::NotFound(original_id))?;
+        let amount = amount.unwrap_or_else(|| original.refundable_amount());
+        let destination_minor_units = match (original.exchange(), original.destination_wallet_id())
+        {
+            (Some(_), Some(destination)) => {
+                self.wallet_gateway
+                    .wallet_currency(destination)
+                    .await?
+                    .minor_units
+            }
+            _ => 0, // Sin conversión no hay montos que truncar
+        };
+        let refund = original
+            .refund(amount, correlation_id, destination_minor_units)?
+            .with_client_id(client_id);
+        let request = RequestSummary::of(&refund);
+        let refund = refund.with_request_fingerprint(Some(request.fingerprint()));
+
+        // 3. Reservar el monto en la original; falla si otro reembolso se adelantó.
+        if self
+            .transaction_repo
+            .reserve_refund(original_id, amount, original.refunded_amount())
+            .await?
+            .is_none()
+        {
+            return Err(TransactionError::InvalidState(format!(
+                "La transacción {} cambió durante el reembolso; vuelva a intentarlo",
+                original_id
+            )));
+        }
+
+        // 4. Registrar el hijo como PENDING y mover los fondos de vuelta.
+        let saved_refund = match self
+            .transaction_repo
+            .save(refund, self.idempotency_ttl)
+            .await
+        {
+            Ok(saved) => saved,
+            Err(e) => {
+                let _ = self
+                    .transaction_repo
+                    .release_refund(original_id, amount)
+                    .await;
+                // Un reembolso concurrente con la misma clave se guardó primero.
+                if let TransactionError::IdempotencyError(correlation_id) = e {
+                    return find_unexpired(
+                        self.transaction_repo.as_ref(),
+                        correlation_id,
+                        self.idempotency_ttl,
+                    )
+                    .await?
+                    .map(|existing| replay(existing, &request))
+                    .unwrap_or(Err(e));
+                }
+                return Err(TransactionError::RepositoryError(format!(
+                    "DB Save Error: {}",
+                    e
+                )));
+            }
+        };
+        record_event(
+            self.event_repo.as_ref(),
+            TransactionEvent::created(&saved_refund, EventActor::Api),
+        )
+        .await;
+
+        let result = self.saga_executor.execute(&saved_refund).await;
+
+        // 5. Estado final del hijo y efecto sobre la original. Una saga interrumpida deja el
+        // reembolso PENDING (con su monto reservado) hasta que el job de reintentos la termine.
+        let mut finished_refund = saved_refund;
+        let previous = match result {
+            Err(TransactionError::SagaIncomplete(id)) => {
+                return Err(TransactionError::SagaIncomplete(id))
+            }
+            Ok(true) => finished_refund.update_status(TransactionStatus::COMPLETED)?,
+            _ => finished_refund.update_status(TransactionStatus::FAILED)?,
+        };
+        let finished_refund = self
+            .transaction_repo
+            .update(finished_refund, previous)
+            .await
+            .map_err(|e| match e {
+                TransactionError::InvalidState(_) => e,
+                e => TransactionError::RepositoryError(format!("DB Commit Error: {}", e)),
+            })?;
+        record_event(
+            self.event_repo.as_ref(),
+            TransactionEvent::transition(&finished_refund, previous, EventActor::Api)
+                .with_gateway_result(&result),
+        )
+        .await;
+        settle_refund(
+            self.transaction_repo.as_ref(),
+            self.event_repo.as_ref(),
+            &finished_refund,
+            EventActor::Api,
+        )
+        .await?;
+
+        match result {
+            Ok(true) => Ok(finished_refund),
+            other => Err(rejection_error(other)),
+        }
+    }
+}
+
+/// Solicitud de reembolso recibida, expresada como el resumen de `existing` (la transacción
+/// que ya usa su `correlation_id`) para poder comparar sus huellas.
+///
+/// Sólo `original_id`, `amount` y `client_id` vienen del cliente; sin `amount` (reembolso
+/// total) se toma lo que `existing` reembolsó, porque lo pendiente ya cambió desde entonces.
+fn received_refund(
+    existing: &Transaction,
+    original_id: TransactionId,
+    amount: Option<Decimal>,
+    client_id: Option<String>,
+) -> RequestSummary {
+    let mut request = RequestSummary::of(existing);
+    request.refund_of = Some(original_id);
+    if let Some(amount) = amount {
+        request.amount = amount.normalize();
+    }
+    request.client_id = client_id;
+    request
+}
+
+/// Intentos de asentar la original ante cambios de estado concurrentes.
+const SETTLE_ATTEMPTS: u32 = 3;
+
+/// Refleja en la transacción original el estado final de uno de sus reembolsos.
+///
+/// - Reembolso COMPLETED: si ya no queda nada por reembolsar, la original pasa a REVERSED.
+/// - Reembolso FAILED: se libera el monto reservado y, si la original estaba REVERSED,
+///   vuelve a COMPLETED.
+///
+/// No hace nada con transacciones que no son reembolsos o que siguen PENDING. Lo usan este
+/// caso de uso y el job de reintentos, que cierra los reembolsos que quedaron pendientes.
+pub(crate) async fn settle_refund(
+    transaction_repo: &dyn TransactionRepository,
+    event_repo: &dyn TransactionEventRepository,
+    refund: &Transaction,
+    actor: EventActor,
+) -> Result<(), TransactionError> {
+    let Some(parent_id) = refund.parent_transaction_id() else {
+        return Ok(());
+    };
+
+    match refund.status() {
+        TransactionStatus::COMPLETED => {}
+        TransactionStatus::FAILED => {
+            // El hijo acredita en la original exactamente lo reembolsado.
+            transaction_repo
+                .release_refund(parent_id, refund.destination_amount())
+                .await?;
+        }
+        _ => return Ok(()),
+    }
+
+    // Otro reembolso de la misma original puede asentarse a la vez: si el compare-and-set
+    // falla, releemos la original y volvemos a decidir.
+    let mut attempts = 0;
+    loop {
+        let Some(mut parent) = transaction_repo.find_by_id(parent_id).await? else {
+            return Ok(());
+        };
+        let fully_refunded = parent.refundable_amount().is_zero();
+        let next_status = match parent.status() {
+            TransactionStatus::COMPLETED if fully_refunded => TransactionStatus::REVERSED,
+            TransactionStatus::REVERSED if !fully_refunded => TransactionStatus::COMPLETED,
+            _ => return Ok(()),
+        };
+        let previous = parent.update_status(next_status)?;
+
+        attempts += 1;
+        match transaction_repo.update(parent, previous).await {
+            Ok(parent) => {
+                let event =
+                    TransactionEvent::transition(&parent, previous, actor).with_gateway_message(
+                        format!("Reembolso {} {:?}", refund.id(), refund.status()),
+                    );
+                record_event(event_repo, event).await;
+                return Ok(());
+            }
+            Err(TransactionError::InvalidState(_)) if attempts < SETTLE_ATTEMPTS => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{TransactionDetails, TransactionType};
+    use crate::domain::saga::SagaStep;
+    use crate::domain::types::WalletId;
+    use crate::test_support::{
+        compensations, events, sagas, MockTransactionRepositoryImpl, MockWalletGatewayImpl,
+    };
+    use chrono::Utc;
+    use mockall::predicate::*;
+
+    fn completed_transfer(amount: i64, refunded: i64) -> Transaction {
+        Transaction::reconstitute(
+            TransactionId::new(),
+            Some(WalletId::new()),
+            Some(WalletId::new()),
+            None,
+            Decimal::from(amount),
+            TransactionStatus::COMPLETED,
+            TransactionType::TRANSFER,
+            Utc::now(),
+            Uuid::new_v4(),
+            None,
+            None,
+            Decimal::from(refunded),
+            TransactionDetails::default(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_full_refund_marks_original_reversed() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        let original = completed_transfer(100, 30);
+        let original_id = original.id();
+        // Tras la reserva la original queda con los 100 reembolsados.
+        let refunded = Transaction::reconstitute(
+            original_id,
+            original.source_wallet_id(),
+            original.destination_wallet_id(),
+            None,
+            Decimal::from(100),
+            TransactionStatus::COMPLETED,
+            TransactionType::TRANSFER,
+            original.created_at(),
+            original.correlation_id(),
+            None,
+            None,
+            Decimal::from(100),
+            TransactionDetails::default(),
+        )
+        .unwrap();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        let mut reads = vec![refunded.clone(), original.clone()];
+        mock_repo
+            .expect_find_by_id()
+            .times(2)
+            .returning(move |_| Ok(reads.pop()));
+        // Sin monto explícito se reembolsa todo lo pendiente (70).
+        mock_repo
+            .expect_reserve_refund()
+            .with(
+                eq(original_id),
+                eq(Decimal::from(70)),
+                eq(Decimal::from(30)),
+            )
+            .times(1)
+            .returning(move |_, _, _| Ok(Some(refunded.clone())));
+        mock_repo.expect_save().times(1).returning(|tx, _| Ok(tx));
+        mock_gateway
+            .expect_execute_transfer()
+            .withf(|debit: &SagaStep, credit: &SagaStep| {
+                debit.amount == Decimal::from(-70) && credit.amount == Decimal::from(70)
+            })
+            .times(1)
+            .returning(|_, _| Ok(true));
+        mock_repo
+            .expect_update()
+            .withf(move |tx: &Transaction, expected: &TransactionStatus| {
+                tx.parent_transaction_id() == Some(original_id)
+                    && tx.status() == TransactionStatus::COMPLETED
+                    && *expected == TransactionStatus::PENDING
+            })
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+        mock_repo
+            .expect_update()
+            .withf(move |tx: &Transaction, expected: &TransactionStatus| {
+                tx.id() == original_id
+                    && tx.status() == TransactionStatus::REVERSED
+                    && *expected == TransactionStatus::COMPLETED
+            })
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+
+        let use_case = RefundTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let refund = use_case
+            .execute(original_id, None, Uuid::new_v4(), None)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(refund.status(), TransactionStatus::COMPLETED);
+    }
+
+    #[tokio::test]
+    async fn test_failed_refund_releases_reserved_amount() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        let original = completed_transfer(100, 0);
+        let original_id = original.id();
+        let dest = original.destination_wallet_id().unwrap();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        let stored = original.clone();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(stored.clone())));
+        mock_repo
+            .expect_reserve_refund()
+            .returning(move |_, _, _| Ok(Some(original.clone())));
+        mock_repo.expect_save().returning(|tx, _| Ok(tx));
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(move |_, _| Err(TransactionError::InsufficientFunds(dest)));
+        mock_repo
+            .expect_update()
+            .withf(|tx: &Transaction, _: &TransactionStatus| {
+                tx.status() == TransactionStatus::FAILED
+            })
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+        mock_repo
+            .expect_release_refund()
+            .with(eq(original_id), eq(Decimal::from(25)))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let use_case = RefundTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_gateway),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(original_id, Some(Decimal::from(25)), Uuid::new_v4(), None)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InsufficientFunds(dest)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retried_refund_is_replayed_unless_the_amount_changed() {
+        let original = completed_transfer(100, 0);
+        let original_id = original.id();
+        let correlation_id = Uuid::new_v4();
+        let previous = original
+            .refund(Decimal::from(40), correlation_id, 0)
+            .unwrap();
+        let fingerprint = RequestSummary::of(&previous).fingerprint();
+        let previous = previous.with_request_fingerprint(Some(fingerprint));
+        let previous_id = previous.id();
+
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_by_correlation_id()
+            .with(eq(correlation_id))
+            .returning(move |_| Ok(Some(previous.clone())));
+        mock_repo.expect_find_by_id().never();
+        mock_repo.expect_save().never();
+
+        let use_case = RefundTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockWalletGatewayImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // El mismo monto, o el reembolso total que lo originó, es un reintento.
+        for amount in [Some(Decimal::new(4000, 2)), None] {
+            let replayed = use_case
+                .execute(original_id, amount, correlation_id, None)
+                .await
+                .unwrap();
+            assert_eq!(replayed.id(), previous_id);
+        }
+        let result = use_case
+            .execute(original_id, Some(Decimal::from(50)), correlation_id, None)
+            .await;
+        match result {
+            Err(TransactionError::IdempotencyConflict {
+                original, received, ..
+            }) => {
+                assert_eq!(original.amount, Decimal::from(40));
+                assert_eq!(received.amount, Decimal::from(50));
+                assert_eq!(received.refund_of, Some(original_id));
+            }
+            other => panic!("expected IdempotencyConflict, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refund_over_remaining_amount_is_rejected() {
+        // Arrange
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        let original = completed_transfer(100, 90);
+        let original_id = original.id();
+
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(|_| Ok(None));
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(original.clone())));
+        mock_repo.expect_reserve_refund().never();
+        mock_repo.expect_save().never();
+
+        let use_case = RefundTransactionUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockWalletGatewayImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        );
+
+        // Act
+        let result = use_case
+            .execute(original_id, Some(Decimal::from(20)), Uuid::new_v4(), None)
+            .await;
+
+        // Assert
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::RefundExceeded(original_id, Decimal::from(10))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::domain::{
+    compensation::{CompensationStatus, PendingCompensation},
+    error::TransactionError,
+    gateways::WalletGateway,
+    repository::{CompensationRepository, SagaRepository},
+};
+use crate::use_cases::saga_executor::SagaExecutor;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Caso de uso administrativo para revisar las compensaciones de saga que no se pudieron
+/// aplicar.
+///
+/// Las compensaciones suspendidas (cuenta de suspenso) retienen fondos en el Wallet Service
+/// hasta que un operador las resuelve a mano o las vuelve a poner en cola.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::review_compensations::ReviewCompensationsUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = ReviewCompensationsUseCase::new(compensation_repo, saga_repo, wallet_gateway);
+/// let suspended = use_case.list(Some(CompensationStatus::SUSPENDED)).await?;
+/// ```
+pub struct ReviewCompensationsUseCase {
+    compensation_repo: Arc<dyn CompensationRepository>,
+    saga_executor: SagaExecutor,
+}
+
+impl ReviewCompensationsUseCase {
+    /// Construye una nueva instancia de `ReviewCompensationsUseCase`.
+    pub fn new(
+        compensation_repo: Arc<dyn CompensationRepository>,
+        saga_repo: Arc<dyn SagaRepository>,
+        wallet_gateway: Arc<dyn WalletGateway>,
+    ) -> Self {
+        Self {
+            saga_executor: SagaExecutor::new(saga_repo, compensation_repo.clone(), wallet_gateway),
+            compensation_repo,
+        }
+    }
+
+    /// Lista las compensaciones en `status`, o todas si se omite.
+    #[tracing::instrument(name = "ReviewCompensationsUseCase::list", skip(self))]
+    pub async fn list(
+        &self,
+        status: Option<CompensationStatus>,
+    ) -> Result<Vec<PendingCompensation>, TransactionError> {
+        self.compensation_repo.find_by_status(status).await
+    }
+
+    /// Retorna la compensación `id` o `TransactionError::CompensationNotFound`.
+    #[tracing::instrument(name = "ReviewCompensationsUseCase::get", skip(self))]
+    pub async fn get(&self, id: Uuid) -> Result<PendingCompensation, TransactionError> {
+        self.compensation_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(TransactionError::CompensationNotFound(id))
+    }
+
+    /// Da por resuelta una compensación que un operador liberó por fuera del sistema.
+    ///
+    /// La nota es obligatoria: queda como registro de qué se hizo con los fondos retenidos.
+    /// La pata se marca compensada en la saga para que la recuperación no la reintente.
+    #[tracing::instrument(name = "ReviewCompensationsUseCase::resolve", skip(self))]
+    pub async fn resolve(
+        &self,
+        id: Uuid,
+        note: &str,
+    ) -> Result<PendingCompensation, TransactionError> {
+        let note = note.trim();
+        if note.is_empty() {
+            return Err(TransactionError::ValidationError(
+                "La nota de resolución es obligatoria".into(),
+            ));
+        }
+
+        let mut compensation = self.get(id).await?;
+        compensation.resolve(note.to_string())?;
+        self.saga_executor.close_compensation(&compensation).await?;
+        self.compensation_repo.update(&compensation).await?;
+        Ok(compensation)
+    }
+
+    /// Vuelve a poner en cola una compensación para que el job la reintente de inmediato,
+    /// con los intentos desde cero.
+    #[tracing::instrument(name = "ReviewCompensationsUseCase::retry", skip(self))]
+    pub async fn retry(&self, id: Uuid) -> Result<PendingCompensation, TransactionError> {
+        let mut compensation = self.get(id).await?;
+        compensation.requeue()?;
+        self.compensation_repo.update(&compensation).await?;
+        Ok(compensation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Transaction;
+    use crate::domain::saga::{Saga, SagaStep, SagaStepStatus};
+    use crate::domain::types::WalletId;
+    use crate::test_support::{
+        MockCompensationRepositoryImpl, MockSagaRepositoryImpl, MockWalletGatewayImpl,
+    };
+    use chrono::Utc;
+    use mockall::predicate::function;
+    use rust_decimal::Decimal;
+
+    fn suspended() -> PendingCompensation {
+        let tx =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+        let mut compensation = PendingCompensation::new(
+            &Saga::plan(&tx).steps[0],
+            &TransactionError::GatewayError("unavailable".into()),
+        );
+        compensation.status = CompensationStatus::SUSPENDED;
+        compensation
+    }
+
+    #[tokio::test]
+    async fn test_resolve_requires_note_and_closes_saga_step() {
+        let compensation = suspended();
+        let id = compensation.id;
+        let mut mock_compensations = MockCompensationRepositoryImpl::new();
+        mock_compensations
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(compensation.clone())));
+        mock_compensations
+            .expect_update()
+            .with(function(|c: &PendingCompensation| {
+                c.status == CompensationStatus::RESOLVED
+                    && c.resolution_note.as_deref() == Some("Liberada desde el backoffice")
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut mock_sagas = MockSagaRepositoryImpl::new();
+        mock_sagas
+            .expect_update_step()
+            .with(function(|step: &SagaStep| {
+                step.status == SagaStepStatus::COMPENSATED
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let use_case = ReviewCompensationsUseCase::new(
+            Arc::new(mock_compensations),
+            Arc::new(mock_sagas),
+            Arc::new(MockWalletGatewayImpl::new()),
+        );
+
+        assert!(matches!(
+            use_case.resolve(id, "  ").await,
+            Err(TransactionError::ValidationError(_))
+        ));
+        let resolved = use_case
+            .resolve(id, "Liberada desde el backoffice")
+            .await
+            .unwrap();
+        assert_eq!(resolved.status, CompensationStatus::RESOLVED);
+    }
+
+    #[tokio::test]
+    async fn test_retry_requeues_suspended_compensation() {
+        let compensation = suspended();
+        let id = compensation.id;
+        let mut mock_compensations = MockCompensationRepositoryImpl::new();
+        mock_compensations
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(compensation.clone())));
+        mock_compensations
+            .expect_update()
+            .with(function(|c: &PendingCompensation| {
+                c.status == CompensationStatus::PENDING && c.attempts == 0
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let use_case = ReviewCompensationsUseCase::new(
+            Arc::new(mock_compensations),
+            Arc::new(MockSagaRepositoryImpl::new()),
+            Arc::new(MockWalletGatewayImpl::new()),
+        );
+
+        let requeued = use_case.retry(id).await.unwrap();
+        assert!(requeued.next_attempt_at <= Utc::now());
+    }
+}
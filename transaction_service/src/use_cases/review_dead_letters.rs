@@ -0,0 +1,299 @@
+use crate::domain::{
+    entities::{Transaction, TransactionStatus},
+    error::TransactionError,
+    events::{EventActor, TransactionEvent},
+    history::HistoryCursor,
+    repository::{TransactionEventRepository, TransactionRepository},
+    retry::RetryState,
+    types::TransactionId,
+};
+use crate::use_cases::get_wallet_history::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::use_cases::process_transaction::record_event;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Transacción en `DEAD_LETTER`, con los intentos del job de reintentos.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetter {
+    pub transaction: Transaction,
+    pub retry: RetryState,
+    /// Historial de estados; sólo se incluye al inspeccionar una transacción.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<TransactionEvent>,
+}
+
+impl DeadLetter {
+    fn new(transaction: Transaction, events: Vec<TransactionEvent>) -> Self {
+        Self {
+            retry: transaction.retry_state().clone(),
+            transaction,
+            events,
+        }
+    }
+}
+
+/// Página de transacciones en `DEAD_LETTER`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetterPage {
+    pub dead_letters: Vec<DeadLetter>,
+    /// Token para pedir la página siguiente; `None` en la última página.
+    pub next_cursor: Option<String>,
+}
+
+/// Caso de uso administrativo para revisar las transacciones que el job de reintentos envió a
+/// `DEAD_LETTER` y reenviarlas.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::review_dead_letters::ReviewDeadLettersUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = ReviewDeadLettersUseCase::new(transaction_repo, event_repo);
+/// let page = use_case.list(None, Some(50)).await?;
+/// ```
+#[derive(Clone)]
+pub struct ReviewDeadLettersUseCase {
+    transaction_repo: Arc<dyn TransactionRepository>,
+    event_repo: Arc<dyn TransactionEventRepository>,
+}
+
+impl ReviewDeadLettersUseCase {
+    /// Construye una nueva instancia de `ReviewDeadLettersUseCase`.
+    pub fn new(
+        transaction_repo: Arc<dyn TransactionRepository>,
+        event_repo: Arc<dyn TransactionEventRepository>,
+    ) -> Self {
+        Self {
+            transaction_repo,
+            event_repo,
+        }
+    }
+
+    /// Lista una página de transacciones en `DEAD_LETTER`, las más recientes primero.
+    ///
+    /// `cursor` es el `next_cursor` de la página anterior y `page_size` se acota a
+    /// `1..=MAX_PAGE_SIZE` (por defecto `DEFAULT_PAGE_SIZE`). Falla con
+    /// `TransactionError::ValidationError` si el cursor no es válido.
+    #[tracing::instrument(name = "ReviewDeadLettersUseCase::list", skip(self))]
+    pub async fn list(
+        &self,
+        cursor: Option<String>,
+        page_size: Option<i64>,
+    ) -> Result<DeadLetterPage, TransactionError> {
+        let after = cursor.as_deref().map(HistoryCursor::decode).transpose()?;
+        let limit = page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        // Pedimos una transacción de más para saber si existe una página siguiente.
+        let mut transactions = self
+            .transaction_repo
+            .find_dead_lettered(after, limit + 1)
+            .await?;
+
+        let next_cursor = if transactions.len() as i64 > limit {
+            transactions.truncate(limit as usize);
+            transactions
+                .last()
+                .map(|last| HistoryCursor::after(last).encode())
+        } else {
+            None
+        };
+
+        Ok(DeadLetterPage {
+            dead_letters: transactions
+                .into_iter()
+                .map(|tx| DeadLetter::new(tx, Vec::new()))
+                .collect(),
+            next_cursor,
+        })
+    }
+
+    /// Retorna la transacción `id` con sus intentos y su historial de estados.
+    ///
+    /// Falla con `TransactionError::NotFound` si no existe y con
+    /// `TransactionError::InvalidState` si no está en `DEAD_LETTER`.
+    #[tracing::instrument(name = "ReviewDeadLettersUseCase::get", skip(self))]
+    pub async fn get(&self, id: TransactionId) -> Result<DeadLetter, TransactionError> {
+        let transaction = self.find_dead_lettered(id).await?;
+        let events = self.event_repo.find_by_transaction_id(id).await?;
+        Ok(DeadLetter::new(transaction, events))
+    }
+
+    /// Devuelve la transacción al job de reintentos, con los intentos desde cero.
+    ///
+    /// El job la retoma en su siguiente pasada y la concilia con el Wallet Service antes de
+    /// reenviar nada, igual que a cualquier transacción PENDING.
+    #[tracing::instrument(name = "ReviewDeadLettersUseCase::redrive", skip(self))]
+    pub async fn redrive(&self, id: TransactionId) -> Result<Transaction, TransactionError> {
+        let mut transaction = self.find_dead_lettered(id).await?;
+        let previous = transaction.requeue()?;
+        let transaction = self.transaction_repo.update(transaction, previous).await?;
+
+        let event = TransactionEvent::transition(&transaction, previous, EventActor::Admin)
+            .with_gateway_message("Reenviada al job de reintentos");
+        record_event(self.event_repo.as_ref(), event).await;
+        Ok(transaction)
+    }
+
+    async fn find_dead_lettered(&self, id: TransactionId) -> Result<Transaction, TransactionError> {
+        let transaction = self
+            .transaction_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(TransactionError::NotFound(id))?;
+        if transaction.status() != TransactionStatus::DEAD_LETTER {
+            return Err(TransactionError::InvalidState(format!(
+                "La transacción {} está {:?}, no en DEAD_LETTER",
+                id,
+                transaction.status()
+            )));
+        }
+        Ok(transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::retry::RetryPolicy;
+    use crate::domain::types::WalletId;
+    use crate::test_support::{MockTransactionEventRepositoryImpl, MockTransactionRepositoryImpl};
+    use mockall::predicate::{eq, function};
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    /// Transacción que agotó su único intento.
+    fn dead_lettered() -> Transaction {
+        let mut tx =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+        let policy = RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        };
+        tx.record_retry_failure(&TransactionError::GatewayError("timeout".into()), &policy)
+            .unwrap();
+        tx
+    }
+
+    #[tokio::test]
+    async fn test_get_includes_retry_state_and_rejects_live_transactions() {
+        let tx = dead_lettered();
+        let id = tx.id();
+        let pending =
+            Transaction::new(None, WalletId::new(), Decimal::from(10), Uuid::new_v4()).unwrap();
+        let pending_id = pending.id();
+
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(id))
+            .returning(move |_| Ok(Some(tx.clone())));
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(pending_id))
+            .returning(move |_| Ok(Some(pending.clone())));
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events
+            .expect_find_by_transaction_id()
+            .returning(|_| Ok(Vec::new()));
+
+        let use_case = ReviewDeadLettersUseCase::new(Arc::new(mock_repo), Arc::new(mock_events));
+
+        let dead_letter = use_case.get(id).await.unwrap();
+        assert_eq!(dead_letter.retry.attempts, 1);
+        assert!(dead_letter.retry.last_error.unwrap().contains("timeout"));
+        assert!(matches!(
+            use_case.get(pending_id).await,
+            Err(TransactionError::InvalidState(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_redrive_requeues_transaction_as_pending() {
+        let tx = dead_lettered();
+        let id = tx.id();
+
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(tx.clone())));
+        mock_repo
+            .expect_update()
+            .with(
+                function(|tx: &Transaction| {
+                    tx.status() == TransactionStatus::PENDING && tx.retry_state().attempts == 0
+                }),
+                eq(TransactionStatus::DEAD_LETTER),
+            )
+            .times(1)
+            .returning(|tx, _| Ok(tx));
+        let mut mock_events = MockTransactionEventRepositoryImpl::new();
+        mock_events
+            .expect_record()
+            .withf(|event: &TransactionEvent| {
+                event.from_status == Some(TransactionStatus::DEAD_LETTER)
+                    && event.to_status == TransactionStatus::PENDING
+                    && event.actor == EventActor::Admin
+            })
+            .times(1)
+            .returning(Ok);
+
+        let use_case = ReviewDeadLettersUseCase::new(Arc::new(mock_repo), Arc::new(mock_events));
+
+        let requeued = use_case.redrive(id).await.unwrap();
+        assert_eq!(requeued.status(), TransactionStatus::PENDING);
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_with_cursor_and_caps_page_size() {
+        let transactions: Vec<Transaction> = (0..3).map(|_| dead_lettered()).collect();
+        // El token guarda microsegundos: comparamos contra el cursor ya decodificado.
+        let last_of_page =
+            HistoryCursor::decode(&HistoryCursor::after(&transactions[1]).encode()).unwrap();
+
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_dead_lettered()
+            .with(eq(None), eq(3))
+            .times(1)
+            .returning(move |_, _| Ok(transactions.clone()));
+        mock_repo
+            .expect_find_dead_lettered()
+            .with(eq(Some(last_of_page)), eq(MAX_PAGE_SIZE + 1))
+            .times(1)
+            .returning(|_, _| Ok(Vec::new()));
+
+        let use_case = ReviewDeadLettersUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTransactionEventRepositoryImpl::new()),
+        );
+
+        let page = use_case.list(None, Some(2)).await.unwrap();
+        assert_eq!(page.dead_letters.len(), 2);
+        let next_cursor = page.next_cursor.unwrap();
+
+        let last_page = use_case
+            .list(Some(next_cursor), Some(10_000))
+            .await
+            .unwrap();
+        assert!(last_page.dead_letters.is_empty());
+        assert!(last_page.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_rejects_invalid_cursor() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo.expect_find_dead_lettered().never();
+
+        let use_case = ReviewDeadLettersUseCase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTransactionEventRepositoryImpl::new()),
+        );
+
+        assert!(matches!(
+            use_case.list(Some("not-a-cursor".into()), None).await,
+            Err(TransactionError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,606 @@
+use crate::domain::{
+    compensation::PendingCompensation,
+    entities::Transaction,
+    error::TransactionError,
+    gateways::WalletGateway,
+    repository::{CompensationRepository, SagaRepository},
+    saga::{Saga, SagaStep, SagaStepStatus},
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Orquestador de la saga de una transacción en el Wallet Service.
+///
+/// Los depósitos y retiros mueven una pata en dos fases: primero se reserva y después se
+/// captura. Las transferencias mueven sus dos patas (débito y crédito) en una sola operación
+/// atómica del Wallet Service. Cada paso queda en el registro de sagas antes de actuar, de
+/// modo que si el proceso muere a mitad de camino `recover` sabe qué patas quedaron
+/// retenidas y puede completarlas o liberarlas.
+///
+/// Una liberación que falla queda registrada como compensación pendiente, que el job de
+/// compensaciones reintenta con espera creciente.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::saga_executor::SagaExecutor;
+///
+/// let executor = SagaExecutor::new(saga_repo, compensation_repo, wallet_gateway);
+/// let applied = executor.execute(&transaction).await?;
+/// ```
+pub struct SagaExecutor {
+    saga_repo: Arc<dyn SagaRepository>,
+    compensation_repo: Arc<dyn CompensationRepository>,
+    wallet_gateway: Arc<dyn WalletGateway>,
+}
+
+impl SagaExecutor {
+    /// Antigüedad mínima (sin avances) de una saga para que `recover` la tome.
+    pub const RECOVERY_MIN_AGE_MINUTES: i64 = 5;
+
+    /// Duración por defecto del lease de `recover` sobre las sagas reclamadas.
+    pub const DEFAULT_RECOVERY_LEASE_MINUTES: i64 = 5;
+
+    pub fn new(
+        saga_repo: Arc<dyn SagaRepository>,
+        compensation_repo: Arc<dyn CompensationRepository>,
+        wallet_gateway: Arc<dyn WalletGateway>,
+    ) -> Self {
+        Self {
+            saga_repo,
+            compensation_repo,
+            wallet_gateway,
+        }
+    }
+
+    /// Ejecuta la saga de `transaction`, o la retoma desde donde quedó si ya existía.
+    ///
+    /// # Retornos
+    ///
+    /// `Ok(true)` si todas las patas quedaron capturadas. Si el Wallet Service rechaza una
+    /// pata o falla la comunicación al reservar, se liberan las patas ya reservadas y se
+    /// retorna `Ok(false)` o el error correspondiente. Si falla una captura, las patas
+    /// restantes siguen reservadas y se retorna `TransactionError::SagaIncomplete`: la
+    /// transacción no debe cerrarse, ya que un reintento (o `recover`) la completará. Lo mismo
+    /// ocurre si una transferencia atómica no obtiene respuesta.
+    #[tracing::instrument(name = "SagaExecutor::execute", skip_all, fields(transaction_id = %transaction.id()))]
+    pub async fn execute(&self, transaction: &Transaction) -> Result<bool, TransactionError> {
+        let mut saga = self.saga_repo.start(Saga::plan(transaction)).await?;
+
+        // Un intento anterior ya abortó la saga: sólo queda terminar de compensarla.
+        if saga.is_aborted() {
+            let _ = self.compensate(&mut saga).await;
+            return Ok(false);
+        }
+
+        // Las transferencias se ejecutan en una sola operación atómica del Wallet Service.
+        if saga.runs_as_transfer() {
+            return self.transfer(&mut saga).await;
+        }
+
+        // Fase 1: reservamos (hold) cada pata pendiente, en orden.
+        for index in 0..saga.steps.len() {
+            if !matches!(
+                saga.steps[index].status,
+                SagaStepStatus::PLANNED | SagaStepStatus::SENT
+            ) {
+                continue;
+            }
+
+            self.advance(&mut saga.steps[index], SagaStepStatus::SENT)
+                .await?;
+            let result = self
+                .wallet_gateway
+                .reserve_leg(transaction, &saga.steps[index])
+                .await;
+
+            match result {
+                Ok(true) => {
+                    self.advance(&mut saga.steps[index], SagaStepStatus::ACKNOWLEDGED)
+                        .await?;
+                }
+                Ok(false) => {
+                    self.advance(&mut saga.steps[index], SagaStepStatus::REJECTED)
+                        .await?;
+                    let _ = self.compensate(&mut saga).await;
+                    return Ok(false);
+                }
+                Err(e) if e.is_rejection() => {
+                    self.advance(&mut saga.steps[index], SagaStepStatus::REJECTED)
+                        .await?;
+                    let _ = self.compensate(&mut saga).await;
+                    return Err(e);
+                }
+                Err(e) => {
+                    // No sabemos si la reserva llegó: la pata queda SENT y se libera también.
+                    let _ = self.compensate(&mut saga).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        // Fase 2: todas las patas quedaron reservadas; las capturamos.
+        self.commit(&mut saga).await?;
+
+        info!("Todos los movimientos de la transacción procesados exitosamente");
+        Ok(true)
+    }
+
+    /// Completa o compensa las sagas que quedaron a medio camino (p. ej. por un reinicio).
+    ///
+    /// Las sagas con todas sus patas reservadas (o ya capturadas) se completan y las
+    /// transferencias atómicas sin respuesta se reenvían; el resto se compensa liberando lo
+    /// retenido. El estado de la transacción lo concilia después el job
+    /// de reintentos a partir del estado de las patas en el Wallet Service.
+    ///
+    /// Sólo toma las sagas que nadie avanza hace `RECOVERY_MIN_AGE_MINUTES` y las reclama
+    /// por `owner` durante `lease`, así que puede correr en todas las réplicas sin compensar
+    /// sagas que otra aún ejecuta. Las más recientes las retoma el job de reintentos. Cada
+    /// saga se libera en cuanto se termina de procesar.
+    pub async fn recover(&self, owner: &str, lease: Duration) -> Result<(), TransactionError> {
+        let cutoff = Utc::now() - Duration::minutes(Self::RECOVERY_MIN_AGE_MINUTES);
+        let sagas = self
+            .saga_repo
+            .claim_unfinished(cutoff, owner, lease)
+            .await?;
+        if sagas.is_empty() {
+            return Ok(());
+        }
+
+        info!("Found {} unfinished sagas. Recovering...", sagas.len());
+
+        for mut saga in sagas {
+            let result = if saga.can_commit() {
+                info!("Completing saga of transaction {}", saga.transaction_id);
+                self.commit(&mut saga).await
+            } else if saga.is_transfer_in_flight() {
+                // No sabemos si la transferencia se aplicó, pero reenviarla es idempotente.
+                info!("Resending transfer of transaction {}", saga.transaction_id);
+                self.transfer(&mut saga).await.map(|_| ())
+            } else {
+                info!("Compensating saga of transaction {}", saga.transaction_id);
+                self.compensate(&mut saga).await
+            };
+
+            if let Err(e) = result {
+                error!(
+                    "Saga of transaction {} is still unfinished: {:?}",
+                    saga.transaction_id, e
+                );
+            }
+
+            // Sin el reclamo, el job de reintentos concilia la transacción en su siguiente
+            // pasada en lugar de esperar a que venza el lease.
+            if let Err(e) = self.saga_repo.release(saga.transaction_id, owner).await {
+                warn!(
+                    "Could not release saga of transaction {}; it stays claimed until its lease expires: {:?}",
+                    saga.transaction_id, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reintenta liberar la pata de una compensación pendiente y, si lo logra, la marca
+    /// compensada en el registro de la saga.
+    pub async fn retry_compensation(
+        &self,
+        compensation: &PendingCompensation,
+    ) -> Result<(), TransactionError> {
+        let mut step = compensation.step();
+        self.wallet_gateway.release_leg(&step).await?;
+        self.advance(&mut step, SagaStepStatus::COMPENSATED).await
+    }
+
+    /// Marca compensada la pata de una compensación que un operador resolvió a mano, para que
+    /// la recuperación no vuelva a intentarla.
+    pub async fn close_compensation(
+        &self,
+        compensation: &PendingCompensation,
+    ) -> Result<(), TransactionError> {
+        self.advance(&mut compensation.step(), SagaStepStatus::COMPENSATED)
+            .await
+    }
+
+    /// Ejecuta las dos patas de una transferencia en una sola operación del Wallet Service.
+    ///
+    /// Ambas patas se marcan `SENT` antes de enviarla y terminan juntas: capturadas o
+    /// rechazadas. Si no hay respuesta quedan `SENT` y la saga queda incompleta, para que un
+    /// reintento (o `recover`) la reenvíe.
+    async fn transfer(&self, saga: &mut Saga) -> Result<bool, TransactionError> {
+        for step in saga.steps.iter_mut() {
+            if step.status != SagaStepStatus::SENT {
+                self.advance(step, SagaStepStatus::SENT).await?;
+            }
+        }
+
+        let result = self
+            .wallet_gateway
+            .execute_transfer(&saga.steps[0], &saga.steps[1])
+            .await;
+        let (status, outcome) = match result {
+            Ok(true) => (SagaStepStatus::CAPTURED, Ok(true)),
+            Ok(false) => (SagaStepStatus::REJECTED, Ok(false)),
+            Err(e) if e.is_rejection() => (SagaStepStatus::REJECTED, Err(e)),
+            Err(e) => {
+                error!(
+                    "Transfer of transaction {} got no answer: {:?}",
+                    saga.transaction_id, e
+                );
+                return Err(TransactionError::SagaIncomplete(saga.transaction_id));
+            }
+        };
+
+        for step in saga.steps.iter_mut() {
+            self.advance(step, status).await?;
+        }
+        outcome
+    }
+
+    /// Captura las patas reservadas. Si alguna falla la saga queda incompleta.
+    async fn commit(&self, saga: &mut Saga) -> Result<(), TransactionError> {
+        for step in saga.steps.iter_mut() {
+            if step.status != SagaStepStatus::ACKNOWLEDGED {
+                continue;
+            }
+
+            if let Err(e) = self.wallet_gateway.capture_leg(step).await {
+                error!(
+                    "Capture of leg {} of transaction {} failed: {:?}",
+                    step.leg, step.transaction_id, e
+                );
+                return Err(TransactionError::SagaIncomplete(step.transaction_id));
+            }
+            self.advance(step, SagaStepStatus::CAPTURED).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Libera, en orden inverso, las patas que pudieron quedar reservadas; las que nunca se
+    /// enviaron se marcan compensadas directamente.
+    ///
+    /// Sigue con las demás patas aunque una falle y retorna el último error: las patas que no
+    /// se pudieron liberar se registran como compensaciones pendientes.
+    async fn compensate(&self, saga: &mut Saga) -> Result<(), TransactionError> {
+        let mut outcome = Ok(());
+
+        for step in saga.steps.iter_mut().rev() {
+            match step.status {
+                SagaStepStatus::PLANNED => {}
+                SagaStepStatus::SENT | SagaStepStatus::ACKNOWLEDGED => {
+                    if let Err(e) = self.wallet_gateway.release_leg(step).await {
+                        warn!(
+                            "Release of leg {} of transaction {} failed: {:?}",
+                            step.leg, step.transaction_id, e
+                        );
+                        self.schedule_compensation(step, &e).await;
+                        outcome = Err(e);
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+
+            if let Err(e) = self.advance(step, SagaStepStatus::COMPENSATED).await {
+                outcome = Err(e);
+            }
+        }
+
+        outcome
+    }
+
+    /// Registra la liberación fallida de `step` para que el job la reintente.
+    async fn schedule_compensation(&self, step: &SagaStep, error: &TransactionError) {
+        let compensation = PendingCompensation::new(step, error);
+        if let Err(e) = self.compensation_repo.schedule(&compensation).await {
+            error!(
+                "Error crítico: no se pudo registrar la compensación pendiente de la pata {} de la transacción {}: {:?}",
+                step.leg, step.transaction_id, e
+            );
+        }
+    }
+
+    /// Pasa la pata a `status` y lo persiste.
+    async fn advance(
+        &self,
+        step: &mut SagaStep,
+        status: SagaStepStatus,
+    ) -> Result<(), TransactionError> {
+        step.mark(status);
+        self.saga_repo.update_step(step).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::compensation::CompensationStatus;
+    use crate::domain::types::WalletId;
+    use crate::test_support::{
+        MockCompensationRepositoryImpl, MockSagaRepositoryImpl, MockWalletGatewayImpl,
+    };
+    use mockall::predicate::function;
+    use rust_decimal::Decimal;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Registro de sagas que guarda cada transición persistida en `log`.
+    fn recording_repo(log: Arc<Mutex<Vec<(i16, SagaStepStatus)>>>) -> MockSagaRepositoryImpl {
+        let mut mock_repo = MockSagaRepositoryImpl::new();
+        mock_repo.expect_start().returning(Ok);
+        mock_repo.expect_update_step().returning(move |step| {
+            log.lock().unwrap().push((step.leg, step.status));
+            Ok(())
+        });
+        mock_repo
+    }
+
+    fn transfer() -> Transaction {
+        Transaction::new(
+            Some(WalletId::new()),
+            WalletId::new(),
+            Decimal::from(10),
+            Uuid::new_v4(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transfer_moves_both_legs_in_one_call() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_execute_transfer()
+            .with(
+                function(|debit: &SagaStep| debit.leg == 0 && debit.amount < Decimal::ZERO),
+                function(|credit: &SagaStep| credit.leg == 1 && credit.amount > Decimal::ZERO),
+            )
+            .times(1)
+            .returning(|_, _| Ok(true));
+        mock_gateway.expect_reserve_leg().never();
+        mock_gateway.expect_capture_leg().never();
+
+        let executor = SagaExecutor::new(
+            Arc::new(recording_repo(log.clone())),
+            Arc::new(MockCompensationRepositoryImpl::new()),
+            Arc::new(mock_gateway),
+        );
+        let result = executor.execute(&transfer()).await;
+
+        assert_eq!(result, Ok(true));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                (0, SagaStepStatus::SENT),
+                (1, SagaStepStatus::SENT),
+                (0, SagaStepStatus::CAPTURED),
+                (1, SagaStepStatus::CAPTURED),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_transfer_rejects_both_legs_without_releasing() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(|debit, _| Err(TransactionError::InsufficientFunds(debit.wallet_id)));
+        // El Wallet Service no aplicó ninguna pata: no hay nada retenido que liberar.
+        mock_gateway.expect_release_leg().never();
+
+        let executor = SagaExecutor::new(
+            Arc::new(recording_repo(log.clone())),
+            Arc::new(MockCompensationRepositoryImpl::new()),
+            Arc::new(mock_gateway),
+        );
+        let result = executor.execute(&transfer()).await;
+
+        assert!(matches!(
+            result,
+            Err(TransactionError::InsufficientFunds(_))
+        ));
+        assert_eq!(
+            log.lock().unwrap()[2..],
+            [(0, SagaStepStatus::REJECTED), (1, SagaStepStatus::REJECTED)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_transfer_stays_sent_and_is_not_released() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_execute_transfer()
+            .returning(|_, _| Err(TransactionError::GatewayError("timeout".into())));
+        mock_gateway.expect_release_leg().never();
+
+        let tx = transfer();
+        let executor = SagaExecutor::new(
+            Arc::new(recording_repo(log.clone())),
+            Arc::new(MockCompensationRepositoryImpl::new()),
+            Arc::new(mock_gateway),
+        );
+        let result = executor.execute(&tx).await;
+
+        assert_eq!(result, Err(TransactionError::SagaIncomplete(tx.id())));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![(0, SagaStepStatus::SENT), (1, SagaStepStatus::SENT)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_capture_leaves_saga_incomplete() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway.expect_reserve_leg().returning(|_, _| Ok(true));
+        mock_gateway
+            .expect_capture_leg()
+            .returning(|_| Err(TransactionError::GatewayError("timeout".into())));
+        mock_gateway.expect_release_leg().never();
+
+        let tx = Transaction::new_withdrawal(
+            WalletId::new(),
+            "bank:0042-123456".into(),
+            Decimal::from(10),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let executor = SagaExecutor::new(
+            Arc::new(recording_repo(log.clone())),
+            Arc::new(MockCompensationRepositoryImpl::new()),
+            Arc::new(mock_gateway),
+        );
+        let result = executor.execute(&tx).await;
+
+        assert_eq!(result, Err(TransactionError::SagaIncomplete(tx.id())));
+        assert_eq!(
+            log.lock().unwrap().last(),
+            Some(&(0, SagaStepStatus::ACKNOWLEDGED))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_completes_reserved_saga_and_compensates_partial_one() {
+        let mut reserved = Saga::plan(&transfer());
+        reserved.steps[0].mark(SagaStepStatus::CAPTURED);
+        reserved.steps[1].mark(SagaStepStatus::ACKNOWLEDGED);
+        let mut partial = Saga::plan(&transfer());
+        partial.steps[0].mark(SagaStepStatus::ACKNOWLEDGED);
+        partial.steps[1].mark(SagaStepStatus::SENT);
+        let mut in_flight = Saga::plan(&transfer());
+        in_flight.steps[0].mark(SagaStepStatus::SENT);
+        in_flight.steps[1].mark(SagaStepStatus::SENT);
+        let (reserved_id, partial_id, in_flight_id) = (
+            reserved.transaction_id,
+            partial.transaction_id,
+            in_flight.transaction_id,
+        );
+
+        let mut mock_repo = MockSagaRepositoryImpl::new();
+        mock_repo
+            .expect_claim_unfinished()
+            .returning(move |_, _, _| {
+                Ok(vec![reserved.clone(), partial.clone(), in_flight.clone()])
+            });
+        mock_repo.expect_update_step().returning(|_| Ok(()));
+        // Cada saga se libera tras procesarla, para que el job de reintentos la concilie.
+        mock_repo
+            .expect_release()
+            .withf(|_, owner| owner == "replica-a")
+            .times(3)
+            .returning(|_, _| Ok(()));
+
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_capture_leg()
+            .with(function(move |step: &SagaStep| {
+                step.transaction_id == reserved_id && step.leg == 1
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_gateway
+            .expect_release_leg()
+            .with(function(move |step: &SagaStep| {
+                step.transaction_id == partial_id
+            }))
+            .times(2)
+            .returning(|_| Ok(()));
+        // La transferencia sin respuesta se reenvía en lugar de liberarse.
+        mock_gateway
+            .expect_execute_transfer()
+            .withf(move |debit: &SagaStep, _: &SagaStep| debit.transaction_id == in_flight_id)
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let executor = SagaExecutor::new(
+            Arc::new(mock_repo),
+            Arc::new(MockCompensationRepositoryImpl::new()),
+            Arc::new(mock_gateway),
+        );
+        assert_eq!(
+            executor.recover("replica-a", Duration::minutes(5)).await,
+            Ok(())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_only_claims_stale_sagas_for_its_owner() {
+        let mut mock_repo = MockSagaRepositoryImpl::new();
+        mock_repo
+            .expect_claim_unfinished()
+            .withf(|older_than, owner, lease| {
+                *older_than
+                    <= Utc::now() - Duration::minutes(SagaExecutor::RECOVERY_MIN_AGE_MINUTES)
+                    && owner == "replica-a"
+                    && *lease == Duration::minutes(2)
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(Vec::new()));
+        mock_repo.expect_release().never();
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway.expect_release_leg().never();
+
+        let executor = SagaExecutor::new(
+            Arc::new(mock_repo),
+            Arc::new(MockCompensationRepositoryImpl::new()),
+            Arc::new(mock_gateway),
+        );
+        assert_eq!(
+            executor.recover("replica-a", Duration::minutes(2)).await,
+            Ok(())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_release_is_scheduled_as_pending_compensation() {
+        // Saga en dos fases que quedó con el débito reservado y el crédito sin respuesta.
+        let mut partial = Saga::plan(&transfer());
+        partial.steps[0].mark(SagaStepStatus::ACKNOWLEDGED);
+        partial.steps[1].mark(SagaStepStatus::REJECTED);
+        let partial_id = partial.transaction_id;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut mock_repo = recording_repo(log.clone());
+        mock_repo
+            .expect_claim_unfinished()
+            .returning(move |_, _, _| Ok(vec![partial.clone()]));
+        // Aunque la pata siga retenida, la saga se libera: la compensación queda a cargo del
+        // job de compensaciones.
+        mock_repo
+            .expect_release()
+            .withf(move |id, _| *id == partial_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut mock_gateway = MockWalletGatewayImpl::new();
+        mock_gateway
+            .expect_release_leg()
+            .returning(|_| Err(TransactionError::GatewayError("unavailable".into())));
+
+        let mut mock_compensations = MockCompensationRepositoryImpl::new();
+        mock_compensations
+            .expect_schedule()
+            .with(function(|c: &PendingCompensation| {
+                c.leg == 0 && c.status == CompensationStatus::PENDING && c.attempts == 1
+            }))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let executor = SagaExecutor::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_compensations),
+            Arc::new(mock_gateway),
+        );
+
+        // La recuperación sigue adelante; la pata retenida queda a cargo del job.
+        assert_eq!(
+            executor.recover("replica-a", Duration::minutes(5)).await,
+            Ok(())
+        );
+        assert!(log.lock().unwrap().is_empty());
+    }
+}
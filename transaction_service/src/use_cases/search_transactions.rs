@@ -0,0 +1,162 @@
+use crate::domain::{
+    entities::Transaction, error::TransactionError, repository::TransactionRepository,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Caso de uso para buscar transacciones por la referencia externa del cliente o por el
+/// `correlation_id` con que se crearon.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::search_transactions::SearchTransactionsUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = SearchTransactionsUseCase::new(transaction_repo);
+/// let payments = use_case.execute("INV-2024-001".into()).await?;
+/// ```
+#[derive(Clone)]
+pub struct SearchTransactionsUseCase {
+    transaction_repo: Arc<dyn TransactionRepository>,
+}
+
+impl SearchTransactionsUseCase {
+    pub fn new(transaction_repo: Arc<dyn TransactionRepository>) -> Self {
+        Self { transaction_repo }
+    }
+
+    /// Retorna las transacciones con `external_reference` (más recientes primero). La
+    /// referencia se compara tras recortar espacios, igual que al guardarla.
+    #[tracing::instrument(name = "SearchTransactionsUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        external_reference: String,
+    ) -> Result<Vec<Transaction>, TransactionError> {
+        let external_reference = external_reference.trim();
+        if external_reference.is_empty() {
+            return Err(TransactionError::ValidationError(
+                "La referencia externa no puede estar vacía".into(),
+            ));
+        }
+
+        self.transaction_repo
+            .find_by_external_reference(external_reference)
+            .await
+    }
+
+    /// Recupera la transacción creada con `correlation_id`, para que un cliente que perdió la
+    /// respuesta de `POST /transactions` conozca su id y su estado sin volver a enviarla.
+    ///
+    /// Sólo el cliente que la inició puede verla: sin `client_id` se retorna
+    /// `TransactionError::Unauthorized`, y si la transacción no existe o pertenece a otro
+    /// cliente, `TransactionError::CorrelationNotFound` (sin revelar cuál de los dos casos es).
+    /// Las transacciones creadas sin cliente (todas las anteriores a la columna `client_id`)
+    /// no tienen dueño a quien devolverlas: se retorna `TransactionError::ClientNotRecorded`.
+    ///
+    /// `client_id` se toma como una identidad ya autenticada (ver
+    /// [`CLIENT_ID_HEADER`](crate::api::http_routes::CLIENT_ID_HEADER)), no como credencial.
+    #[tracing::instrument(name = "SearchTransactionsUseCase::find_by_correlation_id", skip(self))]
+    pub async fn find_by_correlation_id(
+        &self,
+        correlation_id: Uuid,
+        client_id: Option<String>,
+    ) -> Result<Transaction, TransactionError> {
+        let client_id = client_id.ok_or_else(|| {
+            TransactionError::Unauthorized(
+                "Se requiere identificar al cliente para consultar por correlation_id".into(),
+            )
+        })?;
+
+        let transaction = self
+            .transaction_repo
+            .find_by_correlation_id(correlation_id)
+            .await?
+            .ok_or(TransactionError::CorrelationNotFound(correlation_id))?;
+        if transaction.client_id().is_none() {
+            return Err(TransactionError::ClientNotRecorded(correlation_id));
+        }
+        if !transaction.is_visible_to(&client_id) {
+            return Err(TransactionError::CorrelationNotFound(correlation_id));
+        }
+        Ok(transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::TransactionStatus;
+    use crate::domain::types::WalletId;
+    use crate::test_support::MockTransactionRepositoryImpl;
+    use rust_decimal::Decimal;
+
+    /// Repositorio con un depósito creado por `owner` (o sin cliente, si es `None`).
+    fn repo_with_deposit_of(owner: Option<&'static str>) -> Arc<MockTransactionRepositoryImpl> {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo
+            .expect_find_by_correlation_id()
+            .returning(move |correlation_id| {
+                Ok(Some(
+                    Transaction::new(None, WalletId::new(), Decimal::from(10), correlation_id)
+                        .unwrap()
+                        .with_client_id(owner.map(str::to_string)),
+                ))
+            });
+        Arc::new(mock_repo)
+    }
+
+    #[tokio::test]
+    async fn test_initiating_client_recovers_transaction_and_status() {
+        let use_case = SearchTransactionsUseCase::new(repo_with_deposit_of(Some("shop-a")));
+        let correlation_id = Uuid::new_v4();
+
+        let transaction = use_case
+            .find_by_correlation_id(correlation_id, Some("shop-a".into()))
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.correlation_id(), correlation_id);
+        assert_eq!(transaction.status(), TransactionStatus::PENDING);
+    }
+
+    #[tokio::test]
+    async fn test_other_client_gets_not_found() {
+        let use_case = SearchTransactionsUseCase::new(repo_with_deposit_of(Some("shop-a")));
+        let correlation_id = Uuid::new_v4();
+
+        let result = use_case
+            .find_by_correlation_id(correlation_id, Some("shop-b".into()))
+            .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::CorrelationNotFound(correlation_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_without_recorded_client_is_reported_as_such() {
+        let use_case = SearchTransactionsUseCase::new(repo_with_deposit_of(None));
+        let correlation_id = Uuid::new_v4();
+
+        let result = use_case
+            .find_by_correlation_id(correlation_id, Some("shop-a".into()))
+            .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::ClientNotRecorded(correlation_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_without_client_is_unauthorized() {
+        let mut mock_repo = MockTransactionRepositoryImpl::new();
+        mock_repo.expect_find_by_correlation_id().never();
+        let use_case = SearchTransactionsUseCase::new(Arc::new(mock_repo));
+
+        let result = use_case.find_by_correlation_id(Uuid::new_v4(), None).await;
+
+        assert!(matches!(result, Err(TransactionError::Unauthorized(_))));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::domain::{
+    error::TransactionError, exchange::ExchangeRate, repository::ExchangeRateRepository,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Caso de uso administrativo para publicar la tasa vigente de un par de divisas.
+///
+/// Las cotizaciones ya emitidas conservan su tasa; sólo las nuevas toman el valor actualizado.
+///
+/// # Examples
+/// ```ignore
+/// use transaction_service::use_cases::update_exchange_rate::UpdateExchangeRateUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = UpdateExchangeRateUseCase::new(rate_repo);
+/// ```
+#[derive(Clone)]
+pub struct UpdateExchangeRateUseCase {
+    rate_repo: Arc<dyn ExchangeRateRepository>,
+}
+
+impl UpdateExchangeRateUseCase {
+    /// Construye una nueva instancia de `UpdateExchangeRateUseCase`.
+    pub fn new(rate_repo: Arc<dyn ExchangeRateRepository>) -> Self {
+        Self { rate_repo }
+    }
+
+    /// Reemplaza la tasa de `base_currency` -> `quote_currency`, fechándola ahora.
+    ///
+    /// # Retornos
+    ///
+    /// La tasa persistida o `TransactionError::ValidationError` si las divisas o la tasa no
+    /// son válidas.
+    #[tracing::instrument(name = "UpdateExchangeRateUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+        rate: Decimal,
+    ) -> Result<ExchangeRate, TransactionError> {
+        let rate = ExchangeRate::new(base_currency, quote_currency, rate, Utc::now())?;
+        self.rate_repo.save_rate(rate).await
+    }
+}
@@ -1,5 +1,7 @@
 use async_trait::async_trait;
-use axum::extract::State;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
 use axum::Json;
 use chrono::{DateTime, Utc};
 use mockall::mock;
@@ -7,17 +9,37 @@ use mockall::predicate::*;
 use rust_decimal::Decimal;
 use std::sync::Arc;
 use transaction_service::api::http_routes::{
-    initiate_transaction, AppState, CreateTransactionRequest,
+    initiate_transaction, search_transactions, AppState, CreateTransactionRequest,
+    SearchTransactionsQuery, CLIENT_ID_HEADER,
 };
 use transaction_service::api::response::ApiResponse;
+use transaction_service::domain::compensation::{CompensationStatus, PendingCompensation};
 use transaction_service::domain::entities::{Transaction, TransactionStatus};
 use transaction_service::domain::error::TransactionError;
-use transaction_service::domain::gateways::WalletGateway;
-use transaction_service::domain::repository::TransactionRepository;
-use transaction_service::domain::types::{TransactionId, WalletId};
+use transaction_service::domain::events::TransactionEvent;
+use transaction_service::domain::exchange::{ExchangeRate, ExchangeRateProvider, FxQuote};
+use transaction_service::domain::gateways::{
+    HistoricalBalance, LedgerPosting, MovementStatus, WalletCurrency, WalletGateway,
+};
+use transaction_service::domain::history::{HistoryCursor, HistoryFilter};
+use transaction_service::domain::repository::{
+    CompensationRepository, ExchangeRateRepository, FxQuoteRepository, SagaRepository,
+    TransactionEventRepository, TransactionRepository, WalletStatementRepository,
+};
+use transaction_service::domain::saga::{Saga, SagaStep};
+use transaction_service::domain::statement::{StatementPeriod, WalletStatement};
+use transaction_service::domain::types::{QuoteId, TransactionId, WalletId};
+use transaction_service::use_cases::create_fx_quote::CreateFxQuoteUseCase;
 use transaction_service::use_cases::get_transaction_details::GetTransactionDetailsUseCase;
+use transaction_service::use_cases::get_transaction_events::GetTransactionEventsUseCase;
 use transaction_service::use_cases::get_wallet_history::GetWalletHistoryUseCase;
+use transaction_service::use_cases::get_wallet_statement::GetWalletStatementUseCase;
 use transaction_service::use_cases::process_transaction::ProcessTransactionUseCase;
+use transaction_service::use_cases::refund_transaction::RefundTransactionUseCase;
+use transaction_service::use_cases::review_compensations::ReviewCompensationsUseCase;
+use transaction_service::use_cases::review_dead_letters::ReviewDeadLettersUseCase;
+use transaction_service::use_cases::search_transactions::SearchTransactionsUseCase;
+use transaction_service::use_cases::update_exchange_rate::UpdateExchangeRateUseCase;
 use uuid::Uuid;
 
 mock! {
@@ -25,12 +47,18 @@ mock! {
 
     #[async_trait]
     impl TransactionRepository for TransactionRepositoryImpl {
-        async fn save(&self, transaction: Transaction) -> Result<Transaction, TransactionError>;
-        async fn update(&self, transaction: Transaction) -> Result<Transaction, TransactionError>;
+        async fn save(&self, transaction: Transaction, key_ttl: chrono::Duration) -> Result<Transaction, TransactionError>;
+        async fn update(&self, transaction: Transaction, expected_status: TransactionStatus) -> Result<Transaction, TransactionError>;
+        async fn update_claimed(&self, transaction: Transaction, expected_status: TransactionStatus, owner: &str) -> Result<Transaction, TransactionError>;
         async fn find_by_id(&self, id: TransactionId) -> Result<Option<Transaction>, TransactionError>;
-        async fn find_by_wallet_id(&self, wallet_id: WalletId) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_by_ids(&self, ids: &[TransactionId]) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_by_wallet_id(&self, wallet_id: WalletId, filter: &HistoryFilter, after: Option<HistoryCursor>, limit: i64) -> Result<Vec<Transaction>, TransactionError>;
         async fn find_by_correlation_id(&self, correlation_id: Uuid) -> Result<Option<Transaction>, TransactionError>;
-        async fn find_pending_older_than(&self, timestamp: DateTime<Utc>) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_by_external_reference(&self, external_reference: &str) -> Result<Vec<Transaction>, TransactionError>;
+        async fn claim_pending_older_than(&self, timestamp: DateTime<Utc>, owner: &str, lease: chrono::Duration) -> Result<Vec<Transaction>, TransactionError>;
+        async fn find_dead_lettered(&self, after: Option<HistoryCursor>, limit: i64) -> Result<Vec<Transaction>, TransactionError>;
+        async fn reserve_refund(&self, id: TransactionId, amount: Decimal, refunded_before: Decimal) -> Result<Option<Transaction>, TransactionError>;
+        async fn release_refund(&self, id: TransactionId, amount: Decimal) -> Result<(), TransactionError>;
     }
 }
 
@@ -39,15 +67,225 @@ mock! {
 
     #[async_trait]
     impl WalletGateway for WalletGatewayImpl {
-        async fn process_movement(&self, transaction: &Transaction) -> Result<bool, TransactionError>;
+        async fn reserve_leg(&self, transaction: &Transaction, step: &SagaStep) -> Result<bool, TransactionError>;
+        async fn capture_leg(&self, step: &SagaStep) -> Result<(), TransactionError>;
+        async fn execute_transfer(&self, debit: &SagaStep, credit: &SagaStep) -> Result<bool, TransactionError>;
+        async fn release_leg(&self, step: &SagaStep) -> Result<(), TransactionError>;
+        async fn movement_status(&self, transaction: &Transaction) -> Result<MovementStatus, TransactionError>;
+        async fn wallet_currency(&self, wallet_id: WalletId) -> Result<WalletCurrency, TransactionError>;
+        async fn historical_balance(&self, wallet_id: WalletId, as_of: DateTime<Utc>) -> Result<HistoricalBalance, TransactionError>;
+        async fn ledger_postings(&self, wallet_id: WalletId, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<LedgerPosting>, TransactionError>;
+        async fn active_wallets(&self) -> Result<Vec<WalletId>, TransactionError>;
+    }
+}
+
+mock! {
+    pub FxQuoteRepositoryImpl {}
+
+    #[async_trait]
+    impl FxQuoteRepository for FxQuoteRepositoryImpl {
+        async fn save(&self, quote: FxQuote) -> Result<FxQuote, TransactionError>;
+        async fn find_by_id(&self, id: QuoteId) -> Result<Option<FxQuote>, TransactionError>;
+    }
+}
+
+mock! {
+    pub ExchangeRatesImpl {}
+
+    #[async_trait]
+    impl ExchangeRateProvider for ExchangeRatesImpl {
+        async fn current_rate(&self, base_currency: &str, quote_currency: &str) -> Result<ExchangeRate, TransactionError>;
+    }
+
+    #[async_trait]
+    impl ExchangeRateRepository for ExchangeRatesImpl {
+        async fn save_rate(&self, rate: ExchangeRate) -> Result<ExchangeRate, TransactionError>;
+    }
+}
+
+mock! {
+    pub TransactionEventRepositoryImpl {}
+
+    #[async_trait]
+    impl TransactionEventRepository for TransactionEventRepositoryImpl {
+        async fn record(&self, event: TransactionEvent) -> Result<TransactionEvent, TransactionError>;
+        async fn find_by_transaction_id(&self, transaction_id: TransactionId) -> Result<Vec<TransactionEvent>, TransactionError>;
     }
 }
 
+mock! {
+    pub WalletStatementRepositoryImpl {}
+
+    #[async_trait]
+    impl WalletStatementRepository for WalletStatementRepositoryImpl {
+        async fn save(&self, statement: &WalletStatement) -> Result<(), TransactionError>;
+        async fn find(&self, wallet_id: WalletId, period: StatementPeriod) -> Result<Option<WalletStatement>, TransactionError>;
+    }
+}
+
+mock! {
+    pub SagaRepositoryImpl {}
+
+    #[async_trait]
+    impl SagaRepository for SagaRepositoryImpl {
+        async fn start(&self, saga: Saga) -> Result<Saga, TransactionError>;
+        async fn update_step(&self, step: &SagaStep) -> Result<(), TransactionError>;
+        async fn claim_unfinished(&self, older_than: DateTime<Utc>, owner: &str, lease: chrono::Duration) -> Result<Vec<Saga>, TransactionError>;
+        async fn release(&self, transaction_id: TransactionId, owner: &str) -> Result<(), TransactionError>;
+    }
+}
+
+mock! {
+    pub CompensationRepositoryImpl {}
+
+    #[async_trait]
+    impl CompensationRepository for CompensationRepositoryImpl {
+        async fn schedule(&self, compensation: &PendingCompensation) -> Result<(), TransactionError>;
+        async fn update(&self, compensation: &PendingCompensation) -> Result<(), TransactionError>;
+        async fn find_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<PendingCompensation>, TransactionError>;
+        async fn find_by_status(&self, status: Option<CompensationStatus>) -> Result<Vec<PendingCompensation>, TransactionError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<PendingCompensation>, TransactionError>;
+    }
+}
+
+/// Registro de sagas que acepta cualquier paso.
+fn sagas() -> Arc<MockSagaRepositoryImpl> {
+    let mut mock_sagas = MockSagaRepositoryImpl::new();
+    mock_sagas.expect_start().returning(Ok);
+    mock_sagas.expect_update_step().returning(|_| Ok(()));
+    Arc::new(mock_sagas)
+}
+
+/// Compensaciones pendientes sin uso: ninguna liberación falla.
+fn compensations() -> Arc<MockCompensationRepositoryImpl> {
+    Arc::new(MockCompensationRepositoryImpl::new())
+}
+
+/// Configura el gateway para que apruebe todas las patas y transferencias.
+fn approve_legs(gateway: &mut MockWalletGatewayImpl) {
+    gateway.expect_reserve_leg().returning(|_, _| Ok(true));
+    gateway.expect_capture_leg().returning(|_| Ok(()));
+    gateway.expect_execute_transfer().returning(|_, _| Ok(true));
+}
+
+/// Historial que acepta cualquier evento.
+fn events() -> Arc<MockTransactionEventRepositoryImpl> {
+    let mut mock_events = MockTransactionEventRepositoryImpl::new();
+    mock_events.expect_record().returning(Ok);
+    Arc::new(mock_events)
+}
+
+/// Construye el estado HTTP con el caso de uso de transacciones indicado y el resto sin uso.
+fn app_state(process_transaction_uc: ProcessTransactionUseCase) -> Arc<AppState> {
+    Arc::new(base_state(process_transaction_uc))
+}
+
+fn base_state(process_transaction_uc: ProcessTransactionUseCase) -> AppState {
+    AppState {
+        process_transaction_use_case: process_transaction_uc,
+        refund_transaction_use_case: RefundTransactionUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            Arc::new(MockWalletGatewayImpl::new()),
+            Arc::new(MockTransactionEventRepositoryImpl::new()),
+            sagas(),
+            compensations(),
+        ),
+        get_transaction_details_use_case: GetTransactionDetailsUseCase::new(Arc::new(
+            MockTransactionRepositoryImpl::new(),
+        )),
+        get_transaction_events_use_case: GetTransactionEventsUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            Arc::new(MockTransactionEventRepositoryImpl::new()),
+        ),
+        search_transactions_use_case: SearchTransactionsUseCase::new(Arc::new(
+            MockTransactionRepositoryImpl::new(),
+        )),
+        get_wallet_history_use_case: GetWalletHistoryUseCase::new(Arc::new(
+            MockTransactionRepositoryImpl::new(),
+        )),
+        get_wallet_statement_use_case: GetWalletStatementUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            Arc::new(MockWalletStatementRepositoryImpl::new()),
+            Arc::new(MockWalletGatewayImpl::new()),
+        ),
+        create_fx_quote_use_case: CreateFxQuoteUseCase::new(
+            Arc::new(MockExchangeRatesImpl::new()),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+        ),
+        update_exchange_rate_use_case: UpdateExchangeRateUseCase::new(Arc::new(
+            MockExchangeRatesImpl::new(),
+        )),
+        review_compensations_use_case: ReviewCompensationsUseCase::new(
+            compensations(),
+            sagas(),
+            Arc::new(MockWalletGatewayImpl::new()),
+        ),
+        review_dead_letters_use_case: ReviewDeadLettersUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            events(),
+        ),
+    }
+}
+
+/// Consulta `GET /transactions?correlation_id=` contra un depósito creado por `owner` y
+/// retorna el código HTTP de la respuesta.
+async fn recover_by_correlation_id(
+    owner: Option<&'static str>,
+    caller: Option<&str>,
+) -> StatusCode {
+    let mut mock_repo = MockTransactionRepositoryImpl::new();
+    mock_repo
+        .expect_find_by_correlation_id()
+        .returning(move |correlation_id| {
+            Ok(Some(
+                Transaction::new(None, WalletId::new(), Decimal::from(10), correlation_id)
+                    .unwrap()
+                    .with_client_id(owner.map(str::to_string)),
+            ))
+        });
+    let state = Arc::new(AppState {
+        search_transactions_use_case: SearchTransactionsUseCase::new(Arc::new(mock_repo)),
+        ..base_state(ProcessTransactionUseCase::new(
+            Arc::new(MockTransactionRepositoryImpl::new()),
+            Arc::new(MockWalletGatewayImpl::new()),
+            Arc::new(MockFxQuoteRepositoryImpl::new()),
+            events(),
+            sagas(),
+            compensations(),
+        ))
+    });
+
+    let mut headers = HeaderMap::new();
+    if let Some(caller) = caller {
+        headers.insert(CLIENT_ID_HEADER, caller.parse().unwrap());
+    }
+    let query = SearchTransactionsQuery {
+        external_reference: None,
+        correlation_id: Some(Uuid::new_v4()),
+    };
+
+    match search_transactions(State(state), headers, Query(query)).await {
+        Ok(response) => response.into_response().status(),
+        Err(error) => error.into_response().status(),
+    }
+}
+
+/// Ambas billeteras en la misma divisa: la transferencia no requiere cotización.
+fn same_currency(gateway: &mut MockWalletGatewayImpl) {
+    gateway.expect_wallet_currency().returning(|_| {
+        Ok(WalletCurrency {
+            code: "USD".into(),
+            minor_units: 2,
+        })
+    });
+}
+
 #[tokio::test]
 async fn test_successful_transfer_updates_both_wallets() {
     // Arrange
     let mut mock_repo = MockTransactionRepositoryImpl::new();
     let mut mock_gateway = MockWalletGatewayImpl::new();
+    same_currency(&mut mock_gateway);
 
     let source_wallet_uuid = Uuid::new_v4();
     let dest_wallet_uuid = Uuid::new_v4();
@@ -60,41 +298,44 @@ async fn test_successful_transfer_updates_both_wallets() {
         .times(1)
         .returning(|_| Ok(None));
 
-    mock_repo.expect_save().times(1).returning(|tx| Ok(tx));
+    mock_repo.expect_save().times(1).returning(|tx, _| Ok(tx));
 
-    mock_gateway
-        .expect_process_movement()
-        .times(1)
-        .returning(|_| Ok(true));
+    approve_legs(&mut mock_gateway);
 
     mock_repo
         .expect_update()
-        .withf(|tx: &Transaction| tx.status() == TransactionStatus::COMPLETED)
+        .withf(|tx: &Transaction, expected: &TransactionStatus| {
+            tx.status() == TransactionStatus::COMPLETED && *expected == TransactionStatus::PENDING
+        })
         .times(1)
-        .returning(|tx| Ok(tx));
-
-    let process_transaction_uc =
-        ProcessTransactionUseCase::new(Arc::new(mock_repo), Arc::new(mock_gateway));
-    let get_tx_uc =
-        GetTransactionDetailsUseCase::new(Arc::new(MockTransactionRepositoryImpl::new()));
-    let get_history_uc =
-        GetWalletHistoryUseCase::new(Arc::new(MockTransactionRepositoryImpl::new()));
+        .returning(|tx, _| Ok(tx));
 
-    let state = Arc::new(AppState {
-        process_transaction_use_case: process_transaction_uc,
-        get_transaction_details_use_case: get_tx_uc,
-        get_wallet_history_use_case: get_history_uc,
-    });
+    let process_transaction_uc = ProcessTransactionUseCase::new(
+        Arc::new(mock_repo),
+        Arc::new(mock_gateway),
+        Arc::new(MockFxQuoteRepositoryImpl::new()),
+        events(),
+        sagas(),
+        compensations(),
+    );
+    let state = app_state(process_transaction_uc);
 
     let payload = CreateTransactionRequest {
         source_wallet_id: Some(source_wallet_uuid),
         dest_wallet_id: dest_wallet_uuid,
         amount,
         correlation_id,
+        quote_id: None,
+        description: Some("Pago de almuerzo".into()),
+        external_reference: Some("INV-2024-001".into()),
+        metadata: serde_json::json!({ "order_id": 42 })
+            .as_object()
+            .cloned()
+            .unwrap(),
     };
 
     // Act
-    let result = initiate_transaction(State(state), Json(payload)).await;
+    let result = initiate_transaction(State(state), HeaderMap::new(), Json(payload)).await;
 
     // Assert
     assert!(result.is_ok(), "El Request debe ser exitoso");
@@ -108,6 +349,8 @@ async fn test_successful_transfer_updates_both_wallets() {
     assert_eq!(body.status, "success");
     let tx_data = body.data;
     assert_eq!(tx_data["status"], "COMPLETED");
+    assert_eq!(tx_data["external_reference"], "INV-2024-001");
+    assert_eq!(tx_data["metadata"]["order_id"], 42);
 }
 
 #[tokio::test]
@@ -115,6 +358,7 @@ async fn test_failed_transfer_rolls_back_source_wallet() {
     // Arrange
     let mut mock_repo = MockTransactionRepositoryImpl::new();
     let mut mock_gateway = MockWalletGatewayImpl::new();
+    same_currency(&mut mock_gateway);
 
     let source_wallet_uuid = Uuid::new_v4();
     let dest_wallet_uuid = Uuid::new_v4();
@@ -125,41 +369,44 @@ async fn test_failed_transfer_rolls_back_source_wallet() {
         .expect_find_by_correlation_id()
         .returning(|_| Ok(None));
 
-    mock_repo.expect_save().returning(|tx| Ok(tx));
+    mock_repo.expect_save().returning(|tx, _| Ok(tx));
 
     mock_gateway
-        .expect_process_movement()
+        .expect_execute_transfer()
         .times(1)
-        .returning(|_| Ok(false));
+        .returning(|_, _| Ok(false));
 
     mock_repo
         .expect_update()
-        .withf(|tx: &Transaction| tx.status() == TransactionStatus::FAILED)
+        .withf(|tx: &Transaction, expected: &TransactionStatus| {
+            tx.status() == TransactionStatus::FAILED && *expected == TransactionStatus::PENDING
+        })
         .times(1)
-        .returning(|tx| Ok(tx));
-
-    let process_transaction_uc =
-        ProcessTransactionUseCase::new(Arc::new(mock_repo), Arc::new(mock_gateway));
-    let get_tx_uc =
-        GetTransactionDetailsUseCase::new(Arc::new(MockTransactionRepositoryImpl::new()));
-    let get_history_uc =
-        GetWalletHistoryUseCase::new(Arc::new(MockTransactionRepositoryImpl::new()));
+        .returning(|tx, _| Ok(tx));
 
-    let state = Arc::new(AppState {
-        process_transaction_use_case: process_transaction_uc,
-        get_transaction_details_use_case: get_tx_uc,
-        get_wallet_history_use_case: get_history_uc,
-    });
+    let process_transaction_uc = ProcessTransactionUseCase::new(
+        Arc::new(mock_repo),
+        Arc::new(mock_gateway),
+        Arc::new(MockFxQuoteRepositoryImpl::new()),
+        events(),
+        sagas(),
+        compensations(),
+    );
+    let state = app_state(process_transaction_uc);
 
     let payload = CreateTransactionRequest {
         source_wallet_id: Some(source_wallet_uuid),
         dest_wallet_id: dest_wallet_uuid,
         amount,
         correlation_id,
+        quote_id: None,
+        description: None,
+        external_reference: None,
+        metadata: Default::default(),
     };
 
     // Act
-    let result = initiate_transaction(State(state), Json(payload)).await;
+    let result = initiate_transaction(State(state), HeaderMap::new(), Json(payload)).await;
 
     // Assert
     assert!(
@@ -167,3 +414,26 @@ async fn test_failed_transfer_rolls_back_source_wallet() {
         "La petición debe lanzar un ApiError (500 o Gateway Error)"
     );
 }
+
+#[tokio::test]
+async fn test_recovery_by_correlation_id_is_limited_to_initiating_client() {
+    assert_eq!(
+        recover_by_correlation_id(Some("shop-a"), Some("shop-a")).await,
+        StatusCode::OK
+    );
+    // Sin X-Client-Id no se identifica a nadie.
+    assert_eq!(
+        recover_by_correlation_id(Some("shop-a"), None).await,
+        StatusCode::UNAUTHORIZED
+    );
+    // Otro cliente recibe lo mismo que si no existiera.
+    assert_eq!(
+        recover_by_correlation_id(Some("shop-a"), Some("shop-b")).await,
+        StatusCode::NOT_FOUND
+    );
+    // Una transacción creada sin cliente no se confunde con una ajena.
+    assert_eq!(
+        recover_by_correlation_id(None, Some("shop-a")).await,
+        StatusCode::FORBIDDEN
+    );
+}
@@ -0,0 +1,30 @@
+-- Libro mayor (ledger) de doble partida para los movimientos de las billeteras
+
+-- Cuentas internas del sistema usadas como contrapartida
+CREATE TYPE system_account AS ENUM ('EXTERNAL_FUNDING', 'FEES', 'SUSPENSE');
+
+-- Balance de cada cuenta del sistema por divisa
+CREATE TABLE IF NOT EXISTS system_accounts (
+    code system_account NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    balance DECIMAL(20, 2) NOT NULL DEFAULT 0.00,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (code, currency)
+);
+
+-- Asientos inmutables. Cada asiento pertenece a una billetera O a una cuenta del sistema.
+CREATE TABLE IF NOT EXISTS wallet_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    transaction_id VARCHAR(64) NOT NULL, -- transaction_id recibido en ValidateAndReserveRequest
+    wallet_id UUID REFERENCES wallets(id),
+    system_account system_account,
+    amount DECIMAL(20, 2) NOT NULL, -- Con signo: + crédito, - débito
+    balance_after DECIMAL(20, 2) NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT wallet_entries_account_chk CHECK ((wallet_id IS NULL) <> (system_account IS NULL)),
+    CONSTRAINT wallet_entries_amount_chk CHECK (amount <> 0)
+);
+
+CREATE INDEX idx_wallet_entries_wallet_created ON wallet_entries(wallet_id, created_at DESC);
+CREATE INDEX idx_wallet_entries_transaction ON wallet_entries(transaction_id);
@@ -0,0 +1,23 @@
+-- Reservas de fondos (holds) para ValidateAndReserve / ConfirmBalanceUpdate
+
+-- Total reservado por débitos pendientes. Disponible = balance - reserved_balance.
+ALTER TABLE wallets
+    ADD COLUMN reserved_balance DECIMAL(20, 2) NOT NULL DEFAULT 0.00,
+    ADD CONSTRAINT reserved_balance_chk CHECK (reserved_balance >= 0);
+
+CREATE TYPE hold_status AS ENUM ('ACTIVE', 'CAPTURED', 'RELEASED');
+
+CREATE TABLE IF NOT EXISTS wallet_holds (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    wallet_id UUID NOT NULL REFERENCES wallets(id),
+    transaction_id VARCHAR(64) NOT NULL,
+    amount DECIMAL(20, 2) NOT NULL, -- Con signo: - débito reservado, + crédito pendiente
+    counterparty system_account NOT NULL, -- Contrapartida a usar al capturar
+    status hold_status NOT NULL DEFAULT 'ACTIVE',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT wallet_holds_amount_chk CHECK (amount <> 0),
+    CONSTRAINT wallet_holds_tx_wallet_key UNIQUE (transaction_id, wallet_id)
+);
+
+CREATE INDEX idx_wallet_holds_active ON wallet_holds(wallet_id) WHERE status = 'ACTIVE';
@@ -0,0 +1,14 @@
+-- Saldos no negativos con sobregiro opcional por billetera
+
+-- Sobregiro permitido. NULL significa que la billetera no admite saldo negativo.
+ALTER TABLE wallets
+    ADD COLUMN overdraft_limit DECIMAL(20, 2) NULL,
+    ADD CONSTRAINT overdraft_limit_chk CHECK (overdraft_limit IS NULL OR overdraft_limit >= 0);
+
+-- Invariante a nivel de BD: el disponible (balance - reservado) nunca baja del sobregiro.
+-- Al evaluarse sobre la fila ya bloqueada por el UPDATE, dos reservas concurrentes no pueden
+-- llevar la billetera por debajo del límite aunque ambas hayan leído un saldo suficiente.
+ALTER TABLE wallets
+    ADD CONSTRAINT positive_balance CHECK (
+        balance - reserved_balance + COALESCE(overdraft_limit, 0) >= 0
+    );
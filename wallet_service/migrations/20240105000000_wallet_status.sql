@@ -0,0 +1,14 @@
+-- Ciclo de vida de las billeteras: ACTIVE ⇄ FROZEN → CLOSED
+
+CREATE TYPE wallet_status AS ENUM ('ACTIVE', 'FROZEN', 'CLOSED');
+
+ALTER TABLE wallets
+    ADD COLUMN status wallet_status NOT NULL DEFAULT 'ACTIVE',
+    ADD COLUMN status_reason TEXT NULL,
+    ADD COLUMN status_changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP;
+
+-- Una billetera cerrada no puede tener saldo ni reservas, ni volver a recibirlos.
+ALTER TABLE wallets
+    ADD CONSTRAINT wallet_closed_chk CHECK (
+        status <> 'CLOSED' OR (balance = 0 AND reserved_balance = 0)
+    );
@@ -0,0 +1,28 @@
+-- Límites de gasto (débitos) por transacción, por día y por mes calendario (UTC)
+
+-- Configuración por billetera. NULL en una columna significa sin límite para ese período.
+CREATE TABLE IF NOT EXISTS wallet_limits (
+    wallet_id UUID PRIMARY KEY REFERENCES wallets(id),
+    per_transaction_limit DECIMAL(20, 2) NULL,
+    daily_limit DECIMAL(20, 2) NULL,
+    monthly_limit DECIMAL(20, 2) NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT wallet_limits_positive_chk CHECK (
+        (per_transaction_limit IS NULL OR per_transaction_limit > 0)
+        AND (daily_limit IS NULL OR daily_limit > 0)
+        AND (monthly_limit IS NULL OR monthly_limit > 0)
+    )
+);
+
+-- Contadores de débitos reservados por período. Se incrementan al reservar un débito y se
+-- decrementan si la reserva se libera; el UPSERT bloquea la fila del período, por lo que dos
+-- reservas concurrentes no pueden leer el mismo consumo y superar juntas el límite.
+CREATE TABLE IF NOT EXISTS wallet_debit_usage (
+    wallet_id UUID NOT NULL REFERENCES wallets(id),
+    period VARCHAR(8) NOT NULL,
+    period_start DATE NOT NULL,
+    used DECIMAL(20, 2) NOT NULL DEFAULT 0.00,
+    PRIMARY KEY (wallet_id, period, period_start),
+    CONSTRAINT wallet_debit_usage_period_chk CHECK (period IN ('DAILY', 'MONTHLY')),
+    CONSTRAINT wallet_debit_usage_used_chk CHECK (used >= 0)
+);
@@ -0,0 +1,28 @@
+-- Precisión de montos según la divisa (ISO 4217)
+
+-- DECIMAL(20, 2) truncaba los montos de divisas con tres o cuatro decimales (KWD, BHD, CLF)
+-- y forzaba decimales inexistentes en las de cero (JPY, CLP). Se pasa a NUMERIC sin escala
+-- fija, que conserva exactamente el monto recibido; la cantidad de decimales admitida por
+-- cada divisa la valida el servicio contra su registro ISO 4217 antes de escribir.
+ALTER TABLE wallets
+    ALTER COLUMN balance TYPE NUMERIC,
+    ALTER COLUMN reserved_balance TYPE NUMERIC,
+    ALTER COLUMN overdraft_limit TYPE NUMERIC;
+
+ALTER TABLE wallet_holds
+    ALTER COLUMN amount TYPE NUMERIC;
+
+ALTER TABLE wallet_entries
+    ALTER COLUMN amount TYPE NUMERIC,
+    ALTER COLUMN balance_after TYPE NUMERIC;
+
+ALTER TABLE system_accounts
+    ALTER COLUMN balance TYPE NUMERIC;
+
+ALTER TABLE wallet_limits
+    ALTER COLUMN per_transaction_limit TYPE NUMERIC,
+    ALTER COLUMN daily_limit TYPE NUMERIC,
+    ALTER COLUMN monthly_limit TYPE NUMERIC;
+
+ALTER TABLE wallet_debit_usage
+    ALTER COLUMN used TYPE NUMERIC;
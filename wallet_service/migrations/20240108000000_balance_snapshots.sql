@@ -0,0 +1,15 @@
+-- Snapshots diarios de saldo para consultas de saldo histórico
+
+-- Saldo contable de cada billetera al cierre del día (UTC). Los genera un job diario a partir
+-- del snapshot del día anterior más los asientos del día; un saldo en un instante cualquiera
+-- se reconstruye desde el snapshot más cercano sumando los asientos posteriores.
+CREATE TABLE IF NOT EXISTS wallet_balance_snapshots (
+    wallet_id UUID NOT NULL REFERENCES wallets(id),
+    balance_date DATE NOT NULL,
+    ledger_balance NUMERIC NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (wallet_id, balance_date)
+);
+
+CREATE INDEX idx_wallet_balance_snapshots_date ON wallet_balance_snapshots(balance_date);
@@ -11,6 +11,8 @@ use crate::domain::error::{UserError, WalletError};
 pub enum ApiError {
     User(UserError),
     Wallet(WalletError),
+    /// La versión indicada en `If-Match` no coincide con la actual del recurso.
+    PreconditionFailed(String),
 }
 
 // Permitimos convertir errores de dominio al ApiError implícitamente
@@ -44,12 +46,19 @@ impl IntoResponse for ApiError {
                 WalletError::UserNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
                 WalletError::InvalidData(_) => (StatusCode::BAD_REQUEST, e.to_string()),
                 WalletError::InsufficientFunds(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+                WalletError::LimitExceeded { .. } => (StatusCode::BAD_REQUEST, e.to_string()),
+                WalletError::WalletNotActive { .. } => (StatusCode::CONFLICT, e.to_string()),
+                WalletError::InvalidStatusTransition(_) => (StatusCode::CONFLICT, e.to_string()),
+                WalletError::HoldNotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+                WalletError::DuplicateMovement { .. } => (StatusCode::CONFLICT, e.to_string()),
+                WalletError::InvalidHoldState(_) => (StatusCode::CONFLICT, e.to_string()),
                 WalletError::ConcurrencyError(_) => (StatusCode::CONFLICT, e.to_string()),
                 WalletError::RepositoryError(_) => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Internal server error".to_string(),
                 ),
             },
+            ApiError::PreconditionFailed(message) => (StatusCode::PRECONDITION_FAILED, message),
         };
 
         let body = Json(json!({
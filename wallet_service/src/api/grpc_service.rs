@@ -1,11 +1,31 @@
 use crate::api::proto::wallet::wallet_service_server::WalletService;
 use crate::api::proto::wallet::{
-    ConfirmBalanceUpdateRequest, ConfirmBalanceUpdateResponse, ValidateAndReserveRequest,
-    ValidateAndReserveResponse,
+    ConfirmBalanceUpdateRequest, ConfirmBalanceUpdateResponse, ExecuteTransferRequest,
+    ExecuteTransferResponse, GetBalanceRequest, GetHistoricalBalanceRequest,
+    GetMovementStatusRequest, GetMovementStatusResponse,
+    HistoricalBalance as ProtoHistoricalBalance, LedgerPosting, MovementResult, MovementStatus,
+    StreamBalancesRequest, StreamLedgerEntriesRequest, ValidateAndReserveRequest,
+    ValidateAndReserveResponse, WalletBalance,
 };
+use crate::domain::currency::Currency;
+use crate::domain::entities::{
+    BalanceSnapshot, HistoricalBalance, Hold, HoldStatus, LedgerEntry, SystemAccount, WalletStatus,
+};
+use crate::domain::error::WalletError;
+use crate::domain::types::WalletId;
+use crate::use_cases::confirm_movement::ConfirmMovementUseCase;
+use crate::use_cases::execute_transfer::ExecuteTransferUseCase;
+use crate::use_cases::get_balance::GetBalanceUseCase;
+use crate::use_cases::get_historical_balance::GetHistoricalBalanceUseCase;
+use crate::use_cases::get_movement_status::GetMovementStatusUseCase;
+use crate::use_cases::get_wallet_entries::GetWalletEntriesUseCase;
+use crate::use_cases::list_balances::ListBalancesUseCase;
 use crate::use_cases::process_movement::ProcessMovementUseCase;
+use chrono::{DateTime, Utc};
 use core::str::FromStr;
 use rust_decimal::Decimal;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
@@ -14,12 +34,36 @@ use uuid::Uuid;
 // que delegan la lógica de negocio a los Casos de Uso.
 pub struct WalletGrpcService {
     process_movement_use_case: ProcessMovementUseCase,
+    confirm_movement_use_case: ConfirmMovementUseCase,
+    execute_transfer_use_case: ExecuteTransferUseCase,
+    get_movement_status_use_case: GetMovementStatusUseCase,
+    get_balance_use_case: GetBalanceUseCase,
+    list_balances_use_case: ListBalancesUseCase,
+    get_historical_balance_use_case: GetHistoricalBalanceUseCase,
+    get_wallet_entries_use_case: GetWalletEntriesUseCase,
 }
 
 impl WalletGrpcService {
-    pub fn new(process_movement_use_case: ProcessMovementUseCase) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        process_movement_use_case: ProcessMovementUseCase,
+        confirm_movement_use_case: ConfirmMovementUseCase,
+        execute_transfer_use_case: ExecuteTransferUseCase,
+        get_movement_status_use_case: GetMovementStatusUseCase,
+        get_balance_use_case: GetBalanceUseCase,
+        list_balances_use_case: ListBalancesUseCase,
+        get_historical_balance_use_case: GetHistoricalBalanceUseCase,
+        get_wallet_entries_use_case: GetWalletEntriesUseCase,
+    ) -> Self {
         Self {
             process_movement_use_case,
+            confirm_movement_use_case,
+            execute_transfer_use_case,
+            get_movement_status_use_case,
+            get_balance_use_case,
+            list_balances_use_case,
+            get_historical_balance_use_case,
+            get_wallet_entries_use_case,
         }
     }
 }
@@ -27,6 +71,9 @@ impl WalletGrpcService {
 // Implementamos el trait autogenerado por Tonic para nuestro servicio
 #[tonic::async_trait]
 impl WalletService for WalletGrpcService {
+    type StreamBalancesStream = ReceiverStream<Result<WalletBalance, Status>>;
+    type StreamLedgerEntriesStream = ReceiverStream<Result<LedgerPosting, Status>>;
+
     #[tracing::instrument(name = "WalletGrpcService::validate_and_reserve", skip(self))]
     async fn validate_and_reserve(
         &self,
@@ -43,29 +90,68 @@ impl WalletService for WalletGrpcService {
         let amount = Decimal::from_str(&req.amount)
             .map_err(|_| Status::invalid_argument("El monto no es un decimal válido"))?;
 
+        if req.transaction_id.trim().is_empty() {
+            return Err(Status::invalid_argument("El transaction_id es obligatorio"));
+        }
+
         // El Transaction Service enviará un monto positivo para depósitos
         // y negativo para retiros/reservas.
         let amount_to_reserve = amount;
 
+        // La contrapartida del asiento depende del tipo de transacción:
+        // depósitos/retiros contra fondeo externo, transferencias contra la cuenta puente.
+        let counterparty = SystemAccount::for_transaction_type(&req.transaction_type);
+
+        // Se crea una reserva (hold): el balance disponible baja pero el contable no cambia
+        // hasta que llegue `ConfirmBalanceUpdate`.
         match self
             .process_movement_use_case
-            .execute(crate::domain::types::WalletId(wallet_id), amount_to_reserve)
+            .execute(
+                WalletId(wallet_id),
+                amount_to_reserve,
+                req.transaction_id,
+                counterparty,
+            )
             .await
         {
-            Ok(_) => {
+            Ok(movement) => {
+                let hold = movement.hold;
+                // Un reintento de una pata ya compensada no debe tratarse como reserva válida.
+                let (success, result, message) = match (movement.replayed, hold.status()) {
+                    (true, HoldStatus::Released) => (
+                        false,
+                        MovementResult::AlreadyReleased,
+                        format!("La reserva {} ya fue liberada.", hold.id()),
+                    ),
+                    (true, _) => (
+                        true,
+                        MovementResult::Ok,
+                        format!("Movimiento ya procesado (hold {}).", hold.id()),
+                    ),
+                    (false, _) => (
+                        true,
+                        MovementResult::Ok,
+                        format!("Saldo reservado exitosamente (hold {}).", hold.id()),
+                    ),
+                };
                 let response = ValidateAndReserveResponse {
-                    success: true,
-                    message: "Saldo reservado exitosamente.".to_string(),
+                    success,
+                    message,
+                    result: result.into(),
+                    replayed: movement.replayed,
+                    status: movement_status_for(Some(&hold)).into(),
                 };
                 Ok(Response::new(response))
             }
             Err(e) => {
                 // Mapear el error de dominio a un error de gRPC
                 tracing::error!("Error al procesar reserva: {:?}", e);
-                // Si usamos thiserror, podríamos hacer un mapping más detallado (ej: Status::not_found)
                 let response = ValidateAndReserveResponse {
                     success: false,
                     message: format!("Fondos insuficientes o error: {}", e),
+                    result: movement_result_for(&e).into(),
+                    replayed: false,
+                    status: MovementStatus::Unspecified.into(),
                 };
                 Ok(Response::new(response))
             }
@@ -84,19 +170,379 @@ impl WalletService for WalletGrpcService {
             req
         );
 
-        // Lógica de compensación/confirmación
-        // Aquí interactuaríamos con otro caso de uso para dar por exitosa la transacción
-        // o deshacer la reserva en base a `req.is_success`.
-        let message = if req.is_success {
-            "Transacción confirmada definitivamente."
+        let wallet_id = Uuid::parse_str(&req.wallet_id)
+            .map_err(|_| Status::invalid_argument("El wallet_id no es un UUID válido"))?;
+
+        if req.transaction_id.trim().is_empty() {
+            return Err(Status::invalid_argument("El transaction_id es obligatorio"));
+        }
+
+        // Lógica de compensación/confirmación:
+        // `is_success = true` captura la reserva (aplica el movimiento al balance contable)
+        // y `is_success = false` la libera (devuelve los fondos retenidos al disponible).
+        match self
+            .confirm_movement_use_case
+            .execute(WalletId(wallet_id), req.transaction_id, req.is_success)
+            .await
+        {
+            Ok(_) => {
+                let message = if req.is_success {
+                    "Transacción confirmada definitivamente. Reserva capturada."
+                } else {
+                    "Transacción fallida. Reserva liberada."
+                };
+
+                Ok(Response::new(ConfirmBalanceUpdateResponse {
+                    success: true,
+                    message: message.to_string(),
+                }))
+            }
+            Err(e) => {
+                tracing::error!("Error al confirmar reserva: {:?}", e);
+                Ok(Response::new(ConfirmBalanceUpdateResponse {
+                    success: false,
+                    message: format!("No se pudo confirmar la reserva: {}", e),
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "WalletGrpcService::execute_transfer", skip(self))]
+    async fn execute_transfer(
+        &self,
+        request: Request<ExecuteTransferRequest>,
+    ) -> Result<Response<ExecuteTransferResponse>, Status> {
+        let req = request.into_inner();
+
+        tracing::info!(
+            "Recibida petición gRPC para ejecutar transferencia: {:?}",
+            req
+        );
+
+        let source = Uuid::parse_str(&req.source_wallet_id)
+            .map_err(|_| Status::invalid_argument("El source_wallet_id no es un UUID válido"))?;
+        let destination = Uuid::parse_str(&req.destination_wallet_id).map_err(|_| {
+            Status::invalid_argument("El destination_wallet_id no es un UUID válido")
+        })?;
+        let source_amount = Decimal::from_str(&req.source_amount)
+            .map_err(|_| Status::invalid_argument("El source_amount no es un decimal válido"))?;
+        let destination_amount = Decimal::from_str(&req.destination_amount).map_err(|_| {
+            Status::invalid_argument("El destination_amount no es un decimal válido")
+        })?;
+
+        if req.transaction_id.trim().is_empty() {
+            return Err(Status::invalid_argument("El transaction_id es obligatorio"));
+        }
+
+        match self
+            .execute_transfer_use_case
+            .execute(
+                req.transaction_id,
+                WalletId(source),
+                WalletId(destination),
+                source_amount,
+                destination_amount,
+            )
+            .await
+        {
+            Ok(transfer) => {
+                let message = if transfer.replayed {
+                    "Transferencia ya ejecutada.".to_string()
+                } else {
+                    "Transferencia ejecutada exitosamente.".to_string()
+                };
+                Ok(Response::new(ExecuteTransferResponse {
+                    success: true,
+                    message,
+                    result: MovementResult::Ok.into(),
+                    replayed: transfer.replayed,
+                    rejected_wallet_id: String::new(),
+                }))
+            }
+            Err(e) => {
+                tracing::error!("Error al ejecutar transferencia: {:?}", e);
+                Ok(Response::new(ExecuteTransferResponse {
+                    success: false,
+                    message: format!("No se pudo ejecutar la transferencia: {}", e),
+                    result: movement_result_for(&e).into(),
+                    replayed: false,
+                    rejected_wallet_id: rejected_wallet(&e)
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "WalletGrpcService::get_movement_status", skip(self))]
+    async fn get_movement_status(
+        &self,
+        request: Request<GetMovementStatusRequest>,
+    ) -> Result<Response<GetMovementStatusResponse>, Status> {
+        let req = request.into_inner();
+
+        let wallet_id = Uuid::parse_str(&req.wallet_id)
+            .map_err(|_| Status::invalid_argument("El wallet_id no es un UUID válido"))?;
+
+        let hold = self
+            .get_movement_status_use_case
+            .execute(WalletId(wallet_id), req.transaction_id)
+            .await
+            .map_err(|e| match e {
+                WalletError::InvalidData(message) => Status::invalid_argument(message),
+                other => Status::internal(other.to_string()),
+            })?;
+
+        Ok(Response::new(GetMovementStatusResponse {
+            status: movement_status_for(hold.as_ref()).into(),
+            amount: hold.map(|h| h.amount().to_string()).unwrap_or_default(),
+        }))
+    }
+
+    #[tracing::instrument(name = "WalletGrpcService::get_balance", skip(self))]
+    async fn get_balance(
+        &self,
+        request: Request<GetBalanceRequest>,
+    ) -> Result<Response<WalletBalance>, Status> {
+        let req = request.into_inner();
+
+        let wallet_id = Uuid::parse_str(&req.wallet_id)
+            .map_err(|_| Status::invalid_argument("El wallet_id no es un UUID válido"))?;
+
+        let snapshot = self
+            .get_balance_use_case
+            .execute(WalletId(wallet_id))
+            .await
+            .map_err(|e| match e {
+                WalletError::NotFound(_) => Status::not_found(e.to_string()),
+                other => Status::internal(other.to_string()),
+            })?;
+
+        Ok(Response::new(snapshot.into()))
+    }
+
+    #[tracing::instrument(name = "WalletGrpcService::stream_balances", skip(self))]
+    async fn stream_balances(
+        &self,
+        request: Request<StreamBalancesRequest>,
+    ) -> Result<Response<Self::StreamBalancesStream>, Status> {
+        let req = request.into_inner();
+
+        let mut after = if req.after_wallet_id.is_empty() {
+            None
         } else {
-            "Transacción fallida. Reserva deshecha (implementación parcial)."
+            Some(WalletId(Uuid::parse_str(&req.after_wallet_id).map_err(
+                |_| Status::invalid_argument("El after_wallet_id no es un UUID válido"),
+            )?))
         };
+        let page_size = (req.page_size > 0).then_some(i64::from(req.page_size));
 
-        let response = ConfirmBalanceUpdateResponse {
-            success: true,
-            message: message.to_string(),
-        };
-        Ok(Response::new(response))
+        // Las páginas se leen en una tarea aparte; el canal acotado aplica backpressure, así que
+        // sólo se consulta la siguiente página cuando el cliente consumió la anterior.
+        let (tx, rx) = mpsc::channel(128);
+        let use_case = self.list_balances_use_case.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let page = match use_case.execute(after, page_size).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        tracing::error!("Error al paginar saldos: {:?}", e);
+                        let _ = tx.send(Err(Status::internal(e.to_string()))).await;
+                        return;
+                    }
+                };
+                if page.is_empty() {
+                    return;
+                }
+
+                for snapshot in page {
+                    after = Some(snapshot.wallet_id);
+                    if tx.send(Ok(snapshot.into())).await.is_err() {
+                        // El cliente cerró el stream.
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    #[tracing::instrument(name = "WalletGrpcService::get_historical_balance", skip(self))]
+    async fn get_historical_balance(
+        &self,
+        request: Request<GetHistoricalBalanceRequest>,
+    ) -> Result<Response<ProtoHistoricalBalance>, Status> {
+        let req = request.into_inner();
+
+        let wallet_id = Uuid::parse_str(&req.wallet_id)
+            .map_err(|_| Status::invalid_argument("El wallet_id no es un UUID válido"))?;
+        let as_of = DateTime::parse_from_rfc3339(&req.as_of)
+            .map_err(|_| Status::invalid_argument("El as_of no es una fecha RFC 3339 válida"))?
+            .with_timezone(&Utc);
+
+        let balance = self
+            .get_historical_balance_use_case
+            .execute(WalletId(wallet_id), as_of)
+            .await
+            .map_err(|e| match e {
+                WalletError::NotFound(_) => Status::not_found(e.to_string()),
+                WalletError::InvalidData(message) => Status::invalid_argument(message),
+                other => Status::internal(other.to_string()),
+            })?;
+
+        Ok(Response::new(balance.into()))
+    }
+
+    #[tracing::instrument(name = "WalletGrpcService::stream_ledger_entries", skip(self))]
+    async fn stream_ledger_entries(
+        &self,
+        request: Request<StreamLedgerEntriesRequest>,
+    ) -> Result<Response<Self::StreamLedgerEntriesStream>, Status> {
+        let req = request.into_inner();
+
+        let wallet_id = WalletId(
+            Uuid::parse_str(&req.wallet_id)
+                .map_err(|_| Status::invalid_argument("El wallet_id no es un UUID válido"))?,
+        );
+        let from = DateTime::parse_from_rfc3339(&req.from)
+            .map_err(|_| Status::invalid_argument("El from no es una fecha RFC 3339 válida"))?
+            .with_timezone(&Utc);
+        let to = DateTime::parse_from_rfc3339(&req.to)
+            .map_err(|_| Status::invalid_argument("El to no es una fecha RFC 3339 válida"))?
+            .with_timezone(&Utc);
+        let page_size = (req.page_size > 0).then_some(i64::from(req.page_size));
+
+        // La primera página se lee antes de abrir el stream para reportar los errores de la
+        // petición (billetera inexistente, rango vacío) como estado de la llamada.
+        let use_case = self.get_wallet_entries_use_case.clone();
+        let first = use_case
+            .execute_between(wallet_id, from, to, None, page_size)
+            .await
+            .map_err(|e| match e {
+                WalletError::NotFound(_) => Status::not_found(e.to_string()),
+                WalletError::InvalidData(message) => Status::invalid_argument(message),
+                other => Status::internal(other.to_string()),
+            })?;
+
+        // Igual que en `stream_balances`: el canal acotado aplica backpressure entre páginas.
+        let (tx, rx) = mpsc::channel(128);
+        tokio::spawn(async move {
+            let mut page = first;
+            loop {
+                let Some(last) = page.last().cloned() else {
+                    return;
+                };
+                for entry in page {
+                    if tx.send(Ok(entry.into())).await.is_err() {
+                        // El cliente cerró el stream.
+                        return;
+                    }
+                }
+
+                page = match use_case
+                    .execute_between(wallet_id, from, to, Some(&last), page_size)
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(e) => {
+                        tracing::error!("Error al paginar asientos: {:?}", e);
+                        let _ = tx.send(Err(Status::internal(e.to_string()))).await;
+                        return;
+                    }
+                };
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Traduce el error de dominio al resultado tipado que viaja en `ValidateAndReserveResponse`.
+fn movement_result_for(error: &WalletError) -> MovementResult {
+    match error {
+        WalletError::InsufficientFunds(_) => MovementResult::InsufficientFunds,
+        WalletError::NotFound(_) => MovementResult::WalletNotFound,
+        WalletError::WalletNotActive { .. } => MovementResult::WalletNotActive,
+        WalletError::LimitExceeded { .. } => MovementResult::LimitExceeded,
+        WalletError::InvalidData(_) | WalletError::InvalidHoldState(_) => {
+            MovementResult::InvalidRequest
+        }
+        _ => MovementResult::InternalError,
+    }
+}
+
+/// Billetera que originó el rechazo de un movimiento, si el error la identifica.
+fn rejected_wallet(error: &WalletError) -> Option<WalletId> {
+    match error {
+        WalletError::InsufficientFunds(id) | WalletError::NotFound(id) => Some(*id),
+        WalletError::WalletNotActive { wallet_id, .. }
+        | WalletError::LimitExceeded { wallet_id, .. } => Some(*wallet_id),
+        _ => None,
+    }
+}
+
+/// Estado de la pata expuesto por gRPC a partir de la reserva (si existe).
+fn movement_status_for(hold: Option<&Hold>) -> MovementStatus {
+    match hold.map(|h| h.status()) {
+        None => MovementStatus::NotFound,
+        Some(HoldStatus::Active) => MovementStatus::Reserved,
+        Some(HoldStatus::Captured) => MovementStatus::Applied,
+        Some(HoldStatus::Released) => MovementStatus::Released,
+    }
+}
+
+impl From<BalanceSnapshot> for WalletBalance {
+    fn from(b: BalanceSnapshot) -> Self {
+        let minor_units = Currency::from_code(&b.currency)
+            .map(|c| c.minor_units())
+            .unwrap_or(2);
+        Self {
+            wallet_id: b.wallet_id.to_string(),
+            currency: b.currency,
+            ledger_balance: b.ledger_balance.to_string(),
+            available_balance: b.available_balance.to_string(),
+            version: b.version,
+            snapshot_at: b.taken_at.to_rfc3339(),
+            minor_units,
+            status: wallet_status_code(b.status).to_string(),
+        }
+    }
+}
+
+impl From<HistoricalBalance> for ProtoHistoricalBalance {
+    fn from(b: HistoricalBalance) -> Self {
+        let minor_units = Currency::from_code(&b.currency)
+            .map(|c| c.minor_units())
+            .unwrap_or(2);
+        Self {
+            wallet_id: b.wallet_id.to_string(),
+            currency: b.currency,
+            ledger_balance: b.ledger_balance.to_string(),
+            as_of: b.as_of.to_rfc3339(),
+            minor_units,
+        }
+    }
+}
+
+impl From<LedgerEntry> for LedgerPosting {
+    fn from(e: LedgerEntry) -> Self {
+        Self {
+            entry_id: e.id().0.to_string(),
+            transaction_id: e.transaction_id().to_string(),
+            amount: e.amount().to_string(),
+            currency: e.currency().to_string(),
+            posted_at: e.created_at().to_rfc3339(),
+        }
+    }
+}
+
+/// Código del estado de la billetera tal como viaja en `WalletBalance.status`.
+fn wallet_status_code(status: WalletStatus) -> &'static str {
+    match status {
+        WalletStatus::Active => "ACTIVE",
+        WalletStatus::Frozen => "FROZEN",
+        WalletStatus::Closed => "CLOSED",
     }
 }
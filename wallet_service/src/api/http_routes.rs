@@ -1,8 +1,12 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue},
+    response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::sync::Arc;
 use utoipa::ToSchema;
@@ -10,11 +14,19 @@ use uuid::Uuid;
 
 use crate::api::error::ApiError;
 use crate::api::response::ApiResponse;
+use crate::use_cases::change_wallet_status::{ChangeWalletStatusUseCase, StatusTransition};
 use crate::use_cases::create_user::CreateUserUseCase;
 use crate::use_cases::create_wallet::CreateWalletUseCase;
+use crate::use_cases::get_historical_balance::GetHistoricalBalanceUseCase;
 use crate::use_cases::get_user_wallets::GetWalletsUseCase;
 use crate::use_cases::get_wallet::GetWalletUseCase;
+use crate::use_cases::get_wallet_entries::GetWalletEntriesUseCase;
+use crate::use_cases::get_wallet_limits::GetWalletLimitsUseCase;
+use crate::use_cases::update_wallet::UpdateWalletUseCase;
+use crate::use_cases::update_wallet_limits::UpdateWalletLimitsUseCase;
 
+use crate::domain::entities::HistoricalBalance;
+use crate::domain::error::WalletError;
 use crate::domain::types::{UserId, WalletId};
 
 pub struct AppState {
@@ -22,6 +34,12 @@ pub struct AppState {
     pub create_wallet_use_case: CreateWalletUseCase,
     pub list_user_wallets_use_case: GetWalletsUseCase,
     pub get_wallet_details_use_case: GetWalletUseCase,
+    pub update_wallet_use_case: UpdateWalletUseCase,
+    pub change_wallet_status_use_case: ChangeWalletStatusUseCase,
+    pub get_wallet_limits_use_case: GetWalletLimitsUseCase,
+    pub update_wallet_limits_use_case: UpdateWalletLimitsUseCase,
+    pub get_wallet_entries_use_case: GetWalletEntriesUseCase,
+    pub get_historical_balance_use_case: GetHistoricalBalanceUseCase,
 }
 // Definicion de rutas para la API HTTP
 pub fn routes(state: Arc<AppState>) -> Router {
@@ -29,7 +47,19 @@ pub fn routes(state: Arc<AppState>) -> Router {
         .route("/users", post(create_user))
         .route("/users/{user_id}/wallets", get(list_user_wallets))
         .route("/wallets", post(create_wallet))
-        .route("/wallets/{id}", get(get_wallet_details))
+        .route(
+            "/wallets/{id}",
+            get(get_wallet_details).patch(update_wallet),
+        )
+        .route("/wallets/{id}/freeze", post(freeze_wallet))
+        .route("/wallets/{id}/unfreeze", post(unfreeze_wallet))
+        .route("/wallets/{id}/close", post(close_wallet))
+        .route(
+            "/wallets/{id}/limits",
+            get(get_wallet_limits).put(update_wallet_limits),
+        )
+        .route("/wallets/{id}/entries", get(list_wallet_entries))
+        .route("/wallets/{id}/balance", get(get_wallet_balance))
         .with_state(state)
 }
 
@@ -68,37 +98,52 @@ pub async fn create_user(
 #[derive(Deserialize, ToSchema)]
 pub struct CreateWalletRequest {
     pub user_id: Uuid,
+    /// Código ISO 4217 (p. ej. "COP", "JPY", "KWD").
     pub currency: String,
     pub label: String,
+    /// Sobregiro permitido; si se omite la billetera no admite saldo negativo.
+    pub overdraft_limit: Option<Decimal>,
 }
 
 // Handler: Crear una nueva billetera para un usuario
 // POST /wallets
 // Header: x-user-id requerido
+// Responde con `ETag` para que el cliente pueda condicionar modificaciones posteriores.
 #[utoipa::path(
     post,
     path = "/wallets",
     request_body = CreateWalletRequest,
     responses(
-        (status = 200, description = "Billetera creada exitosamente", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+        (status = 200, description = "Billetera creada exitosamente", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Versión de la billetera")))
     )
 )]
 pub async fn create_wallet(
     State(app_state): State<Arc<AppState>>,
     Json(payload): Json<CreateWalletRequest>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+) -> Result<impl IntoResponse, ApiError> {
     let wallet = app_state
         .create_wallet_use_case
-        .execute(UserId(payload.user_id), payload.currency, payload.label)
+        .execute(
+            UserId(payload.user_id),
+            payload.currency,
+            payload.label,
+            payload.overdraft_limit,
+        )
         .await?;
 
-    Ok(Json(ApiResponse::success(serde_json::json!({
+    Ok((
+        etag_header(wallet.version()),
+        Json(ApiResponse::success(serde_json::json!({
         "id": wallet.id(),
         "user_id": wallet.user_id(),
         "currency": wallet.currency(),
-        "balance": wallet.balance(),
+        "ledger_balance": wallet.ledger_balance(),
+        "available_balance": wallet.available_balance(),
+        "overdraft_limit": wallet.overdraft_limit(),
         "label": wallet.label(),
-    }))))
+        }))),
+    ))
 }
 
 // Handler: Listar todas las billeteras del usuario actual
@@ -133,7 +178,8 @@ pub async fn list_user_wallets(
     get,
     path = "/wallets/{id}",
     responses(
-        (status = 200, description = "Detalles de la billetera obtenidos", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+        (status = 200, description = "Detalles de la billetera obtenidos", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Versión de la billetera")))
     ),
     params(
         ("id" = Uuid, Path, description = "ID de la billetera")
@@ -142,13 +188,392 @@ pub async fn list_user_wallets(
 pub async fn get_wallet_details(
     State(app_state): State<Arc<AppState>>,
     Path(wallet_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+) -> Result<impl IntoResponse, ApiError> {
     let wallet = app_state
         .get_wallet_details_use_case
         .execute(WalletId(wallet_id))
         .await?;
 
+    Ok((
+        etag_header(wallet.version()),
+        Json(ApiResponse::success(serde_json::json!({
+            "wallet": wallet
+        }))),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateWalletRequest {
+    pub label: Option<String>,
+    /// Nuevo sobregiro permitido; cero lo desactiva.
+    pub overdraft_limit: Option<Decimal>,
+}
+
+// Handler: Modificar etiqueta y/o sobregiro de una billetera
+// PATCH /wallets/{id}
+// Header opcional: If-Match con el ETag leído; si la billetera cambió responde 412.
+#[utoipa::path(
+    patch,
+    path = "/wallets/{id}",
+    request_body = UpdateWalletRequest,
+    responses(
+        (status = 200, description = "Billetera actualizada", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Nueva versión de la billetera"))),
+        (status = 412, description = "La versión de If-Match ya no es la actual")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("If-Match" = Option<String>, Header, description = "ETag esperado de la billetera")
+    )
+)]
+pub async fn update_wallet(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateWalletRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let expected_version = parse_if_match(&headers)?;
+
+    let wallet = app_state
+        .update_wallet_use_case
+        .execute(
+            WalletId(wallet_id),
+            payload.label,
+            payload.overdraft_limit,
+            expected_version,
+        )
+        .await
+        .map_err(|e| precondition_error(e, expected_version))?;
+
+    Ok((
+        etag_header(wallet.version()),
+        Json(ApiResponse::success(serde_json::json!({
+            "wallet": wallet
+        }))),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ChangeStatusRequest {
+    /// Motivo del cambio; obligatorio para congelar y cerrar.
+    pub reason: Option<String>,
+}
+
+// Handler: Congelar una billetera (rechaza débitos; créditos según configuración)
+// POST /wallets/{id}/freeze
+#[utoipa::path(
+    post,
+    path = "/wallets/{id}/freeze",
+    request_body = ChangeStatusRequest,
+    responses(
+        (status = 200, description = "Billetera congelada", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Nueva versión de la billetera"))),
+        (status = 409, description = "La billetera no está activa"),
+        (status = 412, description = "La versión de If-Match ya no es la actual")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("If-Match" = Option<String>, Header, description = "ETag esperado de la billetera")
+    )
+)]
+pub async fn freeze_wallet(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<ChangeStatusRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    change_wallet_status(
+        &app_state,
+        wallet_id,
+        &headers,
+        StatusTransition::Freeze,
+        payload,
+    )
+    .await
+}
+
+// Handler: Descongelar una billetera
+// POST /wallets/{id}/unfreeze
+#[utoipa::path(
+    post,
+    path = "/wallets/{id}/unfreeze",
+    request_body = ChangeStatusRequest,
+    responses(
+        (status = 200, description = "Billetera activa nuevamente", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Nueva versión de la billetera"))),
+        (status = 409, description = "La billetera no está congelada"),
+        (status = 412, description = "La versión de If-Match ya no es la actual")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("If-Match" = Option<String>, Header, description = "ETag esperado de la billetera")
+    )
+)]
+pub async fn unfreeze_wallet(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<ChangeStatusRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    change_wallet_status(
+        &app_state,
+        wallet_id,
+        &headers,
+        StatusTransition::Unfreeze,
+        payload,
+    )
+    .await
+}
+
+// Handler: Cerrar definitivamente una billetera (requiere saldo cero y sin reservas)
+// POST /wallets/{id}/close
+#[utoipa::path(
+    post,
+    path = "/wallets/{id}/close",
+    request_body = ChangeStatusRequest,
+    responses(
+        (status = 200, description = "Billetera cerrada", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Nueva versión de la billetera"))),
+        (status = 409, description = "La billetera tiene saldo, reservas o ya está cerrada"),
+        (status = 412, description = "La versión de If-Match ya no es la actual")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("If-Match" = Option<String>, Header, description = "ETag esperado de la billetera")
+    )
+)]
+pub async fn close_wallet(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<ChangeStatusRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    change_wallet_status(
+        &app_state,
+        wallet_id,
+        &headers,
+        StatusTransition::Close,
+        payload,
+    )
+    .await
+}
+
+async fn change_wallet_status(
+    app_state: &AppState,
+    wallet_id: Uuid,
+    headers: &HeaderMap,
+    transition: StatusTransition,
+    payload: ChangeStatusRequest,
+) -> Result<impl IntoResponse, ApiError> {
+    let expected_version = parse_if_match(headers)?;
+
+    let wallet = app_state
+        .change_wallet_status_use_case
+        .execute(
+            WalletId(wallet_id),
+            transition,
+            payload.reason,
+            expected_version,
+        )
+        .await
+        .map_err(|e| precondition_error(e, expected_version))?;
+
+    Ok((
+        etag_header(wallet.version()),
+        Json(ApiResponse::success(serde_json::json!({
+            "wallet": wallet
+        }))),
+    ))
+}
+
+// Handler: Consultar límites de gasto y consumo del día/mes en curso
+// GET /wallets/{id}/limits
+#[utoipa::path(
+    get,
+    path = "/wallets/{id}/limits",
+    responses(
+        (status = 200, description = "Límites y consumo actual", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 404, description = "Billetera no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera")
+    )
+)]
+pub async fn get_wallet_limits(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limits = app_state
+        .get_wallet_limits_use_case
+        .execute(WalletId(wallet_id))
+        .await?;
+
+    Ok(Json(ApiResponse::success(limits)))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateLimitsRequest {
+    /// Débito máximo por transacción; ausente o `null` significa sin límite.
+    pub per_transaction_limit: Option<Decimal>,
+    /// Débitos máximos por día calendario (UTC).
+    pub daily_limit: Option<Decimal>,
+    /// Débitos máximos por mes calendario (UTC).
+    pub monthly_limit: Option<Decimal>,
+}
+
+// Handler: Reemplazar los límites de gasto de una billetera
+// PUT /wallets/{id}/limits
+// Header opcional: If-Match con el ETag de la billetera; si cambió responde 412.
+#[utoipa::path(
+    put,
+    path = "/wallets/{id}/limits",
+    request_body = UpdateLimitsRequest,
+    responses(
+        (status = 200, description = "Límites actualizados", body = inline(crate::api::response::ApiResponse<serde_json::Value>),
+            headers(("ETag" = String, description = "Nueva versión de la billetera"))),
+        (status = 400, description = "Límites no positivos o incoherentes"),
+        (status = 404, description = "Billetera no encontrada"),
+        (status = 412, description = "La versión de If-Match ya no es la actual")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("If-Match" = Option<String>, Header, description = "ETag esperado de la billetera")
+    )
+)]
+pub async fn update_wallet_limits(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateLimitsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let expected_version = parse_if_match(&headers)?;
+
+    let (wallet, limits) = app_state
+        .update_wallet_limits_use_case
+        .execute(
+            WalletId(wallet_id),
+            payload.per_transaction_limit,
+            payload.daily_limit,
+            payload.monthly_limit,
+            expected_version,
+        )
+        .await
+        .map_err(|e| precondition_error(e, expected_version))?;
+
+    Ok((
+        etag_header(wallet.version()),
+        Json(ApiResponse::success(serde_json::json!({
+            "wallet_id": wallet_id,
+            "limits": limits
+        }))),
+    ))
+}
+
+/// Cabecera `ETag` (fuerte) derivada de la versión de la billetera.
+fn etag_header(version: i32) -> [(header::HeaderName, HeaderValue); 1] {
+    let value = HeaderValue::from_str(&format!("\"{}\"", version))
+        .expect("Un entero siempre es un valor de cabecera válido");
+    [(header::ETAG, value)]
+}
+
+/// Extrae la versión esperada de `If-Match`. Acepta `"3"`, `W/"3"` o `*` (sin condición).
+fn parse_if_match(headers: &HeaderMap) -> Result<Option<i32>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let invalid = || {
+        ApiError::from(WalletError::InvalidData(
+            "Cabecera If-Match inválida".into(),
+        ))
+    };
+    let raw = value.to_str().map_err(|_| invalid())?.trim();
+    if raw == "*" {
+        return Ok(None);
+    }
+
+    raw.trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i32>()
+        .map(Some)
+        .map_err(|_| invalid())
+}
+
+/// Un conflicto de versión con `If-Match` presente es una precondición fallida (412), no 409.
+fn precondition_error(error: WalletError, expected_version: Option<i32>) -> ApiError {
+    match (error, expected_version) {
+        (WalletError::ConcurrencyError(message), Some(_)) => ApiError::PreconditionFailed(message),
+        (error, _) => error.into(),
+    }
+}
+
+// Parámetros de paginación para consultar el libro mayor
+#[derive(Deserialize)]
+pub struct EntriesQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+// Handler: Paginar los asientos contables de una billetera
+// GET /wallets/{id}/entries?limit=&offset=
+#[utoipa::path(
+    get,
+    path = "/wallets/{id}/entries",
+    responses(
+        (status = 200, description = "Asientos de la billetera", body = inline(crate::api::response::ApiResponse<serde_json::Value>))
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("limit" = Option<i64>, Query, description = "Tamaño de página (máx. 200)"),
+        ("offset" = Option<i64>, Query, description = "Asientos a saltar")
+    )
+)]
+pub async fn list_wallet_entries(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    Query(query): Query<EntriesQuery>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, ApiError> {
+    let entries = app_state
+        .get_wallet_entries_use_case
+        .execute(WalletId(wallet_id), query.limit, query.offset)
+        .await?;
+
     Ok(Json(ApiResponse::success(serde_json::json!({
-        "wallet": wallet
+        "entries": entries
     }))))
 }
+
+// Parámetros de la consulta de saldo histórico
+#[derive(Deserialize)]
+pub struct BalanceQuery {
+    /// Instante a consultar (RFC 3339); por defecto, ahora.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+// Handler: Saldo contable de una billetera en un instante (por defecto, el actual)
+// GET /wallets/{id}/balance?as_of=
+#[utoipa::path(
+    get,
+    path = "/wallets/{id}/balance",
+    responses(
+        (status = 200, description = "Saldo contable en el instante pedido", body = inline(crate::api::response::ApiResponse<serde_json::Value>)),
+        (status = 400, description = "as_of en el futuro"),
+        (status = 404, description = "Billetera no encontrada")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID de la billetera"),
+        ("as_of" = Option<String>, Query, description = "Instante RFC 3339, p. ej. 2024-03-31T23:59:59Z")
+    )
+)]
+pub async fn get_wallet_balance(
+    State(app_state): State<Arc<AppState>>,
+    Path(wallet_id): Path<Uuid>,
+    Query(query): Query<BalanceQuery>,
+) -> Result<Json<ApiResponse<HistoricalBalance>>, ApiError> {
+    let balance = app_state
+        .get_historical_balance_use_case
+        .execute(WalletId(wallet_id), query.as_of.unwrap_or_else(Utc::now))
+        .await?;
+
+    Ok(Json(ApiResponse::success(balance)))
+}
@@ -1,9 +1,10 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::currency::Currency;
 use crate::domain::error::{UserError, WalletError};
-use crate::domain::types::{UserId, WalletId};
+use crate::domain::types::{EntryId, HoldId, UserId, WalletId};
 
 /// Modelo de Entidad: User.
 /// Representa a un usuario dentro del sistema, con su información básica de identidad.
@@ -92,6 +93,16 @@ impl User {
 /// Modelo de Entidad: Wallet.
 /// Representa una billetera de un usuario, que alinea fondos en una divisa específica e implementa optimistic locking.
 ///
+/// Maneja dos balances:
+/// - `ledger_balance`: fondos contabilizados (sólo cambia al capturar una reserva, con su asiento en el ledger).
+/// - `available_balance`: fondos utilizables, es decir, el balance contable menos las reservas de débito activas.
+///
+/// Regla de saldo: el balance disponible nunca puede quedar por debajo de `-overdraft_limit`
+/// (o de cero si la billetera no tiene sobregiro configurado).
+///
+/// Ciclo de vida: `ACTIVE` ⇄ `FROZEN` → `CLOSED`. Una billetera congelada no admite débitos
+/// nuevos; una cerrada no admite ningún movimiento y sólo puede cerrarse con saldo cero.
+///
 /// # Examples
 /// ```
 /// use wallet_service::domain::entities::Wallet;
@@ -111,9 +122,13 @@ pub struct Wallet {
     id: WalletId,
     user_id: UserId, // FK -> User.id
     label: String,
-    balance: Decimal, // Precisión fija
-    currency: String, // ISO code
-    version: i32,     // Optimistic Locking
+    ledger_balance: Decimal,          // Precisión fija
+    available_balance: Decimal,       // ledger_balance - reservas activas
+    overdraft_limit: Option<Decimal>, // Sobregiro permitido (None = sin sobregiro)
+    currency: String,                 // ISO code
+    version: i32,                     // Optimistic Locking
+    #[serde(flatten)]
+    status: StatusChange, // Estado del ciclo de vida y su último cambio
 }
 
 impl Wallet {
@@ -131,14 +146,29 @@ impl Wallet {
 
     /// Reconstruye una billetera cargada desde la persistencia o en memoria.
     /// Valida los datos esenciales siguiendo reglas de dominio básicas.
+    ///
+    /// `reserved_balance` es el total de reservas de débito activas; el balance disponible se
+    /// deriva como `ledger_balance - reserved_balance`.
+    #[allow(clippy::too_many_arguments)]
     pub fn reconstitute(
         id: WalletId,
         user_id: UserId,
         label: String,
-        balance: Decimal,
+        ledger_balance: Decimal,
+        reserved_balance: Decimal,
+        overdraft_limit: Option<Decimal>,
         currency: String,
         version: i32,
+        status: StatusChange,
     ) -> Result<Self, WalletError> {
+        validate_overdraft_limit(overdraft_limit)?;
+
+        if reserved_balance.is_sign_negative() {
+            return Err(WalletError::InvalidData(
+                "El monto reservado no puede ser negativo".into(),
+            ));
+        }
+
         if label.trim().is_empty() {
             return Err(WalletError::InvalidData(
                 "La etiqueta de la wallet no puede estar en blanco".into(),
@@ -156,9 +186,12 @@ impl Wallet {
             id,
             user_id,
             label,
-            balance,
+            ledger_balance,
+            available_balance: ledger_balance - reserved_balance,
+            overdraft_limit,
             currency,
             version,
+            status,
         })
     }
 
@@ -174,14 +207,187 @@ impl Wallet {
         &self.label
     }
 
-    pub fn balance(&self) -> Decimal {
-        self.balance
+    /// Balance contable: fondos ya capturados y registrados en el ledger.
+    pub fn ledger_balance(&self) -> Decimal {
+        self.ledger_balance
+    }
+
+    /// Balance disponible: balance contable menos las reservas de débito activas.
+    pub fn available_balance(&self) -> Decimal {
+        self.available_balance
+    }
+
+    /// Total retenido por reservas de débito aún no capturadas ni liberadas.
+    pub fn reserved_balance(&self) -> Decimal {
+        self.ledger_balance - self.available_balance
+    }
+
+    /// Sobregiro máximo permitido, si la billetera tiene uno configurado.
+    pub fn overdraft_limit(&self) -> Option<Decimal> {
+        self.overdraft_limit
+    }
+
+    /// Monto máximo que puede debitarse ahora: disponible más el sobregiro permitido.
+    pub fn spendable_balance(&self) -> Decimal {
+        self.available_balance + self.overdraft_limit.unwrap_or(Decimal::ZERO)
+    }
+
+    /// Verifica que un débito de `amount` (magnitud positiva) no deje el saldo por debajo
+    /// del sobregiro permitido.
+    ///
+    /// # Examples
+    /// ```
+    /// use wallet_service::domain::entities::Wallet;
+    /// use wallet_service::domain::types::UserId;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let wallet = Wallet::builder()
+    ///     .user_id(UserId::new())
+    ///     .label("Main".to_string())
+    ///     .currency("USD".to_string())
+    ///     .overdraft_limit(Decimal::from(50))
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(wallet.ensure_can_debit(Decimal::from(50)).is_ok());
+    /// assert!(wallet.ensure_can_debit(Decimal::from(51)).is_err());
+    /// ```
+    pub fn ensure_can_debit(&self, amount: Decimal) -> Result<(), WalletError> {
+        if amount > self.spendable_balance() {
+            return Err(WalletError::InsufficientFunds(self.id));
+        }
+        Ok(())
+    }
+
+    pub fn status(&self) -> WalletStatus {
+        self.status.status
+    }
+
+    /// Último cambio de estado (motivo y fecha).
+    pub fn status_change(&self) -> &StatusChange {
+        &self.status
+    }
+
+    /// Verifica que la billetera admita un movimiento nuevo.
+    ///
+    /// - `CLOSED` rechaza todo.
+    /// - `FROZEN` rechaza débitos, y créditos salvo que `frozen_accepts_credits` sea `true`.
+    pub fn ensure_accepts_movement(
+        &self,
+        is_debit: bool,
+        frozen_accepts_credits: bool,
+    ) -> Result<(), WalletError> {
+        let allowed = match self.status() {
+            WalletStatus::Active => true,
+            WalletStatus::Frozen => !is_debit && frozen_accepts_credits,
+            WalletStatus::Closed => false,
+        };
+        if !allowed {
+            return Err(WalletError::WalletNotActive {
+                wallet_id: self.id,
+                status: self.status(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Congela la billetera (sólo desde `ACTIVE`). El motivo es obligatorio.
+    pub fn freeze(&mut self, reason: String) -> Result<(), WalletError> {
+        self.transition(WalletStatus::Frozen, Some(reason), &[WalletStatus::Active])
+    }
+
+    /// Descongela la billetera (sólo desde `FROZEN`).
+    pub fn unfreeze(&mut self, reason: Option<String>) -> Result<(), WalletError> {
+        self.transition(WalletStatus::Active, reason, &[WalletStatus::Frozen])
+    }
+
+    /// Cierra la billetera de forma definitiva. Requiere saldo contable cero y ninguna reserva
+    /// pendiente. El motivo es obligatorio.
+    pub fn close(&mut self, reason: String) -> Result<(), WalletError> {
+        if !self.ledger_balance.is_zero() || !self.reserved_balance().is_zero() {
+            return Err(WalletError::InvalidStatusTransition(format!(
+                "La wallet {} sólo puede cerrarse con saldo cero y sin reservas (saldo {}, reservado {})",
+                self.id,
+                self.ledger_balance,
+                self.reserved_balance()
+            )));
+        }
+        self.transition(
+            WalletStatus::Closed,
+            Some(reason),
+            &[WalletStatus::Active, WalletStatus::Frozen],
+        )
+    }
+
+    fn transition(
+        &mut self,
+        to: WalletStatus,
+        reason: Option<String>,
+        allowed_from: &[WalletStatus],
+    ) -> Result<(), WalletError> {
+        if !allowed_from.contains(&self.status()) {
+            return Err(WalletError::InvalidStatusTransition(format!(
+                "La wallet {} no puede pasar de {:?} a {:?}",
+                self.id,
+                self.status(),
+                to
+            )));
+        }
+
+        let reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        if reason.is_none() && to != WalletStatus::Active {
+            return Err(WalletError::InvalidData(
+                "Se requiere un motivo para congelar o cerrar una wallet".into(),
+            ));
+        }
+
+        self.status = StatusChange {
+            status: to,
+            reason,
+            changed_at: Utc::now(),
+        };
+        Ok(())
+    }
+
+    /// Cambia la etiqueta de la billetera validando que no quede en blanco.
+    pub fn set_label(&mut self, label: String) -> Result<(), WalletError> {
+        if label.trim().is_empty() {
+            return Err(WalletError::InvalidData(
+                "La etiqueta de la wallet no puede estar en blanco".into(),
+            ));
+        }
+        self.label = label;
+        Ok(())
+    }
+
+    /// Cambia el sobregiro permitido.
+    ///
+    /// No se permite reducirlo por debajo del sobregiro ya utilizado: el saldo disponible
+    /// actual debe seguir cumpliendo la regla con el nuevo límite.
+    pub fn set_overdraft_limit(&mut self, limit: Option<Decimal>) -> Result<(), WalletError> {
+        validate_overdraft_limit(limit)?;
+        if let Some(limit) = limit {
+            self.ensure_amount_scale(limit)?;
+        }
+        if self.available_balance + limit.unwrap_or(Decimal::ZERO) < Decimal::ZERO {
+            return Err(WalletError::InvalidData(
+                "El nuevo sobregiro es menor al saldo negativo actual de la wallet".into(),
+            ));
+        }
+        self.overdraft_limit = limit;
+        Ok(())
     }
 
     pub fn currency(&self) -> &str {
         &self.currency
     }
 
+    /// Verifica que `amount` respete los decimales de la divisa de la billetera (ISO 4217).
+    pub fn ensure_amount_scale(&self, amount: Decimal) -> Result<(), WalletError> {
+        Currency::from_code(&self.currency)?.ensure_scale(amount)
+    }
+
     pub fn version(&self) -> i32 {
         self.version
     }
@@ -205,6 +411,7 @@ pub struct WalletBuilder {
     user_id: Option<UserId>,
     label: Option<String>,
     currency: Option<String>,
+    overdraft_limit: Option<Decimal>,
 }
 
 impl WalletBuilder {
@@ -223,6 +430,12 @@ impl WalletBuilder {
         self
     }
 
+    /// Configura un sobregiro opcional (debe ser mayor o igual a cero).
+    pub fn overdraft_limit(mut self, overdraft_limit: Decimal) -> Self {
+        self.overdraft_limit = Some(overdraft_limit);
+        self
+    }
+
     /// Construye y valida la entidad instanciada.
     ///
     /// # Examples
@@ -255,20 +468,813 @@ impl WalletBuilder {
             ));
         }
 
-        let currency = currency.trim().to_uppercase();
-        if currency.len() != 3 {
-            return Err(WalletError::InvalidData(
-                "La divisa debe ser un código ISO de 3 letras".into(),
-            ));
+        let currency = Currency::from_code(&currency)?;
+
+        validate_overdraft_limit(self.overdraft_limit)?;
+        if let Some(limit) = self.overdraft_limit {
+            currency.ensure_scale(limit)?;
         }
 
         Ok(Wallet {
             id: WalletId::new(),
             user_id,
             label,
-            balance: Decimal::from(0),
-            currency,
+            ledger_balance: Decimal::ZERO,
+            available_balance: Decimal::ZERO,
+            overdraft_limit: self.overdraft_limit,
+            currency: currency.code().to_string(),
             version: 0,
+            status: StatusChange::active(),
         })
     }
 }
+
+/// Estados del ciclo de vida de una billetera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "wallet_status", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WalletStatus {
+    Active,
+    Frozen,
+    Closed,
+}
+
+/// Estado actual de una billetera junto con el motivo y la fecha de su último cambio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: WalletStatus,
+    #[serde(rename = "status_reason")]
+    pub reason: Option<String>,
+    #[serde(rename = "status_changed_at")]
+    pub changed_at: DateTime<Utc>,
+}
+
+impl StatusChange {
+    /// Estado inicial de una billetera recién creada.
+    pub fn active() -> Self {
+        Self {
+            status: WalletStatus::Active,
+            reason: None,
+            changed_at: Utc::now(),
+        }
+    }
+}
+
+/// Saldo de una billetera leído en un instante concreto.
+///
+/// Es un modelo de sólo lectura para conciliaciones: `taken_at` es la hora de la BD en que se
+/// leyó la fila, de modo que los consumidores puedan compararlo con sus propios registros.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceSnapshot {
+    pub wallet_id: WalletId,
+    pub currency: String,
+    pub ledger_balance: Decimal,
+    pub available_balance: Decimal,
+    pub version: i32,
+    pub status: WalletStatus,
+    pub taken_at: DateTime<Utc>,
+}
+
+/// Saldo contable de una billetera al cierre de un día calendario (UTC).
+///
+/// Lo persiste el job diario de snapshots; sirve de punto de partida para reconstruir saldos
+/// históricos sin recorrer todo el libro mayor.
+///
+/// # Examples
+/// ```
+/// use wallet_service::domain::entities::DailyBalanceSnapshot;
+/// use chrono::{NaiveDate, TimeZone, Utc};
+///
+/// let day = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+/// assert_eq!(
+///     DailyBalanceSnapshot::closes_at(day),
+///     Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyBalanceSnapshot {
+    pub wallet_id: WalletId,
+    pub balance_date: NaiveDate,
+    pub ledger_balance: Decimal,
+    pub currency: String,
+}
+
+impl DailyBalanceSnapshot {
+    /// Instante en que cierra `balance_date`: el snapshot incluye los asientos anteriores a él.
+    pub fn closes_at(balance_date: NaiveDate) -> DateTime<Utc> {
+        balance_date
+            .succ_opt()
+            .unwrap_or(balance_date)
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+    }
+}
+
+/// Saldo contable de una billetera en un instante pasado.
+///
+/// `snapshot_date` indica el snapshot diario desde el que se reconstruyó (ninguno si se sumó
+/// el libro mayor completo).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalBalance {
+    pub wallet_id: WalletId,
+    pub currency: String,
+    pub ledger_balance: Decimal,
+    pub as_of: DateTime<Utc>,
+    pub snapshot_date: Option<NaiveDate>,
+}
+
+/// Períodos sobre los que se controla el gasto (débitos) de una billetera.
+///
+/// Los períodos diario y mensual son de calendario en UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LimitPeriod {
+    Transaction,
+    Daily,
+    Monthly,
+}
+
+impl LimitPeriod {
+    /// Primer día del período que contiene a `at` (el propio día para `Transaction`).
+    pub fn start_of(self, at: DateTime<Utc>) -> NaiveDate {
+        let day = at.date_naive();
+        match self {
+            LimitPeriod::Transaction | LimitPeriod::Daily => day,
+            LimitPeriod::Monthly => day.with_day(1).unwrap_or(day),
+        }
+    }
+}
+
+/// Límites de débito (controles de velocidad) configurados para una billetera.
+///
+/// `None` significa sin límite para ese período. Los límites se expresan en la divisa de la
+/// billetera y se comparan contra el valor absoluto de los débitos reservados.
+///
+/// # Examples
+/// ```
+/// use wallet_service::domain::entities::{DebitUsage, SpendingLimits};
+/// use wallet_service::domain::types::WalletId;
+/// use rust_decimal::Decimal;
+///
+/// let limits = SpendingLimits::new(Some(Decimal::from(100)), Some(Decimal::from(300)), None).unwrap();
+/// // Consumo del día ya incluyendo el débito evaluado.
+/// let usage = DebitUsage { daily: Decimal::from(300), monthly: Decimal::from(300) };
+/// assert!(limits.ensure_allows(WalletId::new(), Decimal::from(50), &usage).is_ok());
+///
+/// let usage = DebitUsage { daily: Decimal::from(301), monthly: Decimal::from(301) };
+/// assert!(limits.ensure_allows(WalletId::new(), Decimal::from(51), &usage).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpendingLimits {
+    pub per_transaction: Option<Decimal>,
+    pub daily: Option<Decimal>,
+    pub monthly: Option<Decimal>,
+}
+
+impl SpendingLimits {
+    /// Crea un conjunto de límites validando que sean positivos y coherentes entre sí
+    /// (por transacción ≤ diario ≤ mensual, cuando ambos existen).
+    pub fn new(
+        per_transaction: Option<Decimal>,
+        daily: Option<Decimal>,
+        monthly: Option<Decimal>,
+    ) -> Result<Self, WalletError> {
+        if [per_transaction, daily, monthly]
+            .iter()
+            .flatten()
+            .any(|l| *l <= Decimal::ZERO)
+        {
+            return Err(WalletError::InvalidData(
+                "Los límites de gasto deben ser mayores a cero".into(),
+            ));
+        }
+        let ordered = |lower: Option<Decimal>, upper: Option<Decimal>| match (lower, upper) {
+            (Some(l), Some(u)) => l <= u,
+            _ => true,
+        };
+        if !ordered(per_transaction, daily)
+            || !ordered(daily, monthly)
+            || !ordered(per_transaction, monthly)
+        {
+            return Err(WalletError::InvalidData(
+                "Los límites deben cumplir por transacción ≤ diario ≤ mensual".into(),
+            ));
+        }
+
+        Ok(Self {
+            per_transaction,
+            daily,
+            monthly,
+        })
+    }
+
+    /// Verifica que un débito de `amount` (valor absoluto) quepa en los límites.
+    ///
+    /// `usage_after` es el consumo de los períodos **incluyendo** este débito, tal como queda
+    /// tras incrementar los contadores; así la verificación es válida aunque otras reservas
+    /// concurrentes hayan consumido del mismo período.
+    pub fn ensure_allows(
+        &self,
+        wallet_id: WalletId,
+        amount: Decimal,
+        usage_after: &DebitUsage,
+    ) -> Result<(), WalletError> {
+        let checks = [
+            (LimitPeriod::Transaction, self.per_transaction, amount),
+            (LimitPeriod::Daily, self.daily, usage_after.daily),
+            (LimitPeriod::Monthly, self.monthly, usage_after.monthly),
+        ];
+        for (period, limit, used) in checks {
+            if let Some(limit) = limit {
+                if used > limit {
+                    return Err(WalletError::LimitExceeded {
+                        wallet_id,
+                        period,
+                        limit,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Débitos acumulados de una billetera en el día y el mes en curso.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebitUsage {
+    pub daily: Decimal,
+    pub monthly: Decimal,
+}
+
+/// Valida que el sobregiro, si existe, no sea negativo.
+fn validate_overdraft_limit(limit: Option<Decimal>) -> Result<(), WalletError> {
+    if limit.is_some_and(|l| l.is_sign_negative()) {
+        return Err(WalletError::InvalidData(
+            "El sobregiro permitido no puede ser negativo".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Cuentas internas del sistema que actúan como contrapartida en la contabilidad de doble partida.
+///
+/// Cada movimiento sobre una billetera genera un asiento espejo en una de estas cuentas,
+/// de modo que la suma de los montos de todos los asientos de una transacción sea cero.
+///
+/// # Examples
+/// ```
+/// use wallet_service::domain::entities::SystemAccount;
+///
+/// let account = SystemAccount::for_transaction_type("DEPOSIT");
+/// assert_eq!(account, SystemAccount::ExternalFunding);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "system_account", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SystemAccount {
+    /// Dinero que entra o sale del sistema (recargas y retiros hacia medios externos).
+    ExternalFunding,
+    /// Comisiones cobradas por la plataforma.
+    Fees,
+    /// Cuenta puente para fondos en tránsito entre dos billeteras (ej. las dos patas de una transferencia).
+    Suspense,
+}
+
+impl SystemAccount {
+    /// Determina la cuenta de contrapartida a partir del tipo de transacción que origina el movimiento.
+    ///
+    /// Los depósitos y retiros mueven dinero desde/hacia el exterior (`ExternalFunding`), mientras que
+    /// las transferencias (o tipos desconocidos) usan `Suspense`, que queda en cero cuando ambas patas se aplican.
+    pub fn for_transaction_type(transaction_type: &str) -> Self {
+        match transaction_type.trim().to_uppercase().as_str() {
+            "DEPOSIT" | "WITHDRAWAL" => SystemAccount::ExternalFunding,
+            _ => SystemAccount::Suspense,
+        }
+    }
+}
+
+/// Cuenta afectada por un asiento contable: una billetera de usuario o una cuenta del sistema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LedgerAccount {
+    Wallet(WalletId),
+    System(SystemAccount),
+}
+
+/// Modelo de Entidad: LedgerEntry.
+/// Asiento inmutable del libro mayor (`wallet_entries`) que explica cada cambio de balance.
+///
+/// El monto es con signo: positivo para créditos y negativo para débitos.
+/// `balance_after` guarda el balance de la cuenta inmediatamente después de aplicar el asiento.
+///
+/// # Examples
+/// ```
+/// use wallet_service::domain::entities::{LedgerAccount, LedgerEntry};
+/// use wallet_service::domain::types::WalletId;
+/// use rust_decimal::Decimal;
+///
+/// let entry = LedgerEntry::new(
+///     "tx-1".to_string(),
+///     LedgerAccount::Wallet(WalletId::new()),
+///     Decimal::from(-20),
+///     Decimal::from(80),
+///     "USD".to_string(),
+/// );
+/// assert!(entry.is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    id: EntryId,
+    transaction_id: String,
+    account: LedgerAccount,
+    amount: Decimal,        // Con signo: + crédito, - débito
+    balance_after: Decimal, // Balance resultante de la cuenta
+    currency: String,
+    created_at: DateTime<Utc>,
+}
+
+impl LedgerEntry {
+    /// Crea un nuevo asiento validando que tenga transacción asociada y un monto distinto de cero.
+    pub fn new(
+        transaction_id: String,
+        account: LedgerAccount,
+        amount: Decimal,
+        balance_after: Decimal,
+        currency: String,
+    ) -> Result<Self, WalletError> {
+        Self::reconstitute(
+            EntryId::new(),
+            transaction_id,
+            account,
+            amount,
+            balance_after,
+            currency,
+            Utc::now(),
+        )
+    }
+
+    /// Reconstruye un asiento desde la persistencia aplicando las mismas validaciones que `new`.
+    pub fn reconstitute(
+        id: EntryId,
+        transaction_id: String,
+        account: LedgerAccount,
+        amount: Decimal,
+        balance_after: Decimal,
+        currency: String,
+        created_at: DateTime<Utc>,
+    ) -> Result<Self, WalletError> {
+        if transaction_id.trim().is_empty() {
+            return Err(WalletError::InvalidData(
+                "El asiento debe referenciar una transacción".into(),
+            ));
+        }
+        if amount.is_zero() {
+            return Err(WalletError::InvalidData(
+                "El monto de un asiento no puede ser cero".into(),
+            ));
+        }
+
+        Ok(Self {
+            id,
+            transaction_id,
+            account,
+            amount,
+            balance_after,
+            currency,
+            created_at,
+        })
+    }
+
+    /// Verifica la regla fundamental de la doble partida: los asientos de un movimiento suman cero.
+    ///
+    /// # Examples
+    /// ```
+    /// use wallet_service::domain::entities::{LedgerAccount, LedgerEntry, SystemAccount};
+    /// use wallet_service::domain::types::WalletId;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let credit = LedgerEntry::new("tx".into(), LedgerAccount::Wallet(WalletId::new()), Decimal::from(10), Decimal::from(10), "USD".into()).unwrap();
+    /// let debit = LedgerEntry::new("tx".into(), LedgerAccount::System(SystemAccount::ExternalFunding), Decimal::from(-10), Decimal::from(-10), "USD".into()).unwrap();
+    /// assert!(LedgerEntry::ensure_balanced(&[credit, debit]).is_ok());
+    /// ```
+    pub fn ensure_balanced(entries: &[LedgerEntry]) -> Result<(), WalletError> {
+        let total: Decimal = entries.iter().map(|e| e.amount).sum();
+        if !total.is_zero() {
+            return Err(WalletError::InvalidData(format!(
+                "Los asientos no cuadran: la suma es {}",
+                total
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn id(&self) -> EntryId {
+        self.id
+    }
+
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    pub fn account(&self) -> LedgerAccount {
+        self.account
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn balance_after(&self) -> Decimal {
+        self.balance_after
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+/// Estado del ciclo de vida de una reserva de fondos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "hold_status", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HoldStatus {
+    /// Reserva vigente: los fondos de débito están retenidos.
+    Active,
+    /// La transacción se confirmó y el movimiento se contabilizó en el ledger.
+    Captured,
+    /// La transacción se abortó y los fondos retenidos se devolvieron al disponible.
+    Released,
+}
+
+/// Modelo de Entidad: Hold.
+/// Reserva de fondos creada por `ValidateAndReserve`, identificada por (`transaction_id`, `wallet_id`).
+///
+/// Un hold de débito (monto negativo) reduce el balance disponible pero no el contable.
+/// Un hold de crédito (monto positivo) no altera ningún balance hasta ser capturado, de modo
+/// que los fondos entrantes sólo son utilizables cuando la transacción se confirma.
+///
+/// # Examples
+/// ```
+/// use wallet_service::domain::entities::{Hold, HoldStatus, SystemAccount};
+/// use wallet_service::domain::types::WalletId;
+/// use rust_decimal::Decimal;
+///
+/// let mut hold = Hold::new(WalletId::new(), "tx-1".into(), Decimal::from(-30), SystemAccount::Suspense).unwrap();
+/// assert!(hold.is_debit());
+/// hold.capture().unwrap();
+/// assert_eq!(hold.status(), HoldStatus::Captured);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hold {
+    id: HoldId,
+    wallet_id: WalletId,
+    transaction_id: String,
+    amount: Decimal, // Con signo: - débito reservado, + crédito pendiente
+    counterparty: SystemAccount,
+    status: HoldStatus,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Hold {
+    /// Crea una reserva activa validando que tenga transacción asociada y monto distinto de cero.
+    pub fn new(
+        wallet_id: WalletId,
+        transaction_id: String,
+        amount: Decimal,
+        counterparty: SystemAccount,
+    ) -> Result<Self, WalletError> {
+        let now = Utc::now();
+        Self::reconstitute(
+            HoldId::new(),
+            wallet_id,
+            transaction_id,
+            amount,
+            counterparty,
+            HoldStatus::Active,
+            now,
+            now,
+        )
+    }
+
+    /// Reconstruye una reserva desde la persistencia.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reconstitute(
+        id: HoldId,
+        wallet_id: WalletId,
+        transaction_id: String,
+        amount: Decimal,
+        counterparty: SystemAccount,
+        status: HoldStatus,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<Self, WalletError> {
+        if transaction_id.trim().is_empty() {
+            return Err(WalletError::InvalidData(
+                "La reserva debe referenciar una transacción".into(),
+            ));
+        }
+        if amount.is_zero() {
+            return Err(WalletError::InvalidData(
+                "El monto de una reserva no puede ser cero".into(),
+            ));
+        }
+
+        Ok(Self {
+            id,
+            wallet_id,
+            transaction_id,
+            amount,
+            counterparty,
+            status,
+            created_at,
+            updated_at,
+        })
+    }
+
+    /// Marca la reserva como capturada. Sólo es válido desde `Active`.
+    pub fn capture(&mut self) -> Result<(), WalletError> {
+        self.transition(HoldStatus::Captured)
+    }
+
+    /// Marca la reserva como liberada. Sólo es válido desde `Active`.
+    pub fn release(&mut self) -> Result<(), WalletError> {
+        self.transition(HoldStatus::Released)
+    }
+
+    fn transition(&mut self, next: HoldStatus) -> Result<(), WalletError> {
+        if self.status != HoldStatus::Active {
+            return Err(WalletError::InvalidHoldState(format!(
+                "La reserva de la transacción {} ya está en estado {:?}",
+                self.transaction_id, self.status
+            )));
+        }
+        self.status = next;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Indica si la reserva retiene fondos (débito).
+    pub fn is_debit(&self) -> bool {
+        self.amount.is_sign_negative()
+    }
+
+    /// Monto que la reserva descuenta del balance disponible mientras está activa.
+    pub fn reserved_amount(&self) -> Decimal {
+        if self.is_debit() {
+            -self.amount
+        } else {
+            Decimal::ZERO
+        }
+    }
+
+    pub fn id(&self) -> HoldId {
+        self.id
+    }
+
+    pub fn wallet_id(&self) -> WalletId {
+        self.wallet_id
+    }
+
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn counterparty(&self) -> SystemAccount {
+        self.counterparty
+    }
+
+    pub fn status(&self) -> HoldStatus {
+        self.status
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet_with(ledger: i64, reserved: i64, overdraft: Option<i64>) -> Wallet {
+        Wallet::reconstitute(
+            WalletId::new(),
+            UserId::new(),
+            "Main".into(),
+            Decimal::from(ledger),
+            Decimal::from(reserved),
+            overdraft.map(Decimal::from),
+            "USD".into(),
+            1,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_debit_within_available_balance() {
+        let wallet = wallet_with(100, 30, None);
+
+        assert_eq!(wallet.available_balance(), Decimal::from(70));
+        assert!(wallet.ensure_can_debit(Decimal::from(70)).is_ok());
+        assert!(matches!(
+            wallet.ensure_can_debit(Decimal::from(71)),
+            Err(WalletError::InsufficientFunds(_))
+        ));
+    }
+
+    #[test]
+    fn test_debit_uses_overdraft_limit() {
+        let wallet = wallet_with(10, 0, Some(40));
+
+        assert_eq!(wallet.spendable_balance(), Decimal::from(50));
+        assert!(wallet.ensure_can_debit(Decimal::from(50)).is_ok());
+        assert!(wallet.ensure_can_debit(Decimal::from(51)).is_err());
+    }
+
+    #[test]
+    fn test_builder_rejects_negative_overdraft() {
+        let result = Wallet::builder()
+            .user_id(UserId::new())
+            .label("Main".into())
+            .currency("USD".into())
+            .overdraft_limit(Decimal::from(-1))
+            .build();
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_overdraft_cannot_drop_below_current_usage() {
+        let mut wallet = wallet_with(-20, 0, Some(50));
+
+        assert!(wallet.set_overdraft_limit(Some(Decimal::from(10))).is_err());
+        assert!(wallet.set_overdraft_limit(Some(Decimal::from(20))).is_ok());
+        assert_eq!(wallet.overdraft_limit(), Some(Decimal::from(20)));
+    }
+
+    #[test]
+    fn test_frozen_wallet_rejects_debits() {
+        let mut wallet = wallet_with(100, 0, None);
+        wallet.freeze("Fraude reportado".into()).unwrap();
+
+        assert!(matches!(
+            wallet.ensure_accepts_movement(true, true),
+            Err(WalletError::WalletNotActive {
+                status: WalletStatus::Frozen,
+                ..
+            })
+        ));
+        assert!(wallet.ensure_accepts_movement(false, true).is_ok());
+        assert!(wallet.ensure_accepts_movement(false, false).is_err());
+    }
+
+    #[test]
+    fn test_freeze_requires_reason_and_active_status() {
+        let mut wallet = wallet_with(0, 0, None);
+
+        assert!(matches!(
+            wallet.freeze("  ".into()),
+            Err(WalletError::InvalidData(_))
+        ));
+        wallet.freeze("Revisión".into()).unwrap();
+        assert!(matches!(
+            wallet.freeze("Otra vez".into()),
+            Err(WalletError::InvalidStatusTransition(_))
+        ));
+
+        wallet.unfreeze(None).unwrap();
+        assert_eq!(wallet.status(), WalletStatus::Active);
+        assert_eq!(wallet.status_change().reason, None);
+    }
+
+    #[test]
+    fn test_close_requires_zero_balance() {
+        let mut funded = wallet_with(10, 0, None);
+        assert!(matches!(
+            funded.close("Baja".into()),
+            Err(WalletError::InvalidStatusTransition(_))
+        ));
+
+        let mut reserved = wallet_with(0, 5, Some(5));
+        assert!(reserved.close("Baja".into()).is_err());
+
+        let mut empty = wallet_with(0, 0, None);
+        empty.close("Baja".into()).unwrap();
+        assert_eq!(empty.status(), WalletStatus::Closed);
+        assert!(empty.ensure_accepts_movement(false, true).is_err());
+        assert!(empty.unfreeze(None).is_err());
+    }
+
+    #[test]
+    fn test_spending_limits_validation() {
+        assert!(SpendingLimits::new(Some(Decimal::ZERO), None, None).is_err());
+        assert!(
+            SpendingLimits::new(None, Some(Decimal::from(500)), Some(Decimal::from(100))).is_err()
+        );
+        assert!(
+            SpendingLimits::new(Some(Decimal::from(50)), None, Some(Decimal::from(100))).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_spending_limits_report_exceeded_period() {
+        let limits = SpendingLimits::new(
+            Some(Decimal::from(100)),
+            Some(Decimal::from(200)),
+            Some(Decimal::from(1000)),
+        )
+        .unwrap();
+        let wallet_id = WalletId::new();
+        let usage = |daily: i64, monthly: i64| DebitUsage {
+            daily: Decimal::from(daily),
+            monthly: Decimal::from(monthly),
+        };
+
+        assert!(matches!(
+            limits.ensure_allows(wallet_id, Decimal::from(150), &usage(150, 150)),
+            Err(WalletError::LimitExceeded {
+                period: LimitPeriod::Transaction,
+                ..
+            })
+        ));
+        assert!(matches!(
+            limits.ensure_allows(wallet_id, Decimal::from(80), &usage(210, 210)),
+            Err(WalletError::LimitExceeded {
+                period: LimitPeriod::Daily,
+                ..
+            })
+        ));
+        assert!(matches!(
+            limits.ensure_allows(wallet_id, Decimal::from(80), &usage(80, 1080)),
+            Err(WalletError::LimitExceeded {
+                period: LimitPeriod::Monthly,
+                ..
+            })
+        ));
+        assert!(limits
+            .ensure_allows(wallet_id, Decimal::from(80), &usage(200, 1000))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_limit_period_start() {
+        let at = DateTime::parse_from_rfc3339("2024-03-17T23:59:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            LimitPeriod::Daily.start_of(at),
+            NaiveDate::from_ymd_opt(2024, 3, 17).unwrap()
+        );
+        assert_eq!(
+            LimitPeriod::Monthly.start_of(at),
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_unknown_currency() {
+        let result = Wallet::builder()
+            .user_id(UserId::new())
+            .label("Main".into())
+            .currency("ZZZ".into())
+            .build();
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+
+    #[test]
+    fn test_amount_scale_follows_currency() {
+        let build = |currency: &str| {
+            Wallet::builder()
+                .user_id(UserId::new())
+                .label("Main".into())
+                .currency(currency.into())
+                .build()
+                .unwrap()
+        };
+        let jpy = build("jpy");
+        let kwd = build("KWD");
+
+        assert_eq!(jpy.currency(), "JPY");
+        assert!(jpy.ensure_amount_scale(Decimal::from(1500)).is_ok());
+        assert!(jpy.ensure_amount_scale(Decimal::new(15005, 1)).is_err());
+        assert!(kwd.ensure_amount_scale(Decimal::new(1005, 3)).is_ok());
+        assert!(kwd.ensure_amount_scale(Decimal::new(10005, 4)).is_err());
+    }
+}
@@ -1,4 +1,6 @@
+use crate::domain::entities::{LimitPeriod, WalletStatus};
 use crate::domain::types::{UserId, WalletId};
+use rust_decimal::Decimal;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -33,7 +35,38 @@ pub enum WalletError {
     #[error("Insufficient funds in wallet: {0}")]
     InsufficientFunds(WalletId),
 
-    #[error("Optimistic locking conversion error: {0}")]
+    #[error("Spending limit exceeded in wallet {wallet_id}: {period:?} limit is {limit}")]
+    LimitExceeded {
+        wallet_id: WalletId,
+        period: LimitPeriod,
+        limit: Decimal,
+    },
+
+    #[error("Wallet {wallet_id} is not active (status: {status:?})")]
+    WalletNotActive {
+        wallet_id: WalletId,
+        status: WalletStatus,
+    },
+
+    #[error("Invalid wallet status transition: {0}")]
+    InvalidStatusTransition(String),
+
+    #[error("Hold not found for transaction {transaction_id} in wallet {wallet_id}")]
+    HoldNotFound {
+        wallet_id: WalletId,
+        transaction_id: String,
+    },
+
+    #[error("Movement already registered for transaction {transaction_id} in wallet {wallet_id}")]
+    DuplicateMovement {
+        wallet_id: WalletId,
+        transaction_id: String,
+    },
+
+    #[error("Invalid hold state: {0}")]
+    InvalidHoldState(String),
+
+    #[error("Concurrent modification conflict: {0}")]
     ConcurrencyError(String),
 
     #[error("Repository error: {0}")]
@@ -1,7 +1,13 @@
-use crate::domain::entities::{User, Wallet};
+use crate::domain::entities::{
+    BalanceSnapshot, DailyBalanceSnapshot, DebitUsage, Hold, LedgerEntry, SpendingLimits, User,
+    Wallet,
+};
 use crate::domain::error::{UserError, WalletError};
-use crate::domain::types::{UserId, WalletId};
+use crate::domain::types::{EntryId, UserId, WalletId};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::str::FromStr;
 
 // Interface (Port) for User persistence
 #[cfg_attr(test, mockall::automock)]
@@ -12,16 +18,188 @@ pub trait UserRepository: Send + Sync {
     async fn exists_by_username(&self, username: &str) -> Result<bool, UserError>;
 }
 
+/// Estrategia de control de concurrencia sobre la fila de la billetera.
+///
+/// - `Optimistic`: las escrituras son compare-and-set sobre `version`; un conflicto se reporta
+///   como `WalletError::ConcurrencyError` y el caso de uso decide si reintentar.
+/// - `Pessimistic`: cada escritura bloquea la fila con `SELECT ... FOR UPDATE` antes de
+///   modificarla, serializando a los escritores concurrentes en lugar de hacerlos fallar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockingMode {
+    #[default]
+    Optimistic,
+    Pessimistic,
+}
+
+impl FromStr for LockingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "optimistic" => Ok(Self::Optimistic),
+            "pessimistic" => Ok(Self::Pessimistic),
+            other => Err(format!("Modo de bloqueo desconocido: {}", other)),
+        }
+    }
+}
+
 // Interface (Port) for Wallet persistence
+//
+// Los métodos de escritura reciben `expected_version`: si es `Some(v)` y la versión actual de la
+// billetera no coincide, fallan con `WalletError::ConcurrencyError` sin aplicar cambios. Con
+// `None` no se exige versión (la escritura sigue siendo atómica). Toda escritura exitosa
+// incrementa la versión.
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait WalletRepository: Send + Sync {
     async fn find_by_id(&self, id: WalletId) -> Result<Option<Wallet>, WalletError>;
     async fn find_by_user_id(&self, user_id: UserId) -> Result<Vec<Wallet>, WalletError>;
     async fn create(&self, wallet: Wallet) -> Result<Wallet, WalletError>;
-    async fn update_balance(
+
+    /// Lee el saldo de una billetera junto con la hora de la BD de la lectura.
+    async fn find_balance(&self, id: WalletId) -> Result<Option<BalanceSnapshot>, WalletError>;
+
+    /// Página de saldos ordenada por id, comenzando después de `after` (keyset pagination).
+    async fn list_balances(
+        &self,
+        after: Option<WalletId>,
+        limit: i64,
+    ) -> Result<Vec<BalanceSnapshot>, WalletError>;
+
+    /// Límites de gasto de la billetera (sin límites si nunca se configuraron).
+    async fn find_limits(&self, wallet_id: WalletId) -> Result<SpendingLimits, WalletError>;
+
+    /// Reemplaza los límites de gasto de la billetera e incrementa su versión, de modo que el
+    /// `ETag` de la billetera también cubre sus límites.
+    ///
+    /// Retorna la billetera con su nueva versión y los límites guardados.
+    async fn save_limits(
+        &self,
+        wallet_id: WalletId,
+        limits: SpendingLimits,
+        expected_version: Option<i32>,
+    ) -> Result<(Wallet, SpendingLimits), WalletError>;
+
+    /// Débitos reservados en el día y el mes que contienen a `at`.
+    async fn find_debit_usage(
+        &self,
+        wallet_id: WalletId,
+        at: DateTime<Utc>,
+    ) -> Result<DebitUsage, WalletError>;
+
+    /// Persiste los atributos configurables de la billetera (etiqueta y sobregiro).
+    ///
+    /// La BD rechaza un sobregiro menor al saldo negativo ya utilizado.
+    async fn update(
+        &self,
+        wallet: Wallet,
+        expected_version: Option<i32>,
+    ) -> Result<Wallet, WalletError>;
+
+    /// Registra una nueva reserva de fondos (hold) en estado `Active`.
+    ///
+    /// Para reservas de débito, el `reserved_balance` de la billetera debe aumentar en la misma
+    /// transacción de BD, reduciendo el balance disponible sin tocar el balance contable, y
+    /// sumarse a los contadores de gasto del día y del mes. Si el débito supera algún límite de
+    /// gasto falla con `WalletError::LimitExceeded` sin aplicar cambios.
+    async fn reserve(&self, hold: Hold, expected_version: Option<i32>)
+        -> Result<Hold, WalletError>;
+
+    /// Busca la reserva de una billetera asociada a una transacción.
+    async fn find_hold(
+        &self,
+        wallet_id: WalletId,
+        transaction_id: String,
+    ) -> Result<Option<Hold>, WalletError>;
+
+    /// Captura una reserva (ya transicionada a `Captured` en el dominio).
+    ///
+    /// Aplica el monto al balance contable, libera lo reservado y registra los asientos de
+    /// doble partida contra `hold.counterparty()`, todo en la misma transacción de BD.
+    /// Retorna el asiento correspondiente a la billetera.
+    async fn capture_hold(
+        &self,
+        hold: Hold,
+        expected_version: Option<i32>,
+    ) -> Result<LedgerEntry, WalletError>;
+
+    /// Libera una reserva (ya transicionada a `Released` en el dominio), devolviendo lo
+    /// reservado al balance disponible. No genera asientos porque el balance contable no cambia.
+    /// Para débitos, también descuenta lo reservado de los contadores de gasto.
+    async fn release_hold(
+        &self,
+        hold: Hold,
+        expected_version: Option<i32>,
+    ) -> Result<Hold, WalletError>;
+
+    /// Ejecuta las dos patas de una transferencia (ya en estado `Captured` en el dominio) en
+    /// una única transacción de BD, sin pasar por una reserva activa.
+    ///
+    /// Ambas billeteras se bloquean en orden de id antes de modificarlas, de modo que dos
+    /// transferencias cruzadas no se bloqueen mutuamente. Se registran los holds, se suma el
+    /// débito a los contadores de gasto (verificando los límites), se aplican los montos a los
+    /// balances contables y se registran los asientos de doble partida de cada pata. Si algo
+    /// falla no se aplica nada. Retorna los asientos de las billeteras (débito, crédito).
+    async fn transfer(
+        &self,
+        debit: Hold,
+        credit: Hold,
+        debit_version: Option<i32>,
+        credit_version: Option<i32>,
+    ) -> Result<(LedgerEntry, LedgerEntry), WalletError>;
+}
+
+// Interface (Port) for Ledger (wallet_entries) queries
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait LedgerRepository: Send + Sync {
+    /// Lista los asientos de una billetera, del más reciente al más antiguo.
+    async fn find_by_wallet_id(
+        &self,
+        wallet_id: WalletId,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LedgerEntry>, WalletError>;
+
+    /// Página de los asientos de una billetera con `from <= created_at < to`, en orden
+    /// `(created_at, id)` ascendente, a partir del asiento siguiente a `after`.
+    async fn find_between(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, EntryId)>,
+        limit: i64,
+    ) -> Result<Vec<LedgerEntry>, WalletError>;
+
+    /// Suma de los asientos de una billetera con `from <= created_at <= to`.
+    ///
+    /// Sin `from` suma desde el primer asiento.
+    async fn sum_amounts_between(
+        &self,
+        wallet_id: WalletId,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Decimal, WalletError>;
+}
+
+// Interface (Port) for daily balance snapshots
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait BalanceSnapshotRepository: Send + Sync {
+    /// Último día con snapshots generados, si hubo alguno.
+    async fn latest_snapshot_date(&self) -> Result<Option<NaiveDate>, WalletError>;
+
+    /// Persiste el saldo al cierre de `balance_date` de todas las billeteras existentes a esa
+    /// hora. Es idempotente: no reemplaza snapshots ya generados para ese día.
+    ///
+    /// Retorna la cantidad de snapshots creados.
+    async fn create_daily_snapshots(&self, balance_date: NaiveDate) -> Result<u64, WalletError>;
+
+    /// Snapshot más reciente de la billetera cuyo día es anterior a `before`.
+    async fn find_latest_before(
         &self,
-        id: WalletId,
-        amount: rust_decimal::Decimal,
-    ) -> Result<(), WalletError>;
+        wallet_id: WalletId,
+        before: NaiveDate,
+    ) -> Result<Option<DailyBalanceSnapshot>, WalletError>;
 }
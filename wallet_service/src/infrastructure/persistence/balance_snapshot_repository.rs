@@ -0,0 +1,92 @@
+use crate::domain::entities::DailyBalanceSnapshot;
+use crate::domain::error::WalletError;
+use crate::domain::repository::BalanceSnapshotRepository;
+use crate::domain::types::WalletId;
+use crate::infrastructure::persistence::models::DailyBalanceSnapshotModel;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+/// Repositorio de snapshots diarios de saldo (`wallet_balance_snapshots`) basado en PostgreSQL.
+pub struct PostgresBalanceSnapshotRepository {
+    pool: PgPool,
+}
+
+impl PostgresBalanceSnapshotRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BalanceSnapshotRepository for PostgresBalanceSnapshotRepository {
+    async fn latest_snapshot_date(&self) -> Result<Option<NaiveDate>, WalletError> {
+        sqlx::query_scalar(r#"SELECT MAX(balance_date) FROM wallet_balance_snapshots"#)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))
+    }
+
+    /// Calcula todos los snapshots del día en una sola sentencia.
+    ///
+    /// Cada saldo es el snapshot del día anterior más los asientos del día; si la billetera no
+    /// tiene snapshot previo (primera ejecución o billetera nueva) se suman todos sus asientos
+    /// hasta el cierre. `ON CONFLICT DO NOTHING` hace la operación reejecutable.
+    async fn create_daily_snapshots(&self, balance_date: NaiveDate) -> Result<u64, WalletError> {
+        let closes_at = DailyBalanceSnapshot::closes_at(balance_date);
+        let day_start =
+            DailyBalanceSnapshot::closes_at(balance_date.pred_opt().unwrap_or_default());
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO wallet_balance_snapshots (wallet_id, balance_date, ledger_balance, currency)
+            SELECT
+                w.id,
+                $1,
+                COALESCE(prev.ledger_balance, 0) + COALESCE(SUM(e.amount), 0),
+                w.currency
+            FROM wallets w
+            LEFT JOIN wallet_balance_snapshots prev
+                ON prev.wallet_id = w.id AND prev.balance_date = $1 - 1
+            LEFT JOIN wallet_entries e
+                ON e.wallet_id = w.id
+                AND e.created_at < $2
+                AND (prev.wallet_id IS NULL OR e.created_at >= $3)
+            WHERE w.created_at < $2
+            GROUP BY w.id, w.currency, prev.ledger_balance
+            ON CONFLICT (wallet_id, balance_date) DO NOTHING
+            "#,
+        )
+        .bind(balance_date)
+        .bind(closes_at)
+        .bind(day_start)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_latest_before(
+        &self,
+        wallet_id: WalletId,
+        before: NaiveDate,
+    ) -> Result<Option<DailyBalanceSnapshot>, WalletError> {
+        let model_opt = sqlx::query_as::<_, DailyBalanceSnapshotModel>(
+            r#"
+            SELECT wallet_id, balance_date, ledger_balance, currency
+            FROM wallet_balance_snapshots
+            WHERE wallet_id = $1 AND balance_date < $2
+            ORDER BY balance_date DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(before)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(Into::into))
+    }
+}
@@ -0,0 +1,109 @@
+use crate::domain::entities::LedgerEntry;
+use crate::domain::error::WalletError;
+use crate::domain::repository::LedgerRepository;
+use crate::domain::types::{EntryId, WalletId};
+use crate::infrastructure::persistence::models::LedgerEntryModel;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+/// Repositorio de consulta del libro mayor (`wallet_entries`) basado en PostgreSQL.
+///
+/// La escritura de asientos ocurre dentro de `PostgresWalletRepository::update_balance`
+/// para compartir la transacción con el cambio de balance; este adaptador sólo lee.
+pub struct PostgresLedgerRepository {
+    pool: PgPool,
+}
+
+impl PostgresLedgerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LedgerRepository for PostgresLedgerRepository {
+    /// Pagina los asientos de una billetera (más recientes primero).
+    ///
+    /// Aprovecha el índice `idx_wallet_entries_wallet_created` (wallet_id, created_at DESC).
+    async fn find_by_wallet_id(
+        &self,
+        wallet_id: WalletId,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LedgerEntry>, WalletError> {
+        let models = sqlx::query_as::<_, LedgerEntryModel>(
+            r#"
+            SELECT * FROM wallet_entries
+            WHERE wallet_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    /// Pagina por keyset sobre `(created_at, id)` con el mismo índice por fecha.
+    async fn find_between(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, EntryId)>,
+        limit: i64,
+    ) -> Result<Vec<LedgerEntry>, WalletError> {
+        let models = sqlx::query_as::<_, LedgerEntryModel>(
+            r#"
+            SELECT * FROM wallet_entries
+            WHERE wallet_id = $1 AND created_at >= $2 AND created_at < $3
+              AND ($4::timestamptz IS NULL OR (created_at, id) > ($4, $5::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $6
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(from)
+        .bind(to)
+        .bind(after.map(|(created_at, _)| created_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    /// Suma los asientos de la billetera en el rango, usando el mismo índice por fecha.
+    async fn sum_amounts_between(
+        &self,
+        wallet_id: WalletId,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Decimal, WalletError> {
+        let total: Option<Decimal> = sqlx::query_scalar(
+            r#"
+            SELECT SUM(amount) FROM wallet_entries
+            WHERE wallet_id = $1
+              AND ($2::timestamptz IS NULL OR created_at >= $2)
+              AND created_at <= $3
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(total.unwrap_or(Decimal::ZERO))
+    }
+}
@@ -1,3 +1,5 @@
+pub mod balance_snapshot_repository;
+pub mod ledger_repository;
 pub mod models;
 pub mod user_repository;
 pub mod wallet_repository;
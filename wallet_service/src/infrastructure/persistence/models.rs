@@ -1,9 +1,12 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use sqlx::FromRow;
 
-use crate::domain::entities::{User, Wallet};
-use crate::domain::types::{UserId, WalletId};
+use crate::domain::entities::{
+    BalanceSnapshot, DailyBalanceSnapshot, Hold, HoldStatus, LedgerAccount, LedgerEntry,
+    SpendingLimits, StatusChange, SystemAccount, User, Wallet, WalletStatus,
+};
+use crate::domain::types::{EntryId, HoldId, UserId, WalletId};
 
 // Modelo de Base de Datos para User (especifico de SQLx)
 // Representa la tabla 'users' en PostgreSQL.
@@ -47,8 +50,13 @@ pub struct WalletModel {
     pub user_id: UserId,
     pub label: String,
     pub balance: Decimal,
+    pub reserved_balance: Decimal,
+    pub overdraft_limit: Option<Decimal>,
     pub currency: String,
     pub version: i32,
+    pub status: WalletStatus,
+    pub status_reason: Option<String>,
+    pub status_changed_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -60,9 +68,14 @@ impl From<Wallet> for WalletModel {
             id: w.id(),
             user_id: w.user_id(),
             label: w.label().to_string(),
-            balance: w.balance(),
+            balance: w.ledger_balance(),
+            reserved_balance: w.reserved_balance(),
+            overdraft_limit: w.overdraft_limit(),
             currency: w.currency().to_string(),
             version: w.version(),
+            status: w.status(),
+            status_reason: w.status_change().reason.clone(),
+            status_changed_at: w.status_change().changed_at,
             // Asignamos la fecha actual (UTC) al persistir.
             // IMPORTANTE: Esto asume que estamos creando el registro.
             // Para updates, se debería usar una query que ignore este campo o preserve el valor original.
@@ -75,7 +88,197 @@ impl From<Wallet> for WalletModel {
 // Ignoramos el campo 'created_at' del modelo ya que la entidad no lo necesita.
 impl From<WalletModel> for Wallet {
     fn from(w: WalletModel) -> Self {
-        Wallet::reconstitute(w.id, w.user_id, w.label, w.balance, w.currency, w.version)
-            .expect("Invalid Wallet state from DB")
+        Wallet::reconstitute(
+            w.id,
+            w.user_id,
+            w.label,
+            w.balance,
+            w.reserved_balance,
+            w.overdraft_limit,
+            w.currency,
+            w.version,
+            StatusChange {
+                status: w.status,
+                reason: w.status_reason,
+                changed_at: w.status_changed_at,
+            },
+        )
+        .expect("Invalid Wallet state from DB")
+    }
+}
+
+// Proyección de sólo lectura sobre 'wallets' para consultas de saldo.
+// `taken_at` es el NOW() de la consulta que leyó la fila.
+#[derive(Debug, FromRow)]
+pub struct BalanceSnapshotModel {
+    pub id: WalletId,
+    pub currency: String,
+    pub balance: Decimal,
+    pub reserved_balance: Decimal,
+    pub version: i32,
+    pub status: WalletStatus,
+    pub taken_at: DateTime<Utc>,
+}
+
+// Conversión Modelo -> Dominio
+impl From<BalanceSnapshotModel> for BalanceSnapshot {
+    fn from(m: BalanceSnapshotModel) -> Self {
+        Self {
+            wallet_id: m.id,
+            currency: m.currency,
+            ledger_balance: m.balance,
+            available_balance: m.balance - m.reserved_balance,
+            version: m.version,
+            status: m.status,
+            taken_at: m.taken_at,
+        }
+    }
+}
+
+// Modelo de Base de Datos para los límites de gasto.
+// Representa la tabla 'wallet_limits'.
+#[derive(Debug, FromRow)]
+pub struct SpendingLimitsModel {
+    pub per_transaction_limit: Option<Decimal>,
+    pub daily_limit: Option<Decimal>,
+    pub monthly_limit: Option<Decimal>,
+}
+
+// Conversión Modelo -> Dominio
+impl From<SpendingLimitsModel> for SpendingLimits {
+    fn from(m: SpendingLimitsModel) -> Self {
+        Self {
+            per_transaction: m.per_transaction_limit,
+            daily: m.daily_limit,
+            monthly: m.monthly_limit,
+        }
+    }
+}
+
+// Modelo de Base de Datos para los snapshots diarios de saldo.
+// Representa la tabla 'wallet_balance_snapshots'.
+#[derive(Debug, FromRow)]
+pub struct DailyBalanceSnapshotModel {
+    pub wallet_id: WalletId,
+    pub balance_date: NaiveDate,
+    pub ledger_balance: Decimal,
+    pub currency: String,
+}
+
+// Conversión Modelo -> Dominio
+impl From<DailyBalanceSnapshotModel> for DailyBalanceSnapshot {
+    fn from(m: DailyBalanceSnapshotModel) -> Self {
+        Self {
+            wallet_id: m.wallet_id,
+            balance_date: m.balance_date,
+            ledger_balance: m.ledger_balance,
+            currency: m.currency,
+        }
+    }
+}
+
+// Modelo de Base de Datos para los asientos del libro mayor.
+// Representa la tabla 'wallet_entries'. Exactamente uno de `wallet_id` o `system_account` tiene valor.
+#[derive(Debug, FromRow)]
+pub struct LedgerEntryModel {
+    pub id: EntryId,
+    pub transaction_id: String,
+    pub wallet_id: Option<WalletId>,
+    pub system_account: Option<SystemAccount>,
+    pub amount: Decimal,
+    pub balance_after: Decimal,
+    pub currency: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Conversión Dominio -> Modelo
+// Aplanamos el enum `LedgerAccount` en las dos columnas anulables de la tabla.
+impl From<&LedgerEntry> for LedgerEntryModel {
+    fn from(e: &LedgerEntry) -> Self {
+        let (wallet_id, system_account) = match e.account() {
+            LedgerAccount::Wallet(id) => (Some(id), None),
+            LedgerAccount::System(account) => (None, Some(account)),
+        };
+
+        Self {
+            id: e.id(),
+            transaction_id: e.transaction_id().to_string(),
+            wallet_id,
+            system_account,
+            amount: e.amount(),
+            balance_after: e.balance_after(),
+            currency: e.currency().to_string(),
+            created_at: e.created_at(),
+        }
+    }
+}
+
+// Conversión Modelo -> Dominio
+// El CHECK de la tabla garantiza que una de las dos columnas de cuenta está presente.
+impl From<LedgerEntryModel> for LedgerEntry {
+    fn from(m: LedgerEntryModel) -> Self {
+        let account = match (m.wallet_id, m.system_account) {
+            (Some(id), _) => LedgerAccount::Wallet(id),
+            (None, Some(account)) => LedgerAccount::System(account),
+            (None, None) => panic!("Invalid LedgerEntry state from DB: entry without account"),
+        };
+
+        LedgerEntry::reconstitute(
+            m.id,
+            m.transaction_id,
+            account,
+            m.amount,
+            m.balance_after,
+            m.currency,
+            m.created_at,
+        )
+        .expect("Invalid LedgerEntry state from DB")
+    }
+}
+
+// Modelo de Base de Datos para las reservas de fondos.
+// Representa la tabla 'wallet_holds'.
+#[derive(Debug, FromRow)]
+pub struct HoldModel {
+    pub id: HoldId,
+    pub wallet_id: WalletId,
+    pub transaction_id: String,
+    pub amount: Decimal,
+    pub counterparty: SystemAccount,
+    pub status: HoldStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Conversión Dominio -> Modelo
+impl From<&Hold> for HoldModel {
+    fn from(h: &Hold) -> Self {
+        Self {
+            id: h.id(),
+            wallet_id: h.wallet_id(),
+            transaction_id: h.transaction_id().to_string(),
+            amount: h.amount(),
+            counterparty: h.counterparty(),
+            status: h.status(),
+            created_at: h.created_at(),
+            updated_at: h.updated_at(),
+        }
+    }
+}
+
+// Conversión Modelo -> Dominio
+impl From<HoldModel> for Hold {
+    fn from(m: HoldModel) -> Self {
+        Hold::reconstitute(
+            m.id,
+            m.wallet_id,
+            m.transaction_id,
+            m.amount,
+            m.counterparty,
+            m.status,
+            m.created_at,
+            m.updated_at,
+        )
+        .expect("Invalid Hold state from DB")
     }
 }
@@ -1,20 +1,76 @@
-use crate::domain::entities::Wallet;
+use crate::domain::entities::{
+    BalanceSnapshot, DebitUsage, Hold, LedgerAccount, LedgerEntry, LimitPeriod, SpendingLimits,
+    Wallet, WalletStatus,
+};
 use crate::domain::error::WalletError;
-use crate::domain::repository::WalletRepository;
+use crate::domain::repository::{LockingMode, WalletRepository};
 use crate::domain::types::{UserId, WalletId};
-use crate::infrastructure::persistence::models::WalletModel;
+use crate::infrastructure::persistence::models::{
+    BalanceSnapshotModel, HoldModel, LedgerEntryModel, SpendingLimitsModel, WalletModel,
+};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 
 /// Repositorio de Billeteras basado en PostgreSQL.
+///
+/// Toda escritura sobre `wallets` es un compare-and-set sobre `version` cuando el llamador
+/// indica la versión esperada. En `LockingMode::Pessimistic` además se bloquea la fila con
+/// `SELECT ... FOR UPDATE` al inicio de la transacción.
 pub struct PostgresWalletRepository {
     pool: PgPool,
+    locking_mode: LockingMode,
 }
 
 impl PostgresWalletRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            locking_mode: LockingMode::default(),
+        }
+    }
+
+    /// Selecciona la estrategia de bloqueo (por defecto optimista).
+    pub fn with_locking_mode(mut self, locking_mode: LockingMode) -> Self {
+        self.locking_mode = locking_mode;
+        self
+    }
+
+    /// Prepara la fila de la billetera para ser modificada dentro de `tx`.
+    ///
+    /// En modo pesimista la bloquea hasta el fin de la transacción y valida la versión esperada
+    /// contra la fila bloqueada. En modo optimista no hace nada: la validación ocurre en el
+    /// propio `UPDATE`.
+    async fn lock_for_write(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: WalletId,
+        expected_version: Option<i32>,
+    ) -> Result<(), WalletError> {
+        if self.locking_mode != LockingMode::Pessimistic {
+            return Ok(());
+        }
+
+        let row: Option<(i32,)> = sqlx::query_as(
+            r#"
+            SELECT version FROM wallets
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        let Some((version,)) = row else {
+            return Err(WalletError::NotFound(id));
+        };
+        match expected_version {
+            Some(expected) if expected != version => Err(version_conflict(id, expected)),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -61,9 +117,10 @@ impl WalletRepository for PostgresWalletRepository {
         let saved_model = sqlx::query_as::<_, WalletModel>(
             r#"
             INSERT INTO wallets (
-                id, user_id, label, balance, currency, version, created_at
+                id, user_id, label, balance, reserved_balance, overdraft_limit, currency,
+                version, status, status_reason, status_changed_at, created_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             RETURNING *
             "#,
         )
@@ -71,8 +128,13 @@ impl WalletRepository for PostgresWalletRepository {
         .bind(model.user_id)
         .bind(model.label)
         .bind(model.balance)
+        .bind(model.reserved_balance)
+        .bind(model.overdraft_limit)
         .bind(model.currency)
         .bind(model.version)
+        .bind(model.status)
+        .bind(model.status_reason)
+        .bind(model.status_changed_at)
         .bind(model.created_at)
         .fetch_one(&self.pool)
         .await
@@ -81,36 +143,778 @@ impl WalletRepository for PostgresWalletRepository {
         Ok(saved_model.into())
     }
 
-    /// Actualiza el balance de forma atómica.
+    /// Lee el saldo de una billetera con la hora de la BD de la lectura.
+    async fn find_balance(&self, id: WalletId) -> Result<Option<BalanceSnapshot>, WalletError> {
+        let model_opt = sqlx::query_as::<_, BalanceSnapshotModel>(
+            r#"
+            SELECT id, currency, balance, reserved_balance, version, status, NOW() AS taken_at
+            FROM wallets
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(|m| m.into()))
+    }
+
+    /// Página de saldos por id ascendente. Usa la PK como cursor, por lo que cada página es
+    /// una búsqueda por índice independientemente de cuántas billeteras se hayan recorrido.
+    async fn list_balances(
+        &self,
+        after: Option<WalletId>,
+        limit: i64,
+    ) -> Result<Vec<BalanceSnapshot>, WalletError> {
+        let models = sqlx::query_as::<_, BalanceSnapshotModel>(
+            r#"
+            SELECT id, currency, balance, reserved_balance, version, status, NOW() AS taken_at
+            FROM wallets
+            WHERE $1::UUID IS NULL OR id > $1
+            ORDER BY id ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(models.into_iter().map(|m| m.into()).collect())
+    }
+
+    /// Lee los límites de gasto; una billetera sin fila en `wallet_limits` no tiene límites.
+    async fn find_limits(&self, wallet_id: WalletId) -> Result<SpendingLimits, WalletError> {
+        let model_opt = sqlx::query_as::<_, SpendingLimitsModel>(
+            r#"
+            SELECT per_transaction_limit, daily_limit, monthly_limit
+            FROM wallet_limits
+            WHERE wallet_id = $1
+            "#,
+        )
+        .bind(wallet_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(|m| m.into()).unwrap_or_default())
+    }
+
+    /// Inserta o reemplaza los límites de gasto de la billetera, incrementando su versión en
+    /// la misma transacción.
+    async fn save_limits(
+        &self,
+        wallet_id: WalletId,
+        limits: SpendingLimits,
+        expected_version: Option<i32>,
+    ) -> Result<(Wallet, SpendingLimits), WalletError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        self.lock_for_write(&mut tx, wallet_id, expected_version)
+            .await?;
+
+        let wallet_model = sqlx::query_as::<_, WalletModel>(
+            r#"
+            UPDATE wallets
+            SET version = version + 1
+            WHERE id = $1 AND ($2::INT IS NULL OR version = $2)
+            RETURNING *
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(expected_version)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        let Some(wallet_model) = wallet_model else {
+            return Err(not_updated(&mut tx, wallet_id, expected_version).await);
+        };
+
+        let limits_model = sqlx::query_as::<_, SpendingLimitsModel>(
+            r#"
+            INSERT INTO wallet_limits (wallet_id, per_transaction_limit, daily_limit, monthly_limit)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (wallet_id) DO UPDATE
+            SET per_transaction_limit = EXCLUDED.per_transaction_limit,
+                daily_limit = EXCLUDED.daily_limit,
+                monthly_limit = EXCLUDED.monthly_limit,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING per_transaction_limit, daily_limit, monthly_limit
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(limits.per_transaction)
+        .bind(limits.daily)
+        .bind(limits.monthly)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok((wallet_model.into(), limits_model.into()))
+    }
+
+    /// Lee los contadores de gasto del día y del mes que contienen a `at`.
+    async fn find_debit_usage(
+        &self,
+        wallet_id: WalletId,
+        at: DateTime<Utc>,
+    ) -> Result<DebitUsage, WalletError> {
+        let rows: Vec<(String, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT period, used FROM wallet_debit_usage
+            WHERE wallet_id = $1
+              AND ((period = 'DAILY' AND period_start = $2)
+                OR (period = 'MONTHLY' AND period_start = $3))
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(LimitPeriod::Daily.start_of(at))
+        .bind(LimitPeriod::Monthly.start_of(at))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        let mut usage = DebitUsage::default();
+        for (period, used) in rows {
+            match period.as_str() {
+                "DAILY" => usage.daily = used,
+                _ => usage.monthly = used,
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Actualiza los atributos configurables de la billetera (etiqueta, sobregiro y estado).
     ///
-    /// Se suma (o resta si es negativo) el `amount` al balance actual.
-    async fn update_balance(&self, id: WalletId, amount: Decimal) -> Result<(), WalletError> {
-        // Ejecutamos UPDATE directo para atomicidad.
-        // Incrementamos la versión para optimistic locking implícito.
-        let result = sqlx::query(
+    /// Los balances no se tocan aquí: sólo cambian a través de reservas y capturas.
+    async fn update(
+        &self,
+        wallet: Wallet,
+        expected_version: Option<i32>,
+    ) -> Result<Wallet, WalletError> {
+        let id = wallet.id();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        self.lock_for_write(&mut tx, id, expected_version).await?;
+
+        let saved_model = sqlx::query_as::<_, WalletModel>(
             r#"
-            UPDATE wallets 
-            SET balance = balance + $1,
+            UPDATE wallets
+            SET label = $1,
+                overdraft_limit = $2,
+                status = $3,
+                status_reason = $4,
+                status_changed_at = $5,
                 version = version + 1
-            WHERE id = $2 
+            WHERE id = $6 AND ($7::INT IS NULL OR version = $7)
+            RETURNING *
             "#,
         )
-        .bind(amount)
+        .bind(wallet.label())
+        .bind(wallet.overdraft_limit())
+        .bind(wallet.status())
+        .bind(&wallet.status_change().reason)
+        .bind(wallet.status_change().changed_at)
         .bind(id)
-        .execute(&self.pool)
+        .bind(expected_version)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
-            // Check constraint violation (e.g. balance < 0 constraint)
-            if e.to_string().contains("balance_chk") || e.to_string().contains("positive_balance") {
-                return WalletError::InsufficientFunds(id);
+            // El sobregiro no puede reducirse por debajo de lo ya utilizado.
+            if e.to_string().contains("positive_balance") {
+                return WalletError::InvalidData(
+                    "El nuevo sobregiro es menor al saldo negativo actual de la wallet".into(),
+                );
+            }
+            // Otra operación dejó saldo o reservas entre la lectura y el cierre.
+            if e.to_string().contains("wallet_closed_chk") {
+                return WalletError::InvalidStatusTransition(format!(
+                    "La wallet {} sólo puede cerrarse con saldo cero y sin reservas",
+                    id
+                ));
             }
             WalletError::RepositoryError(e.to_string())
         })?;
 
+        let Some(saved_model) = saved_model else {
+            return Err(not_updated(&mut tx, id, expected_version).await);
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(saved_model.into())
+    }
+
+    /// Registra una reserva y retiene los fondos de débito de forma atómica.
+    ///
+    /// Dentro de una única transacción de BD se incrementa `reserved_balance` (sólo para débitos),
+    /// se suman los contadores de gasto verificando los límites y se inserta el hold. El balance
+    /// contable (`balance`) no se modifica.
+    async fn reserve(
+        &self,
+        hold: Hold,
+        expected_version: Option<i32>,
+    ) -> Result<Hold, WalletError> {
+        let wallet_id = hold.wallet_id();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        self.lock_for_write(&mut tx, wallet_id, expected_version)
+            .await?;
+
+        // Retenemos lo reservado (0 para créditos) e incrementamos la versión.
+        let result = sqlx::query(
+            r#"
+            UPDATE wallets
+            SET reserved_balance = reserved_balance + $1,
+                version = version + 1
+            WHERE id = $2 AND ($3::INT IS NULL OR version = $3)
+            "#,
+        )
+        .bind(hold.reserved_amount())
+        .bind(wallet_id)
+        .bind(expected_version)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| map_balance_error(e, wallet_id))?;
+
         if result.rows_affected() == 0 {
-            return Err(WalletError::NotFound(id));
+            return Err(not_updated(&mut tx, wallet_id, expected_version).await);
         }
 
-        Ok(())
+        if hold.is_debit() {
+            consume_debit_limits(&mut tx, &hold).await?;
+        }
+
+        let saved = insert_hold(&mut tx, &hold).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(saved)
     }
+
+    /// Busca la reserva asociada a (`transaction_id`, `wallet_id`).
+    async fn find_hold(
+        &self,
+        wallet_id: WalletId,
+        transaction_id: String,
+    ) -> Result<Option<Hold>, WalletError> {
+        let model_opt = sqlx::query_as::<_, HoldModel>(
+            r#"
+            SELECT * FROM wallet_holds
+            WHERE wallet_id = $1 AND transaction_id = $2
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(transaction_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(model_opt.map(|m| m.into()))
+    }
+
+    /// Captura una reserva: contabiliza el movimiento y registra los asientos de doble partida.
+    ///
+    /// Dentro de una única transacción de BD:
+    /// 1. Se marca el hold como `CAPTURED` (sólo si seguía `ACTIVE`, evitando dobles capturas).
+    /// 2. Se aplica el monto al balance contable y se descuenta lo reservado.
+    /// 3. Se aplica el monto opuesto a la cuenta del sistema de contrapartida (misma divisa).
+    /// 4. Se insertan ambos asientos en `wallet_entries` con su balance resultante.
+    ///
+    /// Si cualquier paso falla, el `Drop` de la transacción hace rollback y nada cambia.
+    async fn capture_hold(
+        &self,
+        hold: Hold,
+        expected_version: Option<i32>,
+    ) -> Result<LedgerEntry, WalletError> {
+        let id = hold.wallet_id();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        self.lock_for_write(&mut tx, id, expected_version).await?;
+        close_hold(&mut tx, &hold).await?;
+
+        // Ejecutamos UPDATE directo para atomicidad, condicionado a la versión esperada.
+        let updated: Option<(Decimal, String)> = sqlx::query_as(
+            r#"
+            UPDATE wallets 
+            SET balance = balance + $1,
+                reserved_balance = reserved_balance - $2,
+                version = version + 1
+            WHERE id = $3 AND ($4::INT IS NULL OR version = $4)
+            RETURNING balance, currency
+            "#,
+        )
+        .bind(hold.amount())
+        .bind(hold.reserved_amount())
+        .bind(id)
+        .bind(expected_version)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| map_balance_error(e, id))?;
+
+        let Some((wallet_balance, currency)) = updated else {
+            return Err(not_updated(&mut tx, id, expected_version).await);
+        };
+
+        let wallet_entry = post_entries(&mut tx, &hold, wallet_balance, currency).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(wallet_entry)
+    }
+
+    /// Libera una reserva devolviendo lo retenido al balance disponible.
+    async fn release_hold(
+        &self,
+        hold: Hold,
+        expected_version: Option<i32>,
+    ) -> Result<Hold, WalletError> {
+        let id = hold.wallet_id();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        self.lock_for_write(&mut tx, id, expected_version).await?;
+        close_hold(&mut tx, &hold).await?;
+        if hold.is_debit() {
+            restore_debit_limits(&mut tx, &hold).await?;
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE wallets
+            SET reserved_balance = reserved_balance - $1,
+                version = version + 1
+            WHERE id = $2 AND ($3::INT IS NULL OR version = $3)
+            "#,
+        )
+        .bind(hold.reserved_amount())
+        .bind(id)
+        .bind(expected_version)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(not_updated(&mut tx, id, expected_version).await);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(hold)
+    }
+
+    /// Ejecuta una transferencia completa en una sola transacción de BD.
+    ///
+    /// 1. Se bloquean ambas billeteras con `SELECT ... FOR UPDATE` en orden de id, validando
+    ///    las versiones esperadas contra las filas bloqueadas.
+    /// 2. Se suma el débito a los contadores de gasto y se registran ambos holds ya capturados
+    ///    (el índice único sobre (`transaction_id`, `wallet_id`) detecta duplicados).
+    /// 3. Se aplica cada pata a su balance contable y a la cuenta puente, en orden de divisa.
+    async fn transfer(
+        &self,
+        debit: Hold,
+        credit: Hold,
+        debit_version: Option<i32>,
+        credit_version: Option<i32>,
+    ) -> Result<(LedgerEntry, LedgerEntry), WalletError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        // El orden de bloqueo no depende del sentido de la transferencia: A→B y B→A esperan
+        // por la misma fila en lugar de tomar una cada una y quedar en deadlock.
+        let locked: Vec<(WalletId, i32, String)> = sqlx::query_as(
+            r#"
+            SELECT id, version, currency FROM wallets
+            WHERE id = ANY($1)
+            ORDER BY id
+            FOR UPDATE
+            "#,
+        )
+        .bind(vec![debit.wallet_id().0, credit.wallet_id().0])
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        let mut currencies = Vec::with_capacity(2);
+        for (hold, expected_version) in [(&debit, debit_version), (&credit, credit_version)] {
+            let id = hold.wallet_id();
+            let Some((_, version, currency)) =
+                locked.iter().find(|(locked_id, ..)| *locked_id == id)
+            else {
+                return Err(WalletError::NotFound(id));
+            };
+            match expected_version {
+                Some(expected) if expected != *version => {
+                    return Err(version_conflict(id, expected))
+                }
+                _ => currencies.push(currency.as_str()),
+            }
+        }
+
+        consume_debit_limits(&mut tx, &debit).await?;
+        insert_hold(&mut tx, &debit).await?;
+        insert_hold(&mut tx, &credit).await?;
+
+        // Las cuentas del sistema son filas compartidas por divisa: también se actualizan en
+        // un orden fijo para que transferencias entre divisas opuestas no se crucen.
+        let entries = if currencies[1] < currencies[0] {
+            let credit_entry = apply_leg(&mut tx, &credit).await?;
+            (apply_leg(&mut tx, &debit).await?, credit_entry)
+        } else {
+            let debit_entry = apply_leg(&mut tx, &debit).await?;
+            (debit_entry, apply_leg(&mut tx, &credit).await?)
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        Ok(entries)
+    }
+}
+
+/// Traduce errores de constraints de balance a errores de dominio.
+fn map_balance_error(e: sqlx::Error, id: WalletId) -> WalletError {
+    // Check constraint violation (e.g. balance < 0 constraint)
+    if e.to_string().contains("balance_chk") || e.to_string().contains("positive_balance") {
+        return WalletError::InsufficientFunds(id);
+    }
+    if e.to_string().contains("wallet_closed_chk") {
+        return WalletError::WalletNotActive {
+            wallet_id: id,
+            status: WalletStatus::Closed,
+        };
+    }
+    WalletError::RepositoryError(e.to_string())
+}
+
+/// Determina por qué un `UPDATE` condicionado no afectó filas: billetera inexistente o
+/// versión distinta a la esperada.
+async fn not_updated(
+    tx: &mut Transaction<'_, Postgres>,
+    id: WalletId,
+    expected_version: Option<i32>,
+) -> WalletError {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM wallets WHERE id = $1)")
+            .bind(id)
+            .fetch_one(&mut **tx)
+            .await;
+
+    match (exists, expected_version) {
+        (Ok(true), Some(expected)) => version_conflict(id, expected),
+        (Ok(_), _) => WalletError::NotFound(id),
+        (Err(e), _) => WalletError::RepositoryError(e.to_string()),
+    }
+}
+
+fn version_conflict(id: WalletId, expected_version: i32) -> WalletError {
+    WalletError::ConcurrencyError(format!(
+        "La wallet {} ya no está en la versión {}",
+        id, expected_version
+    ))
+}
+
+/// Suma un débito reservado a los contadores del día y del mes y verifica los límites.
+///
+/// El `UPSERT` bloquea la fila del contador hasta el fin de la transacción y devuelve el total
+/// ya incrementado, así que reservas concurrentes se serializan sobre el contador y cada una
+/// valida contra el consumo real. Si se supera un límite, el error aborta la transacción y
+/// deshace tanto el incremento como la retención de fondos.
+async fn consume_debit_limits(
+    tx: &mut Transaction<'_, Postgres>,
+    hold: &Hold,
+) -> Result<(), WalletError> {
+    let wallet_id = hold.wallet_id();
+    let amount = hold.reserved_amount();
+
+    let limits: SpendingLimits = sqlx::query_as::<_, SpendingLimitsModel>(
+        r#"
+        SELECT per_transaction_limit, daily_limit, monthly_limit
+        FROM wallet_limits
+        WHERE wallet_id = $1
+        "#,
+    )
+    .bind(wallet_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| WalletError::RepositoryError(e.to_string()))?
+    .map(|m| m.into())
+    .unwrap_or_default();
+
+    let mut usage = DebitUsage::default();
+    for period in [LimitPeriod::Daily, LimitPeriod::Monthly] {
+        let (label, start) = usage_key(period, hold.created_at());
+        let used: Decimal = sqlx::query_scalar(
+            r#"
+            INSERT INTO wallet_debit_usage (wallet_id, period, period_start, used)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (wallet_id, period, period_start) DO UPDATE
+            SET used = wallet_debit_usage.used + EXCLUDED.used
+            RETURNING used
+            "#,
+        )
+        .bind(wallet_id)
+        .bind(label)
+        .bind(start)
+        .bind(amount)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+        match period {
+            LimitPeriod::Daily => usage.daily = used,
+            _ => usage.monthly = used,
+        }
+    }
+
+    limits.ensure_allows(wallet_id, amount, &usage)
+}
+
+/// Devuelve a los contadores del período en que se creó la reserva lo que retenía.
+async fn restore_debit_limits(
+    tx: &mut Transaction<'_, Postgres>,
+    hold: &Hold,
+) -> Result<(), WalletError> {
+    for period in [LimitPeriod::Daily, LimitPeriod::Monthly] {
+        let (label, start) = usage_key(period, hold.created_at());
+        sqlx::query(
+            r#"
+            UPDATE wallet_debit_usage
+            SET used = GREATEST(used - $4, 0)
+            WHERE wallet_id = $1 AND period = $2 AND period_start = $3
+            "#,
+        )
+        .bind(hold.wallet_id())
+        .bind(label)
+        .bind(start)
+        .bind(hold.reserved_amount())
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Clave (`period`, `period_start`) de la fila de `wallet_debit_usage` para un instante.
+fn usage_key(period: LimitPeriod, at: DateTime<Utc>) -> (&'static str, chrono::NaiveDate) {
+    let label = match period {
+        LimitPeriod::Monthly => "MONTHLY",
+        _ => "DAILY",
+    };
+    (label, period.start_of(at))
+}
+
+/// Inserta un hold usando la transacción abierta por el llamador.
+async fn insert_hold(tx: &mut Transaction<'_, Postgres>, hold: &Hold) -> Result<Hold, WalletError> {
+    let model = HoldModel::from(hold);
+
+    let saved_model = sqlx::query_as::<_, HoldModel>(
+        r#"
+        INSERT INTO wallet_holds (
+            id, wallet_id, transaction_id, amount, counterparty, status, created_at, updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(model.id)
+    .bind(model.wallet_id)
+    .bind(model.transaction_id)
+    .bind(model.amount)
+    .bind(model.counterparty)
+    .bind(model.status)
+    .bind(model.created_at)
+    .bind(model.updated_at)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        // Otra petición con la misma clave (transaction_id, wallet_id) se adelantó.
+        if e.to_string().contains("wallet_holds_tx_wallet_key") {
+            return WalletError::DuplicateMovement {
+                wallet_id: hold.wallet_id(),
+                transaction_id: hold.transaction_id().to_string(),
+            };
+        }
+        WalletError::RepositoryError(e.to_string())
+    })?;
+
+    Ok(saved_model.into())
+}
+
+/// Aplica el monto de un hold capturado directamente al balance contable de su billetera (sin
+/// reserva previa) y registra sus asientos.
+async fn apply_leg(
+    tx: &mut Transaction<'_, Postgres>,
+    hold: &Hold,
+) -> Result<LedgerEntry, WalletError> {
+    let id = hold.wallet_id();
+    let (wallet_balance, currency): (Decimal, String) = sqlx::query_as(
+        r#"
+        UPDATE wallets
+        SET balance = balance + $1,
+            version = version + 1
+        WHERE id = $2
+        RETURNING balance, currency
+        "#,
+    )
+    .bind(hold.amount())
+    .bind(id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| map_balance_error(e, id))?;
+
+    post_entries(tx, hold, wallet_balance, currency).await
+}
+
+/// Registra los asientos de doble partida de un hold capturado: el de la billetera, con su
+/// balance resultante, y el monto opuesto en la cuenta del sistema de contrapartida.
+async fn post_entries(
+    tx: &mut Transaction<'_, Postgres>,
+    hold: &Hold,
+    wallet_balance: Decimal,
+    currency: String,
+) -> Result<LedgerEntry, WalletError> {
+    let amount = hold.amount();
+    let counterparty = hold.counterparty();
+
+    // Contrapartida: la cuenta del sistema recibe el monto opuesto.
+    // Se crea de forma perezosa la primera vez que se usa en una divisa (upsert).
+    let (system_balance,): (Decimal,) = sqlx::query_as(
+        r#"
+        INSERT INTO system_accounts (code, currency, balance, updated_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (code, currency) DO UPDATE
+        SET balance = system_accounts.balance + EXCLUDED.balance,
+            updated_at = NOW()
+        RETURNING balance
+        "#,
+    )
+    .bind(counterparty)
+    .bind(&currency)
+    .bind(-amount)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+    let wallet_entry = LedgerEntry::new(
+        hold.transaction_id().to_string(),
+        LedgerAccount::Wallet(hold.wallet_id()),
+        amount,
+        wallet_balance,
+        currency.clone(),
+    )?;
+    let system_entry = LedgerEntry::new(
+        hold.transaction_id().to_string(),
+        LedgerAccount::System(counterparty),
+        -amount,
+        system_balance,
+        currency,
+    )?;
+    LedgerEntry::ensure_balanced(&[wallet_entry.clone(), system_entry.clone()])?;
+
+    insert_entry(tx, &wallet_entry).await?;
+    insert_entry(tx, &system_entry).await?;
+
+    Ok(wallet_entry)
+}
+
+/// Persiste el estado final de un hold sólo si seguía `ACTIVE` en la BD.
+///
+/// La condición sobre el estado actúa como compare-and-set: si otra petición concurrente ya
+/// capturó o liberó la reserva, no se afecta ninguna fila y se reporta `InvalidHoldState`.
+async fn close_hold(tx: &mut Transaction<'_, Postgres>, hold: &Hold) -> Result<(), WalletError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE wallet_holds
+        SET status = $1, updated_at = $2
+        WHERE id = $3 AND status = 'ACTIVE'
+        "#,
+    )
+    .bind(hold.status())
+    .bind(hold.updated_at())
+    .bind(hold.id())
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(WalletError::InvalidHoldState(format!(
+            "La reserva de la transacción {} ya no está activa",
+            hold.transaction_id()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Inserta un asiento en `wallet_entries` usando la transacción abierta por el llamador.
+async fn insert_entry(
+    tx: &mut Transaction<'_, Postgres>,
+    entry: &LedgerEntry,
+) -> Result<(), WalletError> {
+    let model = LedgerEntryModel::from(entry);
+
+    sqlx::query(
+        r#"
+        INSERT INTO wallet_entries (
+            id, transaction_id, wallet_id, system_account, amount, balance_after, currency, created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(model.id)
+    .bind(model.transaction_id)
+    .bind(model.wallet_id)
+    .bind(model.system_account)
+    .bind(model.amount)
+    .bind(model.balance_after)
+    .bind(model.currency)
+    .bind(model.created_at)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| WalletError::RepositoryError(e.to_string()))?;
+
+    Ok(())
 }
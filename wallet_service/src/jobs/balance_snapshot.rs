@@ -0,0 +1,117 @@
+use crate::domain::repository::BalanceSnapshotRepository;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Job en segundo plano que persiste el saldo de cierre diario de cada billetera.
+///
+/// Genera los snapshots de cada día ya cerrado que falte, en orden, de modo que si el servicio
+/// estuvo caído varios días se pone al día en la siguiente ejecución. Reejecutarlo no duplica
+/// snapshots.
+pub struct DailyBalanceSnapshotJob {
+    snapshot_repo: Arc<dyn BalanceSnapshotRepository>,
+}
+
+impl DailyBalanceSnapshotJob {
+    /// Margen tras la medianoche antes de cerrar el día, para que terminen de confirmarse los
+    /// movimientos que comenzaron antes del corte.
+    const SETTLEMENT_GRACE_MINUTES: i64 = 5;
+
+    pub fn new(snapshot_repo: Arc<dyn BalanceSnapshotRepository>) -> Self {
+        Self { snapshot_repo }
+    }
+
+    /// Ejecuta el proceso de snapshots hasta el último día cerrado.
+    pub async fn run(&self) {
+        self.run_at(Utc::now()).await;
+    }
+
+    async fn run_at(&self, now: DateTime<Utc>) {
+        let Some(last_closed_day) = Self::last_closed_day(now) else {
+            return;
+        };
+
+        let mut day = match self.snapshot_repo.latest_snapshot_date().await {
+            Ok(Some(latest)) => match latest.succ_opt() {
+                Some(next) => next,
+                None => return,
+            },
+            // Primera ejecución: sólo el último día cerrado (suma el ledger completo).
+            Ok(None) => last_closed_day,
+            Err(e) => {
+                error!("Failed to read latest balance snapshot date: {:?}", e);
+                return;
+            }
+        };
+
+        while day <= last_closed_day {
+            match self.snapshot_repo.create_daily_snapshots(day).await {
+                Ok(created) => info!("Created {} balance snapshots for {}", created, day),
+                Err(e) => {
+                    // Sin el snapshot de este día no se puede encadenar el siguiente.
+                    error!("Failed to create balance snapshots for {}: {:?}", day, e);
+                    return;
+                }
+            }
+            day = match day.succ_opt() {
+                Some(next) => next,
+                None => return,
+            };
+        }
+    }
+
+    fn last_closed_day(now: DateTime<Utc>) -> Option<NaiveDate> {
+        (now - Duration::minutes(Self::SETTLEMENT_GRACE_MINUTES))
+            .date_naive()
+            .pred_opt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::MockBalanceSnapshotRepository;
+    use chrono::TimeZone;
+    use mockall::predicate::eq;
+    use mockall::Sequence;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_job_catches_up_missing_days_in_order() {
+        let mut mock_repo = MockBalanceSnapshotRepository::new();
+        let mut seq = Sequence::new();
+
+        mock_repo
+            .expect_latest_snapshot_date()
+            .returning(|| Ok(Some(date(2024, 3, 29))));
+        for day in [date(2024, 3, 30), date(2024, 3, 31)] {
+            mock_repo
+                .expect_create_daily_snapshots()
+                .with(eq(day))
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(|_| Ok(3));
+        }
+
+        let job = DailyBalanceSnapshotJob::new(Arc::new(mock_repo));
+        job.run_at(Utc.with_ymd_and_hms(2024, 4, 1, 8, 0, 0).unwrap())
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_job_waits_for_grace_period_after_midnight() {
+        let mut mock_repo = MockBalanceSnapshotRepository::new();
+
+        mock_repo
+            .expect_latest_snapshot_date()
+            .returning(|| Ok(Some(date(2024, 3, 30))));
+        mock_repo.expect_create_daily_snapshots().never();
+
+        let job = DailyBalanceSnapshotJob::new(Arc::new(mock_repo));
+        job.run_at(Utc.with_ymd_and_hms(2024, 4, 1, 0, 2, 0).unwrap())
+            .await;
+    }
+}
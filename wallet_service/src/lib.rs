@@ -1,4 +1,5 @@
 pub mod api;
 pub mod domain;
 pub mod infrastructure;
+pub mod jobs;
 pub mod use_cases;
@@ -12,13 +12,23 @@ use wallet_service::{
         http_routes::{routes, AppState},
         proto::wallet::wallet_service_server::WalletServiceServer,
     },
+    domain::repository::LockingMode,
     infrastructure::persistence::{
-        user_repository::PostgresUserRepository, wallet_repository::PostgresWalletRepository,
+        balance_snapshot_repository::PostgresBalanceSnapshotRepository,
+        ledger_repository::PostgresLedgerRepository, user_repository::PostgresUserRepository,
+        wallet_repository::PostgresWalletRepository,
     },
+    jobs::balance_snapshot::DailyBalanceSnapshotJob,
     use_cases::{
-        create_user::CreateUserUseCase, create_wallet::CreateWalletUseCase,
-        get_user_wallets::GetWalletsUseCase, get_wallet::GetWalletUseCase,
-        process_movement::ProcessMovementUseCase,
+        change_wallet_status::ChangeWalletStatusUseCase, concurrency::ConcurrencyPolicy,
+        confirm_movement::ConfirmMovementUseCase, create_user::CreateUserUseCase,
+        create_wallet::CreateWalletUseCase, execute_transfer::ExecuteTransferUseCase,
+        get_balance::GetBalanceUseCase, get_historical_balance::GetHistoricalBalanceUseCase,
+        get_movement_status::GetMovementStatusUseCase, get_user_wallets::GetWalletsUseCase,
+        get_wallet::GetWalletUseCase, get_wallet_entries::GetWalletEntriesUseCase,
+        get_wallet_limits::GetWalletLimitsUseCase, list_balances::ListBalancesUseCase,
+        process_movement::ProcessMovementUseCase, update_wallet::UpdateWalletUseCase,
+        update_wallet_limits::UpdateWalletLimitsUseCase,
     },
 };
 
@@ -28,11 +38,22 @@ use wallet_service::{
         wallet_service::api::http_routes::create_user,
         wallet_service::api::http_routes::create_wallet,
         wallet_service::api::http_routes::list_user_wallets,
-        wallet_service::api::http_routes::get_wallet_details
+        wallet_service::api::http_routes::get_wallet_details,
+        wallet_service::api::http_routes::update_wallet,
+        wallet_service::api::http_routes::freeze_wallet,
+        wallet_service::api::http_routes::unfreeze_wallet,
+        wallet_service::api::http_routes::close_wallet,
+        wallet_service::api::http_routes::get_wallet_limits,
+        wallet_service::api::http_routes::update_wallet_limits,
+        wallet_service::api::http_routes::list_wallet_entries,
+        wallet_service::api::http_routes::get_wallet_balance
     ),
     components(schemas(
         wallet_service::api::http_routes::CreateUserRequest,
         wallet_service::api::http_routes::CreateWalletRequest,
+        wallet_service::api::http_routes::UpdateWalletRequest,
+        wallet_service::api::http_routes::ChangeStatusRequest,
+        wallet_service::api::http_routes::UpdateLimitsRequest,
         wallet_service::api::response::ApiResponse<serde_json::Value>
     ))
 )]
@@ -61,21 +82,71 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 4. Instanciar Dependencias (Infraestructura)
     let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
-    let wallet_repo = Arc::new(PostgresWalletRepository::new(pool.clone()));
+    // Control de concurrencia sobre wallets: "optimistic" (por defecto) o "pessimistic".
+    let locking_mode: LockingMode = env::var("WALLET_LOCKING_MODE")
+        .unwrap_or_else(|_| "optimistic".to_string())
+        .parse()?;
+    let conflict_retries = env::var("WALLET_CONFLICT_RETRIES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(ConcurrencyPolicy::DEFAULT_MAX_ATTEMPTS);
+    let concurrency_policy = ConcurrencyPolicy::new(locking_mode, conflict_retries);
+    info!("Wallet locking mode: {:?}", locking_mode);
+    // Si una wallet congelada sigue recibiendo créditos (los débitos siempre se rechazan).
+    let frozen_accepts_credits = env::var("WALLET_FROZEN_ACCEPTS_CREDITS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(true);
+
+    let wallet_repo =
+        Arc::new(PostgresWalletRepository::new(pool.clone()).with_locking_mode(locking_mode));
+    let ledger_repo = Arc::new(PostgresLedgerRepository::new(pool.clone()));
+    let snapshot_repo = Arc::new(PostgresBalanceSnapshotRepository::new(pool.clone()));
 
     // 5. Instanciar Casos de Uso
     let create_user_use_case = CreateUserUseCase::new(user_repo.clone());
     let create_wallet_use_case = CreateWalletUseCase::new(wallet_repo.clone(), user_repo.clone());
     let list_user_wallets_use_case = GetWalletsUseCase::new(wallet_repo.clone());
     let get_wallet_details_use_case = GetWalletUseCase::new(wallet_repo.clone());
-    let process_movement_use_case = ProcessMovementUseCase::new(wallet_repo.clone());
+    let update_wallet_use_case =
+        UpdateWalletUseCase::new(wallet_repo.clone()).with_concurrency_policy(concurrency_policy);
+    let change_wallet_status_use_case = ChangeWalletStatusUseCase::new(wallet_repo.clone())
+        .with_concurrency_policy(concurrency_policy);
+    let get_wallet_limits_use_case = GetWalletLimitsUseCase::new(wallet_repo.clone());
+    let update_wallet_limits_use_case = UpdateWalletLimitsUseCase::new(wallet_repo.clone());
+    let get_wallet_entries_use_case =
+        GetWalletEntriesUseCase::new(wallet_repo.clone(), ledger_repo.clone());
+    let process_movement_use_case = ProcessMovementUseCase::new(wallet_repo.clone())
+        .with_concurrency_policy(concurrency_policy)
+        .with_frozen_accepts_credits(frozen_accepts_credits);
+    let confirm_movement_use_case = ConfirmMovementUseCase::new(wallet_repo.clone());
+    let execute_transfer_use_case = ExecuteTransferUseCase::new(wallet_repo.clone())
+        .with_concurrency_policy(concurrency_policy)
+        .with_frozen_accepts_credits(frozen_accepts_credits);
+    let get_movement_status_use_case = GetMovementStatusUseCase::new(wallet_repo.clone());
+    let get_balance_use_case = GetBalanceUseCase::new(wallet_repo.clone());
+    let list_balances_use_case = ListBalancesUseCase::new(wallet_repo.clone());
+    let get_historical_balance_use_case = GetHistoricalBalanceUseCase::new(
+        wallet_repo.clone(),
+        snapshot_repo.clone(),
+        ledger_repo.clone(),
+    );
 
     // 6. Configurar Servidor gRPC
     let grpc_host = env::var("GRPC_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let grpc_port = env::var("GRPC_PORT").unwrap_or_else(|_| "50051".to_string());
     let grpc_addr = format!("{}:{}", grpc_host, grpc_port).parse()?;
 
-    let grpc_service = WalletGrpcService::new(process_movement_use_case);
+    let grpc_service = WalletGrpcService::new(
+        process_movement_use_case,
+        confirm_movement_use_case,
+        execute_transfer_use_case,
+        get_movement_status_use_case,
+        get_balance_use_case,
+        list_balances_use_case,
+        get_historical_balance_use_case.clone(),
+        get_wallet_entries_use_case.clone(),
+    );
 
     info!("gRPC Server listening on {}", grpc_addr);
 
@@ -96,9 +167,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         create_wallet_use_case,
         list_user_wallets_use_case,
         get_wallet_details_use_case,
+        update_wallet_use_case,
+        change_wallet_status_use_case,
+        get_wallet_limits_use_case,
+        update_wallet_limits_use_case,
+        get_wallet_entries_use_case,
+        get_historical_balance_use_case,
     });
 
-    // 8. Configurar Rutas y Servidor HTTP
+    // 8. Iniciar Background Jobs (Procesos en Segundo Plano)
+    let job_snapshot_repo = snapshot_repo.clone();
+    tokio::spawn(async move {
+        // Cada hora: el job sólo trabaja cuando hay un día cerrado sin snapshot.
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+        let job = DailyBalanceSnapshotJob::new(job_snapshot_repo);
+
+        info!("Balance snapshot job started");
+
+        loop {
+            interval.tick().await;
+            job.run().await;
+        }
+    });
+
+    // 9. Configurar Rutas y Servidor HTTP
     let app = routes(app_state)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()));
 
@@ -0,0 +1,219 @@
+use crate::domain::{
+    entities::Wallet, error::WalletError, repository::WalletRepository, types::WalletId,
+};
+use crate::use_cases::concurrency::ConcurrencyPolicy;
+use std::sync::Arc;
+
+/// Transiciones del ciclo de vida de una billetera solicitables por un operador.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusTransition {
+    Freeze,
+    Unfreeze,
+    Close,
+}
+
+/// Caso de uso para congelar, descongelar o cerrar una billetera.
+///
+/// Las reglas de transición (estados de origen válidos, motivo obligatorio, saldo cero para
+/// cerrar) viven en la entidad `Wallet`; este caso de uso sólo coordina la lectura, la
+/// precondición de versión (`If-Match`) y la persistencia, igual que `UpdateWalletUseCase`.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::change_wallet_status::ChangeWalletStatusUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = ChangeWalletStatusUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct ChangeWalletStatusUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+    concurrency: ConcurrencyPolicy,
+}
+
+impl ChangeWalletStatusUseCase {
+    /// Construye una nueva instancia de `ChangeWalletStatusUseCase`.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self {
+            wallet_repo,
+            concurrency: ConcurrencyPolicy::default(),
+        }
+    }
+
+    /// Reemplaza la política de concurrencia (modo de bloqueo y reintentos).
+    pub fn with_concurrency_policy(mut self, concurrency: ConcurrencyPolicy) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Aplica la transición indicada sobre la billetera.
+    ///
+    /// # Argumentos
+    ///
+    /// * `wallet_id` - Billetera a modificar.
+    /// * `transition` - Congelar, descongelar o cerrar.
+    /// * `reason` - Motivo del cambio (obligatorio para congelar y cerrar).
+    /// * `expected_version` - Versión que el cliente espera modificar (`If-Match`), si la envió.
+    ///
+    /// # Retornos
+    ///
+    /// La billetera con su nuevo estado, `WalletError::NotFound` si no existe,
+    /// `WalletError::InvalidStatusTransition` si la transición no es válida desde el estado
+    /// actual, `WalletError::InvalidData` si falta el motivo o `WalletError::ConcurrencyError`
+    /// si la versión no coincide.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let wallet = use_case
+    ///     .execute(wallet_id, StatusTransition::Freeze, Some("Fraude".into()), None)
+    ///     .await?;
+    /// ```
+    #[tracing::instrument(name = "ChangeWalletStatusUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        transition: StatusTransition,
+        reason: Option<String>,
+        expected_version: Option<i32>,
+    ) -> Result<Wallet, WalletError> {
+        let attempt = || self.try_change(wallet_id, transition, reason.clone(), expected_version);
+
+        match expected_version {
+            // La precondición del cliente no se reintenta: debe releer y decidir de nuevo.
+            Some(_) => attempt().await,
+            None => self.concurrency.retry(attempt).await,
+        }
+    }
+
+    async fn try_change(
+        &self,
+        wallet_id: WalletId,
+        transition: StatusTransition,
+        reason: Option<String>,
+        expected_version: Option<i32>,
+    ) -> Result<Wallet, WalletError> {
+        let mut wallet = self
+            .wallet_repo
+            .find_by_id(wallet_id)
+            .await?
+            .ok_or(WalletError::NotFound(wallet_id))?;
+
+        if let Some(expected) = expected_version {
+            if wallet.version() != expected {
+                return Err(WalletError::ConcurrencyError(format!(
+                    "La wallet {} está en la versión {}, se esperaba {}",
+                    wallet_id,
+                    wallet.version(),
+                    expected
+                )));
+            }
+        }
+        let expected_version =
+            expected_version.or_else(|| self.concurrency.expected_version(wallet.version()));
+
+        match transition {
+            StatusTransition::Freeze => wallet.freeze(reason.unwrap_or_default())?,
+            StatusTransition::Unfreeze => wallet.unfreeze(reason)?,
+            StatusTransition::Close => wallet.close(reason.unwrap_or_default())?,
+        }
+
+        self.wallet_repo.update(wallet, expected_version).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{StatusChange, WalletStatus};
+    use crate::domain::repository::MockWalletRepository;
+    use crate::domain::types::UserId;
+    use rust_decimal::Decimal;
+
+    fn wallet(id: WalletId, balance: i64) -> Wallet {
+        Wallet::reconstitute(
+            id,
+            UserId::new(),
+            "Main".into(),
+            Decimal::from(balance),
+            Decimal::ZERO,
+            None,
+            "USD".into(),
+            1,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_freeze_wallet_persists_status_and_reason() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet(id, 50))));
+        mock_repo
+            .expect_update()
+            .withf(|w: &Wallet, expected: &Option<i32>| {
+                w.status() == WalletStatus::Frozen
+                    && w.status_change().reason.as_deref() == Some("Fraude")
+                    && *expected == Some(1)
+            })
+            .times(1)
+            .returning(|w, _| Ok(w));
+
+        let use_case = ChangeWalletStatusUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                StatusTransition::Freeze,
+                Some("Fraude".into()),
+                None,
+            )
+            .await;
+
+        assert_eq!(result.unwrap().status(), WalletStatus::Frozen);
+    }
+
+    #[tokio::test]
+    async fn test_close_wallet_with_balance_is_rejected() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet(id, 50))));
+        mock_repo.expect_update().never();
+
+        let use_case = ChangeWalletStatusUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                StatusTransition::Close,
+                Some("Baja".into()),
+                None,
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(WalletError::InvalidStatusTransition(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_freeze_without_reason_is_rejected() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet(id, 0))));
+        mock_repo.expect_update().never();
+
+        let use_case = ChangeWalletStatusUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(WalletId::new(), StatusTransition::Freeze, None, None)
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+}
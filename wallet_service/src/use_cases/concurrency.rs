@@ -0,0 +1,144 @@
+use crate::domain::error::WalletError;
+use crate::domain::repository::LockingMode;
+use std::future::Future;
+use std::time::Duration;
+
+/// Política de concurrencia compartida por los casos de uso que modifican billeteras.
+///
+/// En modo optimista, los casos de uso envían al repositorio la versión que leyeron y, ante un
+/// `WalletError::ConcurrencyError`, vuelven a leer y reintentan hasta `max_attempts` veces con
+/// una espera lineal entre intentos. En modo pesimista el repositorio serializa las escrituras
+/// con bloqueos de fila, por lo que no se envía versión y los conflictos no deberían ocurrir.
+///
+/// # Examples
+/// ```
+/// use wallet_service::domain::repository::LockingMode;
+/// use wallet_service::use_cases::concurrency::ConcurrencyPolicy;
+///
+/// let policy = ConcurrencyPolicy::new(LockingMode::Optimistic, 5);
+/// assert_eq!(policy.expected_version(3), Some(3));
+///
+/// let policy = ConcurrencyPolicy::new(LockingMode::Pessimistic, 5);
+/// assert_eq!(policy.expected_version(3), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConcurrencyPolicy {
+    mode: LockingMode,
+    max_attempts: u32,
+    backoff: Duration,
+}
+
+impl ConcurrencyPolicy {
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+    /// Crea una política con el modo y número máximo de intentos indicados (mínimo 1).
+    pub fn new(mode: LockingMode, max_attempts: u32) -> Self {
+        Self {
+            mode,
+            max_attempts: max_attempts.max(1),
+            backoff: Duration::from_millis(10),
+        }
+    }
+
+    pub fn mode(&self) -> LockingMode {
+        self.mode
+    }
+
+    /// Versión esperada a enviar al repositorio a partir de la versión leída.
+    pub fn expected_version(&self, read_version: i32) -> Option<i32> {
+        match self.mode {
+            LockingMode::Optimistic => Some(read_version),
+            LockingMode::Pessimistic => None,
+        }
+    }
+
+    /// Ejecuta `operation` reintentando sólo ante `WalletError::ConcurrencyError`.
+    ///
+    /// Cada intento debe volver a leer el estado que necesita: reintentar con la misma versión
+    /// obsoleta fallaría de nuevo.
+    pub async fn retry<T, F, Fut>(&self, mut operation: F) -> Result<T, WalletError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, WalletError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(WalletError::ConcurrencyError(reason)) if attempt < self.max_attempts => {
+                    tracing::warn!(
+                        "Conflicto de concurrencia (intento {}/{}): {}",
+                        attempt,
+                        self.max_attempts,
+                        reason
+                    );
+                    tokio::time::sleep(self.backoff * attempt).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for ConcurrencyPolicy {
+    fn default() -> Self {
+        Self::new(LockingMode::Optimistic, Self::DEFAULT_MAX_ATTEMPTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let calls = AtomicU32::new(0);
+        let policy = ConcurrencyPolicy::new(LockingMode::Optimistic, 3);
+
+        let result = policy
+            .retry(|| async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(WalletError::ConcurrencyError("stale".into()))
+                } else {
+                    Ok(42)
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let policy = ConcurrencyPolicy::new(LockingMode::Optimistic, 2);
+
+        let result: Result<(), _> = policy
+            .retry(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(WalletError::ConcurrencyError("stale".into()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(WalletError::ConcurrencyError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_does_not_retry_other_errors() {
+        let calls = AtomicU32::new(0);
+        let policy = ConcurrencyPolicy::default();
+
+        let result: Result<(), _> = policy
+            .retry(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(WalletError::InvalidData("bad".into()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::domain::entities::{Hold, HoldStatus};
+use crate::domain::error::WalletError;
+use crate::domain::repository::WalletRepository;
+use crate::domain::types::WalletId;
+use std::sync::Arc;
+
+/// Caso de uso para cerrar una reserva de fondos creada por `ProcessMovementUseCase`.
+///
+/// - Si la transacción fue exitosa, la reserva se **captura**: el monto se aplica al balance
+///   contable y se registran los asientos de doble partida.
+/// - Si la transacción se abortó, la reserva se **libera**: lo retenido vuelve al disponible.
+///
+/// La operación es idempotente: confirmar de nuevo en el mismo sentido devuelve la reserva
+/// tal como quedó, lo que permite al Transaction Service reintentar sin efectos dobles.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::confirm_movement::ConfirmMovementUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = ConfirmMovementUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct ConfirmMovementUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+}
+
+impl ConfirmMovementUseCase {
+    /// Construye una nueva instancia inyectando el repositorio de billeteras.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self { wallet_repo }
+    }
+
+    /// Captura o libera la reserva de `wallet_id` asociada a `transaction_id`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `wallet_id` - Billetera dueña de la reserva.
+    /// * `transaction_id` - Transacción que creó la reserva.
+    /// * `is_success` - `true` para capturar, `false` para liberar (compensar).
+    ///
+    /// # Retornos
+    ///
+    /// La reserva en su estado final. Falla con `WalletError::HoldNotFound` si no existe y con
+    /// `WalletError::InvalidHoldState` si ya fue cerrada en el sentido contrario.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let hold = use_case.execute(wallet_id, "tx-1".to_string(), true).await?;
+    /// ```
+    #[tracing::instrument(name = "ConfirmMovementUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        transaction_id: String,
+        is_success: bool,
+    ) -> Result<Hold, WalletError> {
+        let mut hold = self
+            .wallet_repo
+            .find_hold(wallet_id, transaction_id.clone())
+            .await?
+            .ok_or(WalletError::HoldNotFound {
+                wallet_id,
+                transaction_id,
+            })?;
+
+        // Reintento de una confirmación ya aplicada: respondemos con el estado actual.
+        let target = if is_success {
+            HoldStatus::Captured
+        } else {
+            HoldStatus::Released
+        };
+        if hold.status() == target {
+            return Ok(hold);
+        }
+
+        // No se exige versión de la billetera: el compare-and-set sobre el estado del hold ya
+        // impide cerrarlo dos veces, y capturar/liberar sólo aplica deltas sobre lo reservado.
+        if is_success {
+            hold.capture()?;
+            self.wallet_repo.capture_hold(hold.clone(), None).await?;
+            Ok(hold)
+        } else {
+            hold.release()?;
+            self.wallet_repo.release_hold(hold, None).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{LedgerAccount, LedgerEntry, SystemAccount};
+    use crate::domain::repository::MockWalletRepository;
+    use rust_decimal::Decimal;
+
+    fn active_hold(wallet_id: WalletId) -> Hold {
+        Hold::new(
+            wallet_id,
+            "tx-1".to_string(),
+            Decimal::from(-40),
+            SystemAccount::Suspense,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_confirm_success_captures_hold() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let hold = active_hold(wallet_id);
+
+        mock_repo
+            .expect_find_hold()
+            .with(
+                mockall::predicate::eq(wallet_id),
+                mockall::predicate::eq("tx-1".to_string()),
+            )
+            .times(1)
+            .returning(move |_, _| Ok(Some(hold.clone())));
+        mock_repo
+            .expect_capture_hold()
+            .withf(|hold: &Hold, _| hold.status() == HoldStatus::Captured)
+            .times(1)
+            .returning(|hold, _| {
+                LedgerEntry::new(
+                    hold.transaction_id().to_string(),
+                    LedgerAccount::Wallet(hold.wallet_id()),
+                    hold.amount(),
+                    Decimal::from(60),
+                    "USD".into(),
+                )
+            });
+
+        let use_case = ConfirmMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, "tx-1".to_string(), true).await;
+
+        assert_eq!(result.unwrap().status(), HoldStatus::Captured);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_failure_releases_hold() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let hold = active_hold(wallet_id);
+
+        mock_repo
+            .expect_find_hold()
+            .returning(move |_, _| Ok(Some(hold.clone())));
+        mock_repo
+            .expect_release_hold()
+            .withf(|hold: &Hold, _| hold.status() == HoldStatus::Released)
+            .times(1)
+            .returning(|hold, _| Ok(hold));
+
+        let use_case = ConfirmMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, "tx-1".to_string(), false).await;
+
+        assert_eq!(result.unwrap().status(), HoldStatus::Released);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_is_idempotent_for_same_outcome() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let mut hold = active_hold(wallet_id);
+        hold.capture().unwrap();
+
+        mock_repo
+            .expect_find_hold()
+            .returning(move |_, _| Ok(Some(hold.clone())));
+        mock_repo.expect_capture_hold().never();
+
+        let use_case = ConfirmMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, "tx-1".to_string(), true).await;
+
+        assert_eq!(result.unwrap().status(), HoldStatus::Captured);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_rejects_opposite_outcome() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let mut hold = active_hold(wallet_id);
+        hold.release().unwrap();
+
+        mock_repo
+            .expect_find_hold()
+            .returning(move |_, _| Ok(Some(hold.clone())));
+
+        let use_case = ConfirmMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, "tx-1".to_string(), true).await;
+
+        assert!(matches!(result, Err(WalletError::InvalidHoldState(_))));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_hold_not_found() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+
+        let use_case = ConfirmMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, "tx-1".to_string(), true).await;
+
+        assert!(matches!(result, Err(WalletError::HoldNotFound { .. })));
+    }
+}
@@ -83,7 +83,7 @@ mod tests {
             .returning(|_| Ok(false));
 
         // Expect the creation of the user to succeed
-        mock_repo.expect_create().times(1).returning(|u| Ok(u));
+        mock_repo.expect_create().times(1).returning(Ok);
 
         let use_case = CreateUserUseCase::new(Arc::new(mock_repo));
 
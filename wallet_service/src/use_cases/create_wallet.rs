@@ -4,6 +4,7 @@ use crate::domain::{
     repository::{UserRepository, WalletRepository},
     types::UserId,
 };
+use rust_decimal::Decimal;
 use std::sync::Arc;
 /// Caso de uso que gestiona la creación segura de una Wallet para un Usuario.
 ///
@@ -39,13 +40,14 @@ impl CreateWalletUseCase {
     /// ```ignore
     /// use uuid::Uuid;
     /// let user_id = Uuid::new_v4();
-    /// let wallet = use_case.execute(user_id, "USD".to_string(), "Main".to_string()).await;
+    /// let wallet = use_case.execute(user_id, "USD".to_string(), "Main".to_string(), None).await;
     /// ```
     pub async fn execute(
         &self,
         user_id: UserId,
         currency: String,
         label: String,
+        overdraft_limit: Option<Decimal>,
     ) -> Result<Wallet, WalletError> {
         let user_option = self
             .user_repo
@@ -58,11 +60,14 @@ impl CreateWalletUseCase {
         }
 
         // Construimos usando el patrón Builder (valida interiormente)
-        let wallet = Wallet::builder()
+        let mut builder = Wallet::builder()
             .user_id(user_id)
             .label(label)
-            .currency(currency)
-            .build()?;
+            .currency(currency);
+        if let Some(limit) = overdraft_limit {
+            builder = builder.overdraft_limit(limit);
+        }
+        let wallet = builder.build()?;
 
         self.wallet_repo.create(wallet).await
     }
@@ -99,13 +104,13 @@ mod tests {
             });
 
         // Espera que se cree el wallet y reciba de vuelta
-        mock_wallet_repo.expect_create().returning(|w| Ok(w));
+        mock_wallet_repo.expect_create().returning(Ok);
 
         let use_case =
             CreateWalletUseCase::new(Arc::new(mock_wallet_repo), Arc::new(mock_user_repo));
 
         let result = use_case
-            .execute(user_id, "USD".to_string(), "Main Wallet".to_string())
+            .execute(user_id, "USD".to_string(), "Main Wallet".to_string(), None)
             .await;
 
         assert!(result.is_ok());
@@ -131,7 +136,7 @@ mod tests {
             CreateWalletUseCase::new(Arc::new(mock_wallet_repo), Arc::new(mock_user_repo));
 
         let result = use_case
-            .execute(user_id, "USD".to_string(), "Main Wallet".to_string())
+            .execute(user_id, "USD".to_string(), "Main Wallet".to_string(), None)
             .await;
 
         assert!(matches!(result, Err(WalletError::UserNotFound(id)) if id == user_id));
@@ -163,7 +168,7 @@ mod tests {
 
         // Pasando un currency en blanco/inválido (inválido por el builder)
         let result = use_case
-            .execute(user_id, "XX".to_string(), "Main Wallet".to_string())
+            .execute(user_id, "XX".to_string(), "Main Wallet".to_string(), None)
             .await;
 
         assert!(matches!(result, Err(WalletError::InvalidData(_))));
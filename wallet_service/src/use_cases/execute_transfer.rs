@@ -0,0 +1,351 @@
+use crate::domain::entities::{Hold, HoldStatus, SystemAccount};
+use crate::domain::error::WalletError;
+use crate::domain::repository::WalletRepository;
+use crate::domain::types::WalletId;
+use crate::use_cases::concurrency::ConcurrencyPolicy;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Resultado de ejecutar una transferencia.
+#[derive(Debug, Clone)]
+pub struct ExecutedTransfer {
+    /// Pata de débito en la billetera de origen (capturada).
+    pub debit: Hold,
+    /// Pata de crédito en la billetera de destino (capturada).
+    pub credit: Hold,
+    /// `true` si la transferencia ya se había ejecutado y se devolvió el resultado original.
+    pub replayed: bool,
+}
+
+/// Caso de uso para ejecutar una transferencia entre dos billeteras en un solo paso.
+///
+/// A diferencia de `ProcessMovementUseCase` + `ConfirmMovementUseCase`, no deja reservas
+/// intermedias: el débito y el crédito se aplican en la misma transacción de BD, o ninguno.
+/// Ambas patas quedan registradas como holds capturados contra la cuenta puente, así que
+/// `GetMovementStatus` las informa como aplicadas.
+///
+/// La operación es idempotente por `transaction_id`: un reenvío devuelve las patas originales.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::execute_transfer::ExecuteTransferUseCase;
+/// use std::sync::Arc;
+///
+/// let use_case = ExecuteTransferUseCase::new(wallet_repo);
+/// let transfer = use_case.execute("tx-1".into(), source, destination, dec!(10), dec!(9.2)).await?;
+/// ```
+#[derive(Clone)]
+pub struct ExecuteTransferUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+    concurrency: ConcurrencyPolicy,
+    frozen_accepts_credits: bool,
+}
+
+impl ExecuteTransferUseCase {
+    /// Construye una nueva instancia de `ExecuteTransferUseCase`.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self {
+            wallet_repo,
+            concurrency: ConcurrencyPolicy::default(),
+            frozen_accepts_credits: true,
+        }
+    }
+
+    /// Define si una billetera congelada sigue recibiendo créditos (por defecto `true`).
+    pub fn with_frozen_accepts_credits(mut self, frozen_accepts_credits: bool) -> Self {
+        self.frozen_accepts_credits = frozen_accepts_credits;
+        self
+    }
+
+    /// Reemplaza la política de concurrencia (modo de bloqueo y reintentos).
+    pub fn with_concurrency_policy(mut self, concurrency: ConcurrencyPolicy) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Debita `source_amount` de `source` y acredita `destination_amount` a `destination`.
+    ///
+    /// Los montos son positivos y cada uno está en la divisa de su billetera (difieren si la
+    /// transferencia tuvo conversión). Se valida como una reserva de débito y otra de crédito
+    /// (estado de las billeteras, decimales, saldo disponible más sobregiro y límites de gasto).
+    ///
+    /// # Retornos
+    ///
+    /// Las dos patas capturadas, o las originales si la transferencia ya existía
+    /// (`replayed = true`). Falla con los mismos errores que `ProcessMovementUseCase`, y con
+    /// `WalletError::InvalidData` si origen y destino coinciden o si el `transaction_id` ya
+    /// registró movimientos distintos.
+    #[tracing::instrument(name = "ExecuteTransferUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        transaction_id: String,
+        source: WalletId,
+        destination: WalletId,
+        source_amount: Decimal,
+        destination_amount: Decimal,
+    ) -> Result<ExecutedTransfer, WalletError> {
+        if source == destination {
+            return Err(WalletError::InvalidData(
+                "La billetera de origen y la de destino deben ser distintas".into(),
+            ));
+        }
+        if source_amount <= Decimal::ZERO || destination_amount <= Decimal::ZERO {
+            return Err(WalletError::InvalidData(
+                "Los montos de una transferencia deben ser positivos".into(),
+            ));
+        }
+
+        let mut debit = Hold::new(
+            source,
+            transaction_id.clone(),
+            -source_amount,
+            SystemAccount::Suspense,
+        )?;
+        let mut credit = Hold::new(
+            destination,
+            transaction_id,
+            destination_amount,
+            SystemAccount::Suspense,
+        )?;
+        debit.capture()?;
+        credit.capture()?;
+
+        if let Some(existing) = self.find_existing(&debit, &credit).await? {
+            return Ok(existing);
+        }
+
+        match self
+            .concurrency
+            .retry(|| self.try_transfer(debit.clone(), credit.clone()))
+            .await
+        {
+            Ok(()) => Ok(ExecutedTransfer {
+                debit,
+                credit,
+                replayed: false,
+            }),
+            // Un reenvío concurrente ganó la inserción: devolvemos su resultado.
+            Err(WalletError::DuplicateMovement { .. }) => self
+                .find_existing(&debit, &credit)
+                .await?
+                .ok_or_else(|| WalletError::DuplicateMovement {
+                    wallet_id: source,
+                    transaction_id: debit.transaction_id().to_string(),
+                }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Busca una transferencia ya ejecutada con el mismo `transaction_id` y verifica que sea
+    /// la misma.
+    async fn find_existing(
+        &self,
+        debit: &Hold,
+        credit: &Hold,
+    ) -> Result<Option<ExecutedTransfer>, WalletError> {
+        let transaction_id = debit.transaction_id().to_string();
+        let existing_debit = self
+            .wallet_repo
+            .find_hold(debit.wallet_id(), transaction_id.clone())
+            .await?;
+        let existing_credit = self
+            .wallet_repo
+            .find_hold(credit.wallet_id(), transaction_id.clone())
+            .await?;
+
+        match (existing_debit, existing_credit) {
+            (None, None) => Ok(None),
+            (Some(existing_debit), Some(existing_credit))
+                if existing_debit.amount() == debit.amount()
+                    && existing_credit.amount() == credit.amount()
+                    && existing_debit.status() == HoldStatus::Captured
+                    && existing_credit.status() == HoldStatus::Captured =>
+            {
+                tracing::info!(
+                    "Transferencia duplicada (tx {}): se devuelven las patas originales",
+                    transaction_id
+                );
+                Ok(Some(ExecutedTransfer {
+                    debit: existing_debit,
+                    credit: existing_credit,
+                    replayed: true,
+                }))
+            }
+            // Otra transferencia con la misma clave, o patas reservadas por separado.
+            _ => Err(WalletError::InvalidData(format!(
+                "La transacción {} ya registró otros movimientos en estas billeteras",
+                transaction_id
+            ))),
+        }
+    }
+
+    /// Un intento de transferencia: lee ambas billeteras, valida las patas y persiste con sus
+    /// versiones.
+    async fn try_transfer(&self, debit: Hold, credit: Hold) -> Result<(), WalletError> {
+        let mut versions = Vec::with_capacity(2);
+        for hold in [&debit, &credit] {
+            let wallet = self
+                .wallet_repo
+                .find_by_id(hold.wallet_id())
+                .await?
+                .ok_or(WalletError::NotFound(hold.wallet_id()))?;
+
+            wallet.ensure_accepts_movement(hold.is_debit(), self.frozen_accepts_credits)?;
+            wallet.ensure_amount_scale(hold.amount())?;
+            if hold.is_debit() {
+                wallet.ensure_can_debit(hold.reserved_amount())?;
+            }
+            versions.push(self.concurrency.expected_version(wallet.version()));
+        }
+
+        self.wallet_repo
+            .transfer(debit, credit, versions[0], versions[1])
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{LedgerAccount, LedgerEntry, StatusChange, Wallet};
+    use crate::domain::repository::MockWalletRepository;
+    use crate::domain::types::UserId;
+    use std::str::FromStr;
+
+    fn wallet_with(id: WalletId, balance: &str, currency: &str) -> Wallet {
+        Wallet::reconstitute(
+            id,
+            UserId::new(),
+            "Main".into(),
+            Decimal::from_str(balance).unwrap(),
+            Decimal::ZERO,
+            None,
+            currency.into(),
+            1,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
+    fn entry(hold: &Hold) -> LedgerEntry {
+        LedgerEntry::new(
+            hold.transaction_id().to_string(),
+            LedgerAccount::Wallet(hold.wallet_id()),
+            hold.amount(),
+            Decimal::from(100),
+            "USD".into(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transfer_applies_both_legs_in_one_call() {
+        let (source, destination) = (WalletId::new(), WalletId::new());
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        mock_repo.expect_find_by_id().returning(move |id| {
+            let currency = if id == source { "USD" } else { "EUR" };
+            Ok(Some(wallet_with(id, "50.00", currency)))
+        });
+        mock_repo
+            .expect_transfer()
+            .withf(move |debit, credit, debit_version, credit_version| {
+                debit.wallet_id() == source
+                    && debit.amount() == Decimal::from(-20)
+                    && credit.wallet_id() == destination
+                    && credit.amount() == Decimal::from_str("18.40").unwrap()
+                    && debit.status() == HoldStatus::Captured
+                    && credit.status() == HoldStatus::Captured
+                    && *debit_version == Some(1)
+                    && *credit_version == Some(1)
+            })
+            .times(1)
+            .returning(|debit, credit, _, _| Ok((entry(&debit), entry(&credit))));
+
+        let use_case = ExecuteTransferUseCase::new(Arc::new(mock_repo));
+        let transfer = use_case
+            .execute(
+                "tx-1".into(),
+                source,
+                destination,
+                Decimal::from(20),
+                Decimal::from_str("18.40").unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!transfer.replayed);
+        assert_eq!(transfer.debit.counterparty(), SystemAccount::Suspense);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_is_idempotent_on_transaction_id() {
+        let (source, destination) = (WalletId::new(), WalletId::new());
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo
+            .expect_find_hold()
+            .returning(move |wallet_id, tx| {
+                let amount = if wallet_id == source { -10 } else { 10 };
+                let mut hold = Hold::new(
+                    wallet_id,
+                    tx,
+                    Decimal::from(amount),
+                    SystemAccount::Suspense,
+                )
+                .unwrap();
+                hold.capture().unwrap();
+                Ok(Some(hold))
+            });
+        mock_repo.expect_transfer().never();
+
+        let use_case = ExecuteTransferUseCase::new(Arc::new(mock_repo));
+        let transfer = use_case
+            .execute(
+                "tx-1".into(),
+                source,
+                destination,
+                Decimal::from(10),
+                Decimal::from(10),
+            )
+            .await
+            .unwrap();
+        assert!(transfer.replayed);
+
+        // Misma clave con otro monto: no es un reenvío.
+        let result = use_case
+            .execute(
+                "tx-1".into(),
+                source,
+                destination,
+                Decimal::from(12),
+                Decimal::from(12),
+            )
+            .await;
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_rejects_insufficient_funds_without_writing() {
+        let (source, destination) = (WalletId::new(), WalletId::new());
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet_with(id, "5.00", "USD"))));
+        mock_repo.expect_transfer().never();
+
+        let use_case = ExecuteTransferUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                "tx-1".into(),
+                source,
+                destination,
+                Decimal::from(10),
+                Decimal::from(10),
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InsufficientFunds(id)) if id == source));
+    }
+}
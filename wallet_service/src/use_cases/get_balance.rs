@@ -0,0 +1,91 @@
+use crate::domain::{
+    entities::BalanceSnapshot, error::WalletError, repository::WalletRepository, types::WalletId,
+};
+use std::sync::Arc;
+
+/// Caso de uso para leer el saldo vivo de una billetera.
+///
+/// Pensado para consumidores internos (auditoría, conciliación) que necesitan una lectura
+/// rápida y con marca de tiempo, sin pasar por la API HTTP.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::get_balance::GetBalanceUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = GetBalanceUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct GetBalanceUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+}
+
+impl GetBalanceUseCase {
+    /// Construye una nueva instancia inyectando el repositorio de billeteras.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self { wallet_repo }
+    }
+
+    /// Devuelve el saldo de `wallet_id` o `WalletError::NotFound` si no existe.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let snapshot = use_case.execute(wallet_id).await?;
+    /// ```
+    #[tracing::instrument(name = "GetBalanceUseCase::execute", skip(self))]
+    pub async fn execute(&self, wallet_id: WalletId) -> Result<BalanceSnapshot, WalletError> {
+        self.wallet_repo
+            .find_balance(wallet_id)
+            .await?
+            .ok_or(WalletError::NotFound(wallet_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::WalletStatus;
+    use crate::domain::repository::MockWalletRepository;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn test_get_balance_success() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo
+            .expect_find_balance()
+            .with(mockall::predicate::eq(wallet_id))
+            .returning(|id| {
+                Ok(Some(BalanceSnapshot {
+                    wallet_id: id,
+                    currency: "USD".into(),
+                    ledger_balance: Decimal::from(100),
+                    available_balance: Decimal::from(60),
+                    version: 4,
+                    status: WalletStatus::Active,
+                    taken_at: chrono::Utc::now(),
+                }))
+            });
+
+        let use_case = GetBalanceUseCase::new(Arc::new(mock_repo));
+        let snapshot = use_case.execute(wallet_id).await.unwrap();
+
+        assert_eq!(snapshot.wallet_id, wallet_id);
+        assert_eq!(snapshot.available_balance, Decimal::from(60));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_not_found() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo.expect_find_balance().returning(|_| Ok(None));
+
+        let use_case = GetBalanceUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id).await;
+
+        assert!(matches!(result, Err(WalletError::NotFound(id)) if id == wallet_id));
+    }
+}
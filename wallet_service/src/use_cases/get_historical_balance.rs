@@ -0,0 +1,216 @@
+use crate::domain::{
+    entities::{DailyBalanceSnapshot, HistoricalBalance},
+    error::WalletError,
+    repository::{BalanceSnapshotRepository, LedgerRepository, WalletRepository},
+    types::WalletId,
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Caso de uso para consultar el saldo contable de una billetera en un instante pasado.
+///
+/// Parte del snapshot diario más reciente anterior a `as_of` y le suma los asientos
+/// registrados desde su cierre hasta `as_of`. Sin snapshots disponibles suma el libro mayor
+/// completo, por lo que el resultado no depende de que el job diario haya corrido.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::get_historical_balance::GetHistoricalBalanceUseCase;
+/// use wallet_service::domain::repository::{
+///     MockBalanceSnapshotRepository, MockLedgerRepository, MockWalletRepository,
+/// };
+/// use std::sync::Arc;
+///
+/// let use_case = GetHistoricalBalanceUseCase::new(
+///     Arc::new(MockWalletRepository::new()),
+///     Arc::new(MockBalanceSnapshotRepository::new()),
+///     Arc::new(MockLedgerRepository::new()),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct GetHistoricalBalanceUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+    snapshot_repo: Arc<dyn BalanceSnapshotRepository>,
+    ledger_repo: Arc<dyn LedgerRepository>,
+}
+
+impl GetHistoricalBalanceUseCase {
+    /// Construye una nueva instancia inyectando los repositorios de billeteras, snapshots y ledger.
+    pub fn new(
+        wallet_repo: Arc<dyn WalletRepository>,
+        snapshot_repo: Arc<dyn BalanceSnapshotRepository>,
+        ledger_repo: Arc<dyn LedgerRepository>,
+    ) -> Self {
+        Self {
+            wallet_repo,
+            snapshot_repo,
+            ledger_repo,
+        }
+    }
+
+    /// Calcula el saldo contable de `wallet_id` en `as_of` (incluye los asientos de ese instante).
+    ///
+    /// # Retornos
+    ///
+    /// El saldo histórico, `WalletError::NotFound` si la billetera no existe o
+    /// `WalletError::InvalidData` si `as_of` está en el futuro.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let balance = use_case.execute(wallet_id, "2024-03-31T23:59:59Z".parse()?).await?;
+    /// ```
+    #[tracing::instrument(name = "GetHistoricalBalanceUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        as_of: DateTime<Utc>,
+    ) -> Result<HistoricalBalance, WalletError> {
+        if as_of > Utc::now() {
+            return Err(WalletError::InvalidData(
+                "as_of no puede ser una fecha futura".to_string(),
+            ));
+        }
+
+        let wallet = self
+            .wallet_repo
+            .find_by_id(wallet_id)
+            .await?
+            .ok_or(WalletError::NotFound(wallet_id))?;
+
+        let snapshot = self
+            .snapshot_repo
+            .find_latest_before(wallet_id, as_of.date_naive())
+            .await?;
+
+        let (opening, from) = match &snapshot {
+            Some(s) => (
+                s.ledger_balance,
+                Some(DailyBalanceSnapshot::closes_at(s.balance_date)),
+            ),
+            None => (Decimal::ZERO, None),
+        };
+        let movements = self
+            .ledger_repo
+            .sum_amounts_between(wallet_id, from, as_of)
+            .await?;
+
+        Ok(HistoricalBalance {
+            wallet_id,
+            currency: wallet.currency().to_string(),
+            ledger_balance: opening + movements,
+            as_of,
+            snapshot_date: snapshot.map(|s| s.balance_date),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Wallet;
+    use crate::domain::repository::{
+        MockBalanceSnapshotRepository, MockLedgerRepository, MockWalletRepository,
+    };
+    use crate::domain::types::UserId;
+    use chrono::{Duration, NaiveDate, TimeZone};
+    use mockall::predicate::eq;
+
+    fn existing_wallet_repo() -> MockWalletRepository {
+        let mut mock_wallet_repo = MockWalletRepository::new();
+        mock_wallet_repo.expect_find_by_id().returning(|_| {
+            Ok(Some(
+                Wallet::builder()
+                    .user_id(UserId::new())
+                    .label("Main".to_string())
+                    .currency("USD".to_string())
+                    .build()
+                    .unwrap(),
+            ))
+        });
+        mock_wallet_repo
+    }
+
+    #[tokio::test]
+    async fn test_balance_from_snapshot_plus_later_movements() {
+        let wallet_id = WalletId::new();
+        let as_of = Utc.with_ymd_and_hms(2024, 4, 2, 15, 0, 0).unwrap();
+        let snapshot_day = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let mut mock_snapshots = MockBalanceSnapshotRepository::new();
+        let mut mock_ledger = MockLedgerRepository::new();
+
+        mock_snapshots
+            .expect_find_latest_before()
+            .with(eq(wallet_id), eq(as_of.date_naive()))
+            .returning(move |id, _| {
+                Ok(Some(DailyBalanceSnapshot {
+                    wallet_id: id,
+                    balance_date: snapshot_day,
+                    ledger_balance: Decimal::from(100),
+                    currency: "USD".into(),
+                }))
+            });
+        mock_ledger
+            .expect_sum_amounts_between()
+            .with(
+                eq(wallet_id),
+                eq(Some(Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap())),
+                eq(as_of),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(Decimal::from(-30)));
+
+        let use_case = GetHistoricalBalanceUseCase::new(
+            Arc::new(existing_wallet_repo()),
+            Arc::new(mock_snapshots),
+            Arc::new(mock_ledger),
+        );
+        let balance = use_case.execute(wallet_id, as_of).await.unwrap();
+
+        assert_eq!(balance.ledger_balance, Decimal::from(70));
+        assert_eq!(balance.snapshot_date, Some(snapshot_day));
+        assert_eq!(balance.currency, "USD");
+    }
+
+    #[tokio::test]
+    async fn test_balance_without_snapshot_sums_whole_ledger() {
+        let wallet_id = WalletId::new();
+        let as_of = Utc::now() - Duration::hours(1);
+        let mut mock_snapshots = MockBalanceSnapshotRepository::new();
+        let mut mock_ledger = MockLedgerRepository::new();
+
+        mock_snapshots
+            .expect_find_latest_before()
+            .returning(|_, _| Ok(None));
+        mock_ledger
+            .expect_sum_amounts_between()
+            .with(eq(wallet_id), eq(None), eq(as_of))
+            .times(1)
+            .returning(|_, _, _| Ok(Decimal::from(45)));
+
+        let use_case = GetHistoricalBalanceUseCase::new(
+            Arc::new(existing_wallet_repo()),
+            Arc::new(mock_snapshots),
+            Arc::new(mock_ledger),
+        );
+        let balance = use_case.execute(wallet_id, as_of).await.unwrap();
+
+        assert_eq!(balance.ledger_balance, Decimal::from(45));
+        assert_eq!(balance.snapshot_date, None);
+    }
+
+    #[tokio::test]
+    async fn test_balance_in_the_future_is_rejected() {
+        let use_case = GetHistoricalBalanceUseCase::new(
+            Arc::new(MockWalletRepository::new()),
+            Arc::new(MockBalanceSnapshotRepository::new()),
+            Arc::new(MockLedgerRepository::new()),
+        );
+
+        let result = use_case
+            .execute(WalletId::new(), Utc::now() + Duration::days(1))
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+}
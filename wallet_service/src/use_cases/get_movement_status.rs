@@ -0,0 +1,93 @@
+use crate::domain::entities::Hold;
+use crate::domain::error::WalletError;
+use crate::domain::repository::WalletRepository;
+use crate::domain::types::WalletId;
+use std::sync::Arc;
+
+/// Caso de uso para consultar el estado de una pata (`transaction_id`, `wallet_id`).
+///
+/// Permite al Transaction Service saber si un movimiento ya fue reservado, aplicado o
+/// liberado antes de volver a enviarlo.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::get_movement_status::GetMovementStatusUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = GetMovementStatusUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct GetMovementStatusUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+}
+
+impl GetMovementStatusUseCase {
+    /// Construye una nueva instancia inyectando el repositorio de billeteras.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self { wallet_repo }
+    }
+
+    /// Devuelve la reserva de la pata, o `None` si nunca se procesó.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let hold = use_case.execute(wallet_id, "tx-1".to_string()).await?;
+    /// ```
+    #[tracing::instrument(name = "GetMovementStatusUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        transaction_id: String,
+    ) -> Result<Option<Hold>, WalletError> {
+        if transaction_id.trim().is_empty() {
+            return Err(WalletError::InvalidData(
+                "El transaction_id es obligatorio".into(),
+            ));
+        }
+
+        self.wallet_repo.find_hold(wallet_id, transaction_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{HoldStatus, SystemAccount};
+    use crate::domain::repository::MockWalletRepository;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn test_get_movement_status_returns_hold() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let hold = Hold::new(
+            wallet_id,
+            "tx-1".into(),
+            Decimal::from(10),
+            SystemAccount::ExternalFunding,
+        )
+        .unwrap();
+
+        mock_repo
+            .expect_find_hold()
+            .with(
+                mockall::predicate::eq(wallet_id),
+                mockall::predicate::eq("tx-1".to_string()),
+            )
+            .returning(move |_, _| Ok(Some(hold.clone())));
+
+        let use_case = GetMovementStatusUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, "tx-1".into()).await.unwrap();
+
+        assert_eq!(result.unwrap().status(), HoldStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_get_movement_status_requires_transaction_id() {
+        let use_case = GetMovementStatusUseCase::new(Arc::new(MockWalletRepository::new()));
+        let result = use_case.execute(WalletId::new(), " ".into()).await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+}
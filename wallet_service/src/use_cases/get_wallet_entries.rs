@@ -0,0 +1,280 @@
+use crate::domain::{
+    entities::LedgerEntry,
+    error::WalletError,
+    repository::{LedgerRepository, WalletRepository},
+    types::WalletId,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Tamaño de página por defecto cuando el cliente no especifica `limit`.
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+/// Tamaño máximo de página permitido para proteger la base de datos.
+pub const MAX_PAGE_SIZE: i64 = 200;
+
+/// Caso de uso para paginar los asientos del libro mayor de una billetera.
+///
+/// Verifica que la billetera exista (para distinguir "sin movimientos" de "no existe")
+/// y luego delega la consulta paginada al `LedgerRepository`.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::get_wallet_entries::GetWalletEntriesUseCase;
+/// use wallet_service::domain::repository::{MockLedgerRepository, MockWalletRepository};
+/// use std::sync::Arc;
+///
+/// let use_case = GetWalletEntriesUseCase::new(
+///     Arc::new(MockWalletRepository::new()),
+///     Arc::new(MockLedgerRepository::new()),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct GetWalletEntriesUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+    ledger_repo: Arc<dyn LedgerRepository>,
+}
+
+impl GetWalletEntriesUseCase {
+    /// Construye una nueva instancia inyectando los repositorios de billeteras y del ledger.
+    pub fn new(
+        wallet_repo: Arc<dyn WalletRepository>,
+        ledger_repo: Arc<dyn LedgerRepository>,
+    ) -> Self {
+        Self {
+            wallet_repo,
+            ledger_repo,
+        }
+    }
+
+    /// Ejecuta la consulta paginada de asientos.
+    ///
+    /// # Argumentos
+    ///
+    /// * `wallet_id` - Billetera a consultar.
+    /// * `limit` - Tamaño de página; se acota a `1..=MAX_PAGE_SIZE` (por defecto `DEFAULT_PAGE_SIZE`).
+    /// * `offset` - Cantidad de asientos a saltar; valores negativos se tratan como cero.
+    ///
+    /// # Retornos
+    ///
+    /// Devuelve los asientos (más recientes primero) o `WalletError::NotFound` si la billetera no existe.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let entries = use_case.execute(wallet_id, Some(20), Some(0)).await.unwrap();
+    /// ```
+    #[tracing::instrument(name = "GetWalletEntriesUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Vec<LedgerEntry>, WalletError> {
+        if self.wallet_repo.find_by_id(wallet_id).await?.is_none() {
+            return Err(WalletError::NotFound(wallet_id));
+        }
+
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let offset = offset.unwrap_or(0).max(0);
+
+        self.ledger_repo
+            .find_by_wallet_id(wallet_id, limit, offset)
+            .await
+    }
+
+    /// Página de los asientos de `wallet_id` con `from <= created_at < to`, en orden
+    /// cronológico y por keyset sobre `(created_at, id)`.
+    ///
+    /// Usa el mismo reloj que `GetHistoricalBalanceUseCase`: el saldo en `from` (exclusive)
+    /// más estos asientos da el saldo en `to` (exclusive), que es lo que necesita un extracto.
+    ///
+    /// # Argumentos
+    ///
+    /// * `after` - Último asiento de la página anterior (`None` para empezar).
+    /// * `limit` - Tamaño de página; se acota a `1..=MAX_PAGE_SIZE` (por defecto `DEFAULT_PAGE_SIZE`).
+    ///
+    /// # Retornos
+    ///
+    /// Los asientos, `WalletError::NotFound` si la billetera no existe o
+    /// `WalletError::InvalidData` si el rango está vacío.
+    #[tracing::instrument(name = "GetWalletEntriesUseCase::execute_between", skip(self))]
+    pub async fn execute_between(
+        &self,
+        wallet_id: WalletId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        after: Option<&LedgerEntry>,
+        limit: Option<i64>,
+    ) -> Result<Vec<LedgerEntry>, WalletError> {
+        if from >= to {
+            return Err(WalletError::InvalidData(
+                "El rango está vacío: 'from' debe ser anterior a 'to'".to_string(),
+            ));
+        }
+        if self.wallet_repo.find_by_id(wallet_id).await?.is_none() {
+            return Err(WalletError::NotFound(wallet_id));
+        }
+
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let after = after.map(|entry| (entry.created_at(), entry.id()));
+
+        self.ledger_repo
+            .find_between(wallet_id, from, to, after, limit)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{LedgerAccount, Wallet};
+    use crate::domain::repository::{MockLedgerRepository, MockWalletRepository};
+    use crate::domain::types::UserId;
+    use rust_decimal::Decimal;
+
+    fn existing_wallet_repo(wallet_id: WalletId) -> MockWalletRepository {
+        let mut mock_wallet_repo = MockWalletRepository::new();
+        mock_wallet_repo
+            .expect_find_by_id()
+            .with(mockall::predicate::eq(wallet_id))
+            .returning(|_| {
+                Ok(Some(
+                    Wallet::builder()
+                        .user_id(UserId::new())
+                        .label("Main".to_string())
+                        .currency("USD".to_string())
+                        .build()
+                        .unwrap(),
+                ))
+            });
+        mock_wallet_repo
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet_entries_success() {
+        let wallet_id = WalletId::new();
+        let mut mock_ledger_repo = MockLedgerRepository::new();
+
+        mock_ledger_repo
+            .expect_find_by_wallet_id()
+            .with(
+                mockall::predicate::eq(wallet_id),
+                mockall::predicate::eq(DEFAULT_PAGE_SIZE),
+                mockall::predicate::eq(0),
+            )
+            .times(1)
+            .returning(|id, _, _| {
+                Ok(vec![LedgerEntry::new(
+                    "tx-1".into(),
+                    LedgerAccount::Wallet(id),
+                    Decimal::from(25),
+                    Decimal::from(25),
+                    "USD".into(),
+                )
+                .unwrap()])
+            });
+
+        let use_case = GetWalletEntriesUseCase::new(
+            Arc::new(existing_wallet_repo(wallet_id)),
+            Arc::new(mock_ledger_repo),
+        );
+        let result = use_case.execute(wallet_id, None, None).await;
+
+        assert!(result.is_ok());
+        let entries = result.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].transaction_id(), "tx-1");
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet_entries_clamps_pagination() {
+        let wallet_id = WalletId::new();
+        let mut mock_ledger_repo = MockLedgerRepository::new();
+
+        mock_ledger_repo
+            .expect_find_by_wallet_id()
+            .with(
+                mockall::predicate::eq(wallet_id),
+                mockall::predicate::eq(MAX_PAGE_SIZE),
+                mockall::predicate::eq(0),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let use_case = GetWalletEntriesUseCase::new(
+            Arc::new(existing_wallet_repo(wallet_id)),
+            Arc::new(mock_ledger_repo),
+        );
+        let result = use_case.execute(wallet_id, Some(10_000), Some(-5)).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet_entries_wallet_not_found() {
+        let wallet_id = WalletId::new();
+        let mut mock_wallet_repo = MockWalletRepository::new();
+        mock_wallet_repo.expect_find_by_id().returning(|_| Ok(None));
+
+        let use_case = GetWalletEntriesUseCase::new(
+            Arc::new(mock_wallet_repo),
+            Arc::new(MockLedgerRepository::new()),
+        );
+        let result = use_case.execute(wallet_id, None, None).await;
+
+        assert!(matches!(result, Err(WalletError::NotFound(id)) if id == wallet_id));
+    }
+
+    #[tokio::test]
+    async fn test_entries_between_resume_after_last_entry() {
+        let wallet_id = WalletId::new();
+        let from = Utc::now() - chrono::Duration::days(30);
+        let to = Utc::now();
+        let last = LedgerEntry::new(
+            "tx-1".into(),
+            LedgerAccount::Wallet(wallet_id),
+            Decimal::from(25),
+            Decimal::from(25),
+            "USD".into(),
+        )
+        .unwrap();
+        let cursor = (last.created_at(), last.id());
+        let mut mock_ledger_repo = MockLedgerRepository::new();
+
+        mock_ledger_repo
+            .expect_find_between()
+            .with(
+                mockall::predicate::eq(wallet_id),
+                mockall::predicate::eq(from),
+                mockall::predicate::eq(to),
+                mockall::predicate::eq(Some(cursor)),
+                mockall::predicate::eq(MAX_PAGE_SIZE),
+            )
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(vec![]));
+
+        let use_case = GetWalletEntriesUseCase::new(
+            Arc::new(existing_wallet_repo(wallet_id)),
+            Arc::new(mock_ledger_repo),
+        );
+        let result = use_case
+            .execute_between(wallet_id, from, to, Some(&last), Some(10_000))
+            .await;
+
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_entries_between_rejects_empty_range() {
+        let now = Utc::now();
+        let use_case = GetWalletEntriesUseCase::new(
+            Arc::new(MockWalletRepository::new()),
+            Arc::new(MockLedgerRepository::new()),
+        );
+
+        let result = use_case
+            .execute_between(WalletId::new(), now, now, None, None)
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::domain::{
+    entities::{DebitUsage, SpendingLimits},
+    error::WalletError,
+    repository::WalletRepository,
+    types::WalletId,
+};
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Límites de gasto de una billetera junto con el consumo del día y del mes en curso.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WalletLimits {
+    pub wallet_id: WalletId,
+    pub limits: SpendingLimits,
+    pub usage: DebitUsage,
+}
+
+/// Caso de uso para consultar los límites de gasto configurados y su consumo actual.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::get_wallet_limits::GetWalletLimitsUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = GetWalletLimitsUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct GetWalletLimitsUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+}
+
+impl GetWalletLimitsUseCase {
+    /// Construye una nueva instancia inyectando el repositorio de billeteras.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self { wallet_repo }
+    }
+
+    /// Devuelve los límites y el consumo de `wallet_id` o `WalletError::NotFound` si no existe.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let limits = use_case.execute(wallet_id).await?;
+    /// ```
+    #[tracing::instrument(name = "GetWalletLimitsUseCase::execute", skip(self))]
+    pub async fn execute(&self, wallet_id: WalletId) -> Result<WalletLimits, WalletError> {
+        if self.wallet_repo.find_by_id(wallet_id).await?.is_none() {
+            return Err(WalletError::NotFound(wallet_id));
+        }
+
+        let limits = self.wallet_repo.find_limits(wallet_id).await?;
+        let usage = self
+            .wallet_repo
+            .find_debit_usage(wallet_id, Utc::now())
+            .await?;
+
+        Ok(WalletLimits {
+            wallet_id,
+            limits,
+            usage,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{StatusChange, Wallet};
+    use crate::domain::repository::MockWalletRepository;
+    use crate::domain::types::UserId;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn test_get_wallet_limits_with_usage() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo.expect_find_by_id().returning(|id| {
+            Ok(Some(
+                Wallet::reconstitute(
+                    id,
+                    UserId::new(),
+                    "Main".into(),
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                    None,
+                    "USD".into(),
+                    1,
+                    StatusChange::active(),
+                )
+                .unwrap(),
+            ))
+        });
+        mock_repo.expect_find_limits().returning(|_| {
+            Ok(SpendingLimits {
+                daily: Some(Decimal::from(500)),
+                ..Default::default()
+            })
+        });
+        mock_repo.expect_find_debit_usage().returning(|_, _| {
+            Ok(DebitUsage {
+                daily: Decimal::from(120),
+                monthly: Decimal::from(900),
+            })
+        });
+
+        let use_case = GetWalletLimitsUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id).await.unwrap();
+
+        assert_eq!(result.limits.daily, Some(Decimal::from(500)));
+        assert_eq!(result.usage.daily, Decimal::from(120));
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet_limits_not_found() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo.expect_find_by_id().returning(|_| Ok(None));
+        mock_repo.expect_find_limits().never();
+
+        let use_case = GetWalletLimitsUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id).await;
+
+        assert!(matches!(result, Err(WalletError::NotFound(id)) if id == wallet_id));
+    }
+}
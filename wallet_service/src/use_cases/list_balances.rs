@@ -0,0 +1,100 @@
+use crate::domain::{
+    entities::BalanceSnapshot, error::WalletError, repository::WalletRepository, types::WalletId,
+};
+use std::sync::Arc;
+
+/// Tamaño de página por defecto al recorrer los saldos.
+pub const DEFAULT_PAGE_SIZE: i64 = 500;
+/// Máximo de billeteras por consulta.
+pub const MAX_PAGE_SIZE: i64 = 5_000;
+
+/// Caso de uso para recorrer los saldos de todas las billeteras por páginas.
+///
+/// Usa paginación por cursor (el id de la última billetera devuelta), así que recorrer
+/// millones de billeteras no degrada cada consulta como lo haría un `OFFSET`.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::list_balances::ListBalancesUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = ListBalancesUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct ListBalancesUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+}
+
+impl ListBalancesUseCase {
+    /// Construye una nueva instancia inyectando el repositorio de billeteras.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self { wallet_repo }
+    }
+
+    /// Devuelve la página de saldos que sigue a `after`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `after` - Última billetera de la página anterior (`None` para empezar).
+    /// * `page_size` - Tamaño de página; se acota a `1..=MAX_PAGE_SIZE` (por defecto `DEFAULT_PAGE_SIZE`).
+    ///
+    /// Una página con menos elementos que `page_size` indica que el recorrido terminó.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let page = use_case.execute(None, Some(100)).await?;
+    /// let next = use_case.execute(page.last().map(|b| b.wallet_id), Some(100)).await?;
+    /// ```
+    #[tracing::instrument(name = "ListBalancesUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        after: Option<WalletId>,
+        page_size: Option<i64>,
+    ) -> Result<Vec<BalanceSnapshot>, WalletError> {
+        let limit = page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        self.wallet_repo.list_balances(after, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::MockWalletRepository;
+    use mockall::predicate::eq;
+
+    #[tokio::test]
+    async fn test_list_balances_uses_default_page_size() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_list_balances()
+            .with(eq(None), eq(DEFAULT_PAGE_SIZE))
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+
+        let use_case = ListBalancesUseCase::new(Arc::new(mock_repo));
+        assert!(use_case.execute(None, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_balances_clamps_page_size_and_passes_cursor() {
+        let mut mock_repo = MockWalletRepository::new();
+        let cursor = WalletId::new();
+
+        mock_repo
+            .expect_list_balances()
+            .with(eq(Some(cursor)), eq(MAX_PAGE_SIZE))
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+
+        let use_case = ListBalancesUseCase::new(Arc::new(mock_repo));
+        use_case
+            .execute(Some(cursor), Some(1_000_000))
+            .await
+            .unwrap();
+    }
+}
@@ -1,5 +1,17 @@
+pub mod change_wallet_status;
+pub mod concurrency;
+pub mod confirm_movement;
 pub mod create_user;
 pub mod create_wallet;
+pub mod execute_transfer;
+pub mod get_balance;
+pub mod get_historical_balance;
+pub mod get_movement_status;
 pub mod get_user_wallets;
 pub mod get_wallet;
+pub mod get_wallet_entries;
+pub mod get_wallet_limits;
+pub mod list_balances;
 pub mod process_movement;
+pub mod update_wallet;
+pub mod update_wallet_limits;
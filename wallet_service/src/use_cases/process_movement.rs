@@ -1,12 +1,28 @@
+use crate::domain::entities::{Hold, SystemAccount};
 use crate::domain::error::WalletError;
 use crate::domain::repository::WalletRepository;
 use crate::domain::types::WalletId;
+use crate::use_cases::concurrency::ConcurrencyPolicy;
 use std::sync::Arc;
 
+/// Resultado de reservar un movimiento.
+#[derive(Debug, Clone)]
+pub struct ReservedMovement {
+    /// Reserva asociada a (`transaction_id`, `wallet_id`), en su estado actual.
+    pub hold: Hold,
+    /// `true` si la pata ya se había procesado y se devolvió el resultado original.
+    pub replayed: bool,
+}
+
 /// Casos de uso para procesar movimientos (depósitos/retiros) en una billetera.
 ///
-/// Encapsula la lógica de llamar al repositorio para realizar la actualización
-/// atómica del balance en la base de datos.
+/// El movimiento no se aplica de inmediato: se registra una reserva (hold) identificada por
+/// `transaction_id` que retiene los fondos de débito. El balance contable sólo cambia cuando
+/// la reserva se captura mediante `ConfirmMovementUseCase`.
+///
+/// La operación es idempotente por (`transaction_id`, `wallet_id`): si el Transaction Service
+/// reenvía una pata ya procesada (p. ej. desde el job de reintentos), se devuelve la reserva
+/// original en lugar de retener los fondos dos veces.
 ///
 /// # Examples
 /// ```ignore
@@ -20,6 +36,8 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct ProcessMovementUseCase {
     wallet_repo: Arc<dyn WalletRepository>,
+    concurrency: ConcurrencyPolicy,
+    frozen_accepts_credits: bool,
 }
 
 impl ProcessMovementUseCase {
@@ -28,24 +46,54 @@ impl ProcessMovementUseCase {
     /// Se le inyecta una implementación de `WalletRepository` utilizando `Arc<dyn ...>`
     /// para permitir su uso seguro en entornos multihilo según las reglas de Clean Architecture.
     pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
-        Self { wallet_repo }
+        Self {
+            wallet_repo,
+            concurrency: ConcurrencyPolicy::default(),
+            frozen_accepts_credits: true,
+        }
+    }
+
+    /// Define si una billetera congelada sigue recibiendo créditos (por defecto `true`).
+    /// Los débitos siempre se rechazan mientras esté congelada.
+    pub fn with_frozen_accepts_credits(mut self, frozen_accepts_credits: bool) -> Self {
+        self.frozen_accepts_credits = frozen_accepts_credits;
+        self
     }
 
-    /// Ejecuta el caso de uso para procesar un movimiento financiero.
+    /// Reemplaza la política de concurrencia (modo de bloqueo y reintentos).
+    pub fn with_concurrency_policy(mut self, concurrency: ConcurrencyPolicy) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Ejecuta el caso de uso para reservar un movimiento financiero.
+    ///
+    /// Construye la entidad `Hold` (que valida monto y transacción) y delega al repositorio
+    /// su persistencia atómica junto con la retención de fondos.
     ///
-    /// El proceso se apoya en el repositorio subyacente que asegura la atomicidad de la
-    /// transacción y que el balance no sea menor a cero (en caso de que la implementación
-    /// lo valide mediante constricciones en BD).
+    /// Para débitos se valida primero contra el saldo disponible más el sobregiro de la
+    /// billetera. La reserva se persiste condicionada a la versión leída (modo optimista), así
+    /// que si otra escritura se adelantó se vuelve a leer y validar según la
+    /// `ConcurrencyPolicy`. El constraint `positive_balance` sigue siendo la garantía final.
+    /// Los límites de gasto (por transacción, diario y mensual) se verifican en el repositorio,
+    /// dentro de la misma transacción que incrementa los contadores de consumo.
     ///
     /// # Argumentos
     ///
     /// * `wallet_id` - El identificador único de la billetera destino u origen.
     /// * `amount`    - La cantidad como logaritmo decimal continuo. Puede ser positivo (depósito) o negativo (retiro).
+    /// * `transaction_id` - Identificador de la transacción que origina el movimiento (clave de la reserva).
+    /// * `counterparty` - Cuenta del sistema que recibirá el asiento espejo al capturar (doble partida).
     ///
     /// # Retornos
     ///
-    /// Devuelve un `Result<(), WalletError>`. Falla con `WalletError::NotFound` si la billetera no existe
-    /// o `WalletError::InsufficientFunds` en caso de un balance no viable.
+    /// Devuelve la reserva creada, o la original si la pata ya existía (`replayed = true`).
+    /// Falla con `WalletError::NotFound` si la billetera no existe,
+    /// `WalletError::WalletNotActive` si está congelada o cerrada,
+    /// `WalletError::InsufficientFunds` en caso de un balance no viable,
+    /// `WalletError::LimitExceeded` si el débito supera un límite de gasto o
+    /// `WalletError::InvalidData` si el monto tiene más decimales de los que admite la divisa o
+    /// si el `transaction_id` ya se usó con otro monto.
     ///
     /// # Examples
     /// ```ignore
@@ -54,63 +102,220 @@ impl ProcessMovementUseCase {
     ///
     /// let wallet_id = Uuid::new_v4();
     /// let amount = dec!(50.0);
-    /// use_case.execute(wallet_id, amount).await.unwrap();
+    /// use_case.execute(wallet_id, amount, "tx-1".to_string(), SystemAccount::ExternalFunding).await.unwrap();
     /// ```
     #[tracing::instrument(name = "ProcessMovementUseCase::execute", skip(self))]
     pub async fn execute(
         &self,
         wallet_id: WalletId,
         amount: rust_decimal::Decimal,
-    ) -> Result<(), WalletError> {
-        self.wallet_repo.update_balance(wallet_id, amount).await
+        transaction_id: String,
+        counterparty: SystemAccount,
+    ) -> Result<ReservedMovement, WalletError> {
+        let hold = Hold::new(wallet_id, transaction_id, amount, counterparty)?;
+
+        if let Some(existing) = self.find_existing(&hold).await? {
+            return Ok(existing);
+        }
+
+        match self
+            .concurrency
+            .retry(|| self.try_reserve(hold.clone()))
+            .await
+        {
+            Ok(hold) => Ok(ReservedMovement {
+                hold,
+                replayed: false,
+            }),
+            // Un duplicado concurrente ganó la inserción: devolvemos su resultado.
+            Err(WalletError::DuplicateMovement { .. }) => self
+                .find_existing(&hold)
+                .await?
+                .ok_or_else(|| WalletError::DuplicateMovement {
+                    wallet_id,
+                    transaction_id: hold.transaction_id().to_string(),
+                }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Busca una pata ya procesada con la misma clave y verifica que sea el mismo movimiento.
+    async fn find_existing(&self, hold: &Hold) -> Result<Option<ReservedMovement>, WalletError> {
+        let Some(existing) = self
+            .wallet_repo
+            .find_hold(hold.wallet_id(), hold.transaction_id().to_string())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        if existing.amount() != hold.amount() {
+            return Err(WalletError::InvalidData(format!(
+                "La transacción {} ya registró un movimiento de {} en la wallet {}",
+                hold.transaction_id(),
+                existing.amount(),
+                hold.wallet_id()
+            )));
+        }
+
+        tracing::info!(
+            "Movimiento duplicado (tx {}, wallet {}): se devuelve la reserva original",
+            hold.transaction_id(),
+            hold.wallet_id()
+        );
+        Ok(Some(ReservedMovement {
+            hold: existing,
+            replayed: true,
+        }))
+    }
+
+    /// Un intento de reserva: lee la billetera, valida el débito y persiste con su versión.
+    async fn try_reserve(&self, hold: Hold) -> Result<Hold, WalletError> {
+        let wallet_id = hold.wallet_id();
+        let wallet = self
+            .wallet_repo
+            .find_by_id(wallet_id)
+            .await?
+            .ok_or(WalletError::NotFound(wallet_id))?;
+
+        wallet.ensure_accepts_movement(hold.is_debit(), self.frozen_accepts_credits)?;
+        wallet.ensure_amount_scale(hold.amount())?;
+        if hold.is_debit() {
+            wallet.ensure_can_debit(hold.reserved_amount())?;
+        }
+
+        let expected_version = self.concurrency.expected_version(wallet.version());
+        self.wallet_repo.reserve(hold, expected_version).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::repository::MockWalletRepository;
+    use crate::domain::entities::{HoldStatus, StatusChange, Wallet};
+    use crate::domain::repository::{LockingMode, MockWalletRepository};
+    use crate::domain::types::UserId;
+    use mockall::predicate::eq;
     use rust_decimal::Decimal;
     use std::str::FromStr;
 
+    fn wallet_with(id: WalletId, balance: &str, overdraft: Option<&str>) -> Wallet {
+        Wallet::reconstitute(
+            id,
+            UserId::new(),
+            "Main".into(),
+            Decimal::from_str(balance).unwrap(),
+            Decimal::ZERO,
+            overdraft.map(|o| Decimal::from_str(o).unwrap()),
+            "USD".into(),
+            1,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
+    fn frozen_wallet(id: WalletId) -> Wallet {
+        let mut wallet = wallet_with(id, "200.00", None);
+        wallet.freeze("Fraude".into()).unwrap();
+        wallet
+    }
+
+    fn wallet_at_version(id: WalletId, version: i32) -> Wallet {
+        Wallet::reconstitute(
+            id,
+            UserId::new(),
+            "Main".into(),
+            Decimal::from(200),
+            Decimal::ZERO,
+            None,
+            "USD".into(),
+            version,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn test_process_movement_success() {
         let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
         let wallet_id = WalletId::new();
-        let amount = Decimal::from_str("150.50").unwrap();
+        let amount = Decimal::from_str("-150.50").unwrap();
 
         mock_repo
-            .expect_update_balance()
-            .with(
-                mockall::predicate::eq(wallet_id),
-                mockall::predicate::eq(amount),
-            )
+            .expect_find_by_id()
             .times(1)
-            .returning(|_, _| Ok(()));
+            .returning(move |id| Ok(Some(wallet_with(id, "200.00", None))));
+        mock_repo
+            .expect_reserve()
+            .withf(move |hold: &Hold, expected_version: &Option<i32>| {
+                hold.wallet_id() == wallet_id
+                    && hold.amount() == amount
+                    && hold.transaction_id() == "tx-1"
+                    && hold.counterparty() == SystemAccount::ExternalFunding
+                    && hold.status() == HoldStatus::Active
+                    && *expected_version == Some(1)
+            })
+            .times(1)
+            .returning(|hold, _| Ok(hold));
 
         let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
-        let result = use_case.execute(wallet_id, amount).await;
+        let result = use_case
+            .execute(
+                wallet_id,
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::ExternalFunding,
+            )
+            .await;
 
-        assert!(result.is_ok());
+        let movement = result.unwrap();
+        assert!(!movement.replayed);
+        let hold = movement.hold;
+        assert!(hold.is_debit());
+        assert_eq!(hold.reserved_amount(), Decimal::from_str("150.50").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_requires_transaction_id() {
+        let mock_repo = MockWalletRepository::new();
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Decimal::from(10),
+                "  ".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
     }
 
     #[tokio::test]
     async fn test_process_movement_not_found() {
         let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
         let wallet_id = WalletId::new();
         let amount = Decimal::from_str("150.50").unwrap();
 
         mock_repo
-            .expect_update_balance()
-            .with(
-                mockall::predicate::eq(wallet_id),
-                mockall::predicate::eq(amount),
-            )
+            .expect_find_by_id()
+            .with(eq(wallet_id))
             .times(1)
-            .returning(|id, _| Err(WalletError::NotFound(id)));
+            .returning(|_| Ok(None));
+        mock_repo.expect_reserve().never();
 
         let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
-        let result = use_case.execute(wallet_id, amount).await;
+        let result = use_case
+            .execute(
+                wallet_id,
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -122,20 +327,25 @@ mod tests {
     #[tokio::test]
     async fn test_process_movement_insufficient_funds() {
         let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
         let wallet_id = WalletId::new();
         let amount = Decimal::from_str("-150.50").unwrap();
 
         mock_repo
-            .expect_update_balance()
-            .with(
-                mockall::predicate::eq(wallet_id),
-                mockall::predicate::eq(amount),
-            )
+            .expect_find_by_id()
             .times(1)
-            .returning(move |id, _| Err(WalletError::InsufficientFunds(id)));
+            .returning(move |id| Ok(Some(wallet_with(id, "100.00", None))));
+        mock_repo.expect_reserve().never();
 
         let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
-        let result = use_case.execute(wallet_id, amount).await;
+        let result = use_case
+            .execute(
+                wallet_id,
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -143,4 +353,328 @@ mod tests {
             _ => panic!("Expected InsufficientFunds error"),
         }
     }
+
+    #[tokio::test]
+    async fn test_process_movement_within_overdraft() {
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        let amount = Decimal::from_str("-150.50").unwrap();
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |id| Ok(Some(wallet_with(id, "100.00", Some("50.50")))));
+        mock_repo
+            .expect_reserve()
+            .times(1)
+            .returning(|hold, _| Ok(hold));
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_concurrent_reserve_rejected_by_db() {
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        let wallet_id = WalletId::new();
+        let amount = Decimal::from_str("-150.50").unwrap();
+
+        // El saldo leído alcanza, pero otra reserva concurrente lo consumió antes del UPDATE.
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |id| Ok(Some(wallet_with(id, "200.00", None))));
+        mock_repo
+            .expect_reserve()
+            .times(1)
+            .returning(|hold, _| Err(WalletError::InsufficientFunds(hold.wallet_id())));
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                wallet_id,
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InsufficientFunds(id)) if id == wallet_id));
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_retries_on_version_conflict() {
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        let mut seq = mockall::Sequence::new();
+
+        // Primer intento con la versión 1 pierde la carrera; el segundo relee la versión 2.
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|id| Ok(Some(wallet_with(id, "200.00", None))));
+        mock_repo
+            .expect_reserve()
+            .with(mockall::predicate::always(), eq(Some(1)))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err(WalletError::ConcurrencyError("version".into())));
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|id| Ok(Some(wallet_at_version(id, 2))));
+        mock_repo
+            .expect_reserve()
+            .with(mockall::predicate::always(), eq(Some(2)))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|hold, _| Ok(hold));
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Decimal::from(-10),
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_pessimistic_sends_no_version() {
+        let mut mock_repo = MockWalletRepository::new();
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet_with(id, "200.00", None))));
+        mock_repo
+            .expect_reserve()
+            .with(mockall::predicate::always(), eq(None))
+            .times(1)
+            .returning(|hold, _| Ok(hold));
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo))
+            .with_concurrency_policy(ConcurrencyPolicy::new(LockingMode::Pessimistic, 1));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Decimal::from(-10),
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_replays_existing_hold() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let amount = Decimal::from(-25);
+        let mut original =
+            Hold::new(wallet_id, "tx-1".into(), amount, SystemAccount::Suspense).unwrap();
+        original.capture().unwrap();
+
+        mock_repo
+            .expect_find_hold()
+            .with(eq(wallet_id), eq("tx-1".to_string()))
+            .times(1)
+            .returning(move |_, _| Ok(Some(original.clone())));
+        mock_repo.expect_find_by_id().never();
+        mock_repo.expect_reserve().never();
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let movement = use_case
+            .execute(
+                wallet_id,
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await
+            .unwrap();
+
+        assert!(movement.replayed);
+        assert_eq!(movement.hold.status(), HoldStatus::Captured);
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_rejects_reused_transaction_with_other_amount() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+        let original = Hold::new(
+            wallet_id,
+            "tx-1".into(),
+            Decimal::from(-25),
+            SystemAccount::Suspense,
+        )
+        .unwrap();
+
+        mock_repo
+            .expect_find_hold()
+            .returning(move |_, _| Ok(Some(original.clone())));
+        mock_repo.expect_reserve().never();
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                wallet_id,
+                Decimal::from(-30),
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_concurrent_duplicate_returns_winner() {
+        let mut mock_repo = MockWalletRepository::new();
+        let mut seq = mockall::Sequence::new();
+        let wallet_id = WalletId::new();
+        let amount = Decimal::from(-25);
+        let winner = Hold::new(wallet_id, "tx-1".into(), amount, SystemAccount::Suspense).unwrap();
+
+        mock_repo
+            .expect_find_hold()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(None));
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet_with(id, "200.00", None))));
+        mock_repo
+            .expect_reserve()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|hold, _| {
+                Err(WalletError::DuplicateMovement {
+                    wallet_id: hold.wallet_id(),
+                    transaction_id: hold.transaction_id().to_string(),
+                })
+            });
+        mock_repo
+            .expect_find_hold()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move |_, _| Ok(Some(winner.clone())));
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let movement = use_case
+            .execute(
+                wallet_id,
+                amount,
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await
+            .unwrap();
+
+        assert!(movement.replayed);
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_rejects_debit_on_frozen_wallet() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(frozen_wallet(id))));
+        mock_repo.expect_reserve().never();
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                wallet_id,
+                Decimal::from(-10),
+                "tx-1".to_string(),
+                SystemAccount::Suspense,
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(WalletError::WalletNotActive { wallet_id: id, .. }) if id == wallet_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_frozen_credit_policy() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(frozen_wallet(id))));
+        mock_repo
+            .expect_reserve()
+            .times(1)
+            .returning(|hold, _| Ok(hold));
+        let repo = Arc::new(mock_repo);
+
+        let accepting = ProcessMovementUseCase::new(repo.clone());
+        let result = accepting
+            .execute(
+                WalletId::new(),
+                Decimal::from(10),
+                "tx-1".to_string(),
+                SystemAccount::ExternalFunding,
+            )
+            .await;
+        assert!(result.is_ok());
+
+        let rejecting = ProcessMovementUseCase::new(repo).with_frozen_accepts_credits(false);
+        let result = rejecting
+            .execute(
+                WalletId::new(),
+                Decimal::from(10),
+                "tx-2".to_string(),
+                SystemAccount::ExternalFunding,
+            )
+            .await;
+        assert!(matches!(result, Err(WalletError::WalletNotActive { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_process_movement_rejects_amount_over_currency_precision() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo.expect_find_hold().returning(|_, _| Ok(None));
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet_with(id, "200.00", None))));
+        mock_repo.expect_reserve().never();
+
+        let use_case = ProcessMovementUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Decimal::from_str("1.005").unwrap(),
+                "tx-1".to_string(),
+                SystemAccount::ExternalFunding,
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
 }
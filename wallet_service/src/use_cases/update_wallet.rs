@@ -0,0 +1,252 @@
+use crate::domain::{
+    entities::Wallet, error::WalletError, repository::WalletRepository, types::WalletId,
+};
+use crate::use_cases::concurrency::ConcurrencyPolicy;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Caso de uso para modificar los atributos configurables de una billetera.
+///
+/// Sólo admite cambios de etiqueta y de sobregiro permitido; los balances nunca se
+/// modifican por esta vía.
+///
+/// Si el cliente envía la versión que conoce (`If-Match`), el cambio se aplica sólo si la
+/// billetera sigue en esa versión y un conflicto se devuelve sin reintentar. Sin versión, se
+/// reintenta según la `ConcurrencyPolicy` releyendo el estado actual.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::update_wallet::UpdateWalletUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let repo = Arc::new(MockWalletRepository::new());
+/// let use_case = UpdateWalletUseCase::new(repo);
+/// ```
+#[derive(Clone)]
+pub struct UpdateWalletUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+    concurrency: ConcurrencyPolicy,
+}
+
+impl UpdateWalletUseCase {
+    /// Construye una nueva instancia de `UpdateWalletUseCase`.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self {
+            wallet_repo,
+            concurrency: ConcurrencyPolicy::default(),
+        }
+    }
+
+    /// Reemplaza la política de concurrencia (modo de bloqueo y reintentos).
+    pub fn with_concurrency_policy(mut self, concurrency: ConcurrencyPolicy) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Aplica los cambios indicados sobre la billetera.
+    ///
+    /// # Argumentos
+    ///
+    /// * `wallet_id` - Billetera a modificar.
+    /// * `label` - Nueva etiqueta (`None` la deja igual).
+    /// * `overdraft_limit` - Nuevo sobregiro permitido (`None` lo deja igual; cero lo desactiva).
+    /// * `expected_version` - Versión que el cliente espera modificar (`If-Match`), si la envió.
+    ///
+    /// # Retornos
+    ///
+    /// La billetera actualizada (con su nueva versión), `WalletError::NotFound` si no existe,
+    /// `WalletError::ConcurrencyError` si la versión no coincide o `WalletError::InvalidData`
+    /// si la etiqueta queda vacía o el sobregiro es negativo o menor al ya utilizado.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let wallet = use_case.execute(wallet_id, None, Some(dec!(100)), Some(3)).await.unwrap();
+    /// ```
+    #[tracing::instrument(name = "UpdateWalletUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        label: Option<String>,
+        overdraft_limit: Option<Decimal>,
+        expected_version: Option<i32>,
+    ) -> Result<Wallet, WalletError> {
+        let attempt =
+            || self.try_update(wallet_id, label.clone(), overdraft_limit, expected_version);
+
+        match expected_version {
+            // La precondición del cliente no se reintenta: debe releer y decidir de nuevo.
+            Some(_) => attempt().await,
+            None => self.concurrency.retry(attempt).await,
+        }
+    }
+
+    async fn try_update(
+        &self,
+        wallet_id: WalletId,
+        label: Option<String>,
+        overdraft_limit: Option<Decimal>,
+        expected_version: Option<i32>,
+    ) -> Result<Wallet, WalletError> {
+        let mut wallet = self
+            .wallet_repo
+            .find_by_id(wallet_id)
+            .await?
+            .ok_or(WalletError::NotFound(wallet_id))?;
+
+        if let Some(expected) = expected_version {
+            if wallet.version() != expected {
+                return Err(WalletError::ConcurrencyError(format!(
+                    "La wallet {} está en la versión {}, se esperaba {}",
+                    wallet_id,
+                    wallet.version(),
+                    expected
+                )));
+            }
+        }
+        let expected_version =
+            expected_version.or_else(|| self.concurrency.expected_version(wallet.version()));
+
+        if let Some(label) = label {
+            wallet.set_label(label)?;
+        }
+        if let Some(limit) = overdraft_limit {
+            wallet.set_overdraft_limit(Some(limit))?;
+        }
+
+        self.wallet_repo.update(wallet, expected_version).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::StatusChange;
+    use crate::domain::repository::MockWalletRepository;
+    use crate::domain::types::UserId;
+    use mockall::predicate::{always, eq};
+
+    fn wallet(id: WalletId, balance: i64, overdraft: Option<i64>) -> Wallet {
+        Wallet::reconstitute(
+            id,
+            UserId::new(),
+            "Main".into(),
+            Decimal::from(balance),
+            Decimal::ZERO,
+            overdraft.map(Decimal::from),
+            "USD".into(),
+            1,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_sets_overdraft() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(wallet(id, 0, None))));
+        mock_repo
+            .expect_update()
+            .withf(|w: &Wallet, expected: &Option<i32>| {
+                w.overdraft_limit() == Some(Decimal::from(100))
+                    && w.label() == "Savings"
+                    && *expected == Some(1)
+            })
+            .times(1)
+            .returning(|w, _| Ok(w));
+
+        let use_case = UpdateWalletUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                wallet_id,
+                Some("Savings".into()),
+                Some(Decimal::from(100)),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.spendable_balance(), Decimal::from(100));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_rejects_overdraft_below_usage() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(wallet(id, -80, Some(100)))));
+        mock_repo.expect_update().never();
+
+        let use_case = UpdateWalletUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(WalletId::new(), None, Some(Decimal::from(50)), None)
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_not_found() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo.expect_find_by_id().returning(|_| Ok(None));
+
+        let use_case = UpdateWalletUseCase::new(Arc::new(mock_repo));
+        let result = use_case.execute(wallet_id, None, None, None).await;
+
+        assert!(matches!(result, Err(WalletError::NotFound(id)) if id == wallet_id));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_if_match_mismatch_is_not_retried() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(|id| Ok(Some(wallet(id, 0, None))));
+        mock_repo.expect_update().never();
+
+        let use_case = UpdateWalletUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(WalletId::new(), Some("Savings".into()), None, Some(7))
+            .await;
+
+        assert!(matches!(result, Err(WalletError::ConcurrencyError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_retries_without_if_match() {
+        let mut mock_repo = MockWalletRepository::new();
+        let mut seq = mockall::Sequence::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .times(2)
+            .returning(|id| Ok(Some(wallet(id, 0, None))));
+        mock_repo
+            .expect_update()
+            .with(always(), eq(Some(1)))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err(WalletError::ConcurrencyError("version".into())));
+        mock_repo
+            .expect_update()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|w, _| Ok(w));
+
+        let use_case = UpdateWalletUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(WalletId::new(), Some("Savings".into()), None, None)
+            .await;
+
+        assert!(result.is_ok());
+    }
+}
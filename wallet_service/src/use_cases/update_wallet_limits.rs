@@ -0,0 +1,214 @@
+use crate::domain::{
+    entities::{SpendingLimits, Wallet},
+    error::WalletError,
+    repository::WalletRepository,
+    types::WalletId,
+};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Caso de uso para reemplazar los límites de gasto de una billetera.
+///
+/// Los nuevos límites aplican a las reservas siguientes; el consumo ya acumulado en el día o
+/// el mes se conserva, por lo que bajar un límite por debajo de lo consumido bloquea nuevos
+/// débitos hasta el próximo período.
+///
+/// # Examples
+/// ```ignore
+/// use wallet_service::use_cases::update_wallet_limits::UpdateWalletLimitsUseCase;
+/// use wallet_service::domain::repository::MockWalletRepository;
+/// use std::sync::Arc;
+///
+/// let use_case = UpdateWalletLimitsUseCase::new(Arc::new(MockWalletRepository::new()));
+/// ```
+#[derive(Clone)]
+pub struct UpdateWalletLimitsUseCase {
+    wallet_repo: Arc<dyn WalletRepository>,
+}
+
+impl UpdateWalletLimitsUseCase {
+    /// Construye una nueva instancia inyectando el repositorio de billeteras.
+    pub fn new(wallet_repo: Arc<dyn WalletRepository>) -> Self {
+        Self { wallet_repo }
+    }
+
+    /// Reemplaza los límites de `wallet_id`. `None` elimina el límite de ese período.
+    ///
+    /// Cambiar los límites incrementa la versión de la billetera; con `expected_version`
+    /// (`If-Match`) el cambio sólo se aplica si la billetera sigue en esa versión.
+    ///
+    /// # Retornos
+    ///
+    /// La billetera con su nueva versión y los límites guardados, `WalletError::NotFound` si
+    /// la billetera no existe, `WalletError::ConcurrencyError` si la versión no coincide o
+    /// `WalletError::InvalidData` si algún límite no es positivo, no son coherentes entre sí o
+    /// tienen más decimales de los que admite la divisa de la billetera.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let (wallet, limits) = use_case
+    ///     .execute(wallet_id, Some(dec!(100)), Some(dec!(500)), None, Some(3))
+    ///     .await?;
+    /// ```
+    #[tracing::instrument(name = "UpdateWalletLimitsUseCase::execute", skip(self))]
+    pub async fn execute(
+        &self,
+        wallet_id: WalletId,
+        per_transaction: Option<Decimal>,
+        daily: Option<Decimal>,
+        monthly: Option<Decimal>,
+        expected_version: Option<i32>,
+    ) -> Result<(Wallet, SpendingLimits), WalletError> {
+        let limits = SpendingLimits::new(per_transaction, daily, monthly)?;
+
+        let wallet = self
+            .wallet_repo
+            .find_by_id(wallet_id)
+            .await?
+            .ok_or(WalletError::NotFound(wallet_id))?;
+        if let Some(expected) = expected_version {
+            if wallet.version() != expected {
+                return Err(WalletError::ConcurrencyError(format!(
+                    "La wallet {} está en la versión {}, se esperaba {}",
+                    wallet_id,
+                    wallet.version(),
+                    expected
+                )));
+            }
+        }
+        for limit in [per_transaction, daily, monthly].into_iter().flatten() {
+            wallet.ensure_amount_scale(limit)?;
+        }
+
+        self.wallet_repo
+            .save_limits(wallet_id, limits, expected_version)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::StatusChange;
+    use crate::domain::repository::MockWalletRepository;
+    use crate::domain::types::UserId;
+
+    fn wallet(id: WalletId) -> Wallet {
+        Wallet::reconstitute(
+            id,
+            UserId::new(),
+            "Main".into(),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            None,
+            "USD".into(),
+            1,
+            StatusChange::active(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_limits_saves_limits() {
+        let mut mock_repo = MockWalletRepository::new();
+        let wallet_id = WalletId::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet(id))));
+        mock_repo
+            .expect_save_limits()
+            .withf(move |id, limits, expected_version| {
+                *id == wallet_id
+                    && limits.per_transaction == Some(Decimal::from(100))
+                    && limits.monthly.is_none()
+                    && expected_version.is_none()
+            })
+            .times(1)
+            .returning(|id, limits, _| Ok((wallet(id), limits)));
+
+        let use_case = UpdateWalletLimitsUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                wallet_id,
+                Some(Decimal::from(100)),
+                Some(Decimal::from(500)),
+                None,
+                None,
+            )
+            .await;
+
+        assert_eq!(result.unwrap().1.daily, Some(Decimal::from(500)));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_limits_rejects_inconsistent_limits() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo.expect_save_limits().never();
+
+        let use_case = UpdateWalletLimitsUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Some(Decimal::from(600)),
+                Some(Decimal::from(500)),
+                None,
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_limits_rejects_stale_if_match_version() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        // La billetera está en la versión 1; el cliente leyó la 0.
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet(id))));
+        mock_repo.expect_save_limits().never();
+
+        let use_case = UpdateWalletLimitsUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Some(Decimal::from(100)),
+                None,
+                None,
+                Some(0),
+            )
+            .await;
+
+        assert!(matches!(result, Err(WalletError::ConcurrencyError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_wallet_limits_forwards_if_match_version() {
+        let mut mock_repo = MockWalletRepository::new();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(wallet(id))));
+        mock_repo
+            .expect_save_limits()
+            .withf(|_, _, expected_version| *expected_version == Some(1))
+            .times(1)
+            .returning(|id, limits, _| Ok((wallet(id), limits)));
+
+        let use_case = UpdateWalletLimitsUseCase::new(Arc::new(mock_repo));
+        let result = use_case
+            .execute(
+                WalletId::new(),
+                Some(Decimal::from(100)),
+                None,
+                None,
+                Some(1),
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+}